@@ -1,6 +1,6 @@
 use criterion::{criterion_group, criterion_main, Criterion};
 use tantivy::tokenizer::{
-    LowerCaser, RemoveLongFilter, SimpleTokenizer, TextAnalyzer, TokenizerManager,
+    LowerCaser, RemoveLongFilter, SimpleTokenizer, Stemmer, TextAnalyzer, TokenizerManager,
 };
 
 const ALICE_TXT: &str = include_str!("alice.txt");
@@ -33,6 +33,23 @@ pub fn criterion_benchmark(c: &mut Criterion) {
             assert_eq!(word_count, 30_731);
         })
     });
+    for cache_size in [0, 1_000] {
+        let mut en_stem = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(RemoveLongFilter::limit(40))
+            .filter(LowerCaser)
+            .filter(Stemmer::default().set_cache_size(cache_size))
+            .build();
+        c.bench_function(&format!("en-stem-cache-{cache_size}-alice"), |b| {
+            b.iter(|| {
+                let mut word_count = 0;
+                let mut token_stream = en_stem.token_stream(ALICE_TXT);
+                while token_stream.advance() {
+                    word_count += 1;
+                }
+                assert_eq!(word_count, 30_731);
+            })
+        });
+    }
 }
 
 criterion_group! {
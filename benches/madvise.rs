@@ -0,0 +1,96 @@
+use std::path::Path;
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use tantivy::collector::Count;
+use tantivy::directory::{MadviseAdvice, MadvisePolicy, MmapDirectory};
+use tantivy::query::TermQuery;
+use tantivy::schema::{Field, IndexRecordOption, Schema, STRING};
+use tantivy::{doc, Index, IndexWriter, Term};
+
+const NUM_DOCS: u64 = 200_000;
+const NUM_LOOKUPS: u64 = 1_000;
+
+fn build_index(path: &Path) -> Field {
+    let mut schema_builder = Schema::builder();
+    let id_field = schema_builder.add_text_field("id", STRING);
+    let index = Index::create_in_dir(path, schema_builder.build()).unwrap();
+    let mut index_writer: IndexWriter = index.writer_with_num_threads(1, 100_000_000).unwrap();
+    for id in 0..NUM_DOCS {
+        index_writer
+            .add_document(doc!(id_field => format!("id{id:06}")))
+            .unwrap();
+    }
+    index_writer.commit().unwrap();
+    index_writer.wait_merging_threads().unwrap();
+    id_field
+}
+
+// Opens the index with the given policy, and looks up ids spread over the whole index.
+fn open_and_search(path: &Path, madvise_policy: MadvisePolicy, id_field: Field) -> usize {
+    let directory = MmapDirectory::open_with_madvise(path, madvise_policy).unwrap();
+    let index = Index::open(directory).unwrap();
+    let searcher = index.reader().unwrap().searcher();
+    (0..NUM_LOOKUPS)
+        .map(|i| {
+            let id = i * 7_919 % NUM_DOCS;
+            let query = TermQuery::new(
+                Term::from_field_text(id_field, &format!("id{id:06}")),
+                IndexRecordOption::Basic,
+            );
+            searcher.search(&query, &Count).unwrap()
+        })
+        .sum()
+}
+
+// Drops the page cache, so that the index files are read from the disk again.
+//
+// This requires Linux and root privileges, and returns false otherwise.
+fn drop_page_cache() -> bool {
+    std::fs::write("/proc/sys/vm/drop_caches", "3").is_ok()
+}
+
+// The warm benchmarks keep the page cache across iterations, and measure the overhead of the
+// policies and of the warmup phase. The cold benchmarks drop the page cache before each
+// iteration, and measure the cold start latency the warmup phase is meant to improve. They are
+// skipped when the page cache cannot be dropped.
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let tempdir = tempfile::TempDir::new().unwrap();
+    let id_field = build_index(tempdir.path());
+    let random_policy = MadvisePolicy::default()
+        .with_advice_for_extension("term", MadviseAdvice::Random)
+        .with_advice_for_extension("idx", MadviseAdvice::Random);
+    let policies = [
+        ("default", MadvisePolicy::default()),
+        ("random", random_policy.clone()),
+        (
+            "random-with-warmup",
+            random_policy
+                .with_warmup_for_extension("term")
+                .with_warmup_for_extension("idx"),
+        ),
+    ];
+    for (name, madvise_policy) in &policies {
+        c.bench_function(&format!("madvise-open-and-search-{name}"), |b| {
+            b.iter(|| open_and_search(tempdir.path(), madvise_policy.clone(), id_field))
+        });
+    }
+    if !drop_page_cache() {
+        eprintln!("Skipping the cold page cache benchmarks: the page cache cannot be dropped.");
+        return;
+    }
+    let mut group = c.benchmark_group("madvise-cold");
+    group.sample_size(10);
+    for (name, madvise_policy) in &policies {
+        group.bench_function(format!("madvise-open-and-search-{name}"), |b| {
+            b.iter_batched(
+                || assert!(drop_page_cache()),
+                |()| open_and_search(tempdir.path(), madvise_policy.clone(), id_field),
+                BatchSize::PerIteration,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
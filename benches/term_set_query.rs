@@ -0,0 +1,49 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use tantivy::collector::Count;
+use tantivy::query::{BooleanQuery, TermSetQuery};
+use tantivy::schema::{Schema, STRING};
+use tantivy::{doc, Index, IndexWriter, Searcher, Term};
+
+const NUM_DOCS: u64 = 100_000;
+
+fn build_searcher() -> (Searcher, tantivy::schema::Field) {
+    let mut schema_builder = Schema::builder();
+    let id_field = schema_builder.add_text_field("id", STRING);
+    let index = Index::create_in_ram(schema_builder.build());
+    let mut index_writer: IndexWriter = index.writer_with_num_threads(1, 100_000_000).unwrap();
+    for id in 0..NUM_DOCS {
+        index_writer
+            .add_document(doc!(id_field => format!("id{id:06}")))
+            .unwrap();
+    }
+    index_writer.commit().unwrap();
+    let searcher = index.reader().unwrap().searcher();
+    (searcher, id_field)
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let (searcher, id_field) = build_searcher();
+    for num_terms in [10u64, 1_000, 10_000] {
+        // The terms are spread over twice the range of ids, so half of them are missing from
+        // the index.
+        let step = 2 * NUM_DOCS / num_terms;
+        let terms: Vec<Term> = (0..num_terms)
+            .map(|i| Term::from_field_text(id_field, &format!("id{:06}", i * step)))
+            .collect();
+        c.bench_function(&format!("term-set-query-{num_terms}"), |b| {
+            b.iter(|| {
+                let query = TermSetQuery::new(terms.clone());
+                searcher.search(&query, &Count).unwrap()
+            })
+        });
+        c.bench_function(&format!("boolean-or-query-{num_terms}"), |b| {
+            b.iter(|| {
+                let query = BooleanQuery::new_multiterms_query(terms.clone());
+                searcher.search(&query, &Count).unwrap()
+            })
+        });
+    }
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -136,6 +136,13 @@ impl<T: PartialOrd + Copy + Debug + Send + Sync + 'static> Column<T> {
             .map(|value_row_id: RowId| self.values.get_val(value_row_id))
     }
 
+    /// Returns the number of values associated with `doc_id`, without reading them.
+    #[inline]
+    pub fn num_values(&self, doc_id: DocId) -> u32 {
+        let value_row_ids = self.index.value_row_ids(doc_id);
+        value_row_ids.end - value_row_ids.start
+    }
+
     /// Get the docids of values which are in the provided value range.
     #[inline]
     pub fn get_docids_for_value_range(
@@ -43,6 +43,9 @@ fn field_name(inp: &str) -> IResult<&str, String> {
 }
 
 /// Consume a word outside of any context.
+///
+/// A `~` ends the word, unless it is its first character: `search~1` is the word `search`
+/// followed by a fuzzy distance, while `~Document` is a word.
 // TODO should support escape sequences
 fn word(inp: &str) -> IResult<&str, Cow<str>> {
     map_res(
@@ -61,7 +64,8 @@ fn word(inp: &str) -> IResult<&str, Cow<str>> {
                 preceded(char('\\'), anychar),
                 satisfy(|c: char| {
                     !c.is_whitespace()
-                        && ![':', '^', '{', '}', '"', '\'', '[', ']', '(', ')', '\\'].contains(&c)
+                        && ![':', '^', '~', '{', '}', '"', '\'', '[', ']', '(', ')', '\\']
+                            .contains(&c)
                 }),
             ))),
         ))),
@@ -73,6 +77,9 @@ fn word(inp: &str) -> IResult<&str, Cow<str>> {
     )(inp)
 }
 
+/// Consume a word ending at a whitespace or at one of the `delimiter` characters.
+///
+/// A `~` delimiter is only a delimiter after the first character of the word, like in [`word`].
 fn word_infallible(
     delimiter: &str,
     emit_error: bool,
@@ -84,10 +91,16 @@ fn word_infallible(
             opt_i_err(
                 preceded(
                     multispace0,
-                    recognize(many1(alt((
-                        preceded(char::<&str, _>('\\'), anychar),
-                        satisfy(|c| !c.is_whitespace() && !delimiter.contains(c)),
-                    )))),
+                    recognize(tuple((
+                        alt((
+                            preceded(char::<&str, _>('\\'), anychar),
+                            satisfy(|c| !c.is_whitespace() && (c == '~' || !delimiter.contains(c))),
+                        )),
+                        many0(alt((
+                            preceded(char::<&str, _>('\\'), anychar),
+                            satisfy(|c| !c.is_whitespace() && !delimiter.contains(c)),
+                        ))),
+                    ))),
                 ),
                 "expected word",
             ),
@@ -227,7 +240,7 @@ fn term_or_phrase(inp: &str) -> IResult<&str, UserInputLeaf> {
 fn term_or_phrase_infallible(inp: &str) -> JResult<&str, Option<UserInputLeaf>> {
     map(
         // ~* for slop/prefix, ) inside group or ast tree, ^ if boost
-        tuple_infallible((simple_term_infallible(")^"), slop_or_prefix_val)),
+        tuple_infallible((simple_term_infallible(")^~"), slop_or_prefix_val)),
         |((delimiter_phrase, (slop, prefix)), errors)| {
             let leaf = if let Some((delimiter, phrase)) = delimiter_phrase {
                 Some(
@@ -1536,6 +1549,7 @@ mod test {
         test_parse_query_to_ast_helper("abc: IN [1]", r#""abc": IN ["1"]"#);
         test_parse_query_to_ast_helper("abc: IN []", r#""abc": IN []"#);
         test_parse_query_to_ast_helper("IN [1 2]", r#"IN ["1" "2"]"#);
+        test_parse_query_to_ast_helper(r#"abc: IN [a "b c" 'd']"#, r#""abc": IN ["a" "b c" "d"]"#);
         test_is_parse_err("IN [1 2", r#"IN ["1" "2"]"#);
 
         // TODO maybe support these too?
@@ -1592,6 +1606,17 @@ mod test {
         test_parse_query_to_ast_helper("\"a b\"~300^2", "(\"a b\"~300)^2");
     }
 
+    #[test]
+    fn test_fuzzy() {
+        test_parse_query_to_ast_helper("search~1", "search~1");
+        test_parse_query_to_ast_helper("title:search~1", "\"title\":search~1");
+        test_parse_query_to_ast_helper("rust~1^2", "(rust~1)^2");
+        test_parse_query_to_ast_helper("a~1 AND b~2", "(+a~1 +b~2)");
+        test_parse_query_to_ast_helper("~Document", "~Document");
+        test_is_parse_err("term~abc", "(*term *~abc)");
+        test_is_parse_err("term~", "(*term *~)");
+    }
+
     #[test]
     fn test_phrase_prefix() {
         test_parse_query_to_ast_helper("\"a b\"*", "\"a b\"*");
@@ -34,8 +34,9 @@ use super::bucket::{
     DateHistogramAggregationReq, HistogramAggregation, RangeAggregation, TermsAggregation,
 };
 use super::metric::{
-    AverageAggregation, CountAggregation, MaxAggregation, MinAggregation,
-    PercentilesAggregationReq, StatsAggregation, SumAggregation, TopHitsAggregation,
+    AverageAggregation, CardinalityAggregationReq, CountAggregation, ExtendedStatsAggregation,
+    MaxAggregation, MinAggregation, PercentilesAggregationReq, StatsAggregation, SumAggregation,
+    TopHitsAggregation, WeightedAverageAggregation,
 };
 
 /// The top-level aggregation request structure, which contains [`Aggregation`] and their user
@@ -133,6 +134,9 @@ pub enum AggregationVariants {
     /// Computes the average of the extracted values.
     #[serde(rename = "avg")]
     Average(AverageAggregation),
+    /// Computes the average of the extracted values, weighted by the values of another field.
+    #[serde(rename = "weighted_avg")]
+    WeightedAverage(WeightedAverageAggregation),
     /// Counts the number of extracted values.
     #[serde(rename = "value_count")]
     Count(CountAggregation),
@@ -146,12 +150,19 @@ pub enum AggregationVariants {
     /// extracted values.
     #[serde(rename = "stats")]
     Stats(StatsAggregation),
+    /// Computes the statistics of `stats`, as well as the variance and standard deviation of the
+    /// extracted values.
+    #[serde(rename = "extended_stats")]
+    ExtendedStats(ExtendedStatsAggregation),
     /// Computes the sum of the extracted values.
     #[serde(rename = "sum")]
     Sum(SumAggregation),
     /// Computes the sum of the extracted values.
     #[serde(rename = "percentiles")]
     Percentiles(PercentilesAggregationReq),
+    /// Approximates the number of distinct values.
+    #[serde(rename = "cardinality")]
+    Cardinality(CardinalityAggregationReq),
     /// Finds the top k values matching some order
     #[serde(rename = "top_hits")]
     TopHits(TopHitsAggregation),
@@ -166,12 +177,17 @@ impl AggregationVariants {
             AggregationVariants::Histogram(histogram) => vec![histogram.field.as_str()],
             AggregationVariants::DateHistogram(histogram) => vec![histogram.field.as_str()],
             AggregationVariants::Average(avg) => vec![avg.field_name()],
+            AggregationVariants::WeightedAverage(weighted_avg) => weighted_avg.field_names(),
             AggregationVariants::Count(count) => vec![count.field_name()],
             AggregationVariants::Max(max) => vec![max.field_name()],
             AggregationVariants::Min(min) => vec![min.field_name()],
             AggregationVariants::Stats(stats) => vec![stats.field_name()],
+            AggregationVariants::ExtendedStats(extended_stats) => {
+                vec![extended_stats.field_name()]
+            }
             AggregationVariants::Sum(sum) => vec![sum.field_name()],
             AggregationVariants::Percentiles(per) => vec![per.field_name()],
+            AggregationVariants::Cardinality(card) => vec![card.field_name()],
             AggregationVariants::TopHits(top_hits) => top_hits.field_names(),
         }
     }
@@ -11,8 +11,8 @@ use super::bucket::{
     DateHistogramAggregationReq, HistogramAggregation, RangeAggregation, TermsAggregation,
 };
 use super::metric::{
-    AverageAggregation, CountAggregation, MaxAggregation, MinAggregation, StatsAggregation,
-    SumAggregation,
+    AverageAggregation, CountAggregation, ExtendedStatsAggregation, MaxAggregation, MinAggregation,
+    StatsAggregation, SumAggregation,
 };
 use super::segment_agg_result::AggregationLimits;
 use super::VecWithNames;
@@ -49,7 +49,8 @@ pub struct AggregationWithAccessor {
     pub(crate) limits: ResourceLimitGuard,
     pub(crate) column_block_accessor: ColumnBlockAccessor<u64>,
     /// Used for missing term aggregation, which checks all columns for existence.
-    /// And also for `top_hits` aggregation, which may sort on multiple fields.
+    /// And also for `top_hits` aggregation, which may sort on multiple fields, and for the
+    /// `weighted_avg` aggregation, which reads a value and a weight field.
     /// By convention the missing aggregation is chosen, when this property is set
     /// (instead bein set in `agg`).
     /// If this needs to used by other aggregations, we need to refactor this.
@@ -276,6 +277,10 @@ impl AggregationWithAccessor {
                 field: ref field_name,
                 ..
             })
+            | ExtendedStats(ExtendedStatsAggregation {
+                field: ref field_name,
+                ..
+            })
             | Sum(SumAggregation {
                 field: ref field_name,
                 ..
@@ -292,6 +297,41 @@ impl AggregationWithAccessor {
                 )?;
                 add_agg_with_accessor(&agg, accessor, column_type, &mut res)?;
             }
+            Cardinality(ref cardinality) => {
+                let allowed_column_types = [
+                    ColumnType::I64,
+                    ColumnType::U64,
+                    ColumnType::F64,
+                    ColumnType::Str,
+                    ColumnType::DateTime,
+                    ColumnType::Bool,
+                ];
+                let (accessor, column_type) = get_ff_reader(
+                    reader,
+                    cardinality.field_name(),
+                    Some(&allowed_column_types),
+                )?;
+                // The terms of a text column are resolved from their ordinals.
+                let str_dict_column = if column_type == ColumnType::Str {
+                    reader.fast_fields().str(cardinality.field_name())?
+                } else {
+                    None
+                };
+                add_agg_with_accessor(&agg, accessor, column_type, &mut res)?;
+                if let Some(agg_with_accessor) = res.last_mut() {
+                    agg_with_accessor.str_dict_column = str_dict_column;
+                }
+            }
+            WeightedAverage(ref weighted_avg) => {
+                let accessors: Vec<(Column<u64>, ColumnType)> = weighted_avg
+                    .field_names()
+                    .iter()
+                    .map(|field| {
+                        get_ff_reader(reader, field, Some(get_numeric_or_date_column_types()))
+                    })
+                    .collect::<crate::Result<_>>()?;
+                add_agg_with_accessors(&agg, accessors, &mut res, Default::default())?;
+            }
             TopHits(ref mut top_hits) => {
                 top_hits.validate_and_resolve_field_names(reader.fast_fields().columnar())?;
                 let accessors: Vec<(Column<u64>, ColumnType)> = top_hits
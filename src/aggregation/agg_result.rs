@@ -8,7 +8,9 @@ use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
 
 use super::bucket::GetDocCount;
-use super::metric::{PercentilesMetricResult, SingleMetricResult, Stats, TopHitsMetricResult};
+use super::metric::{
+    ExtendedStats, PercentilesMetricResult, SingleMetricResult, Stats, TopHitsMetricResult,
+};
 use super::{AggregationError, Key};
 use crate::TantivyError;
 
@@ -80,12 +82,18 @@ impl AggregationResult {
 pub enum MetricResult {
     /// Average metric result.
     Average(SingleMetricResult),
+    /// Weighted average metric result.
+    WeightedAverage(SingleMetricResult),
     /// Count metric result.
     Count(SingleMetricResult),
     /// Max metric result.
     Max(SingleMetricResult),
     /// Min metric result.
     Min(SingleMetricResult),
+    /// Extended stats metric result.
+    ///
+    /// Comes before `Stats`, which would otherwise match it when deserializing.
+    ExtendedStats(ExtendedStats),
     /// Stats metric result.
     Stats(Stats),
     /// Sum metric result.
@@ -94,16 +102,20 @@ pub enum MetricResult {
     Percentiles(PercentilesMetricResult),
     /// Top hits metric result
     TopHits(TopHitsMetricResult),
+    /// Cardinality metric result.
+    Cardinality(SingleMetricResult),
 }
 
 impl MetricResult {
     fn get_value(&self, agg_property: &str) -> crate::Result<Option<f64>> {
         match self {
             MetricResult::Average(avg) => Ok(avg.value),
+            MetricResult::WeightedAverage(weighted_avg) => Ok(weighted_avg.value),
             MetricResult::Count(count) => Ok(count.value),
             MetricResult::Max(max) => Ok(max.value),
             MetricResult::Min(min) => Ok(min.value),
             MetricResult::Stats(stats) => stats.get_value(agg_property),
+            MetricResult::ExtendedStats(extended_stats) => extended_stats.get_value(agg_property),
             MetricResult::Sum(sum) => Ok(sum.value),
             MetricResult::Percentiles(_) => Err(TantivyError::AggregationError(
                 AggregationError::InvalidRequest("percentiles can't be used to order".to_string()),
@@ -111,6 +123,7 @@ impl MetricResult {
             MetricResult::TopHits(_) => Err(TantivyError::AggregationError(
                 AggregationError::InvalidRequest("top_hits can't be used to order".to_string()),
             )),
+            MetricResult::Cardinality(cardinality) => Ok(cardinality.value),
         }
     }
 }
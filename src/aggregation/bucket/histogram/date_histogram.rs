@@ -75,6 +75,16 @@ pub struct DateHistogramAggregationReq {
     /// The `offset` parameter is has the same syntax as the `fixed_interval` parameter, but
     /// also allows for negative values.
     pub offset: Option<String>,
+    /// The time zone in which the buckets are aligned, e.g. `"+01:00"` for daily buckets starting
+    /// at midnight in UTC+1. Defaults to UTC.
+    ///
+    /// Only fixed offsets from UTC are supported, in the format `+HH:MM` or `-HH:MM` (or `Z`,
+    /// `UTC`). Time zone names like `Europe/Paris` return an error, since their offset depends on
+    /// the date.
+    ///
+    /// The keys of the buckets are still timestamps of their start, and the `key_as_string` is
+    /// formatted in UTC.
+    pub time_zone: Option<String>,
     /// The minimum number of documents in a bucket to be returned. Defaults to 0.
     pub min_doc_count: Option<u64>,
     /// Limits the data range to `[min, max]` closed interval.
@@ -119,15 +129,26 @@ pub struct DateHistogramAggregationReq {
 impl DateHistogramAggregationReq {
     pub(crate) fn to_histogram_req(&self) -> crate::Result<HistogramAggregation> {
         self.validate()?;
+        let offset = self
+            .offset
+            .as_ref()
+            .map(|offset| parse_offset_into_milliseconds(offset))
+            .transpose()?;
+        let time_zone_offset = self
+            .time_zone
+            .as_ref()
+            .map(|time_zone| parse_time_zone_into_milliseconds(time_zone))
+            .transpose()?;
+        // Aligning the buckets on the local time of the time zone, shifts them by the opposite of
+        // its offset from UTC.
+        let offset = match (offset, time_zone_offset) {
+            (None, None) => None,
+            (offset, time_zone_offset) => Some(offset.unwrap_or(0) - time_zone_offset.unwrap_or(0)),
+        };
         Ok(HistogramAggregation {
             field: self.field.to_string(),
             interval: parse_into_milliseconds(self.fixed_interval.as_ref().unwrap())? as f64,
-            offset: self
-                .offset
-                .as_ref()
-                .map(|offset| parse_offset_into_milliseconds(offset))
-                .transpose()?
-                .map(|el| el as f64),
+            offset: offset.map(|el| el as f64),
             min_doc_count: self.min_doc_count,
             hard_bounds: self.hard_bounds,
             extended_bounds: self.extended_bounds,
@@ -185,6 +206,37 @@ pub enum DateHistogramParseError {
     /// Value out of bounds
     #[error("passed value is out of bounds: {0:?}")]
     OutOfBounds(String),
+    /// Time zone invalid or unsupported
+    #[error("passed time zone is invalid or unsupported, only fixed offsets are supported {0:?}")]
+    InvalidTimeZone(String),
+}
+
+/// Parses a fixed offset from UTC, e.g. `+01:00`, into milliseconds.
+fn parse_time_zone_into_milliseconds(input: &str) -> Result<i64, AggregationError> {
+    if input == "Z" || input == "UTC" {
+        return Ok(0);
+    }
+    let invalid_time_zone = || DateHistogramParseError::InvalidTimeZone(input.to_string());
+    if !input.is_ascii() || input.len() != 6 || &input[3..4] != ":" {
+        return Err(invalid_time_zone().into());
+    }
+    let sign = match &input[..1] {
+        "+" => 1,
+        "-" => -1,
+        _ => return Err(invalid_time_zone().into()),
+    };
+    let parse_digits = |digits: &str| -> Result<i64, AggregationError> {
+        if !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(invalid_time_zone().into());
+        }
+        digits.parse().map_err(|_err| invalid_time_zone().into())
+    };
+    let hours = parse_digits(&input[1..3])?;
+    let minutes = parse_digits(&input[4..6])?;
+    if hours > 18 || minutes >= 60 {
+        return Err(invalid_time_zone().into());
+    }
+    Ok(sign * (hours * 60 + minutes) * 60 * 1000)
 }
 
 fn parse_offset_into_milliseconds(input: &str) -> Result<i64, AggregationError> {
@@ -296,6 +348,34 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_time_zone_into_milliseconds() {
+        assert_eq!(parse_time_zone_into_milliseconds("Z").unwrap(), 0);
+        assert_eq!(parse_time_zone_into_milliseconds("UTC").unwrap(), 0);
+        assert_eq!(parse_time_zone_into_milliseconds("+00:00").unwrap(), 0);
+        assert_eq!(
+            parse_time_zone_into_milliseconds("+01:00").unwrap(),
+            3_600_000
+        );
+        assert_eq!(
+            parse_time_zone_into_milliseconds("-05:30").unwrap(),
+            -19_800_000
+        );
+        for invalid_time_zone in [
+            "Europe/Paris",
+            "+1:00",
+            "01:00",
+            "+01:60",
+            "+19:00",
+            "+０1:00",
+        ] {
+            assert_eq!(
+                parse_time_zone_into_milliseconds(invalid_time_zone).unwrap_err(),
+                DateHistogramParseError::InvalidTimeZone(invalid_time_zone.to_string()).into()
+            );
+        }
+    }
+
     #[test]
     fn test_parse_into_milliseconds_do_not_accept_non_ascii() {
         assert!(parse_into_milliseconds("１m").is_err());
@@ -646,6 +726,80 @@ pub mod tests {
             assert_eq!(res, expected_res);
         }
     }
+    #[test]
+    fn histogram_test_date_time_zone() {
+        let docs = vec![
+            vec![
+                r#"{ "date": "2015-01-01T12:10:30Z", "mixed": { "val": 1 } }"#,
+                r#"{ "date": "2015-01-01T11:11:30Z", "mixed": { "val": 2 } }"#,
+            ],
+            vec![
+                r#"{ "date": "2015-01-01T11:11:30Z", "mixed": { "val": 3 } }"#,
+                r#"{ "date": "2015-01-02T00:00:00Z", "mixed": { "val": 6 } }"#,
+            ],
+            vec![r#"{ "date": "2015-01-06T00:00:00Z", "mixed": { "val": 10 } }"#],
+        ];
+        let index = get_test_index_from_docs(false, &docs).unwrap();
+        // At UTC-1, 2015-01-02T00:00:00Z is still the 1st of January.
+        let elasticsearch_compatible_json = json!(
+            {
+                "sales_over_time": {
+                    "date_histogram": {
+                        "field": "date",
+                        "fixed_interval": "1d",
+                        "time_zone": "-01:00",
+                        "min_doc_count": 1
+                    },
+                    "aggs": {
+                        "avg_val": {
+                            "avg": {"field": "mixed.val"}
+                        }
+                    }
+                }
+            }
+        );
+        let agg_req: Aggregations =
+            serde_json::from_str(&serde_json::to_string(&elasticsearch_compatible_json).unwrap())
+                .unwrap();
+        let res = exec_request(agg_req, &index).unwrap();
+        let expected_res = json!({
+            "sales_over_time" : {
+                "buckets": [
+                    {
+                        "doc_count": 4,
+                        "key": 1420074000000.0,
+                        "key_as_string": "2015-01-01T01:00:00Z",
+                        "avg_val": {"value": 3.0}
+                    },
+                    {
+                        "doc_count": 1,
+                        "key": 1420419600000.0,
+                        "key_as_string": "2015-01-05T01:00:00Z",
+                        "avg_val": {"value": 10.0}
+                    }
+                ]
+            }
+        });
+        assert_eq!(res, expected_res);
+
+        let elasticsearch_compatible_json = json!(
+            {
+                "sales_over_time": {
+                    "date_histogram": {
+                        "field": "date",
+                        "fixed_interval": "1d",
+                        "time_zone": "Europe/Paris"
+                    }
+                }
+            }
+        );
+        let agg_req: Aggregations =
+            serde_json::from_str(&serde_json::to_string(&elasticsearch_compatible_json).unwrap())
+                .unwrap();
+        let err = exec_request(agg_req, &index).unwrap_err();
+        assert!(err.to_string().contains("Europe/Paris"));
+    }
+
     #[test]
     fn histogram_test_invalid_req() {
         let docs = vec![];
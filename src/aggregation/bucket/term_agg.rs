@@ -1790,6 +1790,56 @@ mod tests {
 
         Ok(())
     }
+    #[test]
+    fn terms_aggregation_missing_and_min_doc_count_after_merge() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let category = schema_builder.add_text_field("category", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer = index.writer_with_num_threads(1, 20_000_000)?;
+            index_writer.set_merge_policy(Box::new(NoMergePolicy));
+            index_writer.add_document(doc!(category => "books"))?;
+            index_writer.add_document(doc!(category => "books"))?;
+            index_writer.add_document(doc!(category => "toys"))?;
+            index_writer.add_document(doc!())?;
+            index_writer.commit()?;
+            index_writer.add_document(doc!(category => "books"))?;
+            index_writer.add_document(doc!(category => "toys"))?;
+            index_writer.add_document(doc!())?;
+            index_writer.add_document(doc!())?;
+            index_writer.commit()?;
+            index_writer.add_document(doc!(category => "games"))?;
+            index_writer.add_document(doc!())?;
+            index_writer.commit()?;
+        }
+
+        let agg_req: Aggregations = serde_json::from_value(json!({
+            "categories": {
+                "terms": {
+                    "field": "category",
+                    "missing": "NO_CATEGORY",
+                    "min_doc_count": 2,
+                    "shard_size": 10
+                },
+            }
+        }))
+        .unwrap();
+
+        let res = exec_request_with_query(agg_req, &index, None)?;
+
+        // "toys" has a single document per segment, min_doc_count is applied after merging.
+        // "games" is filtered out.
+        assert_eq!(
+            res["categories"]["buckets"],
+            json!([
+                { "key": "NO_CATEGORY", "doc_count": 4 },
+                { "key": "books", "doc_count": 3 },
+                { "key": "toys", "doc_count": 2 },
+            ])
+        );
+        Ok(())
+    }
+
     #[test]
     fn terms_aggregation_missing_empty() -> crate::Result<()> {
         let mut schema_builder = Schema::builder();
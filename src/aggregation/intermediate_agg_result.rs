@@ -19,8 +19,9 @@ use super::bucket::{
     GetDocCount, Order, OrderTarget, RangeAggregation, TermsAggregation,
 };
 use super::metric::{
-    IntermediateAverage, IntermediateCount, IntermediateMax, IntermediateMin, IntermediateStats,
-    IntermediateSum, PercentilesCollector, TopHitsTopNComputer,
+    CardinalityCollector, IntermediateAverage, IntermediateCount, IntermediateExtendedStats,
+    IntermediateMax, IntermediateMin, IntermediateStats, IntermediateSum,
+    IntermediateWeightedAverage, PercentilesCollector, TopHitsTopNComputer,
 };
 use super::segment_agg_result::AggregationLimits;
 use super::{format_date, AggregationError, Key, SerializedKey};
@@ -203,6 +204,9 @@ pub(crate) fn empty_from_req(req: &Aggregation) -> IntermediateAggregationResult
         Average(_) => IntermediateAggregationResult::Metric(IntermediateMetricResult::Average(
             IntermediateAverage::default(),
         )),
+        WeightedAverage(_) => IntermediateAggregationResult::Metric(
+            IntermediateMetricResult::WeightedAverage(IntermediateWeightedAverage::default()),
+        ),
         Count(_) => IntermediateAggregationResult::Metric(IntermediateMetricResult::Count(
             IntermediateCount::default(),
         )),
@@ -215,11 +219,17 @@ pub(crate) fn empty_from_req(req: &Aggregation) -> IntermediateAggregationResult
         Stats(_) => IntermediateAggregationResult::Metric(IntermediateMetricResult::Stats(
             IntermediateStats::default(),
         )),
+        ExtendedStats(_) => IntermediateAggregationResult::Metric(
+            IntermediateMetricResult::ExtendedStats(IntermediateExtendedStats::default()),
+        ),
         Sum(_) => IntermediateAggregationResult::Metric(IntermediateMetricResult::Sum(
             IntermediateSum::default(),
         )),
-        Percentiles(_) => IntermediateAggregationResult::Metric(
-            IntermediateMetricResult::Percentiles(PercentilesCollector::default()),
+        Percentiles(ref req) => IntermediateAggregationResult::Metric(
+            IntermediateMetricResult::Percentiles(PercentilesCollector::for_req(req)),
+        ),
+        Cardinality(ref req) => IntermediateAggregationResult::Metric(
+            IntermediateMetricResult::Cardinality(CardinalityCollector::for_req(req)),
         ),
         TopHits(ref req) => IntermediateAggregationResult::Metric(
             IntermediateMetricResult::TopHits(TopHitsTopNComputer::new(req.clone())),
@@ -274,6 +284,8 @@ pub enum IntermediateMetricResult {
     Percentiles(PercentilesCollector),
     /// Intermediate average result.
     Average(IntermediateAverage),
+    /// Intermediate weighted average result.
+    WeightedAverage(IntermediateWeightedAverage),
     /// Intermediate count result.
     Count(IntermediateCount),
     /// Intermediate max result.
@@ -282,10 +294,14 @@ pub enum IntermediateMetricResult {
     Min(IntermediateMin),
     /// Intermediate stats result.
     Stats(IntermediateStats),
+    /// Intermediate extended stats result.
+    ExtendedStats(IntermediateExtendedStats),
     /// Intermediate sum result.
     Sum(IntermediateSum),
     /// Intermediate top_hits result
     TopHits(TopHitsTopNComputer),
+    /// Intermediate cardinality result.
+    Cardinality(CardinalityCollector),
 }
 
 impl IntermediateMetricResult {
@@ -294,6 +310,9 @@ impl IntermediateMetricResult {
             IntermediateMetricResult::Average(intermediate_avg) => {
                 MetricResult::Average(intermediate_avg.finalize().into())
             }
+            IntermediateMetricResult::WeightedAverage(intermediate_weighted_avg) => {
+                MetricResult::WeightedAverage(intermediate_weighted_avg.finalize().into())
+            }
             IntermediateMetricResult::Count(intermediate_count) => {
                 MetricResult::Count(intermediate_count.finalize().into())
             }
@@ -306,6 +325,9 @@ impl IntermediateMetricResult {
             IntermediateMetricResult::Stats(intermediate_stats) => {
                 MetricResult::Stats(intermediate_stats.finalize())
             }
+            IntermediateMetricResult::ExtendedStats(intermediate_extended_stats) => {
+                MetricResult::ExtendedStats(intermediate_extended_stats.finalize())
+            }
             IntermediateMetricResult::Sum(intermediate_sum) => {
                 MetricResult::Sum(intermediate_sum.finalize().into())
             }
@@ -316,6 +338,9 @@ impl IntermediateMetricResult {
             IntermediateMetricResult::TopHits(top_hits) => {
                 MetricResult::TopHits(top_hits.into_final_result())
             }
+            IntermediateMetricResult::Cardinality(cardinality) => {
+                MetricResult::Cardinality(cardinality.finalize().into())
+            }
         }
     }
 
@@ -328,6 +353,12 @@ impl IntermediateMetricResult {
             ) => {
                 avg_left.merge_fruits(avg_right);
             }
+            (
+                IntermediateMetricResult::WeightedAverage(weighted_avg_left),
+                IntermediateMetricResult::WeightedAverage(weighted_avg_right),
+            ) => {
+                weighted_avg_left.merge_fruits(weighted_avg_right);
+            }
             (
                 IntermediateMetricResult::Count(count_left),
                 IntermediateMetricResult::Count(count_right),
@@ -346,6 +377,12 @@ impl IntermediateMetricResult {
             ) => {
                 stats_left.merge_fruits(stats_right);
             }
+            (
+                IntermediateMetricResult::ExtendedStats(extended_stats_left),
+                IntermediateMetricResult::ExtendedStats(extended_stats_right),
+            ) => {
+                extended_stats_left.merge_fruits(extended_stats_right);
+            }
             (IntermediateMetricResult::Sum(sum_left), IntermediateMetricResult::Sum(sum_right)) => {
                 sum_left.merge_fruits(sum_right);
             }
@@ -358,6 +395,12 @@ impl IntermediateMetricResult {
             (IntermediateMetricResult::TopHits(left), IntermediateMetricResult::TopHits(right)) => {
                 left.merge_fruits(right)?;
             }
+            (
+                IntermediateMetricResult::Cardinality(left),
+                IntermediateMetricResult::Cardinality(right),
+            ) => {
+                left.merge_fruits(right)?;
+            }
             _ => {
                 panic!("incompatible fruit types in tree or missing merge_fruits handler");
             }
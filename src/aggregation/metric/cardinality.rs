@@ -0,0 +1,586 @@
+use std::hash::Hasher;
+
+use columnar::{ColumnType, MonotonicallyMappableToU64};
+use rustc_hash::FxHashSet;
+use serde::{Deserialize, Serialize};
+
+use crate::aggregation::agg_req_with_accessor::AggregationsWithAccessor;
+use crate::aggregation::intermediate_agg_result::{
+    IntermediateAggregationResult, IntermediateAggregationResults, IntermediateMetricResult,
+};
+use crate::aggregation::segment_agg_result::SegmentAggregationCollector;
+use crate::aggregation::*;
+use crate::TantivyError;
+
+const DEFAULT_PRECISION_THRESHOLD: u32 = 3_000;
+const MAX_PRECISION_THRESHOLD: u32 = 40_000;
+const MIN_PRECISION: u32 = 4;
+const MAX_PRECISION: u32 = 18;
+
+/// # Cardinality
+///
+/// A single-value metric aggregation that approximates the number of distinct values that are
+/// extracted from the aggregated documents. It works on numeric, date, bool and text fields.
+/// See [super::SingleMetricResult] for return value.
+///
+/// # JSON Format
+/// ```json
+/// {
+///     "cardinality": {
+///         "field": "user_id",
+///         "precision_threshold": 1000
+///     }
+/// }
+/// ```
+///
+/// # Accuracy and memory
+///
+/// The distinct values are counted exactly, until their number exceeds the
+/// `precision_threshold`. Beyond that, the count is estimated with
+/// [HyperLogLog](https://en.wikipedia.org/wiki/HyperLogLog), using a 64-bit hash and the small
+/// range correction of HyperLogLog++.
+///
+/// The sketch has `2^p` registers of one byte, where `p` is `log2(precision_threshold) + 2`
+/// rounded up, so that the memory used is about 4 to 8 times the `precision_threshold` in bytes,
+/// whatever the number of documents. The relative standard error of the estimation is
+/// `1.04 / sqrt(2^p)`, i.e. about 0.8% with the default threshold of 3000, and 0.2% with the
+/// maximum of 40000.
+///
+/// Below the threshold, the exact count keeps 8 bytes per distinct value.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CardinalityAggregationReq {
+    /// The field name to compute the cardinality on.
+    pub field: String,
+    /// The number of distinct values below which the count is expected to be exact.
+    /// Defaults to 3000, and is capped at 40000.
+    ///
+    /// Higher values give more accurate counts, at the cost of more memory.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub precision_threshold: Option<u32>,
+    /// The missing parameter defines how documents that are missing a value should be treated.
+    /// By default they will be ignored but it is also possible to treat them as if they had a
+    /// value. Examples in JSON format:
+    /// { "field": "my_numbers", "missing": 10.0 }
+    /// { "field": "my_terms", "missing": "N/A" }
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub missing: Option<Key>,
+}
+
+impl CardinalityAggregationReq {
+    /// Creates a new [`CardinalityAggregationReq`] instance from a field name.
+    pub fn from_field_name(field_name: String) -> Self {
+        CardinalityAggregationReq {
+            field: field_name,
+            precision_threshold: None,
+            missing: None,
+        }
+    }
+    /// Returns the field name the aggregation is computed on.
+    pub fn field_name(&self) -> &str {
+        &self.field
+    }
+}
+
+/// The distinct values, either exact or in HyperLogLog registers.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+enum Sketch {
+    /// The hashes of the distinct values.
+    Exact(FxHashSet<u64>),
+    /// The HyperLogLog registers.
+    Registers(Vec<u8>),
+}
+
+/// The cardinality collector used during segment collection and for merging results.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CardinalityCollector {
+    precision_threshold: u32,
+    sketch: Sketch,
+}
+
+impl CardinalityCollector {
+    /// Creates an empty collector, with the precision of the request.
+    ///
+    /// Collectors can only be merged if they share the same precision.
+    pub(crate) fn for_req(req: &CardinalityAggregationReq) -> Self {
+        let precision_threshold = req
+            .precision_threshold
+            .unwrap_or(DEFAULT_PRECISION_THRESHOLD)
+            .min(MAX_PRECISION_THRESHOLD);
+        CardinalityCollector {
+            precision_threshold,
+            sketch: Sketch::Exact(FxHashSet::default()),
+        }
+    }
+
+    /// The number of bits of the hash used to pick a register.
+    fn precision(&self) -> u32 {
+        // ceil(log2(precision_threshold))
+        let log2 = u32::BITS - self.precision_threshold.saturating_sub(1).leading_zeros();
+        (log2 + 2).clamp(MIN_PRECISION, MAX_PRECISION)
+    }
+
+    fn insert_hash(&mut self, hash: u64) {
+        let precision = self.precision();
+        match &mut self.sketch {
+            Sketch::Exact(hashes) => {
+                hashes.insert(hash);
+                if hashes.len() > self.precision_threshold as usize {
+                    self.convert_to_registers();
+                }
+            }
+            Sketch::Registers(registers) => {
+                insert_into_registers(registers, precision, hash);
+            }
+        }
+    }
+
+    fn convert_to_registers(&mut self) {
+        let precision = self.precision();
+        let mut registers = vec![0u8; 1 << precision];
+        if let Sketch::Exact(hashes) = &self.sketch {
+            for &hash in hashes {
+                insert_into_registers(&mut registers, precision, hash);
+            }
+        }
+        self.sketch = Sketch::Registers(registers);
+    }
+
+    pub(crate) fn merge_fruits(&mut self, right: CardinalityCollector) -> crate::Result<()> {
+        match right.sketch {
+            Sketch::Exact(hashes) => {
+                for hash in hashes {
+                    self.insert_hash(hash);
+                }
+            }
+            Sketch::Registers(right_registers) => {
+                if let Sketch::Exact(_) = self.sketch {
+                    self.convert_to_registers();
+                }
+                let Sketch::Registers(registers) = &mut self.sketch else {
+                    unreachable!()
+                };
+                if registers.len() != right_registers.len() {
+                    return Err(TantivyError::AggregationError(
+                        AggregationError::InternalError(
+                            "Cannot merge cardinalities computed with different precisions"
+                                .to_string(),
+                        ),
+                    ));
+                }
+                for (register, right_register) in registers.iter_mut().zip(right_registers) {
+                    *register = (*register).max(right_register);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Computes the final number of distinct values.
+    pub fn finalize(&self) -> f64 {
+        match &self.sketch {
+            Sketch::Exact(hashes) => hashes.len() as f64,
+            Sketch::Registers(registers) => estimate_from_registers(registers).round(),
+        }
+    }
+}
+
+fn insert_into_registers(registers: &mut [u8], precision: u32, hash: u64) {
+    let register_id = (hash >> (64 - precision)) as usize;
+    // The position of the first 1 bit in the remaining bits of the hash.
+    let rank = ((hash << precision).leading_zeros() + 1).min(64 - precision + 1) as u8;
+    if registers[register_id] < rank {
+        registers[register_id] = rank;
+    }
+}
+
+fn estimate_from_registers(registers: &[u8]) -> f64 {
+    let num_registers = registers.len() as f64;
+    let mut inverse_sum = 0.0;
+    let mut num_zeros = 0u32;
+    for &register in registers {
+        inverse_sum += 2f64.powi(-(register as i32));
+        if register == 0 {
+            num_zeros += 1;
+        }
+    }
+    let alpha = match registers.len() {
+        16 => 0.673,
+        32 => 0.697,
+        64 => 0.709,
+        _ => 0.7213 / (1.0 + 1.079 / num_registers),
+    };
+    let estimate = alpha * num_registers * num_registers / inverse_sum;
+    if estimate <= 2.5 * num_registers && num_zeros > 0 {
+        // Linear counting is more accurate for small cardinalities.
+        num_registers * (num_registers / num_zeros as f64).ln()
+    } else {
+        estimate
+    }
+}
+
+/// The kind of a numeric value, hashed together with it so that values of different kinds
+/// sharing the same bits, e.g. `u64::MAX` and `-1i64`, are counted separately.
+#[derive(Clone, Copy)]
+enum NumericKind {
+    NonNegativeInteger = 0,
+    NegativeInteger = 1,
+    Float = 2,
+}
+
+/// Maps the fast field values of numeric columns to a common representation, so that a value is
+/// counted once whatever the type of its column, e.g. in a JSON field.
+fn numeric_key(val: u64, column_type: ColumnType) -> (NumericKind, u64) {
+    match column_type {
+        ColumnType::I64 => {
+            let val = i64::from_u64(val);
+            if val < 0 {
+                (NumericKind::NegativeInteger, val as u64)
+            } else {
+                (NumericKind::NonNegativeInteger, val as u64)
+            }
+        }
+        ColumnType::F64 => {
+            let val = f64::from_u64(val);
+            if val.fract() == 0.0 && (0.0..u64::MAX as f64).contains(&val) {
+                (NumericKind::NonNegativeInteger, val as u64)
+            } else if val.fract() == 0.0 && (i64::MIN as f64..0.0).contains(&val) {
+                (NumericKind::NegativeInteger, val as i64 as u64)
+            } else {
+                (NumericKind::Float, val.to_bits())
+            }
+        }
+        _ => (NumericKind::NonNegativeInteger, val),
+    }
+}
+
+fn hash_numeric(val: u64, column_type: ColumnType) -> u64 {
+    let (kind, key) = numeric_key(val, column_type);
+    fmix64(key ^ fmix64(kind as u64))
+}
+
+/// The finalizer of murmur3, which spreads the bits of fast field values and dictionary hashes.
+fn fmix64(mut hash: u64) -> u64 {
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51afd7ed558ccd);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xc4ceb9fe1a85ec53);
+    hash ^= hash >> 33;
+    hash
+}
+
+fn hash_bytes(bytes: &[u8]) -> u64 {
+    let mut hasher = fnv::FnvHasher::default();
+    hasher.write(bytes);
+    fmix64(hasher.finish())
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct SegmentCardinalityCollector {
+    cardinality: CardinalityCollector,
+    column_type: ColumnType,
+    /// The term ordinals of a text column. They are only valid within the segment, so they are
+    /// resolved to their terms, and hashed, when the segment is harvested.
+    term_ords: FxHashSet<u64>,
+    accessor_idx: usize,
+    missing: Option<Key>,
+    has_missing: bool,
+}
+
+impl SegmentCardinalityCollector {
+    pub fn from_req(
+        req: &CardinalityAggregationReq,
+        column_type: ColumnType,
+        accessor_idx: usize,
+    ) -> Self {
+        Self {
+            cardinality: CardinalityCollector::for_req(req),
+            column_type,
+            term_ords: FxHashSet::default(),
+            accessor_idx,
+            missing: req.missing.clone(),
+            has_missing: false,
+        }
+    }
+
+    #[inline]
+    fn collect_value(&mut self, val: u64) {
+        if self.column_type == ColumnType::Str {
+            self.term_ords.insert(val);
+        } else {
+            self.cardinality
+                .insert_hash(hash_numeric(val, self.column_type));
+        }
+    }
+
+    fn hash_missing(&self, missing: &Key) -> u64 {
+        match missing {
+            Key::Str(text) => hash_bytes(text.as_bytes()),
+            // Hashed like the values of the column, so that it is counted once if it also
+            // appears in the documents.
+            Key::F64(val) => match f64_to_fastfield_u64(*val, &self.column_type) {
+                Some(val) => hash_numeric(val, self.column_type),
+                None => hash_numeric(val.to_u64(), ColumnType::F64),
+            },
+        }
+    }
+}
+
+impl SegmentAggregationCollector for SegmentCardinalityCollector {
+    fn add_intermediate_aggregation_result(
+        mut self: Box<Self>,
+        agg_with_accessor: &AggregationsWithAccessor,
+        results: &mut IntermediateAggregationResults,
+    ) -> crate::Result<()> {
+        let name = agg_with_accessor.aggs.keys[self.accessor_idx].to_string();
+        let agg_accessor = &agg_with_accessor.aggs.values[self.accessor_idx];
+
+        if !self.term_ords.is_empty() {
+            let str_dict_column = agg_accessor.str_dict_column.as_ref().ok_or_else(|| {
+                TantivyError::InternalError("Missing dictionary of text column".to_string())
+            })?;
+            let mut term = Vec::new();
+            for term_ord in std::mem::take(&mut self.term_ords) {
+                term.clear();
+                if !str_dict_column
+                    .dictionary()
+                    .ord_to_term(term_ord, &mut term)?
+                {
+                    return Err(TantivyError::InternalError(format!(
+                        "Couldn't find term_ord {term_ord} in dict"
+                    )));
+                }
+                self.cardinality.insert_hash(hash_bytes(&term));
+            }
+        }
+        if self.has_missing {
+            if let Some(missing) = self.missing.as_ref() {
+                let hash = self.hash_missing(missing);
+                self.cardinality.insert_hash(hash);
+            }
+        }
+
+        let intermediate_metric_result = IntermediateMetricResult::Cardinality(self.cardinality);
+        results.push(
+            name,
+            IntermediateAggregationResult::Metric(intermediate_metric_result),
+        )?;
+
+        Ok(())
+    }
+
+    #[inline]
+    fn collect(
+        &mut self,
+        doc: crate::DocId,
+        agg_with_accessor: &mut AggregationsWithAccessor,
+    ) -> crate::Result<()> {
+        let accessor = &agg_with_accessor.aggs.values[self.accessor_idx].accessor;
+        let mut has_val = false;
+        for val in accessor.values_for_doc(doc) {
+            self.collect_value(val);
+            has_val = true;
+        }
+        if !has_val {
+            self.has_missing = true;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn collect_block(
+        &mut self,
+        docs: &[crate::DocId],
+        agg_with_accessor: &mut AggregationsWithAccessor,
+    ) -> crate::Result<()> {
+        let agg_accessor = &mut agg_with_accessor.aggs.values[self.accessor_idx];
+        if self.missing.is_some()
+            && !self.has_missing
+            && !agg_accessor.accessor.get_cardinality().is_full()
+        {
+            self.has_missing = docs
+                .iter()
+                .any(|doc| !agg_accessor.accessor.index.has_value(*doc));
+        }
+        agg_accessor
+            .column_block_accessor
+            .fetch_block(docs, &agg_accessor.accessor);
+        for val in agg_accessor.column_block_accessor.iter_vals() {
+            self.collect_value(val);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregation::agg_req::Aggregations;
+    use crate::aggregation::tests::{
+        exec_request_with_query, get_test_index_from_values, get_test_index_from_values_and_terms,
+    };
+    use crate::schema::{Schema, FAST, STRING};
+    use crate::{Index, IndexWriter};
+
+    fn cardinality_request(precision_threshold: u32) -> Aggregations {
+        serde_json::from_value(json!({
+            "score_cardinality": {
+                "cardinality": {
+                    "field": "score",
+                    "precision_threshold": precision_threshold,
+                }
+            },
+            "term_cardinality": {
+                "cardinality": {
+                    "field": "string_id",
+                    "precision_threshold": precision_threshold,
+                }
+            },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_cardinality_aggregation_empty_index() -> crate::Result<()> {
+        let index = get_test_index_from_values(false, &[])?;
+        let agg_req: Aggregations = serde_json::from_value(json!({
+            "cardinality": { "cardinality": { "field": "score" } }
+        }))
+        .unwrap();
+        let res = exec_request_with_query(agg_req, &index, None)?;
+        assert_eq!(res["cardinality"]["value"], 0.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cardinality_aggregation_exact_below_threshold() -> crate::Result<()> {
+        let segment_and_values: Vec<Vec<(f64, String)>> = (0..3)
+            .map(|segment| {
+                (0..400)
+                    .map(|i| {
+                        let val = segment * 50 + i;
+                        (val as f64, format!("term_{val}"))
+                    })
+                    .collect()
+            })
+            .collect();
+        // 500 distinct values spread over 3 segments.
+        for merge_segments in [false, true] {
+            let index = get_test_index_from_values_and_terms(merge_segments, &segment_and_values)?;
+            let res = exec_request_with_query(cardinality_request(1_000), &index, None)?;
+            assert_eq!(res["score_cardinality"]["value"], 500.0);
+            assert_eq!(res["term_cardinality"]["value"], 500.0);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_cardinality_aggregation_estimate() -> crate::Result<()> {
+        let segment_and_values: Vec<Vec<(f64, String)>> = (0..4)
+            .map(|segment| {
+                (0..6_000)
+                    .map(|i| {
+                        let val = (segment * 4_000 + i) % 15_000;
+                        (val as f64, format!("term_{val}"))
+                    })
+                    .collect()
+            })
+            .collect();
+        let index = get_test_index_from_values_and_terms(false, &segment_and_values)?;
+        // p = 14, the relative standard error is 1.04 / sqrt(2^14) = 0.8%.
+        let res = exec_request_with_query(cardinality_request(3_000), &index, None)?;
+        for agg_name in ["score_cardinality", "term_cardinality"] {
+            let value = res[agg_name]["value"].as_f64().unwrap();
+            assert!(
+                (value - 15_000.0).abs() <= 15_000.0 * 0.03,
+                "{agg_name}: {value} is too far from 15000"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_cardinality_aggregation_missing() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let score = schema_builder.add_u64_field("score", FAST);
+        let string_id = schema_builder.add_text_field("string_id", STRING | FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        index_writer.add_document(doc!(score => 1u64, string_id => "a"))?;
+        index_writer.add_document(doc!(score => 2u64, string_id => "b"))?;
+        index_writer.add_document(doc!())?;
+        index_writer.commit()?;
+        // A segment without any value.
+        index_writer.add_document(doc!())?;
+        index_writer.commit()?;
+
+        let agg_req: Aggregations = serde_json::from_value(json!({
+            "no_missing": { "cardinality": { "field": "score" } },
+            "existing_missing": { "cardinality": { "field": "score", "missing": 1.0 } },
+            "new_missing": { "cardinality": { "field": "score", "missing": 10.0 } },
+            "existing_missing_term": { "cardinality": { "field": "string_id", "missing": "a" } },
+            "new_missing_term": { "cardinality": { "field": "string_id", "missing": "c" } },
+        }))
+        .unwrap();
+        let res = exec_request_with_query(agg_req, &index, None)?;
+        assert_eq!(res["no_missing"]["value"], 2.0);
+        assert_eq!(res["existing_missing"]["value"], 2.0);
+        assert_eq!(res["new_missing"]["value"], 3.0);
+        assert_eq!(res["existing_missing_term"]["value"], 2.0);
+        assert_eq!(res["new_missing_term"]["value"], 3.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cardinality_aggregation_mixed_numeric_types() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let json = schema_builder.add_json_field("json", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        // An i64 column.
+        index_writer.add_document(doc!(json => json!({"score": -1i64})))?;
+        index_writer.add_document(doc!(json => json!({"score": 1i64})))?;
+        index_writer.commit()?;
+        // A u64 column, as u64::MAX does not fit in an i64.
+        index_writer.add_document(doc!(json => json!({"score": u64::MAX})))?;
+        index_writer.add_document(doc!(json => json!({"score": 1u64})))?;
+        index_writer.commit()?;
+        // A f64 column.
+        index_writer.add_document(doc!(json => json!({"score": -1.0})))?;
+        index_writer.add_document(doc!(json => json!({"score": 1.5})))?;
+        index_writer.commit()?;
+
+        let agg_req: Aggregations = serde_json::from_value(json!({
+            "cardinality": { "cardinality": { "field": "json.score" } }
+        }))
+        .unwrap();
+        let res = exec_request_with_query(agg_req, &index, None)?;
+        // -1, 1, u64::MAX and 1.5.
+        assert_eq!(res["cardinality"]["value"], 4.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cardinality_collector_merge_registers() {
+        let req = CardinalityAggregationReq {
+            precision_threshold: Some(100),
+            ..CardinalityAggregationReq::from_field_name("score".to_string())
+        };
+        let mut left = CardinalityCollector::for_req(&req);
+        let mut right = CardinalityCollector::for_req(&req);
+        for val in 0..1_000u64 {
+            left.insert_hash(hash_numeric(val, ColumnType::U64));
+        }
+        for val in 500..1_050u64 {
+            right.insert_hash(hash_numeric(val, ColumnType::U64));
+        }
+        assert!(matches!(left.sketch, Sketch::Registers(_)));
+        left.merge_fruits(right).unwrap();
+        // p = 9, the relative standard error is 1.04 / sqrt(2^9) = 4.6%.
+        let value = left.finalize();
+        assert!((value - 1_050.0).abs() <= 1_050.0 * 0.15, "{value}");
+
+        let serialized = serde_json::to_string(&left).unwrap();
+        let deserialized: CardinalityCollector = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, left);
+    }
+}
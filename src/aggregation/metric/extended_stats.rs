@@ -0,0 +1,475 @@
+use serde::{Deserialize, Serialize};
+
+use crate::aggregation::agg_req_with_accessor::{
+    AggregationWithAccessor, AggregationsWithAccessor,
+};
+use crate::aggregation::intermediate_agg_result::{
+    IntermediateAggregationResult, IntermediateAggregationResults, IntermediateMetricResult,
+};
+use crate::aggregation::segment_agg_result::SegmentAggregationCollector;
+use crate::aggregation::*;
+use crate::{DocId, TantivyError};
+
+/// A multi-value metric aggregation that computes the statistics of
+/// [`StatsAggregation`](super::StatsAggregation), as well as the variance and standard deviation of
+/// the numeric values that are extracted from the aggregated documents.
+/// See [`ExtendedStats`] for returned statistics.
+///
+/// # JSON Format
+/// ```json
+/// {
+///     "extended_stats": {
+///         "field": "score"
+///     }
+///  }
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExtendedStatsAggregation {
+    /// The field name to compute the extended stats on.
+    pub field: String,
+    /// The missing parameter defines how documents that are missing a value should be treated.
+    /// By default they will be ignored but it is also possible to treat them as if they had a
+    /// value. Examples in JSON format:
+    /// { "field": "my_numbers", "missing": "10.0" }
+    #[serde(default, deserialize_with = "deserialize_option_f64")]
+    pub missing: Option<f64>,
+}
+
+impl ExtendedStatsAggregation {
+    /// Creates a new [`ExtendedStatsAggregation`] instance from a field name.
+    pub fn from_field_name(field_name: String) -> Self {
+        ExtendedStatsAggregation {
+            field: field_name,
+            missing: None,
+        }
+    }
+    /// Returns the field name the aggregation is computed on.
+    pub fn field_name(&self) -> &str {
+        &self.field
+    }
+}
+
+/// ExtendedStats contains the statistics of [`Stats`](super::Stats), the variance and the standard
+/// deviation.
+///
+/// The population variance is the mean of the squared deviations from the average. The sampling
+/// variance divides by `count - 1` instead, and is `None` below two values.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExtendedStats {
+    /// The number of documents.
+    pub count: u64,
+    /// The sum of the fast field values.
+    pub sum: f64,
+    /// The min value of the fast field values.
+    pub min: Option<f64>,
+    /// The max value of the fast field values.
+    pub max: Option<f64>,
+    /// The average of the fast field values. `None` if count equals zero.
+    pub avg: Option<f64>,
+    /// The sum of the squares of the fast field values.
+    pub sum_of_squares: f64,
+    /// The population variance. `None` if count equals zero.
+    pub variance: Option<f64>,
+    /// The population variance. `None` if count equals zero.
+    pub variance_population: Option<f64>,
+    /// The sampling variance. `None` if count is lower than two.
+    pub variance_sampling: Option<f64>,
+    /// The population standard deviation. `None` if count equals zero.
+    pub std_deviation: Option<f64>,
+    /// The population standard deviation. `None` if count equals zero.
+    pub std_deviation_population: Option<f64>,
+    /// The sampling standard deviation. `None` if count is lower than two.
+    pub std_deviation_sampling: Option<f64>,
+}
+
+impl ExtendedStats {
+    pub(crate) fn get_value(&self, agg_property: &str) -> crate::Result<Option<f64>> {
+        match agg_property {
+            "count" => Ok(Some(self.count as f64)),
+            "sum" => Ok(Some(self.sum)),
+            "min" => Ok(self.min),
+            "max" => Ok(self.max),
+            "avg" => Ok(self.avg),
+            "sum_of_squares" => Ok(Some(self.sum_of_squares)),
+            "variance" => Ok(self.variance),
+            "variance_population" => Ok(self.variance_population),
+            "variance_sampling" => Ok(self.variance_sampling),
+            "std_deviation" => Ok(self.std_deviation),
+            "std_deviation_population" => Ok(self.std_deviation_population),
+            "std_deviation_sampling" => Ok(self.std_deviation_sampling),
+            _ => Err(TantivyError::InvalidArgument(format!(
+                "Unknown property {agg_property} on extended_stats metric aggregation"
+            ))),
+        }
+    }
+}
+
+/// Intermediate result of the extended stats aggregation that can be combined with other
+/// intermediate results.
+///
+/// The variance is tracked as the sum of the squared deviations from the mean, updated with
+/// Welford's algorithm, and merged with the parallel algorithm of Chan et al. Unlike the
+/// difference between the sum of squares and the squared sum, it does not suffer from
+/// catastrophic cancellation when the values are large compared to their spread.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IntermediateExtendedStats {
+    /// The number of extracted values.
+    count: u64,
+    /// The sum of the extracted values.
+    sum: f64,
+    /// The sum of the squares of the extracted values.
+    sum_of_squares: f64,
+    /// The min value.
+    min: f64,
+    /// The max value.
+    max: f64,
+    /// The mean of the extracted values.
+    mean: f64,
+    /// The sum of the squared deviations from the mean.
+    squared_deviations: f64,
+}
+
+impl Default for IntermediateExtendedStats {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            sum: 0.0,
+            sum_of_squares: 0.0,
+            min: f64::MAX,
+            max: f64::MIN,
+            mean: 0.0,
+            squared_deviations: 0.0,
+        }
+    }
+}
+
+impl IntermediateExtendedStats {
+    /// Merges the other extended stats intermediate result into self.
+    pub fn merge_fruits(&mut self, other: IntermediateExtendedStats) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = other;
+            return;
+        }
+        let count = self.count + other.count;
+        let delta = other.mean - self.mean;
+        self.mean += delta * other.count as f64 / count as f64;
+        self.squared_deviations += other.squared_deviations
+            + delta * delta * (self.count as f64 * other.count as f64 / count as f64);
+        self.count = count;
+        self.sum += other.sum;
+        self.sum_of_squares += other.sum_of_squares;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    /// Computes the final extended stats value.
+    pub fn finalize(&self) -> ExtendedStats {
+        let if_not_empty = |val: f64| if self.count == 0 { None } else { Some(val) };
+        let variance_population = if_not_empty(self.squared_deviations / self.count as f64);
+        let variance_sampling = if self.count < 2 {
+            None
+        } else {
+            Some(self.squared_deviations / (self.count - 1) as f64)
+        };
+        ExtendedStats {
+            count: self.count,
+            sum: self.sum,
+            min: if_not_empty(self.min),
+            max: if_not_empty(self.max),
+            avg: if_not_empty(self.sum / self.count as f64),
+            sum_of_squares: self.sum_of_squares,
+            variance: variance_population,
+            variance_population,
+            variance_sampling,
+            std_deviation: variance_population.map(f64::sqrt),
+            std_deviation_population: variance_population.map(f64::sqrt),
+            std_deviation_sampling: variance_sampling.map(f64::sqrt),
+        }
+    }
+
+    #[inline]
+    fn collect(&mut self, value: f64) {
+        self.count += 1;
+        self.sum += value;
+        self.sum_of_squares += value * value;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.squared_deviations += delta * (value - self.mean);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct SegmentExtendedStatsCollector {
+    missing: Option<u64>,
+    field_type: ColumnType,
+    pub(crate) extended_stats: IntermediateExtendedStats,
+    pub(crate) accessor_idx: usize,
+}
+
+impl SegmentExtendedStatsCollector {
+    pub fn from_req(field_type: ColumnType, accessor_idx: usize, missing: Option<f64>) -> Self {
+        let missing = missing.and_then(|val| f64_to_fastfield_u64(val, &field_type));
+        Self {
+            field_type,
+            extended_stats: IntermediateExtendedStats::default(),
+            accessor_idx,
+            missing,
+        }
+    }
+    #[inline]
+    pub(crate) fn collect_block_with_field(
+        &mut self,
+        docs: &[DocId],
+        agg_accessor: &mut AggregationWithAccessor,
+    ) {
+        if let Some(missing) = self.missing.as_ref() {
+            agg_accessor.column_block_accessor.fetch_block_with_missing(
+                docs,
+                &agg_accessor.accessor,
+                *missing,
+            );
+        } else {
+            agg_accessor
+                .column_block_accessor
+                .fetch_block(docs, &agg_accessor.accessor);
+        }
+        for val in agg_accessor.column_block_accessor.iter_vals() {
+            let val1 = f64_from_fastfield_u64(val, &self.field_type);
+            self.extended_stats.collect(val1);
+        }
+    }
+}
+
+impl SegmentAggregationCollector for SegmentExtendedStatsCollector {
+    #[inline]
+    fn add_intermediate_aggregation_result(
+        self: Box<Self>,
+        agg_with_accessor: &AggregationsWithAccessor,
+        results: &mut IntermediateAggregationResults,
+    ) -> crate::Result<()> {
+        let name = agg_with_accessor.aggs.keys[self.accessor_idx].to_string();
+        let intermediate_metric_result =
+            IntermediateMetricResult::ExtendedStats(self.extended_stats);
+
+        results.push(
+            name,
+            IntermediateAggregationResult::Metric(intermediate_metric_result),
+        )?;
+
+        Ok(())
+    }
+
+    #[inline]
+    fn collect(
+        &mut self,
+        doc: crate::DocId,
+        agg_with_accessor: &mut AggregationsWithAccessor,
+    ) -> crate::Result<()> {
+        let field = &agg_with_accessor.aggs.values[self.accessor_idx].accessor;
+        let mut has_val = false;
+        for val in field.values_for_doc(doc) {
+            let val1 = f64_from_fastfield_u64(val, &self.field_type);
+            self.extended_stats.collect(val1);
+            has_val = true;
+        }
+        if let Some(missing) = self.missing {
+            if !has_val {
+                self.extended_stats
+                    .collect(f64_from_fastfield_u64(missing, &self.field_type));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn collect_block(
+        &mut self,
+        docs: &[crate::DocId],
+        agg_with_accessor: &mut AggregationsWithAccessor,
+    ) -> crate::Result<()> {
+        let field = &mut agg_with_accessor.aggs.values[self.accessor_idx];
+        self.collect_block_with_field(docs, field);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::IntermediateExtendedStats;
+    use crate::aggregation::agg_req::Aggregations;
+    use crate::aggregation::tests::{
+        exec_request_with_query, get_test_index_from_values, get_test_index_from_values_and_terms,
+    };
+    use crate::assert_nearly_equals;
+
+    fn extended_stats_request(field: &str) -> Aggregations {
+        serde_json::from_value(json!({
+            "extended_stats": {
+                "extended_stats": {
+                    "field": field,
+                },
+            }
+        }))
+        .unwrap()
+    }
+
+    fn assert_extended_stats(res: &Value, expected: Value) {
+        let expected = expected.as_object().unwrap();
+        assert_eq!(res.as_object().unwrap().len(), expected.len());
+        for (key, expected_value) in expected {
+            match expected_value.as_f64() {
+                Some(expected_value) => {
+                    let value = res[key]
+                        .as_f64()
+                        .unwrap_or_else(|| panic!("{key} is missing"));
+                    assert_nearly_equals!(value, expected_value);
+                }
+                None => assert_eq!(&res[key], expected_value, "{key}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_aggregation_extended_stats_empty_index() -> crate::Result<()> {
+        let index = get_test_index_from_values(false, &[])?;
+        let res = exec_request_with_query(extended_stats_request("score"), &index, None)?;
+        assert_eq!(
+            res["extended_stats"],
+            json!({
+                "avg": Value::Null,
+                "count": 0,
+                "max": Value::Null,
+                "min": Value::Null,
+                "sum": 0.0,
+                "sum_of_squares": 0.0,
+                "variance": Value::Null,
+                "variance_population": Value::Null,
+                "variance_sampling": Value::Null,
+                "std_deviation": Value::Null,
+                "std_deviation_population": Value::Null,
+                "std_deviation_sampling": Value::Null,
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_aggregation_extended_stats_negative_values() -> crate::Result<()> {
+        let segment_and_values = vec![
+            vec![(-10.0, "a".to_string()), (-3.0, "a".to_string())],
+            vec![(0.0, "b".to_string())],
+            vec![(5.0, "b".to_string()), (8.0, "b".to_string())],
+        ];
+        for merge_segments in [false, true] {
+            let index = get_test_index_from_values_and_terms(merge_segments, &segment_and_values)?;
+            for field in ["score_f64", "score_i64"] {
+                let res = exec_request_with_query(extended_stats_request(field), &index, None)?;
+                // The squared deviations are 100 + 9 + 0 + 25 + 64 = 198.
+                assert_extended_stats(
+                    &res["extended_stats"],
+                    json!({
+                        "avg": 0.0,
+                        "count": 5,
+                        "max": 8.0,
+                        "min": -10.0,
+                        "sum": 0.0,
+                        "sum_of_squares": 198.0,
+                        "variance": 39.6,
+                        "variance_population": 39.6,
+                        "variance_sampling": 49.5,
+                        "std_deviation": 39.6f64.sqrt(),
+                        "std_deviation_population": 39.6f64.sqrt(),
+                        "std_deviation_sampling": 49.5f64.sqrt(),
+                    }),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_aggregation_extended_stats_single_document_bucket() -> crate::Result<()> {
+        let segment_and_values = vec![
+            vec![(-2.0, "single".to_string()), (4.0, "pair".to_string())],
+            vec![(-6.0, "pair".to_string())],
+        ];
+        let index = get_test_index_from_values_and_terms(false, &segment_and_values)?;
+        let agg_req: Aggregations = serde_json::from_value(json!({
+            "terms": {
+                "terms": { "field": "string_id" },
+                "aggs": {
+                    "extended_stats": { "extended_stats": { "field": "score_f64" } }
+                }
+            }
+        }))
+        .unwrap();
+        let res = exec_request_with_query(agg_req, &index, None)?;
+        let buckets = &res["terms"]["buckets"];
+        assert_eq!(buckets[0]["key"], "pair");
+        assert_extended_stats(
+            &buckets[0]["extended_stats"],
+            json!({
+                "avg": -1.0,
+                "count": 2,
+                "max": 4.0,
+                "min": -6.0,
+                "sum": -2.0,
+                "sum_of_squares": 52.0,
+                "variance": 25.0,
+                "variance_population": 25.0,
+                "variance_sampling": 50.0,
+                "std_deviation": 5.0,
+                "std_deviation_population": 5.0,
+                "std_deviation_sampling": 50f64.sqrt(),
+            }),
+        );
+        assert_eq!(buckets[1]["key"], "single");
+        assert_eq!(
+            buckets[1]["extended_stats"],
+            json!({
+                "avg": -2.0,
+                "count": 1,
+                "max": -2.0,
+                "min": -2.0,
+                "sum": -2.0,
+                "sum_of_squares": 4.0,
+                "variance": 0.0,
+                "variance_population": 0.0,
+                "variance_sampling": Value::Null,
+                "std_deviation": 0.0,
+                "std_deviation_population": 0.0,
+                "std_deviation_sampling": Value::Null,
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_extended_stats_merge_is_numerically_stable() {
+        // The squares of the values are around 1e18, where a f64 has a precision of 128, so that
+        // computing the variance from the sum of squares would be meaningless.
+        let values = [4.0, 7.0, 13.0, 16.0].map(|val| 1.0e9 + val);
+        let mut merged = IntermediateExtendedStats::default();
+        for val in values {
+            let mut segment_stats = IntermediateExtendedStats::default();
+            segment_stats.collect(val);
+            merged.merge_fruits(segment_stats);
+        }
+        let mut collected = IntermediateExtendedStats::default();
+        for val in values {
+            collected.collect(val);
+        }
+        for extended_stats in [merged.finalize(), collected.finalize()] {
+            assert_nearly_equals!(extended_stats.variance_population.unwrap(), 22.5);
+            assert_nearly_equals!(extended_stats.variance_sampling.unwrap(), 30.0);
+        }
+    }
+}
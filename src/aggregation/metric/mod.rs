@@ -10,25 +10,33 @@
 //! ## Supported Metric Aggregations
 //! - [Average](AverageAggregation)
 //! - [Stats](StatsAggregation)
+//! - [ExtendedStats](ExtendedStatsAggregation)
 //! - [Min](MinAggregation)
 //! - [Max](MaxAggregation)
 //! - [Sum](SumAggregation)
 //! - [Count](CountAggregation)
 //! - [Percentiles](PercentilesAggregationReq)
+//! - [Cardinality](CardinalityAggregationReq)
+//! - [WeightedAverage](WeightedAverageAggregation)
 
 mod average;
+mod cardinality;
 mod count;
+mod extended_stats;
 mod max;
 mod min;
 mod percentiles;
 mod stats;
 mod sum;
 mod top_hits;
+mod weighted_average;
 
 use std::collections::HashMap;
 
 pub use average::*;
+pub use cardinality::*;
 pub use count::*;
+pub use extended_stats::*;
 pub use max::*;
 pub use min::*;
 pub use percentiles::*;
@@ -37,8 +45,10 @@ use serde::{Deserialize, Serialize};
 pub use stats::*;
 pub use sum::*;
 pub use top_hits::*;
+pub use weighted_average::*;
 
 use crate::schema::OwnedValue;
+use crate::DocAddress;
 
 /// Single-metric aggregations use this common result structure.
 ///
@@ -99,6 +109,14 @@ pub struct TopHitsVecEntry {
     #[serde(rename = "docvalue_fields")]
     #[serde(skip_serializing_if = "HashMap::is_empty")]
     pub doc_value_fields: HashMap<String, OwnedValue>,
+
+    /// The address of the document, which can be used to load its stored fields with
+    /// [`Searcher::doc`](crate::Searcher::doc).
+    ///
+    /// It is only meaningful for the searcher that ran the aggregation, and is therefore not
+    /// serialized.
+    #[serde(skip)]
+    pub doc_address: Option<DocAddress>,
 }
 
 /// The top_hits metric aggregation results a list of top hits by sort criteria.
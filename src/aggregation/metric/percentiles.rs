@@ -69,6 +69,20 @@ use crate::{DocId, TantivyError};
 /// calculating exact percentiles for large data sets can be computationally
 /// expensive and time-consuming. As a result, many percentile aggregation
 /// algorithms use approximation techniques to provide faster results.
+///
+/// The percentiles are estimated with a [DDSketch](https://arxiv.org/abs/1908.10693), whose
+/// memory usage does not depend on the number of values. The estimations are within a relative
+/// error of the exact percentiles, that can be set with the `relative_accuracy` parameter. It plays
+/// the role of the `compression` parameter of a t-digest:
+///
+/// ```JSON
+/// {
+///     "percentiles": {
+///         "field": "load_time",
+///         "relative_accuracy": 0.05
+///     }
+/// }
+/// ```
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct PercentilesAggregationReq {
     /// The field name to compute the percentiles on.
@@ -89,6 +103,12 @@ pub struct PercentilesAggregationReq {
         deserialize_with = "deserialize_option_f64"
     )]
     pub missing: Option<f64>,
+    /// The maximum relative error of the estimated percentiles, between 0.0 and 1.0 excluded.
+    /// Defaults to 0.01.
+    ///
+    /// Lower values give more accurate percentiles, at the cost of more memory.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub relative_accuracy: Option<f64>,
 }
 fn default_percentiles() -> &'static [f64] {
     &[1.0, 5.0, 25.0, 50.0, 75.0, 95.0, 99.0]
@@ -105,6 +125,7 @@ impl PercentilesAggregationReq {
             percents: None,
             keyed: default_as_true(),
             missing: None,
+            relative_accuracy: None,
         }
     }
     /// Returns the field name the aggregation is computed on.
@@ -126,6 +147,16 @@ impl PercentilesAggregationReq {
                 ));
             }
         }
+        if let Some(relative_accuracy) = self.relative_accuracy {
+            if relative_accuracy <= 0.0 || relative_accuracy >= 1.0 {
+                return Err(TantivyError::AggregationError(
+                    AggregationError::InvalidRequest(format!(
+                        "The relative accuracy of percentiles has to be between 0.0 and 1.0 \
+                         excluded, got {relative_accuracy}"
+                    )),
+                ));
+            }
+        }
 
         Ok(())
     }
@@ -215,6 +246,19 @@ impl PercentilesCollector {
         let sketch = sketches_ddsketch::DDSketch::new(ddsketch_config);
         Self { sketch }
     }
+
+    /// Creates an empty collector, with the accuracy of the request.
+    ///
+    /// Sketches can only be merged if they share the same configuration.
+    pub(crate) fn for_req(req: &PercentilesAggregationReq) -> Self {
+        let Some(relative_accuracy) = req.relative_accuracy else {
+            return Self::new();
+        };
+        // The other parameters are the ones of `Config::defaults()`.
+        let ddsketch_config = sketches_ddsketch::Config::new(relative_accuracy, 2048, 1.0e-9);
+        let sketch = sketches_ddsketch::DDSketch::new(ddsketch_config);
+        Self { sketch }
+    }
     fn collect(&mut self, val: f64) {
         self.sketch.add(val);
     }
@@ -243,7 +287,7 @@ impl SegmentPercentilesCollector {
 
         Ok(Self {
             field_type,
-            percentiles: PercentilesCollector::new(),
+            percentiles: PercentilesCollector::for_req(req),
             accessor_idx,
             missing,
         })
@@ -583,6 +627,81 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_percentiles_relative_accuracy_sub_agg() -> crate::Result<()> {
+        let segment_values = |range: std::ops::RangeInclusive<u64>, term: &str| {
+            range
+                .map(|val| (val as f64, term.to_string()))
+                .collect_vec()
+        };
+        let segment_and_values = vec![
+            [
+                segment_values(1..=50, "a"),
+                segment_values(1000..=1050, "b"),
+            ]
+            .concat(),
+            [
+                segment_values(51..=100, "a"),
+                segment_values(1051..=1100, "b"),
+            ]
+            .concat(),
+        ];
+        let index = get_test_index_from_values_and_terms(false, &segment_and_values)?;
+
+        let agg_req: Aggregations = serde_json::from_value(json!({
+            "endpoints": {
+                "terms": {
+                    "field": "string_id",
+                    "order": { "_key": "asc" }
+                },
+                "aggs": {
+                    "latency": {
+                        "percentiles": {
+                            "field": "score_f64",
+                            "percents": [50.0, 95.0],
+                            "relative_accuracy": 0.05
+                        }
+                    }
+                }
+            }
+        }))
+        .unwrap();
+        let res = exec_request_with_query(agg_req, &index, None)?;
+
+        let check_percentile = |bucket: &Value, percent: &str, exact: f64| {
+            let val = bucket["latency"]["values"][percent].as_f64().unwrap();
+            assert_le!(val, exact * 1.05);
+            assert_ge!(val, exact * 0.95);
+        };
+        let buckets = &res["endpoints"]["buckets"];
+        assert_eq!(buckets[0]["key"], "a");
+        check_percentile(&buckets[0], "50.0", 50.0);
+        check_percentile(&buckets[0], "95.0", 95.0);
+        assert_eq!(buckets[1]["key"], "b");
+        check_percentile(&buckets[1], "50.0", 1050.0);
+        check_percentile(&buckets[1], "95.0", 1095.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_percentiles_invalid_relative_accuracy() -> crate::Result<()> {
+        let index = get_test_index_from_values(false, &[10.0])?;
+        let agg_req: Aggregations = serde_json::from_value(json!({
+            "mypercentiles": {
+                "percentiles": {
+                    "field": "score",
+                    "relative_accuracy": 1.0
+                }
+            }
+        }))
+        .unwrap();
+        let err = exec_request_with_query(agg_req, &index, None).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("The relative accuracy of percentiles has to be between 0.0 and 1.0"));
+        Ok(())
+    }
+
     #[test]
     fn test_percentiles_missing_sub_agg() -> crate::Result<()> {
         // This test verifies the `collect` method (in contrast to `collect_block`), which is
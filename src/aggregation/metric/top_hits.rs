@@ -33,13 +33,18 @@ use crate::{DocAddress, DocId, SegmentOrdinal};
 /// used as a sub-aggregation, inside a `terms` aggregation or a `filters` aggregation,
 /// for example.
 ///
-/// Note that this aggregator does not return the actual document addresses, but
-/// rather a list of the values of the fields that were requested to be retrieved.
+/// The serialized result does not contain the document addresses, but rather a list of
+/// the values of the fields that were requested to be retrieved.
 /// These values can be specified in the `docvalue_fields` parameter, which can include
 /// a list of fast fields to be retrieved. At the moment, only fast fields are supported
 /// but it is possible that we support the `fields` parameter to retrieve any stored
 /// field in the future.
 ///
+/// Stored fields can be loaded from the
+/// [`doc_address`](super::TopHitsVecEntry::doc_address) of the hits of the
+/// [`AggregationResults`](crate::aggregation::agg_result::AggregationResults), with
+/// [`Searcher::doc`](crate::Searcher::doc).
+///
 /// The following example demonstrates a request for the top_hits aggregation:
 /// ```JSON
 /// {
@@ -475,6 +480,7 @@ impl TopHitsTopNComputer {
                     .into_iter()
                     .map(|(k, v)| (k, v.into()))
                     .collect(),
+                doc_address: Some(doc.doc),
             })
             .collect();
 
@@ -605,13 +611,16 @@ mod tests {
 
     use super::{DocSortValuesAndFields, DocValueAndOrder, Order};
     use crate::aggregation::agg_req::Aggregations;
-    use crate::aggregation::agg_result::AggregationResults;
+    use crate::aggregation::agg_result::{
+        AggregationResult, AggregationResults, BucketResult, MetricResult,
+    };
     use crate::aggregation::bucket::tests::get_test_index_from_docs;
     use crate::aggregation::tests::get_test_index_from_values;
     use crate::aggregation::AggregationCollector;
     use crate::collector::ComparableDoc;
     use crate::query::AllQuery;
-    use crate::schema::OwnedValue;
+    use crate::schema::{OwnedValue, Schema, Value as _, FAST, STORED, STRING};
+    use crate::{Index, IndexWriter, TantivyDocument};
 
     fn invert_order(cmp_feature: DocValueAndOrder) -> DocValueAndOrder {
         let DocValueAndOrder { value, order } = cmp_feature;
@@ -793,14 +802,17 @@ mod tests {
                     super::TopHitsVecEntry {
                         sort: vec![docs[0].feature.sorts[0].value],
                         doc_value_fields: Default::default(),
+                        doc_address: Some(docs[0].doc),
                     },
                     super::TopHitsVecEntry {
                         sort: vec![docs[1].feature.sorts[0].value],
                         doc_value_fields: Default::default(),
+                        doc_address: Some(docs[1].doc),
                     },
                     super::TopHitsVecEntry {
                         sort: vec![docs[2].feature.sorts[0].value],
                         doc_value_fields: Default::default(),
+                        doc_address: Some(docs[2].doc),
                     },
                 ]
             }
@@ -879,6 +891,83 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_aggregation_top_hits_nested_in_terms() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let category = schema_builder.add_text_field("category", STRING | FAST);
+        let name = schema_builder.add_text_field("name", STORED);
+        let price = schema_builder.add_u64_field("price", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        index_writer.add_document(doc!(category => "shoes", name => "boots", price => 5u64))?;
+        index_writer.add_document(doc!(category => "shoes", name => "socks", price => 1u64))?;
+        index_writer.add_document(doc!(category => "hats", name => "cap", price => 7u64))?;
+        index_writer.commit()?;
+        index_writer.add_document(doc!(category => "shoes", name => "heels", price => 9u64))?;
+        index_writer.add_document(doc!(category => "hats", name => "beret", price => 3u64))?;
+        index_writer.add_document(doc!(category => "hats", name => "beanie", price => 4u64))?;
+        index_writer.commit()?;
+
+        let agg_req: Aggregations = serde_json::from_value(json!({
+            "categories": {
+                "terms": { "field": "category", "order": { "_key": "asc" } },
+                "aggs": {
+                    "top_products": {
+                        "top_hits": {
+                            "size": 2,
+                            "sort": [ { "price": "desc" } ],
+                        }
+                    }
+                }
+            }
+        }))?;
+
+        let collector = AggregationCollector::from_aggs(agg_req, Default::default());
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+        let agg_res = searcher.search(&AllQuery, &collector)?;
+
+        let AggregationResult::BucketResult(BucketResult::Terms { buckets, .. }) =
+            &agg_res.0["categories"]
+        else {
+            panic!("unexpected aggregation result");
+        };
+        let top_products_per_category: Vec<(String, Vec<String>)> = buckets
+            .iter()
+            .map(|bucket| {
+                let AggregationResult::MetricResult(MetricResult::TopHits(top_hits)) =
+                    &bucket.sub_aggregation.0["top_products"]
+                else {
+                    panic!("unexpected sub aggregation result");
+                };
+                // The stored fields of the hits are loaded from their address.
+                let names = top_hits
+                    .hits
+                    .iter()
+                    .map(|hit| {
+                        let doc: TantivyDocument = searcher.doc(hit.doc_address.unwrap()).unwrap();
+                        doc.get_first(name).unwrap().as_str().unwrap().to_string()
+                    })
+                    .collect();
+                (bucket.key.to_string(), names)
+            })
+            .collect();
+        assert_eq!(
+            top_products_per_category,
+            vec![
+                (
+                    "hats".to_string(),
+                    vec!["cap".to_string(), "beanie".to_string()]
+                ),
+                (
+                    "shoes".to_string(),
+                    vec!["heels".to_string(), "boots".to_string()]
+                ),
+            ]
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_aggregation_top_hits_single_segment() -> crate::Result<()> {
         test_aggregation_top_hits(true)
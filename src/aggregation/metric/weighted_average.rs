@@ -0,0 +1,280 @@
+use columnar::ColumnType;
+use serde::{Deserialize, Serialize};
+
+use crate::aggregation::agg_req_with_accessor::AggregationsWithAccessor;
+use crate::aggregation::intermediate_agg_result::{
+    IntermediateAggregationResult, IntermediateAggregationResults, IntermediateMetricResult,
+};
+use crate::aggregation::segment_agg_result::SegmentAggregationCollector;
+use crate::aggregation::*;
+
+/// A single-value metric aggregation that computes the average of numeric values that are
+/// extracted from the aggregated documents, weighted by the values of another field:
+/// `sum(value * weight) / sum(weight)`.
+/// See [super::SingleMetricResult] for return value. The value is `None` if the sum of the
+/// weights is zero.
+///
+/// The value field may have several values per document, which all get the weight of the
+/// document. Only the first value of the weight field is used.
+/// Documents without a value, or without a weight, are ignored unless a `missing` value is
+/// provided.
+///
+/// # JSON Format
+/// ```json
+/// {
+///     "weighted_avg": {
+///         "value": { "field": "rating" },
+///         "weight": { "field": "num_reviews", "missing": 1.0 }
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WeightedAverageAggregation {
+    /// The field providing the values to average.
+    pub value: WeightedAverageField,
+    /// The field providing the weights of the values.
+    pub weight: WeightedAverageField,
+}
+
+/// A field of the [`WeightedAverageAggregation`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WeightedAverageField {
+    /// The field name.
+    pub field: String,
+    /// The missing parameter defines how documents that are missing a value should be treated.
+    /// By default they will be ignored but it is also possible to treat them as if they had a
+    /// value. Examples in JSON format:
+    /// { "field": "my_numbers", "missing": "10.0" }
+    #[serde(default, deserialize_with = "deserialize_option_f64")]
+    pub missing: Option<f64>,
+}
+
+impl WeightedAverageAggregation {
+    /// Creates a new [`WeightedAverageAggregation`] instance from the field names of the values
+    /// and the weights.
+    pub fn from_field_names(value_field_name: String, weight_field_name: String) -> Self {
+        Self {
+            value: WeightedAverageField {
+                field: value_field_name,
+                missing: None,
+            },
+            weight: WeightedAverageField {
+                field: weight_field_name,
+                missing: None,
+            },
+        }
+    }
+    /// Returns the field names the aggregation is computed on, the value field first.
+    pub fn field_names(&self) -> Vec<&str> {
+        vec![self.value.field.as_str(), self.weight.field.as_str()]
+    }
+}
+
+/// Intermediate result of the weighted average aggregation that can be combined with other
+/// intermediate results.
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IntermediateWeightedAverage {
+    /// The sum of the values multiplied by their weight.
+    weighted_sum: f64,
+    /// The sum of the weights.
+    weight_sum: f64,
+}
+
+impl IntermediateWeightedAverage {
+    /// Merges the other intermediate result into self.
+    pub fn merge_fruits(&mut self, other: IntermediateWeightedAverage) {
+        self.weighted_sum += other.weighted_sum;
+        self.weight_sum += other.weight_sum;
+    }
+    /// Computes the final weighted average value.
+    pub fn finalize(&self) -> Option<f64> {
+        if self.weight_sum == 0.0 {
+            return None;
+        }
+        Some(self.weighted_sum / self.weight_sum)
+    }
+
+    #[inline]
+    fn collect(&mut self, value: f64, weight: f64) {
+        self.weighted_sum += value * weight;
+        self.weight_sum += weight;
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct SegmentWeightedAverageCollector {
+    value_missing: Option<f64>,
+    weight_missing: Option<f64>,
+    value_type: ColumnType,
+    weight_type: ColumnType,
+    weighted_average: IntermediateWeightedAverage,
+    accessor_idx: usize,
+}
+
+impl SegmentWeightedAverageCollector {
+    pub fn from_req(
+        req: &WeightedAverageAggregation,
+        value_type: ColumnType,
+        weight_type: ColumnType,
+        accessor_idx: usize,
+    ) -> Self {
+        Self {
+            value_missing: req.value.missing,
+            weight_missing: req.weight.missing,
+            value_type,
+            weight_type,
+            weighted_average: IntermediateWeightedAverage::default(),
+            accessor_idx,
+        }
+    }
+}
+
+impl SegmentAggregationCollector for SegmentWeightedAverageCollector {
+    #[inline]
+    fn add_intermediate_aggregation_result(
+        self: Box<Self>,
+        agg_with_accessor: &AggregationsWithAccessor,
+        results: &mut IntermediateAggregationResults,
+    ) -> crate::Result<()> {
+        let name = agg_with_accessor.aggs.keys[self.accessor_idx].to_string();
+        let intermediate_metric_result =
+            IntermediateMetricResult::WeightedAverage(self.weighted_average);
+
+        results.push(
+            name,
+            IntermediateAggregationResult::Metric(intermediate_metric_result),
+        )?;
+
+        Ok(())
+    }
+
+    #[inline]
+    fn collect(
+        &mut self,
+        doc: crate::DocId,
+        agg_with_accessor: &mut AggregationsWithAccessor,
+    ) -> crate::Result<()> {
+        let accessors = &agg_with_accessor.aggs.values[self.accessor_idx].accessors;
+        let (value_column, weight_column) = (&accessors[0].0, &accessors[1].0);
+
+        let weight = weight_column
+            .first(doc)
+            .map(|weight| f64_from_fastfield_u64(weight, &self.weight_type))
+            .or(self.weight_missing);
+        let Some(weight) = weight else {
+            return Ok(());
+        };
+        let mut has_val = false;
+        for val in value_column.values_for_doc(doc) {
+            let val = f64_from_fastfield_u64(val, &self.value_type);
+            self.weighted_average.collect(val, weight);
+            has_val = true;
+        }
+        if let Some(missing) = self.value_missing {
+            if !has_val {
+                self.weighted_average.collect(missing, weight);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn collect_block(
+        &mut self,
+        docs: &[crate::DocId],
+        agg_with_accessor: &mut AggregationsWithAccessor,
+    ) -> crate::Result<()> {
+        for doc in docs {
+            self.collect(*doc, agg_with_accessor)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use crate::aggregation::agg_req::Aggregations;
+    use crate::aggregation::tests::exec_request_with_query;
+    use crate::schema::{Schema, FAST, STRING};
+    use crate::{Index, IndexWriter};
+
+    fn get_test_index() -> crate::Result<Index> {
+        let mut schema_builder = Schema::builder();
+        let category = schema_builder.add_text_field("category", STRING | FAST);
+        let rating = schema_builder.add_f64_field("rating", FAST);
+        let num_reviews = schema_builder.add_u64_field("num_reviews", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        index_writer.add_document(doc!(category => "a", rating => 5.0, num_reviews => 1u64))?;
+        index_writer.add_document(doc!(category => "a", rating => 2.0, num_reviews => 3u64))?;
+        index_writer.commit()?;
+        index_writer.add_document(doc!(category => "b", rating => 4.0, num_reviews => 0u64))?;
+        index_writer.commit()?;
+        index_writer.add_document(doc!(category => "c", rating => 1.0))?;
+        index_writer.commit()?;
+        Ok(index)
+    }
+
+    #[test]
+    fn test_weighted_average() -> crate::Result<()> {
+        let index = get_test_index()?;
+        let agg_req: Aggregations = serde_json::from_value(json!({
+            "weighted": {
+                "weighted_avg": {
+                    "value": { "field": "rating" },
+                    "weight": { "field": "num_reviews" }
+                }
+            },
+            "weighted_with_missing": {
+                "weighted_avg": {
+                    "value": { "field": "rating" },
+                    "weight": { "field": "num_reviews", "missing": 1.0 }
+                }
+            },
+            "unweighted": { "avg": { "field": "rating" } }
+        }))
+        .unwrap();
+
+        let res = exec_request_with_query(agg_req, &index, None)?;
+        // (5 * 1 + 2 * 3 + 4 * 0) / (1 + 3 + 0)
+        assert_eq!(res["weighted"]["value"], 2.75);
+        // (5 * 1 + 2 * 3 + 4 * 0 + 1 * 1) / (1 + 3 + 0 + 1)
+        assert_eq!(res["weighted_with_missing"]["value"], 2.4);
+        assert_eq!(res["unweighted"]["value"], 3.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_weighted_average_sub_aggregation() -> crate::Result<()> {
+        let index = get_test_index()?;
+        let agg_req: Aggregations = serde_json::from_value(json!({
+            "categories": {
+                "terms": { "field": "category", "order": { "_key": "asc" } },
+                "aggs": {
+                    "weighted": {
+                        "weighted_avg": {
+                            "value": { "field": "rating" },
+                            "weight": { "field": "num_reviews" }
+                        }
+                    }
+                }
+            }
+        }))
+        .unwrap();
+
+        let res = exec_request_with_query(agg_req, &index, None)?;
+        let buckets = &res["categories"]["buckets"];
+        assert_eq!(buckets[0]["key"], "a");
+        assert_eq!(buckets[0]["weighted"]["value"], 2.75);
+        // The total weight is zero.
+        assert_eq!(buckets[1]["key"], "b");
+        assert_eq!(buckets[1]["weighted"]["value"], Value::Null);
+        // No weight.
+        assert_eq!(buckets[2]["key"], "c");
+        assert_eq!(buckets[2]["weighted"]["value"], Value::Null);
+        Ok(())
+    }
+}
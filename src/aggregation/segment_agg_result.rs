@@ -11,8 +11,9 @@ use super::agg_req_with_accessor::{AggregationWithAccessor, AggregationsWithAcce
 use super::bucket::{SegmentHistogramCollector, SegmentRangeCollector, SegmentTermCollector};
 use super::intermediate_agg_result::IntermediateAggregationResults;
 use super::metric::{
-    AverageAggregation, CountAggregation, MaxAggregation, MinAggregation,
-    SegmentPercentilesCollector, SegmentStatsCollector, SegmentStatsType, StatsAggregation,
+    AverageAggregation, CountAggregation, ExtendedStatsAggregation, MaxAggregation, MinAggregation,
+    SegmentCardinalityCollector, SegmentExtendedStatsCollector, SegmentPercentilesCollector,
+    SegmentStatsCollector, SegmentStatsType, SegmentWeightedAverageCollector, StatsAggregation,
     SumAggregation,
 };
 use crate::aggregation::bucket::TermMissingAgg;
@@ -124,6 +125,14 @@ pub(crate) fn build_single_agg_segment_collector(
                 *missing,
             )))
         }
+        WeightedAverage(weighted_avg_req) => {
+            Ok(Box::new(SegmentWeightedAverageCollector::from_req(
+                weighted_avg_req,
+                req.accessors[0].1,
+                req.accessors[1].1,
+                accessor_idx,
+            )))
+        }
         Count(CountAggregation { missing, .. }) => Ok(Box::new(SegmentStatsCollector::from_req(
             req.field_type,
             SegmentStatsType::Count,
@@ -148,6 +157,9 @@ pub(crate) fn build_single_agg_segment_collector(
             accessor_idx,
             *missing,
         ))),
+        ExtendedStats(ExtendedStatsAggregation { missing, .. }) => Ok(Box::new(
+            SegmentExtendedStatsCollector::from_req(req.field_type, accessor_idx, *missing),
+        )),
         Sum(SumAggregation { missing, .. }) => Ok(Box::new(SegmentStatsCollector::from_req(
             req.field_type,
             SegmentStatsType::Sum,
@@ -161,6 +173,11 @@ pub(crate) fn build_single_agg_segment_collector(
                 accessor_idx,
             )?,
         )),
+        Cardinality(cardinality_req) => Ok(Box::new(SegmentCardinalityCollector::from_req(
+            cardinality_req,
+            req.field_type,
+            accessor_idx,
+        ))),
         TopHits(top_hits_req) => Ok(Box::new(TopHitsSegmentCollector::from_req(
             top_hits_req,
             accessor_idx,
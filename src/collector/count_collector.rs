@@ -1,5 +1,7 @@
 use super::Collector;
 use crate::collector::SegmentCollector;
+use crate::docset::{DocSet, TERMINATED};
+use crate::query::Weight;
 use crate::{DocId, Score, SegmentOrdinal, SegmentReader};
 
 /// `CountCollector` collector only counts how many
@@ -33,6 +35,11 @@ use crate::{DocId, Score, SegmentOrdinal, SegmentReader};
 ///
 /// assert_eq!(count, 2);
 /// ```
+///
+/// Documents are not scored, and when used on its own, the collector relies on
+/// [`Weight::count`], which some queries implement without iterating over their documents.
+///
+/// See [`CountUpTo`] to stop counting after a given number of documents.
 pub struct Count;
 
 impl Collector for Count {
@@ -55,6 +62,15 @@ impl Collector for Count {
     fn merge_fruits(&self, segment_counts: Vec<usize>) -> crate::Result<usize> {
         Ok(segment_counts.into_iter().sum())
     }
+
+    fn collect_segment(
+        &self,
+        weight: &dyn Weight,
+        _segment_ord: u32,
+        reader: &SegmentReader,
+    ) -> crate::Result<usize> {
+        Ok(weight.count(reader)? as usize)
+    }
 }
 
 #[derive(Default)]
@@ -69,19 +85,216 @@ impl SegmentCollector for SegmentCountCollector {
         self.count += 1;
     }
 
+    fn collect_block(&mut self, docs: &[DocId]) {
+        self.count += docs.len();
+    }
+
     fn harvest(self) -> usize {
         self.count
     }
 }
 
+/// Count of documents returned by the [`CountUpTo`] collector.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CappedCount {
+    /// The number of matching documents, capped at the limit of the collector.
+    pub count: usize,
+    /// True if more than `limit` documents match the query.
+    pub has_more: bool,
+}
+
+/// `CountUpTo` counts the documents matching a query, up to a limit.
+///
+/// It stops iterating over the documents of a segment as soon as the limit is exceeded,
+/// which is cheaper than [`Count`] when many documents match, e.g. to display "1000+ results".
+///
+/// ```rust
+/// use tantivy::collector::{CappedCount, CountUpTo};
+/// use tantivy::query::QueryParser;
+/// use tantivy::schema::{Schema, TEXT};
+/// use tantivy::{doc, Index};
+///
+/// # fn main() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let title = schema_builder.add_text_field("title", TEXT);
+/// let index = Index::create_in_ram(schema_builder.build());
+/// let mut index_writer = index.writer(15_000_000)?;
+/// index_writer.add_document(doc!(title => "The Diary of Muadib"))?;
+/// index_writer.add_document(doc!(title => "The Diary of a Young Girl"))?;
+/// index_writer.add_document(doc!(title => "The Diary of Lena Mukhina"))?;
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// let query = QueryParser::for_index(&index, vec![title]).parse_query("diary")?;
+/// let count = searcher.search(&query, &CountUpTo::new(2))?;
+/// assert_eq!(count, CappedCount { count: 2, has_more: true });
+/// # Ok(())
+/// # }
+/// ```
+pub struct CountUpTo {
+    limit: usize,
+}
+
+impl CountUpTo {
+    /// Creates a `CountUpTo` collector counting at most `limit` documents.
+    pub fn new(limit: usize) -> CountUpTo {
+        CountUpTo { limit }
+    }
+}
+
+impl Collector for CountUpTo {
+    type Fruit = CappedCount;
+
+    type Child = SegmentCountCollector;
+
+    fn for_segment(
+        &self,
+        _: SegmentOrdinal,
+        _: &SegmentReader,
+    ) -> crate::Result<SegmentCountCollector> {
+        Ok(SegmentCountCollector::default())
+    }
+
+    fn requires_scoring(&self) -> bool {
+        false
+    }
+
+    fn merge_fruits(&self, segment_counts: Vec<usize>) -> crate::Result<CappedCount> {
+        let count: usize = segment_counts.into_iter().sum();
+        Ok(CappedCount {
+            count: count.min(self.limit),
+            has_more: count > self.limit,
+        })
+    }
+
+    fn collect_segment(
+        &self,
+        weight: &dyn Weight,
+        _segment_ord: u32,
+        reader: &SegmentReader,
+    ) -> crate::Result<usize> {
+        let mut scorer = weight.scorer(reader, 1.0)?;
+        let alive_bitset_opt = reader.alive_bitset();
+        let mut count = 0;
+        let mut doc = scorer.doc();
+        // Counting one document past the limit is enough to know that there are more.
+        while doc != TERMINATED && count <= self.limit {
+            if alive_bitset_opt.map_or(true, |alive_bitset| alive_bitset.is_alive(doc)) {
+                count += 1;
+            }
+            doc = scorer.advance();
+        }
+        Ok(count)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{Count, SegmentCountCollector};
-    use crate::collector::{Collector, SegmentCollector};
+    use super::{CappedCount, Count, CountUpTo, SegmentCountCollector};
+    use crate::collector::{Collector, SegmentCollector, TopDocs};
+    use crate::query::{AllQuery, TermQuery};
+    use crate::schema::{IndexRecordOption, Schema, STRING};
+    use crate::{Index, IndexWriter, Term};
 
     #[test]
     fn test_count_collect_does_not_requires_scoring() {
         assert!(!Count.requires_scoring());
+        assert!(!CountUpTo::new(10).requires_scoring());
+    }
+
+    fn create_index() -> crate::Result<Index> {
+        let mut schema_builder = Schema::builder();
+        let kind = schema_builder.add_text_field("kind", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        for i in 0..20 {
+            let kind_val = if i % 2 == 0 { "even" } else { "odd" };
+            index_writer.add_document(doc!(kind => kind_val))?;
+            if i == 9 {
+                index_writer.commit()?;
+            }
+        }
+        index_writer.commit()?;
+        // Deleted documents are not counted.
+        index_writer.delete_term(Term::from_field_text(kind, "odd"));
+        index_writer.commit()?;
+        for _ in 0..3 {
+            index_writer.add_document(doc!(kind => "odd"))?;
+        }
+        index_writer.commit()?;
+        Ok(index)
+    }
+
+    #[test]
+    fn test_count_up_to() -> crate::Result<()> {
+        let index = create_index()?;
+        let kind = index.schema().get_field("kind").unwrap();
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.search(&AllQuery, &Count)?, 13);
+        for (limit, expected_count) in [
+            (
+                0,
+                CappedCount {
+                    count: 0,
+                    has_more: true,
+                },
+            ),
+            (
+                5,
+                CappedCount {
+                    count: 5,
+                    has_more: true,
+                },
+            ),
+            (
+                12,
+                CappedCount {
+                    count: 12,
+                    has_more: true,
+                },
+            ),
+            (
+                13,
+                CappedCount {
+                    count: 13,
+                    has_more: false,
+                },
+            ),
+            (
+                100,
+                CappedCount {
+                    count: 13,
+                    has_more: false,
+                },
+            ),
+        ] {
+            assert_eq!(
+                searcher.search(&AllQuery, &CountUpTo::new(limit))?,
+                expected_count
+            );
+        }
+        let odd_query =
+            TermQuery::new(Term::from_field_text(kind, "odd"), IndexRecordOption::Basic);
+        assert_eq!(searcher.search(&odd_query, &Count)?, 3);
+        assert_eq!(
+            searcher.search(&odd_query, &CountUpTo::new(3))?,
+            CappedCount {
+                count: 3,
+                has_more: false
+            }
+        );
+        // Combined with other collectors, the count is capped but documents are not skipped.
+        let (top_docs, count) =
+            searcher.search(&AllQuery, &(TopDocs::with_limit(20), CountUpTo::new(10)))?;
+        assert_eq!(top_docs.len(), 13);
+        assert_eq!(
+            count,
+            CappedCount {
+                count: 10,
+                has_more: true
+            }
+        );
+        Ok(())
     }
 
     #[test]
@@ -296,6 +296,143 @@ impl Collector for FacetCollector {
     }
 }
 
+/// Collector computing the facet counts of several facet fields in a single search.
+///
+/// Each field is configured as with a [`FacetCollector`], by calling
+/// [`MultiFieldFacetCollector::add_facet`]. The fields may request facets at different depths.
+///
+/// The fruit is a map from the field names to their [`FacetCounts`].
+///
+/// ```rust
+/// use tantivy::collector::MultiFieldFacetCollector;
+/// use tantivy::query::AllQuery;
+/// use tantivy::schema::{Facet, FacetOptions, Schema};
+/// use tantivy::{doc, Index};
+///
+/// # fn main() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let category = schema_builder.add_facet_field("category", FacetOptions::default());
+/// let brand = schema_builder.add_facet_field("brand", FacetOptions::default());
+/// let index = Index::create_in_ram(schema_builder.build());
+/// let mut index_writer = index.writer(15_000_000)?;
+/// index_writer.add_document(doc!(
+///     category => Facet::from("/electronics/phone"),
+///     brand => Facet::from("/acme"),
+/// ))?;
+/// index_writer.add_document(doc!(
+///     category => Facet::from("/electronics/laptop"),
+///     brand => Facet::from("/acme"),
+/// ))?;
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// let mut facet_collector = MultiFieldFacetCollector::default();
+/// facet_collector.add_facet("category", "/electronics");
+/// facet_collector.add_facet("brand", "/");
+/// let facet_counts = searcher.search(&AllQuery, &facet_collector)?;
+///
+/// let categories: Vec<(&Facet, u64)> = facet_counts["category"].get("/electronics").collect();
+/// assert_eq!(categories, vec![
+///     (&Facet::from("/electronics/laptop"), 1),
+///     (&Facet::from("/electronics/phone"), 1),
+/// ]);
+/// let brands: Vec<(&Facet, u64)> = facet_counts["brand"].get("/").collect();
+/// assert_eq!(brands, vec![(&Facet::from("/acme"), 2)]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct MultiFieldFacetCollector {
+    facet_collectors: BTreeMap<String, FacetCollector>,
+}
+
+impl MultiFieldFacetCollector {
+    /// Adds a facet of the field `field_name` that we want to record counts.
+    ///
+    /// See [`FacetCollector::add_facet`]. As there, adding two facets of the same field within
+    /// which one is the prefix of the other is forbidden.
+    pub fn add_facet<T>(&mut self, field_name: impl ToString, facet_from: T)
+    where Facet: From<T> {
+        let field_name = field_name.to_string();
+        self.facet_collectors
+            .entry(field_name.clone())
+            .or_insert_with(|| FacetCollector::for_field(field_name))
+            .add_facet(facet_from);
+    }
+}
+
+/// Segment collector of the [`MultiFieldFacetCollector`].
+pub struct MultiFieldFacetSegmentCollector {
+    // One per field, in the order of the field names.
+    segment_collectors: Vec<FacetSegmentCollector>,
+}
+
+impl Collector for MultiFieldFacetCollector {
+    type Fruit = BTreeMap<String, FacetCounts>;
+
+    type Child = MultiFieldFacetSegmentCollector;
+
+    fn for_segment(
+        &self,
+        segment_ord: SegmentOrdinal,
+        reader: &SegmentReader,
+    ) -> crate::Result<MultiFieldFacetSegmentCollector> {
+        let segment_collectors = self
+            .facet_collectors
+            .values()
+            .map(|facet_collector| facet_collector.for_segment(segment_ord, reader))
+            .collect::<crate::Result<_>>()?;
+        Ok(MultiFieldFacetSegmentCollector { segment_collectors })
+    }
+
+    fn requires_scoring(&self) -> bool {
+        false
+    }
+
+    fn merge_fruits(
+        &self,
+        segments_facet_counts: Vec<Vec<FacetCounts>>,
+    ) -> crate::Result<BTreeMap<String, FacetCounts>> {
+        let mut facet_counts_per_field: Vec<Vec<FacetCounts>> = self
+            .facet_collectors
+            .keys()
+            .map(|_| Vec::with_capacity(segments_facet_counts.len()))
+            .collect();
+        for segment_facet_counts in segments_facet_counts {
+            for (field_facet_counts, facet_counts) in
+                facet_counts_per_field.iter_mut().zip(segment_facet_counts)
+            {
+                field_facet_counts.push(facet_counts);
+            }
+        }
+        self.facet_collectors
+            .iter()
+            .zip(facet_counts_per_field)
+            .map(|((field_name, facet_collector), field_facet_counts)| {
+                let facet_counts = facet_collector.merge_fruits(field_facet_counts)?;
+                Ok((field_name.clone(), facet_counts))
+            })
+            .collect()
+    }
+}
+
+impl SegmentCollector for MultiFieldFacetSegmentCollector {
+    type Fruit = Vec<FacetCounts>;
+
+    fn collect(&mut self, doc: DocId, score: Score) {
+        for segment_collector in &mut self.segment_collectors {
+            segment_collector.collect(doc, score);
+        }
+    }
+
+    fn harvest(self) -> Vec<FacetCounts> {
+        self.segment_collectors
+            .into_iter()
+            .map(|segment_collector| segment_collector.harvest())
+            .collect()
+    }
+}
+
 fn is_child_facet(parent_facet: &[u8], possible_child_facet: &[u8]) -> bool {
     if !possible_child_facet.starts_with(parent_facet) {
         return false;
@@ -491,7 +628,7 @@ mod tests {
     use rand::prelude::SliceRandom;
     use rand::{thread_rng, Rng};
 
-    use super::{FacetCollector, FacetCounts};
+    use super::{FacetCollector, FacetCounts, MultiFieldFacetCollector};
     use crate::collector::facet_collector::compress_mapping;
     use crate::collector::Count;
     use crate::index::Index;
@@ -633,6 +770,79 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_multi_field_facet_collector() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let category = schema_builder.add_facet_field("category", FacetOptions::default());
+        let brand = schema_builder.add_facet_field("brand", FacetOptions::default());
+        let color = schema_builder.add_facet_field("color", FacetOptions::default());
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        index_writer.add_document(doc!(
+            category => Facet::from("/electronics/phone/android"),
+            brand => Facet::from("/acme"),
+            color => Facet::from("/black"),
+        ))?;
+        index_writer.add_document(doc!(
+            category => Facet::from("/electronics/phone/ios"),
+            brand => Facet::from("/globex"),
+            color => Facet::from("/black"),
+            color => Facet::from("/white"),
+        ))?;
+        index_writer.commit()?;
+        index_writer.add_document(doc!(
+            category => Facet::from("/electronics/laptop"),
+            brand => Facet::from("/acme"),
+        ))?;
+        index_writer.add_document(doc!(
+            category => Facet::from("/home/kitchen"),
+            color => Facet::from("/white"),
+        ))?;
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+
+        let mut facet_collector = MultiFieldFacetCollector::default();
+        facet_collector.add_facet("category", "/electronics/phone");
+        facet_collector.add_facet("category", "/home");
+        facet_collector.add_facet("brand", "/");
+        facet_collector.add_facet("color", "/");
+        let (count, facet_counts) = searcher.search(&AllQuery, &(Count, facet_collector))?;
+        assert_eq!(count, 4);
+        assert_eq!(
+            facet_counts.keys().collect::<Vec<_>>(),
+            ["brand", "category", "color"]
+        );
+
+        let to_vec = |facet_counts: &FacetCounts, facet: &str| -> Vec<(String, u64)> {
+            facet_counts
+                .get(facet)
+                .map(|(facet, count)| (facet.to_string(), count))
+                .collect()
+        };
+        assert_eq!(
+            to_vec(&facet_counts["category"], "/electronics/phone"),
+            [
+                ("/electronics/phone/android".to_string(), 1),
+                ("/electronics/phone/ios".to_string(), 1)
+            ]
+        );
+        assert_eq!(
+            to_vec(&facet_counts["category"], "/home"),
+            [("/home/kitchen".to_string(), 1)]
+        );
+        assert_eq!(
+            to_vec(&facet_counts["brand"], "/"),
+            [("/acme".to_string(), 2), ("/globex".to_string(), 1)]
+        );
+        assert_eq!(
+            to_vec(&facet_counts["color"], "/"),
+            [("/black".to_string(), 2), ("/white".to_string(), 2)]
+        );
+        Ok(())
+    }
+
     #[test]
     #[should_panic(
         expected = "Tried to add a facet which is a descendant of an already added facet."
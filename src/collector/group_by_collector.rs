@@ -0,0 +1,352 @@
+use std::cmp::Ordering;
+
+use columnar::{Column, ColumnType, MonotonicallyMappableToU64, StrColumn};
+use rustc_hash::FxHashMap;
+
+use crate::collector::{Collector, SegmentCollector, TopDocs};
+use crate::fastfield::FastFieldNotAvailableError;
+use crate::{DocAddress, DocId, Score, SegmentOrdinal, SegmentReader};
+
+/// Value of the grouping field shared by the documents of a [`Group`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum GroupKey {
+    /// Value of a `str` fast field.
+    Str(String),
+    /// Value of a `u64` or `bool` fast field.
+    U64(u64),
+    /// Value of an `i64` fast field.
+    I64(i64),
+}
+
+/// A group of documents, as returned by the [`GroupByCollector`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Group {
+    /// The value of the grouping field shared by the documents of the group.
+    pub key: GroupKey,
+    /// The number of documents of the group matching the query.
+    pub count: u64,
+    /// The score of the best document of the group.
+    pub score: Score,
+    /// The address of the best document of the group.
+    pub doc_address: DocAddress,
+}
+
+impl Group {
+    // Best score first. Ties are broken by the doc address, so that the results are
+    // deterministic.
+    fn cmp_by_relevance(&self, other: &Group) -> Ordering {
+        other
+            .score
+            .partial_cmp(&self.score)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| self.doc_address.cmp(&other.doc_address))
+    }
+}
+
+/// Collapses the documents matching a query by the value of a fast field, similar to the field
+/// collapsing of Solr.
+///
+/// Only the best scoring document of each group is kept, together with the number of matching
+/// documents in the group. If several documents of a group have the same score, the one with
+/// the lowest [`DocAddress`] is kept.
+///
+/// The groups are sorted by decreasing score of their best document, and limited by the limit
+/// and offset of the [`TopDocs`] given to [`GroupByCollector::new()`].
+///
+/// The grouping field must be a `str`, `u64`, `i64` or `bool` fast field. Documents without a
+/// value for it are ignored, and for multivalued fields, only the first value is considered.
+///
+/// ```rust
+/// use tantivy::collector::{GroupByCollector, GroupKey, TopDocs};
+/// use tantivy::query::QueryParser;
+/// use tantivy::schema::{Schema, FAST, STRING, TEXT};
+/// use tantivy::{doc, Index};
+///
+/// # fn main() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let title = schema_builder.add_text_field("title", TEXT);
+/// let domain = schema_builder.add_text_field("domain", STRING | FAST);
+/// let index = Index::create_in_ram(schema_builder.build());
+/// let mut index_writer = index.writer(15_000_000)?;
+/// index_writer.add_document(doc!(title => "diary", domain => "a.com"))?;
+/// index_writer.add_document(doc!(title => "a diary of a young girl", domain => "a.com"))?;
+/// index_writer.add_document(doc!(title => "the diary of muadib", domain => "b.com"))?;
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// let query = QueryParser::for_index(&index, vec![title]).parse_query("diary")?;
+/// let collector = GroupByCollector::new("domain".to_string(), TopDocs::with_limit(10));
+/// let groups = searcher.search(&query, &collector)?;
+/// assert_eq!(groups.len(), 2);
+/// assert_eq!(groups[0].key, GroupKey::Str("a.com".to_string()));
+/// assert_eq!(groups[0].count, 2);
+/// assert_eq!(groups[1].key, GroupKey::Str("b.com".to_string()));
+/// assert_eq!(groups[1].count, 1);
+/// # Ok(())
+/// # }
+/// ```
+pub struct GroupByCollector {
+    group_field: String,
+    limit: usize,
+    offset: usize,
+}
+
+impl GroupByCollector {
+    /// Creates a `GroupByCollector` grouping the documents by the fast field `group_field`.
+    ///
+    /// `top_docs` sets the number of groups returned, see [`TopDocs::with_limit`] and
+    /// [`TopDocs::and_offset`].
+    pub fn new(group_field: String, top_docs: TopDocs) -> GroupByCollector {
+        GroupByCollector {
+            group_field,
+            limit: top_docs.0.limit,
+            offset: top_docs.0.offset,
+        }
+    }
+}
+
+enum GroupColumn {
+    Str(StrColumn),
+    Numerical(Column<u64>, ColumnType),
+}
+
+struct SegmentGroup {
+    count: u64,
+    score: Score,
+    doc: DocId,
+}
+
+/// Segment collector of the [`GroupByCollector`].
+pub struct GroupBySegmentCollector {
+    segment_ord: SegmentOrdinal,
+    group_column: GroupColumn,
+    // term ordinal, or fast field value -> group
+    groups: FxHashMap<u64, SegmentGroup>,
+}
+
+impl Collector for GroupByCollector {
+    type Fruit = Vec<Group>;
+
+    type Child = GroupBySegmentCollector;
+
+    fn for_segment(
+        &self,
+        segment_ord: SegmentOrdinal,
+        reader: &SegmentReader,
+    ) -> crate::Result<GroupBySegmentCollector> {
+        let fast_fields = reader.fast_fields();
+        let group_column = if let Some(str_column) = fast_fields.str(&self.group_field)? {
+            GroupColumn::Str(str_column)
+        } else {
+            let numerical_types = [ColumnType::U64, ColumnType::I64, ColumnType::Bool];
+            let (column, column_type) = fast_fields
+                .u64_lenient_for_type(Some(&numerical_types), &self.group_field)?
+                .ok_or_else(|| FastFieldNotAvailableError {
+                    field_name: self.group_field.clone(),
+                })?;
+            GroupColumn::Numerical(column, column_type)
+        };
+        Ok(GroupBySegmentCollector {
+            segment_ord,
+            group_column,
+            groups: FxHashMap::default(),
+        })
+    }
+
+    fn requires_scoring(&self) -> bool {
+        true
+    }
+
+    fn merge_fruits(
+        &self,
+        segment_groups: Vec<crate::Result<Vec<Group>>>,
+    ) -> crate::Result<Vec<Group>> {
+        let mut groups: FxHashMap<GroupKey, Group> = FxHashMap::default();
+        for segment_group in segment_groups {
+            for group in segment_group? {
+                if let Some(merged_group) = groups.get_mut(&group.key) {
+                    merged_group.count += group.count;
+                    if group.cmp_by_relevance(merged_group) == Ordering::Less {
+                        merged_group.score = group.score;
+                        merged_group.doc_address = group.doc_address;
+                    }
+                } else {
+                    groups.insert(group.key.clone(), group);
+                }
+            }
+        }
+        let mut groups: Vec<Group> = groups.into_values().collect();
+        groups.sort_by(Group::cmp_by_relevance);
+        Ok(groups
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit)
+            .collect())
+    }
+}
+
+impl SegmentCollector for GroupBySegmentCollector {
+    type Fruit = crate::Result<Vec<Group>>;
+
+    fn collect(&mut self, doc: DocId, score: Score) {
+        let group_value_opt = match &self.group_column {
+            GroupColumn::Str(str_column) => str_column.ords().first(doc),
+            GroupColumn::Numerical(column, _) => column.first(doc),
+        };
+        let Some(group_value) = group_value_opt else {
+            return;
+        };
+        let group = self.groups.entry(group_value).or_insert(SegmentGroup {
+            count: 0,
+            score,
+            doc,
+        });
+        group.count += 1;
+        // The documents are collected by increasing doc id, so on ties the first one is kept.
+        if score > group.score {
+            group.score = score;
+            group.doc = doc;
+        }
+    }
+
+    fn harvest(self) -> crate::Result<Vec<Group>> {
+        let mut term_buffer = String::new();
+        let mut groups = Vec::with_capacity(self.groups.len());
+        for (group_value, segment_group) in self.groups {
+            let key = match &self.group_column {
+                GroupColumn::Str(str_column) => {
+                    term_buffer.clear();
+                    if !str_column.ord_to_str(group_value, &mut term_buffer)? {
+                        continue;
+                    }
+                    GroupKey::Str(term_buffer.clone())
+                }
+                GroupColumn::Numerical(_, ColumnType::I64) => {
+                    GroupKey::I64(i64::from_u64(group_value))
+                }
+                GroupColumn::Numerical(_, _) => GroupKey::U64(group_value),
+            };
+            groups.push(Group {
+                key,
+                count: segment_group.count,
+                score: segment_group.score,
+                doc_address: DocAddress::new(self.segment_ord, segment_group.doc),
+            });
+        }
+        Ok(groups)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GroupByCollector, GroupKey};
+    use crate::collector::{Count, TopDocs};
+    use crate::query::{AllQuery, TermQuery};
+    use crate::schema::{IndexRecordOption, Schema, FAST, STRING, TEXT};
+    use crate::{DocAddress, Index, IndexWriter, Term};
+
+    fn create_index() -> crate::Result<Index> {
+        let mut schema_builder = Schema::builder();
+        let id = schema_builder.add_u64_field("id", FAST);
+        let text = schema_builder.add_text_field("text", TEXT);
+        let domain = schema_builder.add_text_field("domain", STRING | FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        let docs = [
+            (Some("a"), "rust"),
+            (Some("b"), "rust search engine"),
+            (Some("a"), "rust search"),
+            (Some("c"), "rust search engine"),
+            (Some("c"), "rust engine library"),
+            (Some("b"), "rust search"),
+            (Some("a"), "rust search engine library"),
+            (Some("a"), "python"),
+            // Documents without a group are ignored.
+            (None, "rust"),
+        ];
+        for (doc_id, (domain_opt, text_val)) in docs.into_iter().enumerate() {
+            let mut doc = doc!(id => doc_id as u64, text => text_val);
+            if let Some(domain_val) = domain_opt {
+                doc.add_text(domain, domain_val);
+            }
+            index_writer.add_document(doc)?;
+            if doc_id == 4 {
+                index_writer.commit()?;
+            }
+        }
+        index_writer.commit()?;
+        Ok(index)
+    }
+
+    // Returns the groups, with the id of their best document.
+    fn search(index: &Index, top_docs: TopDocs) -> crate::Result<Vec<(String, u64, u64)>> {
+        let text = index.schema().get_field("text").unwrap();
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+        let query = TermQuery::new(
+            Term::from_field_text(text, "rust"),
+            IndexRecordOption::WithFreqs,
+        );
+        let collector = GroupByCollector::new("domain".to_string(), top_docs);
+        let groups = searcher.search(&query, &collector)?;
+        groups
+            .into_iter()
+            .map(|group| {
+                let GroupKey::Str(key) = group.key else {
+                    panic!("unexpected group key {:?}", group.key);
+                };
+                let DocAddress {
+                    segment_ord,
+                    doc_id,
+                } = group.doc_address;
+                let id_column = searcher
+                    .segment_reader(segment_ord)
+                    .fast_fields()
+                    .u64("id")?;
+                Ok((key, group.count, id_column.first(doc_id).unwrap()))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_group_by_collector() -> crate::Result<()> {
+        let index = create_index()?;
+        // The shorter the text, the higher the score. Within the group "c", both documents
+        // have the same score, the first one is kept.
+        assert_eq!(
+            search(&index, TopDocs::with_limit(10))?,
+            [
+                ("a".to_string(), 3, 0),
+                ("b".to_string(), 2, 5),
+                ("c".to_string(), 2, 3),
+            ]
+        );
+        assert_eq!(
+            search(&index, TopDocs::with_limit(1).and_offset(1))?,
+            [("b".to_string(), 2, 5)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_group_by_collector_numerical() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let group = schema_builder.add_i64_field("group", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        for val in [-1i64, 2, -1, 3, 2, -1] {
+            index_writer.add_document(doc!(group => val))?;
+        }
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let collector = GroupByCollector::new("group".to_string(), TopDocs::with_limit(2));
+        let (count, groups) = searcher.search(&AllQuery, &(Count, collector))?;
+        assert_eq!(count, 6);
+        let groups: Vec<(GroupKey, u64)> = groups
+            .into_iter()
+            .map(|group| (group.key, group.count))
+            .collect();
+        assert_eq!(groups, [(GroupKey::I64(-1), 3), (GroupKey::I64(2), 2)]);
+        Ok(())
+    }
+}
@@ -18,7 +18,13 @@ use crate::{DocId, Score};
 /// An histogram is then defined as a `Vec<u64>` of length `num_buckets`, that contains a count of
 /// documents for each value bucket.
 ///
-/// See also [`HistogramCollector::new()`].
+/// Values outside of `[min_val, max_val)` are dropped, unless
+/// [`HistogramCollector::with_out_of_range_buckets()`] is called, in which case they are
+/// counted in an underflow and an overflow bucket. Documents without a value are counted as
+/// the value mapped to `0u64`, i.e. 0 for a u64 field and the minimum value for i64 and date
+/// fields.
+///
+/// See also [`HistogramCollector::new()`] and [`HistogramCollector::with_range()`].
 ///
 /// # Warning
 ///
@@ -26,7 +32,9 @@ use crate::{DocId, Score};
 #[derive(Clone)]
 pub struct HistogramCollector {
     min_value: u64,
+    max_value: u64,
     num_buckets: usize,
+    out_of_range_buckets: bool,
     divider: DividerU64,
     field: String,
 }
@@ -51,34 +59,109 @@ impl HistogramCollector {
         bucket_width: u64,
         num_buckets: usize,
     ) -> HistogramCollector {
-        let fast_type = TFastValue::to_type();
-        assert!(fast_type == Type::U64 || fast_type == Type::I64 || fast_type == Type::Date);
+        assert_supported_type::<TFastValue>();
+        let min_value = min_value.to_u64();
+        let max_value = min_value.saturating_add(bucket_width.saturating_mul(num_buckets as u64));
+        HistogramCollector {
+            min_value,
+            max_value,
+            num_buckets,
+            out_of_range_buckets: false,
+            field,
+            divider: DividerU64::divide_by(bucket_width),
+        }
+    }
+
+    /// Builds a new HistogramCollector partitioning `[min_value, max_value)` into buckets of
+    /// width `bucket_width`.
+    ///
+    /// If `max_value - min_value` is not a multiple of `bucket_width`, the last bucket is
+    /// truncated to end at `max_value`.
+    ///
+    /// # Disclaimer
+    /// This function panics if the field given is of type f64, if `max_value` is lower than
+    /// `min_value` or if `bucket_width` is 0.
+    pub fn with_range<TFastValue: FastValue>(
+        field: String,
+        min_value: TFastValue,
+        max_value: TFastValue,
+        bucket_width: u64,
+    ) -> HistogramCollector {
+        assert_supported_type::<TFastValue>();
+        assert!(
+            bucket_width > 0,
+            "The bucket width must be strictly positive"
+        );
+        let min_value = min_value.to_u64();
+        let max_value = max_value.to_u64();
+        assert!(
+            min_value <= max_value,
+            "The min value must be lower than the max value"
+        );
+        let range = max_value - min_value;
+        let num_buckets = (range / bucket_width + u64::from(range % bucket_width != 0)) as usize;
         HistogramCollector {
-            min_value: min_value.to_u64(),
+            min_value,
+            max_value,
             num_buckets,
+            out_of_range_buckets: false,
             field,
             divider: DividerU64::divide_by(bucket_width),
         }
     }
+
+    /// Counts the values outside of the range of the histogram, instead of dropping them.
+    ///
+    /// The histogram then has `num_buckets + 2` buckets: the first one counts the values lower
+    /// than `min_value`, and the last one the values greater or equal to the upper bound of the
+    /// histogram.
+    pub fn with_out_of_range_buckets(mut self) -> HistogramCollector {
+        self.out_of_range_buckets = true;
+        self
+    }
+
+    fn histogram_len(&self) -> usize {
+        if self.out_of_range_buckets {
+            self.num_buckets + 2
+        } else {
+            self.num_buckets
+        }
+    }
+}
+
+fn assert_supported_type<TFastValue: FastValue>() {
+    let fast_type = TFastValue::to_type();
+    assert!(fast_type == Type::U64 || fast_type == Type::I64 || fast_type == Type::Date);
 }
 
 struct HistogramComputer {
     counts: Vec<u64>,
     min_value: u64,
+    max_value: u64,
     divider: DividerU64,
+    // If true, `counts` starts with an underflow bucket and ends with an overflow bucket.
+    out_of_range_buckets: bool,
 }
 
 impl HistogramComputer {
     #[inline]
     pub(crate) fn add_value(&mut self, value: u64) {
         if value < self.min_value {
+            if self.out_of_range_buckets {
+                self.counts[0] += 1;
+            }
             return;
         }
-        let delta = value - self.min_value;
-        let bucket_id: usize = self.divider.divide(delta) as usize;
-        if bucket_id < self.counts.len() {
-            self.counts[bucket_id] += 1;
+        if value >= self.max_value {
+            if self.out_of_range_buckets {
+                *self.counts.last_mut().unwrap() += 1;
+            }
+            return;
         }
+        let delta = value - self.min_value;
+        let bucket_id =
+            self.divider.divide(delta) as usize + usize::from(self.out_of_range_buckets);
+        self.counts[bucket_id] += 1;
     }
 
     fn harvest(self) -> Vec<u64> {
@@ -119,9 +202,11 @@ impl Collector for HistogramCollector {
         let column_u64 = column.first_or_default_col(0u64);
         Ok(SegmentHistogramCollector {
             histogram_computer: HistogramComputer {
-                counts: vec![0; self.num_buckets],
+                counts: vec![0; self.histogram_len()],
                 min_value: self.min_value,
+                max_value: self.max_value,
                 divider: self.divider,
+                out_of_range_buckets: self.out_of_range_buckets,
             },
             column_u64,
         })
@@ -132,7 +217,7 @@ impl Collector for HistogramCollector {
     }
 
     fn merge_fruits(&self, child_histograms: Vec<Vec<u64>>) -> crate::Result<Vec<u64>> {
-        Ok(add_vecs(child_histograms, self.num_buckets))
+        Ok(add_vecs(child_histograms, self.histogram_len()))
     }
 }
 
@@ -158,9 +243,11 @@ mod tests {
     use query::AllQuery;
 
     use super::{add_vecs, HistogramCollector, HistogramComputer};
-    use crate::schema::{Schema, FAST};
+    use crate::collector::{Count, TopDocs};
+    use crate::query::TermQuery;
+    use crate::schema::{IndexRecordOption, Schema, FAST, STRING};
     use crate::time::{Date, Month};
-    use crate::{query, DateTime, Index};
+    use crate::{query, DateTime, Index, Term};
 
     #[test]
     fn test_add_histograms_simple() {
@@ -184,7 +271,9 @@ mod tests {
         let mut histogram_computer = HistogramComputer {
             counts: vec![0; 5],
             min_value: 1,
+            max_value: 11,
             divider: DividerU64::divide_by(2),
+            out_of_range_buckets: false,
         };
         histogram_computer.add_value(1);
         histogram_computer.add_value(7);
@@ -196,7 +285,9 @@ mod tests {
         let mut histogram_computer = HistogramComputer {
             counts: vec![0; 5],
             min_value: 2,
+            max_value: 12,
             divider: DividerU64::divide_by(2),
+            out_of_range_buckets: false,
         };
         histogram_computer.add_value(0);
         assert_eq!(histogram_computer.harvest(), vec![0, 0, 0, 0, 0]);
@@ -207,7 +298,9 @@ mod tests {
         let mut histogram_computer = HistogramComputer {
             counts: vec![0u64; 5],
             min_value: 0,
+            max_value: 10,
             divider: DividerU64::divide_by(2),
+            out_of_range_buckets: false,
         };
         histogram_computer.add_value(10);
         assert_eq!(histogram_computer.harvest(), vec![0, 0, 0, 0, 0]);
@@ -302,4 +395,94 @@ mod tests {
         assert_eq!(week_histogram, vec![0, 0, 1, 1, 0, 0, 1, 0, 0, 0]);
         Ok(())
     }
+
+    #[test]
+    fn test_histogram_builder_out_of_range_buckets() {
+        let mut histogram_computer = HistogramComputer {
+            counts: vec![0u64; 7],
+            min_value: 2,
+            max_value: 12,
+            divider: DividerU64::divide_by(2),
+            out_of_range_buckets: true,
+        };
+        histogram_computer.add_value(0);
+        histogram_computer.add_value(2);
+        histogram_computer.add_value(11);
+        histogram_computer.add_value(12);
+        histogram_computer.add_value(u64::MAX);
+        assert_eq!(histogram_computer.harvest(), vec![1, 1, 0, 0, 0, 1, 2]);
+    }
+
+    fn create_i64_index() -> crate::Result<Index> {
+        let mut schema_builder = Schema::builder();
+        let val_field = schema_builder.add_i64_field("val_field", FAST);
+        let category = schema_builder.add_text_field("category", STRING);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        let mut writer = index.writer_for_tests()?;
+        for (val, cat) in [
+            (-30i64, "a"),
+            (-12, "a"),
+            (-10, "b"),
+            (0, "a"),
+            (7, "b"),
+            (12, "a"),
+            (25, "b"),
+        ] {
+            writer.add_document(doc!(val_field=>val, category=>cat))?;
+        }
+        writer.commit()?;
+        // Documents without a value count as i64::MIN, the value mapped to 0u64.
+        writer.add_document(doc!(category=>"a"))?;
+        writer.commit()?;
+        Ok(index)
+    }
+
+    #[test]
+    fn test_histogram_with_range() -> crate::Result<()> {
+        let index = create_i64_index()?;
+        let searcher = index.reader()?.searcher();
+        let histogram_collector =
+            HistogramCollector::with_range("val_field".to_string(), -20i64, 10i64, 10u64);
+        assert_eq!(
+            searcher.search(&AllQuery, &histogram_collector)?,
+            vec![1, 1, 2]
+        );
+        let histogram_collector = histogram_collector.with_out_of_range_buckets();
+        assert_eq!(
+            searcher.search(&AllQuery, &histogram_collector)?,
+            vec![2, 1, 1, 2, 2]
+        );
+        // The last bucket is truncated to [0, 5).
+        let histogram_collector =
+            HistogramCollector::with_range("val_field".to_string(), -20i64, 5i64, 10u64)
+                .with_out_of_range_buckets();
+        assert_eq!(
+            searcher.search(&AllQuery, &histogram_collector)?,
+            vec![2, 1, 1, 1, 3]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_histogram_with_top_docs() -> crate::Result<()> {
+        let index = create_i64_index()?;
+        let category = index.schema().get_field("category").unwrap();
+        let searcher = index.reader()?.searcher();
+        let query = TermQuery::new(
+            Term::from_field_text(category, "a"),
+            IndexRecordOption::Basic,
+        );
+        let histogram_collector =
+            HistogramCollector::with_range("val_field".to_string(), -20i64, 10i64, 10u64)
+                .with_out_of_range_buckets();
+        let (top_docs, count, histogram) = searcher.search(
+            &query,
+            &(TopDocs::with_limit(2), Count, histogram_collector),
+        )?;
+        assert_eq!(top_docs.len(), 2);
+        assert_eq!(count, 5);
+        assert_eq!(histogram, vec![1, 1, 0, 1, 1]);
+        Ok(())
+    }
 }
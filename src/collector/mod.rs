@@ -6,7 +6,9 @@
 //! Your fruit could for instance be :
 //! - [the count of matching documents](crate::collector::Count)
 //! - [the top 10 documents, by relevancy or by a fast field](crate::collector::TopDocs)
-//! - [facet counts](FacetCollector)
+//! - [facet counts](FacetCollector), possibly [of several fields](MultiFieldFacetCollector)
+//! - [percentiles of a fast field](PercentileCollector)
+//! - [the top document of each group of documents](GroupByCollector)
 //!
 //! At some point in your code, you will trigger the actual search operation by calling
 //! [`Searcher::search()`](crate::Searcher::search).
@@ -86,11 +88,17 @@ use downcast_rs::impl_downcast;
 use crate::{DocId, Score, SegmentOrdinal, SegmentReader};
 
 mod count_collector;
-pub use self::count_collector::Count;
+pub use self::count_collector::{CappedCount, Count, CountUpTo};
+
+mod group_by_collector;
+pub use self::group_by_collector::{Group, GroupByCollector, GroupKey};
 
 mod histogram_collector;
 pub use histogram_collector::HistogramCollector;
 
+mod percentile_collector;
+pub use self::percentile_collector::{PercentileCollector, SegmentPercentileCollector};
+
 mod multi_collector;
 pub use self::multi_collector::{FruitHandle, MultiCollector, MultiFruit};
 
@@ -106,7 +114,7 @@ pub use self::custom_score_top_collector::{CustomScorer, CustomSegmentScorer};
 mod tweak_score_top_collector;
 pub use self::tweak_score_top_collector::{ScoreSegmentTweaker, ScoreTweaker};
 mod facet_collector;
-pub use self::facet_collector::{FacetCollector, FacetCounts};
+pub use self::facet_collector::{FacetCollector, FacetCounts, MultiFieldFacetCollector};
 use crate::query::Weight;
 
 mod docset_collector;
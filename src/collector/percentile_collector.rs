@@ -0,0 +1,265 @@
+use columnar::{Column, ColumnType};
+use sketches_ddsketch::{Config, DDSketch};
+
+use crate::aggregation::f64_from_fastfield_u64;
+use crate::collector::{Collector, SegmentCollector};
+use crate::fastfield::FastFieldNotAvailableError;
+use crate::{DocId, Score, SegmentOrdinal, SegmentReader, TantivyError};
+
+const DEFAULT_RELATIVE_ACCURACY: f64 = 0.01;
+const DEFAULT_MAX_NUM_BINS: u32 = 2048;
+const DEFAULT_MIN_VALUE: f64 = 1.0e-9;
+
+/// `PercentileCollector` estimates percentiles of the values of a numeric fast field over the
+/// collected documents.
+///
+/// The values are accumulated into a [DDSketch](https://arxiv.org/abs/1908.10693), so the memory
+/// used does not depend on the number of documents collected. The estimated percentiles are
+/// within a relative error of the exact ones, see
+/// [`PercentileCollector::with_relative_accuracy()`].
+///
+/// Multivalued fields contribute all of their values, and documents without a value are
+/// ignored.
+///
+/// ```rust
+/// use tantivy::collector::PercentileCollector;
+/// use tantivy::query::AllQuery;
+/// use tantivy::schema::{Schema, FAST};
+/// use tantivy::{doc, Index};
+///
+/// let mut schema_builder = Schema::builder();
+/// let latency = schema_builder.add_u64_field("latency", FAST);
+/// let schema = schema_builder.build();
+/// let index = Index::create_in_ram(schema);
+///
+/// let mut index_writer = index.writer(15_000_000).unwrap();
+/// for val in 1u64..=100 {
+///     index_writer.add_document(doc!(latency => val)).unwrap();
+/// }
+/// assert!(index_writer.commit().is_ok());
+///
+/// let searcher = index.reader().unwrap().searcher();
+/// let collector = PercentileCollector::new("latency".to_string(), &[50.0, 95.0]);
+/// let percentiles = searcher.search(&AllQuery, &collector).unwrap();
+/// let median = percentiles[0].unwrap();
+/// assert!((median - 50.0).abs() <= 50.0 * 0.01);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PercentileCollector {
+    field: String,
+    percentiles: Vec<f64>,
+    relative_accuracy: f64,
+    max_num_bins: u32,
+}
+
+impl PercentileCollector {
+    /// Builds a new `PercentileCollector` estimating the given `percentiles` of the values of
+    /// `field`. A percentile is expressed as a number between 0 and 100, e.g. `50.0` for the
+    /// median.
+    ///
+    /// The fruit of the collector contains one value per requested percentile, in the same
+    /// order, or `None` if no document with a value was collected.
+    ///
+    /// # Disclaimer
+    /// This function panics if one of the percentiles is not within `[0, 100]`.
+    pub fn new(field: String, percentiles: &[f64]) -> PercentileCollector {
+        assert!(
+            percentiles
+                .iter()
+                .all(|percentile| (0.0..=100.0).contains(percentile)),
+            "Percentiles must be within [0, 100]"
+        );
+        PercentileCollector {
+            field,
+            percentiles: percentiles.to_vec(),
+            relative_accuracy: DEFAULT_RELATIVE_ACCURACY,
+            max_num_bins: DEFAULT_MAX_NUM_BINS,
+        }
+    }
+
+    /// Sets the relative accuracy of the estimated percentiles. It defaults to 0.01, meaning that
+    /// the estimated values are within 1% of the exact ones.
+    ///
+    /// A lower value gives more accurate percentiles, at the cost of more memory.
+    ///
+    /// # Disclaimer
+    /// This function panics if `relative_accuracy` is not within `(0, 1)`.
+    pub fn with_relative_accuracy(mut self, relative_accuracy: f64) -> PercentileCollector {
+        assert!(
+            relative_accuracy > 0.0 && relative_accuracy < 1.0,
+            "The relative accuracy must be within (0, 1)"
+        );
+        self.relative_accuracy = relative_accuracy;
+        self
+    }
+
+    /// Sets the maximum number of bins of the sketch, which bounds its memory usage.
+    /// It defaults to 2048.
+    ///
+    /// Once the limit is reached, the bins of the lowest values are collapsed, so that the
+    /// accuracy of the low percentiles degrades first.
+    pub fn with_max_num_bins(mut self, max_num_bins: u32) -> PercentileCollector {
+        self.max_num_bins = max_num_bins;
+        self
+    }
+
+    fn new_sketch(&self) -> DDSketch {
+        DDSketch::new(Config::new(
+            self.relative_accuracy,
+            self.max_num_bins,
+            DEFAULT_MIN_VALUE,
+        ))
+    }
+}
+
+impl Collector for PercentileCollector {
+    type Fruit = Vec<Option<f64>>;
+    type Child = SegmentPercentileCollector;
+
+    fn for_segment(
+        &self,
+        _segment_local_id: SegmentOrdinal,
+        segment: &SegmentReader,
+    ) -> crate::Result<SegmentPercentileCollector> {
+        let numerical_types = [
+            ColumnType::U64,
+            ColumnType::I64,
+            ColumnType::F64,
+            ColumnType::DateTime,
+        ];
+        let (column, column_type) = segment
+            .fast_fields()
+            .u64_lenient_for_type(Some(&numerical_types), &self.field)?
+            .ok_or_else(|| FastFieldNotAvailableError {
+                field_name: self.field.clone(),
+            })?;
+        Ok(SegmentPercentileCollector {
+            sketch: self.new_sketch(),
+            column,
+            column_type,
+        })
+    }
+
+    fn requires_scoring(&self) -> bool {
+        false
+    }
+
+    fn merge_fruits(&self, segment_sketches: Vec<DDSketch>) -> crate::Result<Vec<Option<f64>>> {
+        let mut sketch = self.new_sketch();
+        for segment_sketch in &segment_sketches {
+            sketch.merge(segment_sketch).map_err(|err| {
+                TantivyError::InternalError(format!("Error while merging percentiles {err:?}"))
+            })?;
+        }
+        self.percentiles
+            .iter()
+            .map(|percentile| {
+                sketch.quantile(percentile / 100.0).map_err(|err| {
+                    TantivyError::InternalError(format!(
+                        "Error while computing percentile {percentile}: {err:?}"
+                    ))
+                })
+            })
+            .collect()
+    }
+}
+
+/// Segment collector of the [`PercentileCollector`].
+pub struct SegmentPercentileCollector {
+    sketch: DDSketch,
+    column: Column<u64>,
+    column_type: ColumnType,
+}
+
+impl SegmentCollector for SegmentPercentileCollector {
+    type Fruit = DDSketch;
+
+    fn collect(&mut self, doc: DocId, _score: Score) {
+        for value in self.column.values_for_doc(doc) {
+            self.sketch
+                .add(f64_from_fastfield_u64(value, &self.column_type));
+        }
+    }
+
+    fn harvest(self) -> DDSketch {
+        self.sketch
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PercentileCollector;
+    use crate::collector::Count;
+    use crate::query::{AllQuery, RangeQuery};
+    use crate::schema::{Schema, FAST};
+    use crate::{Index, IndexWriter};
+
+    fn create_index() -> crate::Result<Index> {
+        let mut schema_builder = Schema::builder();
+        let val_field = schema_builder.add_f64_field("val", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        // A uniform distribution over [1, 10_000], spread over several segments.
+        for val in 1..=10_000u64 {
+            index_writer.add_document(doc!(val_field => val as f64))?;
+            if val % 3_000 == 0 {
+                index_writer.commit()?;
+            }
+        }
+        index_writer.commit()?;
+        Ok(index)
+    }
+
+    fn assert_within(value: f64, expected: f64, relative_accuracy: f64) {
+        assert!(
+            (value - expected).abs() <= expected * relative_accuracy,
+            "{value} is not within {relative_accuracy} of {expected}"
+        );
+    }
+
+    #[test]
+    fn test_percentile_collector_uniform() -> crate::Result<()> {
+        let index = create_index()?;
+        let searcher = index.reader()?.searcher();
+        assert!(searcher.segment_readers().len() > 1);
+        let percentile_collector =
+            PercentileCollector::new("val".to_string(), &[1.0, 50.0, 95.0, 100.0]);
+        let percentiles = searcher.search(&AllQuery, &percentile_collector)?;
+        assert_eq!(percentiles.len(), 4);
+        for (percentile, expected) in percentiles.iter().zip([100.0, 5_000.0, 9_500.0, 10_000.0]) {
+            assert_within(percentile.unwrap(), expected, 0.01);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_percentile_collector_relative_accuracy() -> crate::Result<()> {
+        let index = create_index()?;
+        let searcher = index.reader()?.searcher();
+        let query = RangeQuery::new_f64("val".to_string(), 1_001.0..2_001.0);
+        let percentile_collector = PercentileCollector::new("val".to_string(), &[50.0])
+            .with_relative_accuracy(0.05)
+            .with_max_num_bins(128);
+        let (count, percentiles) = searcher.search(&query, &(Count, percentile_collector))?;
+        assert_eq!(count, 1_000);
+        assert_within(percentiles[0].unwrap(), 1_500.0, 0.05);
+        Ok(())
+    }
+
+    #[test]
+    fn test_percentile_collector_no_values() -> crate::Result<()> {
+        let index = create_index()?;
+        let searcher = index.reader()?.searcher();
+        let query = RangeQuery::new_f64("val".to_string(), 20_000.0..30_000.0);
+        let percentile_collector = PercentileCollector::new("val".to_string(), &[50.0, 99.0]);
+        let percentiles = searcher.search(&query, &percentile_collector)?;
+        assert_eq!(percentiles, vec![None, None]);
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "Percentiles must be within [0, 100]")]
+    fn test_percentile_collector_invalid_percentile() {
+        PercentileCollector::new("val".to_string(), &[50.0, 101.0]);
+    }
+}
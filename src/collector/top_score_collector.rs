@@ -2,7 +2,7 @@ use std::fmt;
 use std::marker::PhantomData;
 use std::sync::Arc;
 
-use columnar::ColumnValues;
+use columnar::{Column, ColumnValues};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
@@ -14,9 +14,44 @@ use crate::collector::{
     CustomScorer, CustomSegmentScorer, ScoreSegmentTweaker, ScoreTweaker, SegmentCollector,
 };
 use crate::fastfield::{FastFieldNotAvailableError, FastValue};
-use crate::query::Weight;
+use crate::query::{geo_field_name, Weight};
+use crate::schema::GeoPoint;
 use crate::{DocAddress, DocId, Order, Score, SegmentOrdinal, SegmentReader, TantivyError};
 
+fn check_fast_field_type<TFastValue: FastValue>(
+    segment: &SegmentReader,
+    field_name: &str,
+) -> crate::Result<()> {
+    let schema = segment.schema();
+    let field = schema.get_field(field_name)?;
+    let field_entry = schema.get_field_entry(field);
+    if !field_entry.is_fast() {
+        return Err(TantivyError::SchemaError(format!(
+            "Field {:?} is not a fast field.",
+            field_entry.name()
+        )));
+    }
+    let schema_type = TFastValue::to_type();
+    let requested_type = field_entry.field_type().value_type();
+    if schema_type != requested_type {
+        return Err(TantivyError::SchemaError(format!(
+            "Field {:?} is of type {schema_type:?}!={requested_type:?}",
+            field_entry.name()
+        )));
+    }
+    Ok(())
+}
+
+// Converts the u64 representation of the fast field values, used to rank the documents,
+// back to `TFastValue`.
+fn fast_value_from_sort_key<TFastValue: FastValue>(sort_key: u64, order: &Order) -> TFastValue {
+    if order.is_desc() {
+        TFastValue::from_u64(sort_key)
+    } else {
+        TFastValue::from_u64(u64::MAX - sort_key)
+    }
+}
+
 struct FastFieldConvertCollector<
     TCollector: Collector<Fruit = Vec<(u64, DocAddress)>>,
     TFastValue: FastValue,
@@ -41,23 +76,7 @@ where
         segment_local_id: crate::SegmentOrdinal,
         segment: &SegmentReader,
     ) -> crate::Result<Self::Child> {
-        let schema = segment.schema();
-        let field = schema.get_field(&self.field)?;
-        let field_entry = schema.get_field_entry(field);
-        if !field_entry.is_fast() {
-            return Err(TantivyError::SchemaError(format!(
-                "Field {:?} is not a fast field.",
-                field_entry.name()
-            )));
-        }
-        let schema_type = TFastValue::to_type();
-        let requested_type = field_entry.field_type().value_type();
-        if schema_type != requested_type {
-            return Err(TantivyError::SchemaError(format!(
-                "Field {:?} is of type {schema_type:?}!={requested_type:?}",
-                field_entry.name()
-            )));
-        }
+        check_fast_field_type::<TFastValue>(segment, &self.field)?;
         self.collector.for_segment(segment_local_id, segment)
     }
 
@@ -72,18 +91,100 @@ where
         let raw_result = self.collector.merge_fruits(segment_fruits)?;
         let transformed_result = raw_result
             .into_iter()
-            .map(|(score, doc_address)| {
-                if self.order.is_desc() {
-                    (TFastValue::from_u64(score), doc_address)
-                } else {
-                    (TFastValue::from_u64(u64::MAX - score), doc_address)
-                }
+            .map(|(score, doc_address)| (fast_value_from_sort_key(score, &self.order), doc_address))
+            .collect::<Vec<_>>();
+        Ok(transformed_result)
+    }
+}
+
+struct ScoreThenFastFieldConvertCollector<
+    TCollector: Collector<Fruit = Vec<((Score, u64), DocAddress)>>,
+    TFastValue: FastValue,
+> {
+    collector: TCollector,
+    field: String,
+    fast_value: PhantomData<TFastValue>,
+    order: Order,
+}
+
+impl<TCollector, TFastValue> Collector
+    for ScoreThenFastFieldConvertCollector<TCollector, TFastValue>
+where
+    TCollector: Collector<Fruit = Vec<((Score, u64), DocAddress)>>,
+    TFastValue: FastValue,
+{
+    type Fruit = Vec<((Score, TFastValue), DocAddress)>;
+
+    type Child = TCollector::Child;
+
+    fn for_segment(
+        &self,
+        segment_local_id: crate::SegmentOrdinal,
+        segment: &SegmentReader,
+    ) -> crate::Result<Self::Child> {
+        check_fast_field_type::<TFastValue>(segment, &self.field)?;
+        self.collector.for_segment(segment_local_id, segment)
+    }
+
+    fn requires_scoring(&self) -> bool {
+        self.collector.requires_scoring()
+    }
+
+    fn merge_fruits(
+        &self,
+        segment_fruits: Vec<<Self::Child as SegmentCollector>::Fruit>,
+    ) -> crate::Result<Self::Fruit> {
+        let raw_result = self.collector.merge_fruits(segment_fruits)?;
+        let transformed_result = raw_result
+            .into_iter()
+            .map(|((score, sort_key), doc_address)| {
+                let fast_value = fast_value_from_sort_key(sort_key, &self.order);
+                ((score, fast_value), doc_address)
             })
             .collect::<Vec<_>>();
         Ok(transformed_result)
     }
 }
 
+struct GeoDistanceConvertCollector<TCollector: Collector<Fruit = Vec<(u64, DocAddress)>>> {
+    collector: TCollector,
+    field: String,
+}
+
+impl<TCollector> Collector for GeoDistanceConvertCollector<TCollector>
+where TCollector: Collector<Fruit = Vec<(u64, DocAddress)>>
+{
+    type Fruit = Vec<(f64, DocAddress)>;
+
+    type Child = TCollector::Child;
+
+    fn for_segment(
+        &self,
+        segment_local_id: crate::SegmentOrdinal,
+        segment: &SegmentReader,
+    ) -> crate::Result<Self::Child> {
+        let schema = segment.schema();
+        geo_field_name(schema, schema.get_field(&self.field)?)?;
+        self.collector.for_segment(segment_local_id, segment)
+    }
+
+    fn requires_scoring(&self) -> bool {
+        self.collector.requires_scoring()
+    }
+
+    fn merge_fruits(
+        &self,
+        segment_fruits: Vec<<Self::Child as SegmentCollector>::Fruit>,
+    ) -> crate::Result<Self::Fruit> {
+        let raw_result = self.collector.merge_fruits(segment_fruits)?;
+        let transformed_result = raw_result
+            .into_iter()
+            .map(|(sort_key, doc_address)| (f64::from_bits(u64::MAX - sort_key), doc_address))
+            .collect::<Vec<_>>();
+        Ok(transformed_result)
+    }
+}
+
 /// The `TopDocs` collector keeps track of the top `K` documents
 /// sorted by their score.
 ///
@@ -128,7 +229,7 @@ where
 /// # Ok(())
 /// # }
 /// ```
-pub struct TopDocs(TopCollector<Score>);
+pub struct TopDocs(pub(crate) TopCollector<Score>);
 
 impl fmt::Debug for TopDocs {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -186,6 +287,59 @@ impl CustomScorer<u64> for ScorerByField {
     }
 }
 
+struct ScorerByGeoDistanceReader {
+    geo_column: Column<u64>,
+    center: GeoPoint,
+}
+
+impl CustomSegmentScorer<u64> for ScorerByGeoDistanceReader {
+    fn score(&mut self, doc: DocId) -> u64 {
+        let distance = self
+            .geo_column
+            .values_for_doc(doc)
+            .map(|val| self.center.distance(&GeoPoint::from_u64(val)))
+            .fold(f64::INFINITY, f64::min);
+        // Distances are positive, so their bit representations are ordered like them.
+        u64::MAX - distance.to_bits()
+    }
+}
+
+struct ScorerByGeoDistance {
+    field: String,
+    center: GeoPoint,
+}
+
+impl CustomScorer<u64> for ScorerByGeoDistance {
+    type Child = ScorerByGeoDistanceReader;
+
+    fn segment_scorer(&self, segment_reader: &SegmentReader) -> crate::Result<Self::Child> {
+        Ok(ScorerByGeoDistanceReader {
+            geo_column: segment_reader.fast_fields().u64(&self.field)?,
+            center: self.center,
+        })
+    }
+}
+
+struct ScoreThenFastFieldReader(ScorerByFastFieldReader);
+
+impl ScoreSegmentTweaker<(Score, u64)> for ScoreThenFastFieldReader {
+    fn score(&mut self, doc: DocId, score: Score) -> (Score, u64) {
+        (score, self.0.score(doc))
+    }
+}
+
+struct ScoreThenField(ScorerByField);
+
+impl ScoreTweaker<(Score, u64)> for ScoreThenField {
+    type Child = ScoreThenFastFieldReader;
+
+    fn segment_tweaker(&self, segment_reader: &SegmentReader) -> crate::Result<Self::Child> {
+        Ok(ScoreThenFastFieldReader(
+            self.0.segment_scorer(segment_reader)?,
+        ))
+    }
+}
+
 impl TopDocs {
     /// Creates a top score collector, with a number of documents equal to "limit".
     ///
@@ -411,6 +565,119 @@ impl TopDocs {
         }
     }
 
+    /// Ranks the documents by score, breaking the ties on the score with a given fast field.
+    ///
+    /// The documents with the same score are sorted by the value of the fast field in the
+    /// given order, and then by ascending [`DocAddress`].
+    ///
+    /// As for [`TopDocs::order_by_fast_field`], an error is returned at the moment of collection
+    /// if the field is not a fast field, or if its type does not match the generic type.
+    ///
+    /// ```rust
+    /// # use tantivy::schema::{Schema, FAST, TEXT};
+    /// # use tantivy::{doc, Index, DocAddress, Order};
+    /// # use tantivy::query::QueryParser;
+    /// use tantivy::collector::TopDocs;
+    ///
+    /// # fn main() -> tantivy::Result<()> {
+    /// #   let mut schema_builder = Schema::builder();
+    /// #   let title = schema_builder.add_text_field("title", TEXT);
+    /// #   let timestamp = schema_builder.add_u64_field("timestamp", FAST);
+    /// #   let index = Index::create_in_ram(schema_builder.build());
+    /// #   let mut index_writer = index.writer_with_num_threads(1, 20_000_000)?;
+    /// #   index_writer.add_document(doc!(title => "news", timestamp => 10u64))?;
+    /// #   index_writer.add_document(doc!(title => "news", timestamp => 30u64))?;
+    /// #   index_writer.add_document(doc!(title => "news", timestamp => 20u64))?;
+    /// #   index_writer.commit()?;
+    /// #   let searcher = index.reader()?.searcher();
+    /// #   let query = QueryParser::for_index(&index, vec![title]).parse_query("news")?;
+    /// // The most recent documents come first among the equally relevant ones.
+    /// let top_docs = searcher.search(
+    ///     &query,
+    ///     &TopDocs::with_limit(3).order_by_score_then_fast_field::<u64>("timestamp", Order::Desc),
+    /// )?;
+    /// let timestamps: Vec<u64> = top_docs
+    ///     .iter()
+    ///     .map(|((_score, timestamp), _doc_address)| *timestamp)
+    ///     .collect();
+    /// assert_eq!(timestamps, [30, 20, 10]);
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn order_by_score_then_fast_field<TFastValue>(
+        self,
+        fast_field: impl ToString,
+        order: Order,
+    ) -> impl Collector<Fruit = Vec<((Score, TFastValue), DocAddress)>>
+    where
+        TFastValue: FastValue,
+    {
+        let tweaked_collector = self.tweak_score(ScoreThenField(ScorerByField {
+            field: fast_field.to_string(),
+            order: order.clone(),
+        }));
+        ScoreThenFastFieldConvertCollector {
+            collector: tweaked_collector,
+            field: fast_field.to_string(),
+            fast_value: PhantomData,
+            order,
+        }
+    }
+
+    /// Set top-K to rank documents by their distance to a point, nearest first.
+    ///
+    /// The field must be a geo field, created with
+    /// [`SchemaBuilder::add_geo_field`](crate::schema::SchemaBuilder::add_geo_field). The
+    /// distance of a document is the great-circle distance in meters from `center` to its
+    /// nearest [`GeoPoint`], as computed by [`GeoPoint::distance`]. Documents without a point
+    /// come last, with an infinite distance.
+    ///
+    /// If the field is not a geo field, an error is returned at the moment of collection.
+    ///
+    /// ```rust
+    /// # use tantivy::schema::{GeoPoint, Schema, FAST};
+    /// # use tantivy::{doc, Index, DocAddress};
+    /// # use tantivy::query::AllQuery;
+    /// use tantivy::collector::TopDocs;
+    ///
+    /// # fn main() -> tantivy::Result<()> {
+    /// #   let mut schema_builder = Schema::builder();
+    /// #   let location = schema_builder.add_geo_field("location", FAST);
+    /// #   let index = Index::create_in_ram(schema_builder.build());
+    /// #   let mut index_writer = index.writer_with_num_threads(1, 20_000_000)?;
+    /// #   index_writer.add_document(doc!(location => GeoPoint::new(35.6762, 139.6503)))?;
+    /// #   index_writer.add_document(doc!(location => GeoPoint::new(51.5074, -0.1278)))?;
+    /// #   index_writer.commit()?;
+    /// #   let searcher = index.reader()?.searcher();
+    /// let paris = GeoPoint::new(48.8566, 2.3522);
+    /// let top_docs: Vec<(f64, DocAddress)> = searcher.search(
+    ///     &AllQuery,
+    ///     &TopDocs::with_limit(2).order_by_geo_distance("location", paris),
+    /// )?;
+    /// // London is the nearest to Paris, at about 344km.
+    /// assert_eq!(top_docs[0].1, DocAddress::new(0, 1));
+    /// assert!((top_docs[0].0 - 344_000.0).abs() < 1_000.0);
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn order_by_geo_distance(
+        self,
+        field: impl ToString,
+        center: GeoPoint,
+    ) -> impl Collector<Fruit = Vec<(f64, DocAddress)>> {
+        let u64_collector = CustomScoreTopCollector::new(
+            ScorerByGeoDistance {
+                field: field.to_string(),
+                center,
+            },
+            self.0.into_tscore(),
+        );
+        GeoDistanceConvertCollector {
+            collector: u64_collector,
+            field: field.to_string(),
+        }
+    }
+
     /// Ranks the documents using a custom score.
     ///
     /// This method offers a convenient way to tweak or replace
@@ -1064,6 +1331,39 @@ mod tests {
         assert_eq!(page_0, &page_2[..page_0.len()]);
     }
 
+    #[test]
+    fn test_top_collector_pagination_stable() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        // Several segments, with many ties on the score.
+        for segment in 0..3 {
+            for i in 0..7 {
+                let body = if (segment + i) % 3 == 0 {
+                    "hello hello"
+                } else {
+                    "hello"
+                };
+                index_writer.add_document(doc!(text => body))?;
+            }
+            index_writer.commit()?;
+        }
+        let searcher = index.reader()?.searcher();
+        let query = QueryParser::for_index(&index, vec![text]).parse_query("hello")?;
+        let all_docs = searcher.search(&query, &TopDocs::with_limit(21))?;
+        assert_eq!(all_docs.len(), 21);
+
+        let mut paginated_docs = Vec::new();
+        for offset in (0..21).step_by(4) {
+            let page = searcher.search(&query, &TopDocs::with_limit(4).and_offset(offset))?;
+            assert_eq!(page.len(), 4.min(21 - offset));
+            paginated_docs.extend(page);
+        }
+        assert_eq!(paginated_docs, all_docs);
+        Ok(())
+    }
+
     #[test]
     #[should_panic]
     fn test_top_0() {
@@ -1371,4 +1671,45 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_order_by_score_then_fast_field() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field(TITLE, TEXT);
+        let size = schema_builder.add_u64_field(SIZE, FAST);
+        let schema = schema_builder.build();
+        let (index, query) = index("beer", title, schema, |index_writer| {
+            for (text, size_value) in [
+                ("bottle of beer", 12u64),
+                ("beer", 5),
+                ("growler of beer", 64),
+                ("beer", 9),
+                ("pint of beer", 16),
+            ] {
+                index_writer
+                    .add_document(doc!(title => text, size => size_value))
+                    .unwrap();
+            }
+        });
+        let searcher = index.reader()?.searcher();
+        let sizes = |order: Order| -> crate::Result<Vec<u64>> {
+            let top_collector =
+                TopDocs::with_limit(5).order_by_score_then_fast_field::<u64>(SIZE, order);
+            let top_docs: Vec<((Score, u64), DocAddress)> =
+                searcher.search(&query, &top_collector)?;
+            Ok(top_docs
+                .into_iter()
+                .map(|((_score, size), _doc_address)| size)
+                .collect())
+        };
+        // The shorter titles score higher.
+        assert_eq!(sizes(Order::Desc)?, [9, 5, 64, 16, 12]);
+        assert_eq!(sizes(Order::Asc)?, [5, 9, 12, 16, 64]);
+
+        // Sorting by a fast field only does not compute the scores.
+        assert!(!TopDocs::with_limit(5)
+            .order_by_fast_field::<u64>(SIZE, Order::Desc)
+            .requires_scoring());
+        Ok(())
+    }
 }
@@ -1,16 +1,24 @@
 use std::collections::BTreeMap;
 use std::sync::Arc;
+use std::time::Instant;
 use std::{fmt, io};
 
+use common::OwnedBytes;
+use tantivy_fst::Regex;
+
 use crate::collector::Collector;
 use crate::core::Executor;
 use crate::index::{SegmentId, SegmentReader};
-use crate::query::{Bm25StatisticsProvider, EnableScoring, Query};
+use crate::query::{
+    Bm25Similarity, Bm25StatisticsProvider, DeadlineWeight, EnableScoring, Explanation, Query,
+    SearchDeadlineGuard, Similarity,
+};
 use crate::schema::document::DocumentDeserialize;
-use crate::schema::{Schema, Term};
+use crate::schema::{Field, Schema, TantivyDocument, Term};
 use crate::space_usage::SearcherSpaceUsage;
 use crate::store::{CacheStats, StoreReader};
-use crate::{DocAddress, Index, Opstamp, TrackedObject};
+use crate::termdict::TermMerger;
+use crate::{DocAddress, Index, Opstamp, TantivyError, TrackedObject};
 
 /// Identifies the searcher generation accessed by a [`Searcher`].
 ///
@@ -68,9 +76,32 @@ impl SearcherGeneration {
 #[derive(Clone)]
 pub struct Searcher {
     inner: Arc<SearcherInner>,
+    similarity: Arc<dyn Similarity>,
 }
 
 impl Searcher {
+    /// Returns a `Searcher` scoring the documents with the given [`Similarity`]
+    /// instead of the default [`Bm25Similarity`].
+    ///
+    /// The similarity is only used by the weights of the [`TermQuery`](crate::query::TermQuery),
+    /// [`PhraseQuery`](crate::query::PhraseQuery) and
+    /// [`PhrasePrefixQuery`](crate::query::PhrasePrefixQuery), including when they are nested in
+    /// a `BooleanQuery`. The other queries, e.g. the fuzzy or regex queries, keep their own
+    /// scoring.
+    ///
+    /// The other searchers of the same reader are not affected.
+    pub fn with_similarity(self, similarity: Box<dyn Similarity>) -> Searcher {
+        Searcher {
+            inner: self.inner,
+            similarity: Arc::from(similarity),
+        }
+    }
+
+    /// Returns the [`Similarity`] used to score the documents.
+    pub fn similarity(&self) -> &Arc<dyn Similarity> {
+        &self.similarity
+    }
+
     /// Returns the `Index` associated with the `Searcher`
     pub fn index(&self) -> &Index {
         &self.inner.index
@@ -90,6 +121,37 @@ impl Searcher {
         store_reader.get(doc_address.doc_id)
     }
 
+    /// Fetches a document from tantivy's store given a [`DocAddress`], keeping only the values
+    /// of the given fields.
+    ///
+    /// This is handy to display a list of results, e.g. with their title and url, without
+    /// building the whole stored documents.
+    pub fn doc_fields(
+        &self,
+        doc_address: DocAddress,
+        fields: &[Field],
+    ) -> crate::Result<TantivyDocument> {
+        let store_reader = &self.inner.store_readers[doc_address.segment_ord as usize];
+        store_reader.get_fields(doc_address.doc_id, fields)
+    }
+
+    /// Returns the bytes of the first value of a text or bytes `field` of a document stored in
+    /// tantivy's store, without copying them.
+    ///
+    /// Large stored values can be streamed this way, as the returned [`OwnedBytes`] implements
+    /// [`io::Read`]. It does not borrow the searcher, but keeps the whole decompressed store block
+    /// of the document in memory until it is dropped.
+    ///
+    /// See [`StoreReader::get_field_bytes`].
+    pub fn doc_field_bytes(
+        &self,
+        doc_address: DocAddress,
+        field: Field,
+    ) -> crate::Result<Option<OwnedBytes>> {
+        let store_reader = &self.inner.store_readers[doc_address.segment_ord as usize];
+        store_reader.get_field_bytes(doc_address.doc_id, field)
+    }
+
     /// The cache stats for the underlying store reader.
     ///
     /// Aggregates the sum for each segment store reader.
@@ -140,6 +202,71 @@ impl Searcher {
         Ok(total_doc_freq)
     }
 
+    /// Returns at most `limit` terms of the field starting with `prefix`, in sorted order,
+    /// along with the overall number of documents containing them.
+    ///
+    /// The terms are returned as the keys of the term dictionary, i.e. the utf-8 bytes of the
+    /// text for a text field.
+    pub fn terms_with_prefix(
+        &self,
+        field: Field,
+        prefix: &[u8],
+        limit: usize,
+    ) -> crate::Result<Vec<(Vec<u8>, u64)>> {
+        let inverted_indexes = self
+            .inner
+            .segment_readers
+            .iter()
+            .map(|segment_reader| segment_reader.inverted_index(field))
+            .collect::<crate::Result<Vec<_>>>()?;
+        let term_streams = inverted_indexes
+            .iter()
+            .map(|inverted_index| inverted_index.terms().prefix_range(prefix).into_stream())
+            .collect::<io::Result<Vec<_>>>()?;
+        let mut term_merger = TermMerger::new(term_streams);
+        let mut terms = Vec::new();
+        while terms.len() < limit && term_merger.advance() {
+            let doc_freq: u64 = term_merger
+                .current_segment_ords_and_term_infos()
+                .map(|(_, term_info)| u64::from(term_info.doc_freq))
+                .sum();
+            terms.push((term_merger.key().to_vec(), doc_freq));
+        }
+        Ok(terms)
+    }
+
+    /// Returns at most `limit` terms of the field matching the regular expression `pattern`, in
+    /// sorted order, along with the overall number of documents containing them.
+    ///
+    /// Contrary to a [`RegexQuery`](crate::query::RegexQuery), this only visits the term
+    /// dictionaries, and does not read any posting list.
+    ///
+    /// The regular expression must match the whole term. See
+    /// [`RegexQuery`](crate::query::RegexQuery) for the supported syntax.
+    pub fn terms_matching_regex(
+        &self,
+        field: Field,
+        pattern: &str,
+        limit: usize,
+    ) -> crate::Result<Vec<(Vec<u8>, u64)>> {
+        let regex = Regex::new(pattern)
+            .map_err(|err| TantivyError::InvalidArgument(format!("RegexQueryError: {err}")))?;
+        // The terms of each segment are sorted, so the first `limit` terms overall are among
+        // the first `limit` terms of each segment.
+        let mut doc_freqs: BTreeMap<Vec<u8>, u64> = BTreeMap::new();
+        for segment_reader in &self.inner.segment_readers {
+            let inverted_index = segment_reader.inverted_index(field)?;
+            let mut term_stream = inverted_index.terms().search(&regex).into_stream()?;
+            let mut num_terms = 0;
+            while num_terms < limit && term_stream.advance() {
+                let doc_freq = u64::from(term_stream.value().doc_freq);
+                *doc_freqs.entry(term_stream.key().to_vec()).or_default() += doc_freq;
+                num_terms += 1;
+            }
+        }
+        Ok(doc_freqs.into_iter().take(limit).collect())
+    }
+
     /// Return the overall number of documents containing
     /// the given term in an asynchronous manner.
     #[cfg(feature = "quickwit")]
@@ -205,6 +332,61 @@ impl Searcher {
         self.search_with_executor(query, collector, executor, enabled_scoring)
     }
 
+    /// Returns an [`Explanation`] of the score of the document for the query.
+    ///
+    /// The explanation is a tree of the components of the score, e.g. the boosts, and the
+    /// term frequency, inverse document frequency and field norm of the terms, whose root
+    /// value is the score of the document.
+    ///
+    /// Returns an error if the document does not match the query.
+    pub fn explain(
+        &self,
+        query: &dyn Query,
+        doc_address: DocAddress,
+    ) -> crate::Result<Explanation> {
+        query.explain(self, doc_address)
+    }
+
+    /// Same as [`search(...)`](Searcher::search) but aborts the search with a
+    /// [`TantivyError::TimedOut`] error once `deadline` is exceeded.
+    ///
+    /// The deadline is checked before searching each segment, periodically while expanding an
+    /// automaton, e.g. of a regex or wildcard query, over the term dictionary of a segment, and
+    /// while iterating over the matching documents of a segment, every few thousand documents.
+    /// With a collector relying on `for_each_pruning`, like [`TopDocs`](crate::collector::TopDocs),
+    /// the deadline is checked on each competitive document instead, and the remaining documents
+    /// are pruned once it is exceeded.
+    pub fn search_with_deadline<C: Collector>(
+        &self,
+        query: &dyn Query,
+        collector: &C,
+        deadline: Instant,
+    ) -> crate::Result<C::Fruit> {
+        let enabled_scoring = if collector.requires_scoring() {
+            EnableScoring::enabled_from_searcher(self)
+        } else {
+            EnableScoring::disabled_from_searcher(self)
+        };
+        let weight = DeadlineWeight::new(query.weight(enabled_scoring)?, deadline);
+        let executor = self.inner.index.search_executor();
+        let fruits = executor.map(
+            |(segment_ord, segment_reader)| {
+                if weight.is_timed_out() {
+                    return Err(TantivyError::TimedOut);
+                }
+                let _deadline_guard = SearchDeadlineGuard::new(deadline);
+                let fruit =
+                    collector.collect_segment(&weight, segment_ord as u32, segment_reader)?;
+                if weight.is_timed_out() {
+                    return Err(TantivyError::TimedOut);
+                }
+                Ok(fruit)
+            },
+            self.segment_readers().iter().enumerate(),
+        )?;
+        collector.merge_fruits(fruits)
+    }
+
     /// Same as [`search(...)`](Searcher::search) but multithreaded.
     ///
     /// The current implementation is rather naive :
@@ -247,7 +429,10 @@ impl Searcher {
 
 impl From<Arc<SearcherInner>> for Searcher {
     fn from(inner: Arc<SearcherInner>) -> Self {
-        Searcher { inner }
+        Searcher {
+            inner,
+            similarity: Arc::new(Bm25Similarity),
+        }
     }
 }
 
@@ -0,0 +1,422 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::{fmt, io, result};
+
+use common::HasLen;
+use lru::LruCache;
+
+use super::FileHandle;
+use crate::directory::error::{DeleteError, LockError, OpenReadError, OpenWriteError};
+use crate::directory::{
+    Directory, DirectoryLock, Lock, OwnedBytes, WatchCallback, WatchHandle, WritePtr,
+};
+
+/// Statistics of the block cache of a [`CachingDirectory`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct BlockCacheStats {
+    /// The number of blocks in the cache.
+    pub num_blocks: usize,
+    /// The number of bytes held by the blocks in the cache.
+    pub num_bytes: usize,
+    /// The number of cache hits.
+    pub cache_hits: usize,
+    /// The number of cache misses.
+    pub cache_misses: usize,
+}
+
+/// An LRU cache of file blocks, bounded by the total number of bytes of the blocks.
+///
+/// The blocks are indexed by file, so that the blocks of a file can be removed without
+/// scanning the whole cache.
+#[derive(Debug)]
+struct BlockCache {
+    blocks: LruCache<(PathBuf, usize), OwnedBytes>,
+    file_block_ids: HashMap<PathBuf, HashSet<usize>>,
+    capacity_in_bytes: usize,
+    num_bytes: usize,
+    cache_hits: usize,
+    cache_misses: usize,
+}
+
+impl BlockCache {
+    fn with_capacity(capacity_in_bytes: usize) -> BlockCache {
+        BlockCache {
+            blocks: LruCache::unbounded(),
+            file_block_ids: HashMap::new(),
+            capacity_in_bytes,
+            num_bytes: 0,
+            cache_hits: 0,
+            cache_misses: 0,
+        }
+    }
+
+    fn get(&mut self, path: &Path, block_id: usize) -> Option<OwnedBytes> {
+        let block_opt = self.blocks.get(&(path.to_path_buf(), block_id)).cloned();
+        if block_opt.is_some() {
+            self.cache_hits += 1;
+        } else {
+            self.cache_misses += 1;
+        }
+        block_opt
+    }
+
+    /// Inserts a block, which must own its backing allocation, so that its length is the
+    /// memory it actually holds.
+    fn insert(&mut self, path: &Path, block_id: usize, block: OwnedBytes) {
+        if block.len() > self.capacity_in_bytes {
+            return;
+        }
+        self.num_bytes += block.len();
+        if let Some(previous_block) = self.blocks.put((path.to_path_buf(), block_id), block) {
+            self.num_bytes -= previous_block.len();
+        }
+        self.file_block_ids
+            .entry(path.to_path_buf())
+            .or_default()
+            .insert(block_id);
+        while self.num_bytes > self.capacity_in_bytes {
+            let Some(((evicted_path, evicted_block_id), evicted_block)) = self.blocks.pop_lru()
+            else {
+                break;
+            };
+            self.num_bytes -= evicted_block.len();
+            self.remove_block_id(&evicted_path, evicted_block_id);
+        }
+    }
+
+    fn remove_block_id(&mut self, path: &Path, block_id: usize) {
+        if let Some(block_ids) = self.file_block_ids.get_mut(path) {
+            block_ids.remove(&block_id);
+            if block_ids.is_empty() {
+                self.file_block_ids.remove(path);
+            }
+        }
+    }
+
+    /// Removes the blocks of a file, which is only needed if the file is deleted or written,
+    /// since files are otherwise immutable.
+    fn remove_file(&mut self, path: &Path) {
+        let Some(block_ids) = self.file_block_ids.remove(path) else {
+            return;
+        };
+        for block_id in block_ids {
+            if let Some(block) = self.blocks.pop(&(path.to_path_buf(), block_id)) {
+                self.num_bytes -= block.len();
+            }
+        }
+    }
+
+    fn stats(&self) -> BlockCacheStats {
+        BlockCacheStats {
+            num_blocks: self.blocks.len(),
+            num_bytes: self.num_bytes,
+            cache_hits: self.cache_hits,
+            cache_misses: self.cache_misses,
+        }
+    }
+}
+
+/// A [`Directory`] wrapper caching the blocks read from the files of the underlying directory,
+/// in an in-process LRU cache with a bounded number of bytes.
+///
+/// Contrary to the OS page cache used by the `MmapDirectory`, this gives a predictable memory
+/// usage, and works with any directory, e.g. a
+/// [`RemoteDirectory`](crate::directory::RemoteDirectory) reading files remotely.
+///
+/// Files are read by blocks of a fixed size, and consecutive missing blocks are read from the
+/// underlying directory with a single call. The blocks are copied into their own allocation,
+/// so the cache never keeps alive a larger buffer they were read from. The atomic files, like
+/// `meta.json`, are not cached.
+#[derive(Clone)]
+pub struct CachingDirectory<D: Directory> {
+    inner: D,
+    block_size: usize,
+    block_cache: Arc<Mutex<BlockCache>>,
+}
+
+impl<D: Directory> fmt::Debug for CachingDirectory<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CachingDirectory")
+            .field("inner", &self.inner)
+            .field("block_size", &self.block_size)
+            .field("cache_stats", &self.cache_stats())
+            .finish()
+    }
+}
+
+impl<D: Directory> CachingDirectory<D> {
+    /// Wraps a directory, caching the blocks of `block_size` bytes read from its files, up to
+    /// `capacity_in_bytes` bytes.
+    ///
+    /// # Disclaimer
+    /// This function panics if `block_size` is 0.
+    pub fn new(directory: D, block_size: usize, capacity_in_bytes: usize) -> CachingDirectory<D> {
+        assert!(block_size > 0, "The block size must be greater than 0");
+        CachingDirectory {
+            inner: directory,
+            block_size,
+            block_cache: Arc::new(Mutex::new(BlockCache::with_capacity(capacity_in_bytes))),
+        }
+    }
+
+    /// Returns the wrapped directory.
+    pub fn inner(&self) -> &D {
+        &self.inner
+    }
+
+    /// Returns the statistics of the block cache, shared by the clones of the directory.
+    pub fn cache_stats(&self) -> BlockCacheStats {
+        self.block_cache.lock().unwrap().stats()
+    }
+}
+
+#[derive(Debug)]
+struct CachingFileHandle {
+    path: PathBuf,
+    inner: Arc<dyn FileHandle>,
+    block_size: usize,
+    block_cache: Arc<Mutex<BlockCache>>,
+}
+
+impl CachingFileHandle {
+    fn block_range(&self, block_id: usize) -> Range<usize> {
+        let start = block_id * self.block_size;
+        start..self.inner.len().min(start + self.block_size)
+    }
+
+    /// Returns the blocks `block_ids` of the file, reading the missing ones from the underlying
+    /// file handle.
+    ///
+    /// Consecutive missing blocks are read with a single call.
+    fn read_blocks(&self, block_ids: Range<usize>) -> io::Result<Vec<OwnedBytes>> {
+        let mut blocks: Vec<Option<OwnedBytes>> = {
+            let mut block_cache = self.block_cache.lock().unwrap();
+            block_ids
+                .clone()
+                .map(|block_id| block_cache.get(&self.path, block_id))
+                .collect()
+        };
+        let mut missing_start = 0;
+        while missing_start < blocks.len() {
+            if blocks[missing_start].is_some() {
+                missing_start += 1;
+                continue;
+            }
+            let missing_end = (missing_start..blocks.len())
+                .find(|&i| blocks[i].is_some())
+                .unwrap_or(blocks.len());
+            let first_block_id = block_ids.start + missing_start;
+            let last_block_id = block_ids.start + missing_end - 1;
+            let read_range =
+                self.block_range(first_block_id).start..self.block_range(last_block_id).end;
+            let read_bytes = self.inner.read_bytes(read_range.clone())?;
+            let mut block_cache = self.block_cache.lock().unwrap();
+            for (i, block) in blocks[missing_start..missing_end].iter_mut().enumerate() {
+                let block_id = first_block_id + i;
+                let block_range = self.block_range(block_id);
+                let start = block_range.start - read_range.start;
+                let block_bytes = OwnedBytes::new(
+                    read_bytes.as_slice()[start..start + block_range.len()].to_vec(),
+                );
+                block_cache.insert(&self.path, block_id, block_bytes.clone());
+                *block = Some(block_bytes);
+            }
+            missing_start = missing_end;
+        }
+        Ok(blocks.into_iter().flatten().collect())
+    }
+}
+
+impl FileHandle for CachingFileHandle {
+    fn read_bytes(&self, range: Range<usize>) -> io::Result<OwnedBytes> {
+        if range.is_empty() {
+            return Ok(OwnedBytes::empty());
+        }
+        let first_block_id = range.start / self.block_size;
+        let block_ids = first_block_id..(range.end - 1) / self.block_size + 1;
+        let blocks = self.read_blocks(block_ids)?;
+        let start_in_first_block = range.start - first_block_id * self.block_size;
+        if let [block] = &blocks[..] {
+            return Ok(block.slice(start_in_first_block..start_in_first_block + range.len()));
+        }
+        let mut bytes = Vec::with_capacity(blocks.len() * self.block_size);
+        for block in &blocks {
+            bytes.extend_from_slice(block.as_slice());
+        }
+        bytes.drain(..start_in_first_block);
+        bytes.truncate(range.len());
+        Ok(OwnedBytes::new(bytes))
+    }
+}
+
+impl HasLen for CachingFileHandle {
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<D: Directory + Clone> Directory for CachingDirectory<D> {
+    fn get_file_handle(&self, path: &Path) -> Result<Arc<dyn FileHandle>, OpenReadError> {
+        let inner = self.inner.get_file_handle(path)?;
+        Ok(Arc::new(CachingFileHandle {
+            path: path.to_path_buf(),
+            inner,
+            block_size: self.block_size,
+            block_cache: self.block_cache.clone(),
+        }))
+    }
+
+    fn delete(&self, path: &Path) -> result::Result<(), DeleteError> {
+        self.block_cache.lock().unwrap().remove_file(path);
+        self.inner.delete(path)
+    }
+
+    fn exists(&self, path: &Path) -> Result<bool, OpenReadError> {
+        self.inner.exists(path)
+    }
+
+    fn open_write(&self, path: &Path) -> Result<WritePtr, OpenWriteError> {
+        self.block_cache.lock().unwrap().remove_file(path);
+        self.inner.open_write(path)
+    }
+
+    fn atomic_read(&self, path: &Path) -> Result<Vec<u8>, OpenReadError> {
+        self.inner.atomic_read(path)
+    }
+
+    fn atomic_write(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        self.inner.atomic_write(path, data)
+    }
+
+    fn sync_directory(&self) -> io::Result<()> {
+        self.inner.sync_directory()
+    }
+
+    fn acquire_lock(&self, lock: &Lock) -> Result<DirectoryLock, LockError> {
+        self.inner.acquire_lock(lock)
+    }
+
+    fn watch(&self, watch_callback: WatchCallback) -> crate::Result<WatchHandle> {
+        self.inner.watch(watch_callback)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{BlockCacheStats, CachingDirectory};
+    use crate::collector::Count;
+    use crate::directory::{Directory, RamDirectory};
+    use crate::query::TermQuery;
+    use crate::schema::{IndexRecordOption, Schema, TEXT};
+    use crate::{Index, IndexSettings, IndexWriter, Term};
+
+    fn test_directory(
+        capacity_in_bytes: usize,
+    ) -> crate::Result<(CachingDirectory<RamDirectory>, Vec<u8>)> {
+        let ram_directory = RamDirectory::create();
+        let data: Vec<u8> = (0..=255u8).cycle().take(1_000).collect();
+        ram_directory.atomic_write(Path::new("data"), &data)?;
+        Ok((
+            CachingDirectory::new(ram_directory, 100, capacity_in_bytes),
+            data,
+        ))
+    }
+
+    #[test]
+    fn test_caching_directory_hits() -> crate::Result<()> {
+        let (directory, data) = test_directory(10_000)?;
+        let file_slice = directory.open_read(Path::new("data"))?;
+        assert_eq!(file_slice.read_bytes()?.as_slice(), &data[..]);
+        assert_eq!(
+            directory.cache_stats(),
+            BlockCacheStats {
+                num_blocks: 10,
+                num_bytes: 1_000,
+                cache_hits: 0,
+                cache_misses: 10,
+            }
+        );
+        for range in [95..205, 50..60, 999..1_000] {
+            let bytes = file_slice.read_bytes_slice(range.clone())?;
+            assert_eq!(bytes.as_slice(), &data[range]);
+        }
+        let cache_stats = directory.cache_stats();
+        assert_eq!(cache_stats.cache_hits, 5);
+        assert_eq!(cache_stats.cache_misses, 10);
+        Ok(())
+    }
+
+    #[test]
+    fn test_caching_directory_eviction() -> crate::Result<()> {
+        let (directory, data) = test_directory(350)?;
+        let file_slice = directory.open_read(Path::new("data"))?;
+        assert_eq!(file_slice.read_bytes()?.as_slice(), &data[..]);
+        let cache_stats = directory.cache_stats();
+        assert_eq!(cache_stats.num_blocks, 3);
+        assert_eq!(cache_stats.num_bytes, 300);
+
+        // The most recently read blocks are kept.
+        file_slice.read_bytes_slice(700..1_000)?;
+        assert_eq!(directory.cache_stats().cache_hits, 3);
+        file_slice.read_bytes_slice(0..100)?;
+        let cache_stats = directory.cache_stats();
+        assert_eq!(cache_stats.cache_hits, 3);
+        assert_eq!(cache_stats.cache_misses, 11);
+        assert!(cache_stats.num_bytes <= 350);
+        Ok(())
+    }
+
+    #[test]
+    fn test_caching_directory_remove_file() -> crate::Result<()> {
+        let (directory, data) = test_directory(10_000)?;
+        directory
+            .inner()
+            .atomic_write(Path::new("other"), &data[..250])?;
+        directory.open_read(Path::new("data"))?.read_bytes()?;
+        directory.open_read(Path::new("other"))?.read_bytes()?;
+        assert_eq!(directory.cache_stats().num_blocks, 13);
+        assert_eq!(directory.cache_stats().num_bytes, 1_250);
+
+        directory.delete(Path::new("data"))?;
+        let cache_stats = directory.cache_stats();
+        assert_eq!(cache_stats.num_blocks, 3);
+        assert_eq!(cache_stats.num_bytes, 250);
+        let file_slice = directory.open_read(Path::new("other"))?;
+        assert_eq!(file_slice.read_bytes()?.as_slice(), &data[..250]);
+        assert_eq!(directory.cache_stats().cache_hits, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_caching_directory_index() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let directory = CachingDirectory::new(RamDirectory::create(), 4_096, 1_000_000);
+        let index = Index::create(
+            directory.clone(),
+            schema_builder.build(),
+            IndexSettings::default(),
+        )?;
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        for i in 0..1_000 {
+            let text = if i % 10 == 0 { "hello world" } else { "hello" };
+            index_writer.add_document(doc!(text_field => text))?;
+        }
+        index_writer.commit()?;
+
+        let searcher = index.reader()?.searcher();
+        let query = TermQuery::new(
+            Term::from_field_text(text_field, "world"),
+            IndexRecordOption::Basic,
+        );
+        assert_eq!(searcher.search(&query, &Count)?, 100);
+        let num_misses = directory.cache_stats().cache_misses;
+        assert_eq!(searcher.search(&query, &Count)?, 100);
+        assert_eq!(directory.cache_stats().cache_misses, num_misses);
+        Ok(())
+    }
+}
@@ -100,12 +100,19 @@ fn retry_policy(is_blocking: bool) -> RetryPolicy {
 /// Write-once read many (WORM) abstraction for where
 /// tantivy's data should be stored.
 ///
-/// There are currently two implementations of `Directory`
+/// There are currently three implementations of `Directory`
 ///
 /// - The [`MMapDirectory`][crate::directory::MmapDirectory], this
 /// should be your default choice.
 /// - The [`RamDirectory`][crate::directory::RamDirectory], which
 /// should be used mostly for tests.
+/// - The [`RemoteDirectory`][crate::directory::RemoteDirectory], to search
+/// a read-only index stored remotely, e.g. in an object storage.
+///
+/// The [`EncryptedDirectory`][crate::directory::EncryptedDirectory] wraps any
+/// of them to encrypt the files at rest, and the
+/// [`CachingDirectory`][crate::directory::CachingDirectory] to cache the
+/// blocks read from their files in memory.
 pub trait Directory: DirectoryClone + fmt::Debug + Send + Sync + 'static {
     /// Opens a file and returns a boxed `FileHandle`.
     ///
@@ -0,0 +1,707 @@
+use std::io::{self, BufWriter, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::{fmt, result};
+
+use common::HasLen;
+
+use super::FileHandle;
+use crate::directory::error::{DeleteError, LockError, OpenReadError, OpenWriteError};
+use crate::directory::{
+    AntiCallToken, Directory, DirectoryLock, FileSlice, Lock, OwnedBytes, TerminatingWrite,
+    WatchCallback, WatchHandle, WritePtr,
+};
+
+const DEFAULT_BLOCK_SIZE: usize = 16 * 1024;
+
+/// Size of the random prefix of the nonces of a file.
+const NONCE_PREFIX_LEN: usize = 8;
+/// The header of an encrypted file holds the nonce prefix, followed by the block size as a
+/// little endian `u32`.
+const HEADER_LEN: usize = NONCE_PREFIX_LEN + 4;
+
+/// Length of the nonces given to the [`BlockCipher`], e.g. 96 bits for AES-GCM or
+/// ChaCha20-Poly1305.
+pub const NONCE_LEN: usize = 12;
+
+/// An authenticated cipher, e.g. AES-GCM or ChaCha20-Poly1305, used by the
+/// [`EncryptedDirectory`] to encrypt blocks of data with the caller's key.
+///
+/// Each block is encrypted with a distinct nonce. The associated data has to be authenticated,
+/// so that blocks cannot be reordered or truncated without `decrypt()` failing.
+pub trait BlockCipher: Send + Sync + fmt::Debug + 'static {
+    /// Number of bytes the ciphertext of a block is longer than its plaintext, typically the
+    /// length of the authentication tag.
+    fn overhead(&self) -> usize;
+
+    /// Encrypts the `plaintext` of a block.
+    ///
+    /// The returned ciphertext must be `overhead()` bytes longer than the plaintext.
+    fn encrypt(&self, nonce: &[u8; NONCE_LEN], associated_data: &[u8], plaintext: &[u8])
+        -> Vec<u8>;
+
+    /// Decrypts the `ciphertext` of a block, returning an `Err` if the authentication fails,
+    /// e.g. if the key is wrong or if the data is corrupted.
+    fn decrypt(
+        &self,
+        nonce: &[u8; NONCE_LEN],
+        associated_data: &[u8],
+        ciphertext: &[u8],
+    ) -> io::Result<Vec<u8>>;
+}
+
+/// A [`Directory`] wrapper encrypting the content of the files, so that the underlying
+/// directory only ever sees ciphertext. This includes the atomic files, like `meta.json`.
+///
+/// Files are encrypted by blocks of a configurable size, see
+/// [`EncryptedDirectory::with_block_size()`], to allow random access: reading a range of a
+/// file only decrypts the blocks overlapping it.
+///
+/// Every file starts with a header holding a random nonce prefix and the block size. The nonce
+/// of a block is made of the nonce prefix followed by the block index, and the associated
+/// data tells whether the block is the last one of the file.
+///
+/// The encryption itself is delegated to a [`BlockCipher`]. An AES-256-GCM implementation,
+/// [`AesGcmCipher`], is available with the `aes-gcm-encryption` feature. Without it, tantivy
+/// does not include any cipher.
+pub struct EncryptedDirectory {
+    inner: Box<dyn Directory>,
+    cipher: Arc<dyn BlockCipher>,
+    block_size: usize,
+}
+
+impl Clone for EncryptedDirectory {
+    fn clone(&self) -> Self {
+        EncryptedDirectory {
+            inner: self.inner.box_clone(),
+            cipher: self.cipher.clone(),
+            block_size: self.block_size,
+        }
+    }
+}
+
+impl fmt::Debug for EncryptedDirectory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "EncryptedDirectory({:?})", self.inner)
+    }
+}
+
+impl EncryptedDirectory {
+    /// Wraps a directory, encrypting its files with the given cipher.
+    ///
+    /// By default, files are encrypted by blocks of 16KB.
+    pub fn new<D: Into<Box<dyn Directory>>, C: BlockCipher>(
+        directory: D,
+        cipher: C,
+    ) -> EncryptedDirectory {
+        EncryptedDirectory {
+            inner: directory.into(),
+            cipher: Arc::new(cipher),
+            block_size: DEFAULT_BLOCK_SIZE,
+        }
+    }
+
+    /// Sets the size of the blocks the new files are encrypted by.
+    ///
+    /// The block size is stored in each file, so that files written with different block
+    /// sizes can be read.
+    ///
+    /// # Disclaimer
+    /// This function panics if `block_size` is 0 or does not fit in a `u32`.
+    #[must_use]
+    pub fn with_block_size(mut self, block_size: usize) -> EncryptedDirectory {
+        assert!(
+            block_size > 0 && u32::try_from(block_size).is_ok(),
+            "The block size must be within [1, u32::MAX]"
+        );
+        self.block_size = block_size;
+        self
+    }
+
+    fn new_block_encryptor(&self) -> io::Result<BlockEncryptor> {
+        // The nonces must never be reused with the same key, so the 64 bits of the prefix all
+        // come from the operating system's CSPRNG.
+        let mut nonce_prefix = [0u8; NONCE_PREFIX_LEN];
+        getrandom::getrandom(&mut nonce_prefix).map_err(io::Error::from)?;
+        Ok(BlockEncryptor {
+            cipher: self.cipher.clone(),
+            nonce_prefix,
+            block_size: self.block_size,
+        })
+    }
+
+    fn open_encrypted_file(&self, path: &Path, file: FileSlice) -> io::Result<EncryptedFile> {
+        let header = file.slice_to(HEADER_LEN.min(file.len())).read_bytes()?;
+        if header.len() < HEADER_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("The encrypted file {path:?} is too short."),
+            ));
+        }
+        let mut nonce_prefix = [0u8; NONCE_PREFIX_LEN];
+        nonce_prefix.copy_from_slice(&header.as_slice()[..NONCE_PREFIX_LEN]);
+        let mut block_size_bytes = [0u8; 4];
+        block_size_bytes.copy_from_slice(&header.as_slice()[NONCE_PREFIX_LEN..]);
+        let block_size = u32::from_le_bytes(block_size_bytes) as usize;
+        if block_size == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("The encrypted file {path:?} has an invalid block size."),
+            ));
+        }
+        let block_encryptor = BlockEncryptor {
+            cipher: self.cipher.clone(),
+            nonce_prefix,
+            block_size,
+        };
+        let ciphertext = file.slice_from(HEADER_LEN);
+        let encrypted_block_size = block_size + self.cipher.overhead();
+        let num_blocks = (ciphertext.len() + encrypted_block_size - 1) / encrypted_block_size;
+        let len = ciphertext
+            .len()
+            .checked_sub(num_blocks * self.cipher.overhead())
+            .filter(|_| num_blocks > 0)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("The encrypted file {path:?} is truncated."),
+                )
+            })?;
+        Ok(EncryptedFile {
+            path: path.to_path_buf(),
+            block_encryptor,
+            ciphertext,
+            num_blocks,
+            len,
+        })
+    }
+}
+
+/// Encrypts and decrypts the blocks of a file.
+#[derive(Clone)]
+struct BlockEncryptor {
+    cipher: Arc<dyn BlockCipher>,
+    nonce_prefix: [u8; NONCE_PREFIX_LEN],
+    block_size: usize,
+}
+
+impl BlockEncryptor {
+    fn header(&self) -> [u8; HEADER_LEN] {
+        let mut header = [0u8; HEADER_LEN];
+        header[..NONCE_PREFIX_LEN].copy_from_slice(&self.nonce_prefix);
+        header[NONCE_PREFIX_LEN..].copy_from_slice(&(self.block_size as u32).to_le_bytes());
+        header
+    }
+
+    fn nonce(&self, block_id: usize) -> io::Result<[u8; NONCE_LEN]> {
+        let block_id = u32::try_from(block_id).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Too many blocks in the encrypted file.",
+            )
+        })?;
+        let mut nonce = [0u8; NONCE_LEN];
+        nonce[..NONCE_PREFIX_LEN].copy_from_slice(&self.nonce_prefix);
+        nonce[NONCE_PREFIX_LEN..].copy_from_slice(&block_id.to_be_bytes());
+        Ok(nonce)
+    }
+
+    fn encrypt(&self, block_id: usize, is_last: bool, plaintext: &[u8]) -> io::Result<Vec<u8>> {
+        let nonce = self.nonce(block_id)?;
+        Ok(self.cipher.encrypt(&nonce, &[is_last as u8], plaintext))
+    }
+
+    fn decrypt(&self, block_id: usize, is_last: bool, ciphertext: &[u8]) -> io::Result<Vec<u8>> {
+        let nonce = self.nonce(block_id)?;
+        self.cipher.decrypt(&nonce, &[is_last as u8], ciphertext)
+    }
+}
+
+/// The decrypted view over an encrypted file.
+struct EncryptedFile {
+    path: PathBuf,
+    block_encryptor: BlockEncryptor,
+    /// The encrypted blocks, without the header.
+    ciphertext: FileSlice,
+    num_blocks: usize,
+    /// Length of the plaintext.
+    len: usize,
+}
+
+impl fmt::Debug for EncryptedFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "EncryptedFile({:?}, len={})", self.path, self.len)
+    }
+}
+
+impl EncryptedFile {
+    fn read_block(&self, block_id: usize) -> io::Result<Vec<u8>> {
+        let encrypted_block_size =
+            self.block_encryptor.block_size + self.block_encryptor.cipher.overhead();
+        let start = block_id * encrypted_block_size;
+        let end = self.ciphertext.len().min(start + encrypted_block_size);
+        let ciphertext = self.ciphertext.read_bytes_slice(start..end)?;
+        let is_last = block_id + 1 == self.num_blocks;
+        self.block_encryptor
+            .decrypt(block_id, is_last, ciphertext.as_slice())
+            .map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "Failed to decrypt block {block_id} of {:?}, the key may be wrong or the \
+                         data corrupted: {err}",
+                        self.path
+                    ),
+                )
+            })
+    }
+}
+
+impl FileHandle for EncryptedFile {
+    fn read_bytes(&self, range: Range<usize>) -> io::Result<OwnedBytes> {
+        let block_size = self.block_encryptor.block_size;
+        if range.is_empty() {
+            return Ok(OwnedBytes::empty());
+        }
+        let first_block_id = range.start / block_size;
+        let last_block_id = (range.end - 1) / block_size;
+        let mut bytes = Vec::with_capacity((last_block_id - first_block_id + 1) * block_size);
+        for block_id in first_block_id..=last_block_id {
+            bytes.extend_from_slice(&self.read_block(block_id)?);
+        }
+        let start_in_first_block = range.start - first_block_id * block_size;
+        bytes.drain(..start_in_first_block);
+        bytes.truncate(range.len());
+        Ok(OwnedBytes::new(bytes))
+    }
+}
+
+impl HasLen for EncryptedFile {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+/// Writer of an encrypted file.
+///
+/// The current block is buffered until it is full and more data is written, or until the
+/// writer is terminated, so that the last block is always known when it is encrypted.
+struct EncryptedWriter {
+    inner: WritePtr,
+    block_encryptor: BlockEncryptor,
+    block_id: usize,
+    buffer: Vec<u8>,
+}
+
+impl EncryptedWriter {
+    fn write_block(&mut self, is_last: bool) -> io::Result<()> {
+        let ciphertext = self
+            .block_encryptor
+            .encrypt(self.block_id, is_last, &self.buffer)?;
+        self.inner.write_all(&ciphertext)?;
+        self.buffer.clear();
+        self.block_id += 1;
+        Ok(())
+    }
+}
+
+impl Write for EncryptedWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut remaining = buf;
+        while !remaining.is_empty() {
+            if self.buffer.len() == self.block_encryptor.block_size {
+                self.write_block(false)?;
+            }
+            let num_bytes = remaining
+                .len()
+                .min(self.block_encryptor.block_size - self.buffer.len());
+            self.buffer.extend_from_slice(&remaining[..num_bytes]);
+            remaining = &remaining[num_bytes..];
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        // The buffered block cannot be written before it is complete.
+        self.inner.flush()
+    }
+}
+
+impl TerminatingWrite for EncryptedWriter {
+    fn terminate_ref(&mut self, token: AntiCallToken) -> io::Result<()> {
+        self.write_block(true)?;
+        self.inner.terminate_ref(token)
+    }
+}
+
+impl Directory for EncryptedDirectory {
+    fn get_file_handle(&self, path: &Path) -> Result<Arc<dyn FileHandle>, OpenReadError> {
+        let file = self.inner.open_read(path)?;
+        let encrypted_file = self
+            .open_encrypted_file(path, file)
+            .map_err(|io_error| OpenReadError::wrap_io_error(io_error, path.to_path_buf()))?;
+        Ok(Arc::new(encrypted_file))
+    }
+
+    fn delete(&self, path: &Path) -> result::Result<(), DeleteError> {
+        self.inner.delete(path)
+    }
+
+    fn exists(&self, path: &Path) -> Result<bool, OpenReadError> {
+        self.inner.exists(path)
+    }
+
+    fn open_write(&self, path: &Path) -> Result<WritePtr, OpenWriteError> {
+        let mut inner = self.inner.open_write(path)?;
+        let block_encryptor = self
+            .new_block_encryptor()
+            .map_err(|io_error| OpenWriteError::wrap_io_error(io_error, path.to_path_buf()))?;
+        inner
+            .write_all(&block_encryptor.header())
+            .map_err(|io_error| OpenWriteError::wrap_io_error(io_error, path.to_path_buf()))?;
+        let buffer = Vec::with_capacity(block_encryptor.block_size);
+        let encrypted_writer: Box<dyn TerminatingWrite> = Box::new(EncryptedWriter {
+            inner,
+            block_encryptor,
+            block_id: 0,
+            buffer,
+        });
+        Ok(BufWriter::new(encrypted_writer))
+    }
+
+    fn atomic_read(&self, path: &Path) -> Result<Vec<u8>, OpenReadError> {
+        let data = self.inner.atomic_read(path)?;
+        let file = FileSlice::from(data);
+        let encrypted_file = self
+            .open_encrypted_file(path, file)
+            .map_err(|io_error| OpenReadError::wrap_io_error(io_error, path.to_path_buf()))?;
+        let plaintext = encrypted_file
+            .read_bytes(0..encrypted_file.len)
+            .map_err(|io_error| OpenReadError::wrap_io_error(io_error, path.to_path_buf()))?;
+        Ok(plaintext.as_slice().to_vec())
+    }
+
+    fn atomic_write(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        let block_encryptor = self.new_block_encryptor()?;
+        let block_size = block_encryptor.block_size;
+        let num_blocks = data.len() / block_size + 1;
+        let mut encrypted_data = block_encryptor.header().to_vec();
+        for block_id in 0..num_blocks {
+            let start = block_id * block_size;
+            let end = data.len().min(start + block_size);
+            let is_last = block_id + 1 == num_blocks;
+            encrypted_data.extend(block_encryptor.encrypt(block_id, is_last, &data[start..end])?);
+        }
+        self.inner.atomic_write(path, &encrypted_data)
+    }
+
+    fn sync_directory(&self) -> io::Result<()> {
+        self.inner.sync_directory()
+    }
+
+    fn acquire_lock(&self, lock: &Lock) -> Result<DirectoryLock, LockError> {
+        self.inner.acquire_lock(lock)
+    }
+
+    fn watch(&self, watch_callback: WatchCallback) -> crate::Result<WatchHandle> {
+        self.inner.watch(watch_callback)
+    }
+}
+
+/// A [`BlockCipher`] encrypting the blocks with AES-256-GCM.
+#[cfg(feature = "aes-gcm-encryption")]
+#[derive(Clone)]
+pub struct AesGcmCipher {
+    cipher: aes_gcm::Aes256Gcm,
+}
+
+#[cfg(feature = "aes-gcm-encryption")]
+impl AesGcmCipher {
+    /// Length of the keys, in bytes.
+    pub const KEY_LEN: usize = 32;
+
+    /// Creates a new `AesGcmCipher` from a 256-bit key.
+    pub fn new(key: &[u8; Self::KEY_LEN]) -> AesGcmCipher {
+        use aes_gcm::KeyInit;
+        AesGcmCipher {
+            cipher: aes_gcm::Aes256Gcm::new(key.into()),
+        }
+    }
+}
+
+#[cfg(feature = "aes-gcm-encryption")]
+impl fmt::Debug for AesGcmCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The key is deliberately not displayed.
+        f.write_str("AesGcmCipher")
+    }
+}
+
+#[cfg(feature = "aes-gcm-encryption")]
+impl BlockCipher for AesGcmCipher {
+    fn overhead(&self) -> usize {
+        // The length of the authentication tag.
+        16
+    }
+
+    fn encrypt(
+        &self,
+        nonce: &[u8; NONCE_LEN],
+        associated_data: &[u8],
+        plaintext: &[u8],
+    ) -> Vec<u8> {
+        use aes_gcm::aead::{Aead, Payload};
+        let payload = Payload {
+            msg: plaintext,
+            aad: associated_data,
+        };
+        self.cipher
+            .encrypt(nonce.into(), payload)
+            .expect("AES-GCM encryption only fails on plaintexts longer than 64GB")
+    }
+
+    fn decrypt(
+        &self,
+        nonce: &[u8; NONCE_LEN],
+        associated_data: &[u8],
+        ciphertext: &[u8],
+    ) -> io::Result<Vec<u8>> {
+        use aes_gcm::aead::{Aead, Payload};
+        let payload = Payload {
+            msg: ciphertext,
+            aad: associated_data,
+        };
+        self.cipher
+            .decrypt(nonce.into(), payload)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "authentication failed"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Write};
+    use std::path::Path;
+
+    use common::HasLen;
+
+    use super::{BlockCipher, EncryptedDirectory, NONCE_LEN, NONCE_PREFIX_LEN};
+    use crate::collector::Count;
+    use crate::directory::{Directory, RamDirectory, TerminatingWrite};
+    use crate::query::TermQuery;
+    use crate::schema::{IndexRecordOption, Schema, TEXT};
+    use crate::{Index, IndexSettings, IndexWriter, Term};
+
+    /// A toy cipher, xoring the data with a keystream derived from the key and the nonce, and
+    /// authenticating it with a checksum. It must not be used for anything but tests.
+    #[derive(Debug)]
+    struct ToyCipher {
+        key: u64,
+    }
+
+    impl ToyCipher {
+        fn keystream(&self, nonce: &[u8; NONCE_LEN]) -> impl Iterator<Item = u8> {
+            let mut state = self.key ^ crc32fast::hash(nonce) as u64;
+            std::iter::repeat_with(move || {
+                state = state
+                    .wrapping_mul(6_364_136_223_846_793_005)
+                    .wrapping_add(1_442_695_040_888_963_407);
+                (state >> 56) as u8
+            })
+        }
+
+        fn tag(&self, nonce: &[u8; NONCE_LEN], associated_data: &[u8], plaintext: &[u8]) -> u32 {
+            let mut hasher = crc32fast::Hasher::new();
+            hasher.update(&self.key.to_le_bytes());
+            hasher.update(nonce);
+            hasher.update(associated_data);
+            hasher.update(plaintext);
+            hasher.finalize()
+        }
+    }
+
+    impl BlockCipher for ToyCipher {
+        fn overhead(&self) -> usize {
+            4
+        }
+
+        fn encrypt(
+            &self,
+            nonce: &[u8; NONCE_LEN],
+            associated_data: &[u8],
+            plaintext: &[u8],
+        ) -> Vec<u8> {
+            let mut ciphertext: Vec<u8> = plaintext
+                .iter()
+                .zip(self.keystream(nonce))
+                .map(|(byte, key_byte)| byte ^ key_byte)
+                .collect();
+            ciphertext
+                .extend_from_slice(&self.tag(nonce, associated_data, plaintext).to_le_bytes());
+            ciphertext
+        }
+
+        fn decrypt(
+            &self,
+            nonce: &[u8; NONCE_LEN],
+            associated_data: &[u8],
+            ciphertext: &[u8],
+        ) -> io::Result<Vec<u8>> {
+            let Some(tag_start) = ciphertext.len().checked_sub(4) else {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "ciphertext too short",
+                ));
+            };
+            let (ciphertext, tag) = ciphertext.split_at(tag_start);
+            let plaintext: Vec<u8> = ciphertext
+                .iter()
+                .zip(self.keystream(nonce))
+                .map(|(byte, key_byte)| byte ^ key_byte)
+                .collect();
+            if tag != self.tag(nonce, associated_data, &plaintext).to_le_bytes() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "authentication failed",
+                ));
+            }
+            Ok(plaintext)
+        }
+    }
+
+    #[test]
+    fn test_encrypted_directory_round_trip() -> crate::Result<()> {
+        let ram_directory = RamDirectory::create();
+        let directory = EncryptedDirectory::new(ram_directory.clone(), ToyCipher { key: 42 })
+            .with_block_size(100);
+        let data: Vec<u8> = (0..=255u8).cycle().take(1_000).collect();
+        let path = Path::new("data");
+        {
+            let mut write = directory.open_write(path)?;
+            write.write_all(&data[..300])?;
+            write.write_all(&data[300..])?;
+            write.terminate()?;
+        }
+        // The underlying directory only sees ciphertext.
+        let raw_bytes = ram_directory.open_read(path)?.read_bytes()?;
+        assert!(!raw_bytes
+            .as_slice()
+            .windows(10)
+            .any(|window| window == &data[..10]));
+
+        let file_slice = directory.open_read(path)?;
+        assert_eq!(file_slice.len(), 1_000);
+        for range in [0..0, 0..1_000, 50..60, 95..205, 999..1_000, 300..400] {
+            let bytes = file_slice.read_bytes_slice(range.clone())?;
+            assert_eq!(bytes.as_slice(), &data[range]);
+        }
+
+        // A file whose length is a multiple of the block size, and an empty file.
+        for len in [200, 0] {
+            let path = Path::new("aligned");
+            directory.delete(path).ok();
+            let mut write = directory.open_write(path)?;
+            write.write_all(&data[..len])?;
+            write.terminate()?;
+            assert_eq!(
+                directory.open_read(path)?.read_bytes()?.as_slice(),
+                &data[..len]
+            );
+        }
+
+        let atomic_path = Path::new("atomic.json");
+        directory.atomic_write(atomic_path, b"{\"opstamp\": 3}")?;
+        assert_eq!(directory.atomic_read(atomic_path)?, b"{\"opstamp\": 3}");
+        assert_ne!(ram_directory.atomic_read(atomic_path)?, b"{\"opstamp\": 3}");
+        Ok(())
+    }
+
+    #[test]
+    fn test_encrypted_directory_truncated_file() -> crate::Result<()> {
+        let ram_directory = RamDirectory::create();
+        let directory = EncryptedDirectory::new(ram_directory.clone(), ToyCipher { key: 42 })
+            .with_block_size(100);
+        let data = vec![7u8; 1_000];
+        directory.atomic_write(Path::new("data"), &data)?;
+        // Dropping the last block is detected.
+        let raw_bytes = ram_directory.atomic_read(Path::new("data"))?;
+        ram_directory.atomic_write(Path::new("data"), &raw_bytes[..raw_bytes.len() - 4])?;
+        assert!(directory.atomic_read(Path::new("data")).is_err());
+        // A last block of 2 bytes, shorter than the overhead of the cipher, is an error too.
+        ram_directory.atomic_write(Path::new("data"), &raw_bytes[..raw_bytes.len() - 106])?;
+        assert!(directory.atomic_read(Path::new("data")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_encrypted_directory_index() -> crate::Result<()> {
+        let ram_directory = RamDirectory::create();
+        let mut schema_builder = Schema::builder();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let schema = schema_builder.build();
+        {
+            let directory = EncryptedDirectory::new(ram_directory.clone(), ToyCipher { key: 42 });
+            let index = Index::create(directory, schema, IndexSettings::default())?;
+            let mut index_writer: IndexWriter = index.writer_for_tests()?;
+            for i in 0..1_000 {
+                let text = if i % 10 == 0 { "hello world" } else { "hello" };
+                index_writer.add_document(doc!(text_field => text))?;
+            }
+            index_writer.commit()?;
+        }
+
+        let index = Index::open(EncryptedDirectory::new(
+            ram_directory.clone(),
+            ToyCipher { key: 42 },
+        ))?;
+        let query = TermQuery::new(
+            Term::from_field_text(text_field, "world"),
+            IndexRecordOption::Basic,
+        );
+        assert_eq!(index.reader()?.searcher().search(&query, &Count)?, 100);
+
+        // The index cannot be opened with the wrong key.
+        assert!(Index::open(EncryptedDirectory::new(
+            ram_directory,
+            ToyCipher { key: 43 }
+        ))
+        .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_encrypted_directory_distinct_nonce_prefixes() -> crate::Result<()> {
+        let ram_directory = RamDirectory::create();
+        let directory = EncryptedDirectory::new(ram_directory.clone(), ToyCipher { key: 42 });
+        let data = vec![7u8; 10];
+        directory.atomic_write(Path::new("first"), &data)?;
+        directory.atomic_write(Path::new("second"), &data)?;
+        let first = ram_directory.atomic_read(Path::new("first"))?;
+        let second = ram_directory.atomic_read(Path::new("second"))?;
+        assert_ne!(first[..NONCE_PREFIX_LEN], second[..NONCE_PREFIX_LEN]);
+        assert_ne!(first, second);
+        Ok(())
+    }
+
+    #[cfg(feature = "aes-gcm-encryption")]
+    #[test]
+    fn test_encrypted_directory_aes_gcm() -> crate::Result<()> {
+        use super::AesGcmCipher;
+
+        let ram_directory = RamDirectory::create();
+        let directory =
+            EncryptedDirectory::new(ram_directory.clone(), AesGcmCipher::new(&[1u8; 32]))
+                .with_block_size(100);
+        let data: Vec<u8> = (0..=255u8).cycle().take(1_000).collect();
+        directory.atomic_write(Path::new("data"), &data)?;
+        assert_eq!(directory.atomic_read(Path::new("data"))?, data);
+        let raw_bytes = ram_directory.atomic_read(Path::new("data"))?;
+        // 10 blocks of 100 bytes, and an empty last block, each with a 16 bytes tag.
+        assert_eq!(raw_bytes.len(), super::HEADER_LEN + 1_000 + 11 * 16);
+
+        let wrong_key_directory =
+            EncryptedDirectory::new(ram_directory, AesGcmCipher::new(&[2u8; 32]));
+        assert!(wrong_key_directory.atomic_read(Path::new("data")).is_err());
+        Ok(())
+    }
+}
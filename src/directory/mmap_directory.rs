@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::fs::{self, File, OpenOptions};
 use std::io::{self, BufWriter, Read, Write};
@@ -76,11 +76,149 @@ pub struct CacheInfo {
     pub mmapped: Vec<PathBuf>,
 }
 
+/// An access pattern advice given to the kernel with `madvise`, see [`MadvisePolicy`].
+///
+/// It is only applied on unix platforms, and ignored elsewhere.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum MadviseAdvice {
+    /// No special treatment, the default.
+    Normal,
+    /// Pages are accessed in random order: the kernel read-ahead is disabled.
+    Random,
+    /// Pages are accessed sequentially: the kernel reads ahead aggressively.
+    Sequential,
+    /// The pages are needed soon: the kernel starts reading them.
+    WillNeed,
+    /// Any other advice supported by the platform.
+    #[cfg(unix)]
+    Other(Advice),
+}
+
+#[cfg(unix)]
+impl From<MadviseAdvice> for Advice {
+    fn from(advice: MadviseAdvice) -> Advice {
+        match advice {
+            MadviseAdvice::Normal => Advice::Normal,
+            MadviseAdvice::Random => Advice::Random,
+            MadviseAdvice::Sequential => Advice::Sequential,
+            MadviseAdvice::WillNeed => Advice::WillNeed,
+            MadviseAdvice::Other(advice) => advice,
+        }
+    }
+}
+
+#[cfg(unix)]
+impl From<Advice> for MadviseAdvice {
+    fn from(advice: Advice) -> MadviseAdvice {
+        match advice {
+            Advice::Normal => MadviseAdvice::Normal,
+            Advice::Random => MadviseAdvice::Random,
+            Advice::Sequential => MadviseAdvice::Sequential,
+            Advice::WillNeed => MadviseAdvice::WillNeed,
+            advice => MadviseAdvice::Other(advice),
+        }
+    }
+}
+
+// Gives the advice to the kernel, ignoring errors. This is a no-op on non-unix platforms.
+fn advise(mmap: &Mmap, advice: MadviseAdvice) {
+    #[cfg(unix)]
+    {
+        let _ = mmap.advise(advice.into());
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (mmap, advice);
+    }
+}
+
+/// Describes the `madvise` access pattern advice given to the kernel for the files mmapped by
+/// a [`MmapDirectory`], depending on their extension.
+///
+/// For instance, random term lookups on a large index benefit from `MadviseAdvice::Random` on
+/// the term dictionary (`term`) and postings (`idx`) files, which disables the kernel
+/// read-ahead.
+///
+/// The files of the extensions registered with
+/// [`with_warmup_for_extension()`](MadvisePolicy::with_warmup_for_extension) go through a
+/// warmup phase when they are mmapped: they are advised `Sequential` and `WillNeed`, so that
+/// the kernel reads them entirely ahead with large reads, before their steady advice is
+/// applied. This avoids the page faults of a cold start, at the cost of reading the whole file.
+///
+/// The extensions of the segment files are `idx` (postings), `pos` (positions), `term` (term
+/// dictionary), `store` (doc store), `fast` (fast fields), `fieldnorm` (field norms) and `del`
+/// (alive bitset).
+///
+/// The policy is only applied on unix platforms, and ignored elsewhere.
+#[derive(Clone, Debug, Default)]
+pub struct MadvisePolicy {
+    default_advice: Option<MadviseAdvice>,
+    advice_per_extension: HashMap<String, MadviseAdvice>,
+    warmup_extensions: HashSet<String>,
+}
+
+impl MadvisePolicy {
+    /// Creates a policy with the given advice for all files.
+    pub fn new(default_advice: MadviseAdvice) -> MadvisePolicy {
+        MadvisePolicy {
+            default_advice: Some(default_advice),
+            ..MadvisePolicy::default()
+        }
+    }
+
+    /// Sets the advice for the files with the given extension (without the leading dot),
+    /// overriding the default advice.
+    pub fn with_advice_for_extension(
+        mut self,
+        extension: impl Into<String>,
+        advice: MadviseAdvice,
+    ) -> MadvisePolicy {
+        self.advice_per_extension.insert(extension.into(), advice);
+        self
+    }
+
+    /// Warms up the files with the given extension (without the leading dot) when they are
+    /// mmapped, see [`MadvisePolicy`].
+    pub fn with_warmup_for_extension(mut self, extension: impl Into<String>) -> MadvisePolicy {
+        self.warmup_extensions.insert(extension.into());
+        self
+    }
+
+    /// Returns the advice to give for the file at `path`, if any.
+    pub fn advice_for_path(&self, path: &Path) -> Option<MadviseAdvice> {
+        extension(path)
+            .and_then(|extension| self.advice_per_extension.get(extension).copied())
+            .or(self.default_advice)
+    }
+
+    /// Returns true if the file at `path` is warmed up when it is mmapped.
+    pub fn warmup_for_path(&self, path: &Path) -> bool {
+        extension(path).map_or(false, |extension| {
+            self.warmup_extensions.contains(extension)
+        })
+    }
+
+    fn apply(&self, path: &Path, mmap: &Mmap) {
+        let advice_opt = self.advice_for_path(path);
+        if self.warmup_for_path(path) {
+            advise(mmap, MadviseAdvice::Sequential);
+            advise(mmap, MadviseAdvice::WillNeed);
+            // The sequential advice only holds for the warmup.
+            advise(mmap, advice_opt.unwrap_or(MadviseAdvice::Normal));
+        } else if let Some(advice) = advice_opt {
+            advise(mmap, advice);
+        }
+    }
+}
+
+fn extension(path: &Path) -> Option<&str> {
+    path.extension().and_then(|extension| extension.to_str())
+}
+
 struct MmapCache {
     counters: CacheCounters,
     cache: HashMap<PathBuf, WeakArcBytes>,
-    #[cfg(unix)]
-    madvice_opt: Option<Advice>,
+    madvise_policy: MadvisePolicy,
 }
 
 impl MmapCache {
@@ -88,14 +226,12 @@ impl MmapCache {
         MmapCache {
             counters: CacheCounters::default(),
             cache: HashMap::default(),
-            #[cfg(unix)]
-            madvice_opt: None,
+            madvise_policy: MadvisePolicy::default(),
         }
     }
 
-    #[cfg(unix)]
-    fn set_advice(&mut self, madvice: Advice) {
-        self.madvice_opt = Some(madvice);
+    fn set_madvise_policy(&mut self, madvise_policy: MadvisePolicy) {
+        self.madvise_policy = madvise_policy;
     }
 
     fn get_info(&self) -> CacheInfo {
@@ -120,10 +256,8 @@ impl MmapCache {
 
     fn open_mmap_impl(&self, full_path: &Path) -> Result<Option<Mmap>, OpenReadError> {
         let mmap_opt = open_mmap(full_path)?;
-        #[cfg(unix)]
-        if let (Some(mmap), Some(madvice)) = (mmap_opt.as_ref(), self.madvice_opt) {
-            // We ignore madvise errors.
-            let _ = mmap.advise(madvice);
+        if let Some(mmap) = mmap_opt.as_ref() {
+            self.madvise_policy.apply(full_path, mmap);
         }
         Ok(mmap_opt)
     }
@@ -221,9 +355,24 @@ impl MmapDirectory {
     pub fn open_with_madvice(
         directory_path: impl AsRef<Path>,
         madvice: Advice,
+    ) -> Result<MmapDirectory, OpenDirectoryError> {
+        Self::open_with_madvise(directory_path, MadvisePolicy::new(madvice.into()))
+    }
+
+    /// Opens a MmapDirectory in a directory, with an access pattern per file type
+    /// described by the [`MadvisePolicy`].
+    ///
+    /// The policy is ignored on non-unix platforms.
+    pub fn open_with_madvise(
+        directory_path: impl AsRef<Path>,
+        madvise_policy: MadvisePolicy,
     ) -> Result<MmapDirectory, OpenDirectoryError> {
         let dir = Self::open_impl_to_avoid_monomorphization(directory_path.as_ref())?;
-        dir.inner.mmap_cache.write().unwrap().set_advice(madvice);
+        dir.inner
+            .mmap_cache
+            .write()
+            .unwrap()
+            .set_madvise_policy(madvise_policy);
         Ok(dir)
     }
 
@@ -532,9 +681,11 @@ mod tests {
     use common::HasLen;
 
     use super::*;
+    use crate::collector::Count;
     use crate::indexer::LogMergePolicy;
-    use crate::schema::{Schema, SchemaBuilder, TEXT};
-    use crate::{Index, IndexSettings, IndexWriter, ReloadPolicy};
+    use crate::query::TermQuery;
+    use crate::schema::{IndexRecordOption, Schema, SchemaBuilder, TEXT};
+    use crate::{Index, IndexSettings, IndexWriter, ReloadPolicy, Term};
 
     #[test]
     fn test_open_non_existent_path() {
@@ -698,4 +849,73 @@ mod tests {
             }
         });
     }
+
+    #[test]
+    fn test_madvise_policy() {
+        let madvise_policy = MadvisePolicy::new(MadviseAdvice::Normal)
+            .with_advice_for_extension("term", MadviseAdvice::Random)
+            .with_advice_for_extension("idx", MadviseAdvice::Random)
+            .with_advice_for_extension("store", MadviseAdvice::Sequential)
+            .with_warmup_for_extension("fast");
+        assert_eq!(
+            madvise_policy.advice_for_path(Path::new("abc.term")),
+            Some(MadviseAdvice::Random)
+        );
+        assert_eq!(
+            madvise_policy.advice_for_path(Path::new("abc.store")),
+            Some(MadviseAdvice::Sequential)
+        );
+        assert_eq!(
+            madvise_policy.advice_for_path(Path::new("abc.fast")),
+            Some(MadviseAdvice::Normal)
+        );
+        assert!(madvise_policy.warmup_for_path(Path::new("abc.fast")));
+        assert!(!madvise_policy.warmup_for_path(Path::new("abc.term")));
+        assert_eq!(
+            MadvisePolicy::default().advice_for_path(Path::new("abc.term")),
+            None
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_madvise_advice_round_trip() {
+        for madvise_advice in [
+            MadviseAdvice::Normal,
+            MadviseAdvice::Random,
+            MadviseAdvice::Sequential,
+            MadviseAdvice::WillNeed,
+        ] {
+            let advice: Advice = madvise_advice.into();
+            assert_eq!(MadviseAdvice::from(advice), madvise_advice);
+        }
+    }
+
+    #[test]
+    fn test_open_with_madvise() -> crate::Result<()> {
+        let tempdir = TempDir::new().unwrap();
+        let madvise_policy = MadvisePolicy::default()
+            .with_advice_for_extension("term", MadviseAdvice::Random)
+            .with_advice_for_extension("idx", MadviseAdvice::Random)
+            .with_warmup_for_extension("fast")
+            .with_warmup_for_extension("term");
+        let mmap_directory = MmapDirectory::open_with_madvise(tempdir.path(), madvise_policy)?;
+        let mut schema_builder = Schema::builder();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create(
+            mmap_directory,
+            schema_builder.build(),
+            IndexSettings::default(),
+        )?;
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text_field => "hello"))?;
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let query = TermQuery::new(
+            Term::from_field_text(text_field, "hello"),
+            IndexRecordOption::Basic,
+        );
+        assert_eq!(searcher.search(&query, &Count)?, 1);
+        Ok(())
+    }
 }
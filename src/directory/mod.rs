@@ -3,12 +3,15 @@
 #[cfg(feature = "mmap")]
 mod mmap_directory;
 
+mod caching_directory;
 mod directory;
 mod directory_lock;
+mod encrypted_directory;
 mod file_watcher;
 mod footer;
 mod managed_directory;
 mod ram_directory;
+mod remote_directory;
 mod watch_event_router;
 
 /// Errors specific to the directory module.
@@ -22,10 +25,17 @@ use std::path::PathBuf;
 pub use common::file_slice::{FileHandle, FileSlice};
 pub use common::{AntiCallToken, OwnedBytes, TerminatingWrite};
 
+pub use self::caching_directory::{BlockCacheStats, CachingDirectory};
 pub(crate) use self::composite_file::{CompositeFile, CompositeWrite};
 pub use self::directory::{Directory, DirectoryClone, DirectoryLock};
 pub use self::directory_lock::{Lock, INDEX_WRITER_LOCK, META_LOCK};
+#[cfg(feature = "aes-gcm-encryption")]
+pub use self::encrypted_directory::AesGcmCipher;
+pub use self::encrypted_directory::{BlockCipher, EncryptedDirectory, NONCE_LEN};
 pub use self::ram_directory::RamDirectory;
+#[cfg(feature = "http-range-fetcher")]
+pub use self::remote_directory::HttpRangeFetcher;
+pub use self::remote_directory::{RangeFetcher, RemoteDirectory};
 pub use self::watch_event_router::{WatchCallback, WatchCallbackList, WatchHandle};
 
 /// Outcome of the Garbage collection
@@ -47,7 +57,7 @@ pub use memmap2::Advice;
 
 pub use self::managed_directory::ManagedDirectory;
 #[cfg(feature = "mmap")]
-pub use self::mmap_directory::MmapDirectory;
+pub use self::mmap_directory::{MadviseAdvice, MadvisePolicy, MmapDirectory};
 
 /// Write object for Directory.
 ///
@@ -0,0 +1,492 @@
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::{fmt, io, result};
+
+use common::HasLen;
+
+use super::FileHandle;
+use crate::directory::error::{DeleteError, LockError, OpenReadError, OpenWriteError};
+use crate::directory::{
+    Directory, DirectoryLock, Lock, OwnedBytes, WatchCallback, WatchHandle, WritePtr,
+    INDEX_WRITER_LOCK,
+};
+
+/// Fetches byte ranges of the files of a remote index, typically with HTTP range requests
+/// against an object storage like S3.
+///
+/// This is the only part a [`RemoteDirectory`] needs to be implemented for a given storage.
+/// With the `http-range-fetcher` feature, `HttpRangeFetcher` implements it over HTTP(S).
+pub trait RangeFetcher: Send + Sync + fmt::Debug + 'static {
+    /// Returns the length of the file at the given path, or `None` if it does not exist.
+    fn file_len(&self, path: &Path) -> io::Result<Option<usize>>;
+
+    /// Fetches the given byte range of the file at the given path.
+    ///
+    /// The range is always within the length of the file.
+    fn fetch_range(&self, path: &Path, range: Range<usize>) -> io::Result<Vec<u8>>;
+}
+
+/// A read-only [`Directory`] reading the files of an index stored remotely, without
+/// downloading them fully.
+///
+/// Every read is a call to the [`RangeFetcher`], so the directory should be wrapped in a
+/// [`CachingDirectory`](crate::directory::CachingDirectory), which reads files by blocks and
+/// keeps them in memory:
+///
+/// ```rust,ignore
+/// let directory = CachingDirectory::new(RemoteDirectory::new(fetcher), 64 * 1024, 64 << 20);
+/// let index = Index::open(directory)?;
+/// ```
+///
+/// Writing to the directory is not supported and returns an error, so an [`Index`] opened
+/// on it can be searched but no `IndexWriter` can be created. As there is no concurrent
+/// writer, the other locks are no-ops, and changes in `meta.json` are not watched: the reader
+/// has to be reloaded manually.
+///
+/// [`Index`]: crate::Index
+#[derive(Clone)]
+pub struct RemoteDirectory {
+    fetcher: Arc<dyn RangeFetcher>,
+}
+
+impl fmt::Debug for RemoteDirectory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteDirectory")
+            .field("fetcher", &self.fetcher)
+            .finish()
+    }
+}
+
+impl RemoteDirectory {
+    /// Creates a new `RemoteDirectory` reading its files with the given fetcher.
+    pub fn new<F: RangeFetcher>(fetcher: F) -> RemoteDirectory {
+        RemoteDirectory {
+            fetcher: Arc::new(fetcher),
+        }
+    }
+
+    fn file_len(&self, path: &Path) -> Result<usize, OpenReadError> {
+        self.fetcher
+            .file_len(path)
+            .map_err(|io_error| OpenReadError::wrap_io_error(io_error, path.to_path_buf()))?
+            .ok_or_else(|| OpenReadError::FileDoesNotExist(path.to_path_buf()))
+    }
+}
+
+fn unsupported_write_error() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "The RemoteDirectory is read-only.",
+    )
+}
+
+#[derive(Debug)]
+struct RemoteFileHandle {
+    fetcher: Arc<dyn RangeFetcher>,
+    path: PathBuf,
+    len: usize,
+}
+
+impl FileHandle for RemoteFileHandle {
+    fn read_bytes(&self, range: Range<usize>) -> io::Result<OwnedBytes> {
+        if range.is_empty() {
+            return Ok(OwnedBytes::empty());
+        }
+        let bytes = self.fetcher.fetch_range(&self.path, range.clone())?;
+        if bytes.len() != range.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!(
+                    "Fetched {} bytes instead of {} from {:?}",
+                    bytes.len(),
+                    range.len(),
+                    self.path
+                ),
+            ));
+        }
+        Ok(OwnedBytes::new(bytes))
+    }
+}
+
+impl HasLen for RemoteFileHandle {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl Directory for RemoteDirectory {
+    fn get_file_handle(&self, path: &Path) -> Result<Arc<dyn FileHandle>, OpenReadError> {
+        let len = self.file_len(path)?;
+        Ok(Arc::new(RemoteFileHandle {
+            fetcher: self.fetcher.clone(),
+            path: path.to_path_buf(),
+            len,
+        }))
+    }
+
+    fn delete(&self, path: &Path) -> result::Result<(), DeleteError> {
+        Err(DeleteError::IoError {
+            io_error: Arc::new(unsupported_write_error()),
+            filepath: path.to_path_buf(),
+        })
+    }
+
+    fn exists(&self, path: &Path) -> Result<bool, OpenReadError> {
+        let len_opt = self
+            .fetcher
+            .file_len(path)
+            .map_err(|io_error| OpenReadError::wrap_io_error(io_error, path.to_path_buf()))?;
+        Ok(len_opt.is_some())
+    }
+
+    fn open_write(&self, path: &Path) -> Result<WritePtr, OpenWriteError> {
+        Err(OpenWriteError::wrap_io_error(
+            unsupported_write_error(),
+            path.to_path_buf(),
+        ))
+    }
+
+    fn atomic_read(&self, path: &Path) -> Result<Vec<u8>, OpenReadError> {
+        let len = self.file_len(path)?;
+        self.fetcher
+            .fetch_range(path, 0..len)
+            .map_err(|io_error| OpenReadError::wrap_io_error(io_error, path.to_path_buf()))
+    }
+
+    fn atomic_write(&self, _path: &Path, _data: &[u8]) -> io::Result<()> {
+        Err(unsupported_write_error())
+    }
+
+    fn sync_directory(&self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn acquire_lock(&self, lock: &Lock) -> Result<DirectoryLock, LockError> {
+        if lock.filepath == INDEX_WRITER_LOCK.filepath {
+            return Err(LockError::IoError(Arc::new(unsupported_write_error())));
+        }
+        Ok(DirectoryLock::from(Box::new(())))
+    }
+
+    fn watch(&self, _watch_callback: WatchCallback) -> crate::Result<WatchHandle> {
+        Ok(WatchHandle::empty())
+    }
+}
+
+/// A [`RangeFetcher`] reading the files of an index served over HTTP(S), with `HEAD` requests
+/// for the file lengths and `GET` requests with a `Range` header for their content.
+///
+/// The file at `path` is read from `{base_url}/{path}`. This works with any server supporting
+/// range requests, e.g. a public S3 bucket, or a private one behind a proxy signing the requests.
+/// Headers added with [`HttpRangeFetcher::with_header()`] are sent with every request, e.g. for
+/// authentication.
+#[cfg(feature = "http-range-fetcher")]
+#[derive(Clone)]
+pub struct HttpRangeFetcher {
+    agent: ureq::Agent,
+    base_url: String,
+    headers: Vec<(String, String)>,
+}
+
+#[cfg(feature = "http-range-fetcher")]
+impl fmt::Debug for HttpRangeFetcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The header values may hold credentials.
+        f.debug_struct("HttpRangeFetcher")
+            .field("base_url", &self.base_url)
+            .finish()
+    }
+}
+
+#[cfg(feature = "http-range-fetcher")]
+impl HttpRangeFetcher {
+    /// Creates a new `HttpRangeFetcher` reading the files under the given base URL.
+    pub fn new<S: Into<String>>(base_url: S) -> HttpRangeFetcher {
+        let mut base_url = base_url.into();
+        while base_url.ends_with('/') {
+            base_url.pop();
+        }
+        HttpRangeFetcher {
+            agent: ureq::Agent::new(),
+            base_url,
+            headers: Vec::new(),
+        }
+    }
+
+    /// Adds a header sent with every request.
+    #[must_use]
+    pub fn with_header<K: Into<String>, V: Into<String>>(
+        mut self,
+        name: K,
+        value: V,
+    ) -> HttpRangeFetcher {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    fn request(&self, method: &str, path: &Path) -> io::Result<ureq::Request> {
+        let path = path.to_str().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("The path {path:?} is not valid UTF-8"),
+            )
+        })?;
+        let url = format!("{}/{}", self.base_url, path.trim_start_matches('/'));
+        let mut request = self.agent.request(method, &url);
+        for (name, value) in &self.headers {
+            request = request.set(name, value);
+        }
+        Ok(request)
+    }
+}
+
+#[cfg(feature = "http-range-fetcher")]
+fn http_io_error(err: ureq::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, err)
+}
+
+#[cfg(feature = "http-range-fetcher")]
+impl RangeFetcher for HttpRangeFetcher {
+    fn file_len(&self, path: &Path) -> io::Result<Option<usize>> {
+        let response = match self.request("HEAD", path)?.call() {
+            Ok(response) => response,
+            Err(ureq::Error::Status(404, _)) => return Ok(None),
+            Err(err) => return Err(http_io_error(err)),
+        };
+        let len = response
+            .header("Content-Length")
+            .and_then(|len| len.parse::<usize>().ok())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Missing or invalid Content-Length for {path:?}"),
+                )
+            })?;
+        Ok(Some(len))
+    }
+
+    fn fetch_range(&self, path: &Path, range: Range<usize>) -> io::Result<Vec<u8>> {
+        use std::io::Read;
+
+        if range.is_empty() {
+            return Ok(Vec::new());
+        }
+        let response = self
+            .request("GET", path)?
+            .set("Range", &format!("bytes={}-{}", range.start, range.end - 1))
+            .call()
+            .map_err(http_io_error)?;
+        // A server ignoring the range answers 200 with the whole file.
+        let skip = if response.status() == 206 {
+            0
+        } else {
+            range.start
+        };
+        let mut reader = response.into_reader();
+        io::copy(&mut (&mut reader).take(skip as u64), &mut io::sink())?;
+        let mut bytes = Vec::with_capacity(range.len());
+        reader.take(range.len() as u64).read_to_end(&mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::ops::Range;
+    use std::path::Path;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use common::HasLen;
+
+    use super::{RangeFetcher, RemoteDirectory};
+    use crate::collector::{Count, TopDocs};
+    use crate::directory::error::OpenReadError;
+    use crate::directory::{CachingDirectory, Directory, RamDirectory};
+    use crate::query::TermQuery;
+    use crate::schema::{IndexRecordOption, Schema, Value, STORED, TEXT};
+    use crate::{Index, IndexSettings, IndexWriter, ReloadPolicy, TantivyDocument, Term};
+
+    /// Serves the files of a `RamDirectory`, counting the fetches.
+    #[derive(Debug, Clone, Default)]
+    struct MockFetcher {
+        directory: RamDirectory,
+        num_fetches: Arc<AtomicUsize>,
+    }
+
+    impl RangeFetcher for MockFetcher {
+        fn file_len(&self, path: &Path) -> io::Result<Option<usize>> {
+            match self.directory.open_read(path) {
+                Ok(file_slice) => Ok(Some(file_slice.len())),
+                Err(OpenReadError::FileDoesNotExist(_)) => Ok(None),
+                Err(err) => Err(io::Error::new(io::ErrorKind::Other, err)),
+            }
+        }
+
+        fn fetch_range(&self, path: &Path, range: Range<usize>) -> io::Result<Vec<u8>> {
+            self.num_fetches.fetch_add(1, Ordering::SeqCst);
+            let file_slice = self
+                .directory
+                .open_read(path)
+                .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+            Ok(file_slice.read_bytes_slice(range)?.as_slice().to_vec())
+        }
+    }
+
+    #[test]
+    fn test_remote_directory_read_bytes() -> crate::Result<()> {
+        let fetcher = MockFetcher::default();
+        let data: Vec<u8> = (0..=255u8).cycle().take(1_000).collect();
+        fetcher
+            .directory
+            .atomic_write(Path::new("data"), &data[..])?;
+        let directory = CachingDirectory::new(RemoteDirectory::new(fetcher.clone()), 100, 10_000);
+        let file_slice = directory.open_read(Path::new("data"))?;
+        assert_eq!(file_slice.len(), 1_000);
+
+        for range in [0..0, 0..1_000, 50..60, 95..205, 999..1_000, 300..400] {
+            let bytes = file_slice.read_bytes_slice(range.clone())?;
+            assert_eq!(bytes.as_slice(), &data[range]);
+        }
+        // The first non-empty read fetched all of the blocks, in a single request, and the
+        // next ones were served by the cache.
+        assert_eq!(fetcher.num_fetches.load(Ordering::SeqCst), 1);
+
+        assert!(!directory.exists(Path::new("missing"))?);
+        assert!(matches!(
+            directory.open_read(Path::new("missing")),
+            Err(OpenReadError::FileDoesNotExist(_))
+        ));
+        assert!(directory.atomic_write(Path::new("data"), b"").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_remote_directory_search() -> crate::Result<()> {
+        let fetcher = MockFetcher::default();
+        let mut schema_builder = Schema::builder();
+        let text_field = schema_builder.add_text_field("text", TEXT | STORED);
+        let schema = schema_builder.build();
+        {
+            let index = Index::create(fetcher.directory.clone(), schema, IndexSettings::default())?;
+            let mut index_writer: IndexWriter = index.writer_for_tests()?;
+            for i in 0..1_000 {
+                let text = if i % 10 == 0 { "hello world" } else { "hello" };
+                index_writer.add_document(doc!(text_field => text))?;
+            }
+            index_writer.commit()?;
+        }
+
+        let directory = CachingDirectory::new(RemoteDirectory::new(fetcher.clone()), 512, 1 << 20);
+        let index = Index::open(directory)?;
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::Manual)
+            .try_into()?;
+        let searcher = reader.searcher();
+        let query = TermQuery::new(
+            Term::from_field_text(text_field, "world"),
+            IndexRecordOption::Basic,
+        );
+        assert_eq!(searcher.search(&query, &Count)?, 100);
+        let num_fetches = fetcher.num_fetches.load(Ordering::SeqCst);
+        assert_eq!(searcher.search(&query, &Count)?, 100);
+        assert_eq!(fetcher.num_fetches.load(Ordering::SeqCst), num_fetches);
+
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(1))?;
+        let doc: TantivyDocument = searcher.doc(top_docs[0].1)?;
+        assert_eq!(
+            doc.get_first(text_field).unwrap().as_str(),
+            Some("hello world")
+        );
+
+        assert!(index.writer_for_tests::<TantivyDocument>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_remote_directory_without_cache() -> crate::Result<()> {
+        let fetcher = MockFetcher::default();
+        let data: Vec<u8> = (0..=255u8).cycle().take(1_000).collect();
+        fetcher
+            .directory
+            .atomic_write(Path::new("data"), &data[..])?;
+        let directory = RemoteDirectory::new(fetcher.clone());
+        let file_slice = directory.open_read(Path::new("data"))?;
+        for range in [50..60, 50..60, 95..205] {
+            let bytes = file_slice.read_bytes_slice(range.clone())?;
+            assert_eq!(bytes.as_slice(), &data[range]);
+        }
+        assert_eq!(fetcher.num_fetches.load(Ordering::SeqCst), 3);
+        assert_eq!(directory.atomic_read(Path::new("data"))?, data);
+        Ok(())
+    }
+
+    #[cfg(feature = "http-range-fetcher")]
+    #[test]
+    fn test_http_range_fetcher() -> crate::Result<()> {
+        use std::io::{BufRead, BufReader, Write};
+        use std::net::TcpListener;
+
+        use super::HttpRangeFetcher;
+
+        let data: Vec<u8> = (0..=255u8).cycle().take(1_000).collect();
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let base_url = format!("http://{}/index/", listener.local_addr()?);
+        let served_data = data.clone();
+        // A minimal HTTP server, answering one request per connection.
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else {
+                    return;
+                };
+                let request_lines: Vec<String> = BufReader::new(stream.try_clone().unwrap())
+                    .lines()
+                    .map_while(Result::ok)
+                    .take_while(|line| !line.is_empty())
+                    .collect();
+                let Some(request_line) = request_lines.first() else {
+                    continue;
+                };
+                let range_opt = request_lines.iter().find_map(|line| {
+                    let line = line.to_ascii_lowercase();
+                    let (start, end) = line.strip_prefix("range: bytes=")?.split_once('-')?;
+                    Some(start.parse::<usize>().ok()?..end.parse::<usize>().ok()? + 1)
+                });
+                let (status, body): (&str, &[u8]) = if request_line.starts_with("HEAD /index/data ")
+                {
+                    ("200 OK", &served_data[..])
+                } else if let (true, Some(range)) =
+                    (request_line.starts_with("GET /index/data "), range_opt)
+                {
+                    ("206 Partial Content", &served_data[range])
+                } else {
+                    ("404 Not Found", &[])
+                };
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                );
+                if !request_line.starts_with("HEAD") {
+                    let _ = stream.write_all(body);
+                }
+            }
+        });
+
+        let fetcher = HttpRangeFetcher::new(base_url).with_header("Authorization", "Bearer token");
+        assert_eq!(fetcher.file_len(Path::new("data"))?, Some(1_000));
+        assert_eq!(fetcher.file_len(Path::new("missing"))?, None);
+        assert_eq!(
+            fetcher.fetch_range(Path::new("data"), 95..205)?,
+            &data[95..205]
+        );
+
+        let directory = CachingDirectory::new(RemoteDirectory::new(fetcher), 100, 10_000);
+        let file_slice = directory.open_read(Path::new("data"))?;
+        assert_eq!(file_slice.read_bytes()?.as_slice(), &data[..]);
+        Ok(())
+    }
+}
@@ -110,6 +110,9 @@ pub enum TantivyError {
     #[error("Deserialize error: {0}")]
     /// An error occurred while attempting to deserialize a document.
     DeserializeError(DeserializeError),
+    /// The deadline of a search was exceeded before its completion.
+    #[error("The search timed out")]
+    TimedOut,
 }
 
 impl From<io::Error> for TantivyError {
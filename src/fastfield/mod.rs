@@ -79,7 +79,7 @@ mod tests {
     use std::ops::{Range, RangeInclusive};
     use std::path::Path;
 
-    use columnar::StrColumn;
+    use columnar::{Cardinality, StrColumn};
     use common::{ByteCount, DateTimePrecision, HasLen, TerminatingWrite};
     use once_cell::sync::Lazy;
     use rand::prelude::SliceRandom;
@@ -87,7 +87,7 @@ mod tests {
     use rand::{Rng, SeedableRng};
 
     use super::*;
-    use crate::directory::{Directory, RamDirectory, WritePtr};
+    use crate::directory::{Directory, FileSlice, RamDirectory, WritePtr};
     use crate::index::SegmentId;
     use crate::merge_policy::NoMergePolicy;
     use crate::schema::{
@@ -96,7 +96,7 @@ mod tests {
     };
     use crate::time::OffsetDateTime;
     use crate::tokenizer::{LowerCaser, RawTokenizer, TextAnalyzer, TokenizerManager};
-    use crate::{Index, IndexWriter, SegmentReader};
+    use crate::{DocId, Index, IndexWriter, SegmentReader};
 
     pub static SCHEMA: Lazy<Schema> = Lazy::new(|| {
         let mut schema_builder = Schema::builder();
@@ -144,6 +144,49 @@ mod tests {
         Ok(())
     }
 
+    fn serialize_u64_fast_field(vals: &[u64]) -> FileSlice {
+        let path = Path::new("test");
+        let directory: RamDirectory = RamDirectory::create();
+        let mut write: WritePtr = directory.open_write(path).unwrap();
+        let mut fast_field_writers = FastFieldsWriter::from_schema(&SCHEMA).unwrap();
+        for &val in vals {
+            fast_field_writers.add_document(&doc!(*FIELD=>val)).unwrap();
+        }
+        fast_field_writers.serialize(&mut write, None).unwrap();
+        write.terminate().unwrap();
+        directory.open_read(path).unwrap()
+    }
+
+    #[test]
+    fn test_intfastfield_small_range_is_bitpacked() {
+        // Values are bitpacked with the minimum width required by the range of the values of
+        // the segment, so small counts only take 4 bits per document.
+        let small_vals: Vec<u64> = (0..10_000u64).map(|i| i % 16).collect();
+        let small_file = serialize_u64_fast_field(&small_vals);
+        // Pseudo-random values spanning the whole u64 range.
+        let large_vals: Vec<u64> = (0..10_000u64)
+            .map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+            .collect();
+        let large_file = serialize_u64_fast_field(&large_vals);
+        assert!(small_file.len() < 10_000 / 2 + 1_000);
+        assert!(large_file.len() >= 10_000 * 8);
+
+        let small_column = FastFieldReaders::open(small_file, SCHEMA.clone())
+            .unwrap()
+            .u64("field")
+            .unwrap()
+            .first_or_default_col(0);
+        let large_column = FastFieldReaders::open(large_file, SCHEMA.clone())
+            .unwrap()
+            .u64("field")
+            .unwrap()
+            .first_or_default_col(0);
+        for (doc, (small_val, large_val)) in small_vals.iter().zip(&large_vals).enumerate() {
+            assert_eq!(small_column.get_val(doc as u32), *small_val);
+            assert_eq!(large_column.get_val(doc as u32), *large_val);
+        }
+    }
+
     #[test]
     fn test_intfastfield_large() {
         let path = Path::new("test");
@@ -1050,6 +1093,72 @@ mod tests {
         test_range_variant(1000, 1002);
     }
 
+    #[test]
+    fn test_multivalued_u64_fast_field_values_for_doc() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let tags_field = schema_builder.add_u64_field("tags", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        index_writer.add_document(doc!())?;
+        index_writer.add_document(doc!(tags_field => 7u64))?;
+        index_writer
+            .add_document(doc!(tags_field => 3u64, tags_field => 1u64, tags_field => 3u64))?;
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let tags_column = searcher.segment_reader(0).fast_fields().u64("tags")?;
+        assert_eq!(tags_column.get_cardinality(), Cardinality::Multivalued);
+
+        assert_eq!(tags_column.num_values(0), 0);
+        assert!(tags_column.values_for_doc(0).next().is_none());
+        assert_eq!(tags_column.num_values(1), 1);
+        assert!(tags_column.values_for_doc(1).eq([7]));
+        // The values are kept in the order of the document.
+        assert_eq!(tags_column.num_values(2), 3);
+        assert!(tags_column.values_for_doc(2).eq([3, 1, 3]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sort_by_str_fast_field_term_ords() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let city_field = schema_builder.add_text_field("city", STRING | FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        for city in ["Tokyo", "Berlin", "Paris", "Berlin"] {
+            index_writer.add_document(doc!(city_field => city))?;
+        }
+        index_writer.add_document(doc!())?;
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let city_column = searcher
+            .segment_reader(0)
+            .fast_fields()
+            .str("city")?
+            .unwrap();
+        assert_eq!(city_column.num_terms(), 3);
+
+        // Term ordinals follow the lexicographical order of the terms, so docs can be sorted by
+        // ordinal without reading the terms.
+        let mut docs: Vec<(Option<u64>, DocId)> = (0..5)
+            .map(|doc| (city_column.ords().first(doc), doc))
+            .collect();
+        docs.sort_by_key(|&(ord_opt, doc)| (ord_opt.is_none(), ord_opt, doc));
+        let sorted_docs: Vec<DocId> = docs.iter().map(|&(_, doc)| doc).collect();
+        assert_eq!(sorted_docs, vec![1, 3, 2, 0, 4]);
+
+        let mut city = String::new();
+        let sorted_cities: Vec<String> = docs
+            .iter()
+            .flat_map(|&(ord_opt, _)| ord_opt)
+            .map(|ord| {
+                assert!(city_column.ord_to_str(ord, &mut city).unwrap());
+                city.clone()
+            })
+            .collect();
+        assert_eq!(sorted_cities, ["Berlin", "Berlin", "Paris", "Tokyo"]);
+        Ok(())
+    }
+
     #[test]
     fn test_json_object_fast_field() {
         let mut schema_builder = Schema::builder();
@@ -67,6 +67,7 @@ fn save_new_metas(
             schema,
             opstamp: 0u64,
             payload: None,
+            binary_payload: None,
         },
         directory,
     )?;
@@ -4,13 +4,15 @@ use std::path::PathBuf;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
 use serde::{Deserialize, Serialize};
 
 use super::SegmentComponent;
 use crate::index::SegmentId;
 use crate::schema::Schema;
 use crate::store::Compressor;
-use crate::{Inventory, Opstamp, TrackedObject};
+use crate::{Inventory, Opstamp, TantivyError, TrackedObject};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 struct DeleteMeta {
@@ -343,6 +345,12 @@ pub struct IndexMeta {
     /// This payload is entirely unused by tantivy.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub payload: Option<String>,
+    /// Binary payload associated with the last commit, base64 encoded.
+    ///
+    /// It is kept apart from `payload`, so that a string payload is never mistaken for a
+    /// binary one. See [`IndexMeta::payload_bytes()`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub binary_payload: Option<String>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -354,6 +362,8 @@ struct UntrackedIndexMeta {
     pub opstamp: Opstamp,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub payload: Option<String>,
+    #[serde(default)]
+    pub binary_payload: Option<String>,
 }
 
 impl UntrackedIndexMeta {
@@ -368,6 +378,7 @@ impl UntrackedIndexMeta {
             schema: self.schema,
             opstamp: self.opstamp,
             payload: self.payload,
+            binary_payload: self.binary_payload,
         }
     }
 }
@@ -385,9 +396,31 @@ impl IndexMeta {
             schema,
             opstamp: 0u64,
             payload: None,
+            binary_payload: None,
         }
     }
 
+    /// Returns the binary payload of the commit, set with
+    /// [`PreparedCommit::set_payload_bytes()`](crate::indexer::PreparedCommit::set_payload_bytes).
+    ///
+    /// The string payload set with
+    /// [`PreparedCommit::set_payload()`](crate::indexer::PreparedCommit::set_payload) is
+    /// stored separately, and is not returned here.
+    ///
+    /// Returns an `Err` if the stored payload is not valid base64.
+    pub fn payload_bytes(&self) -> crate::Result<Option<Vec<u8>>> {
+        self.binary_payload
+            .as_ref()
+            .map(|payload| {
+                BASE64.decode(payload).map_err(|err| {
+                    TantivyError::InvalidArgument(format!(
+                        "The payload of the commit is not valid base64: {err}"
+                    ))
+                })
+            })
+            .transpose()
+    }
+
     pub(crate) fn deserialize(
         meta_json: &str,
         inventory: &SegmentMetaInventory,
@@ -438,6 +471,7 @@ mod tests {
             schema,
             opstamp: 0u64,
             payload: None,
+            binary_payload: None,
         };
         let json = serde_json::ser::to_string(&index_metas).expect("serialization failed");
         assert_eq!(
@@ -475,6 +509,7 @@ mod tests {
             schema,
             opstamp: 0u64,
             payload: None,
+            binary_payload: None,
         };
         let json = serde_json::ser::to_string(&index_metas).expect("serialization failed");
         assert_eq!(
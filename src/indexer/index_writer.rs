@@ -1,4 +1,6 @@
+use std::collections::{BTreeMap, HashSet};
 use std::ops::Range;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread;
 use std::thread::JoinHandle;
@@ -9,6 +11,7 @@ use smallvec::smallvec;
 use super::operation::{AddOperation, UserOperation};
 use super::segment_updater::SegmentUpdater;
 use super::{AddBatch, AddBatchReceiver, AddBatchSender, PreparedCommit};
+use crate::collector::DocSetCollector;
 use crate::directory::{DirectoryLock, GarbageCollectionResult, TerminatingWrite};
 use crate::error::TantivyError;
 use crate::fastfield::write_alive_bitset;
@@ -21,8 +24,8 @@ use crate::indexer::stamper::Stamper;
 use crate::indexer::{MergePolicy, SegmentEntry, SegmentWriter};
 use crate::query::{EnableScoring, Query, TermQuery};
 use crate::schema::document::Document;
-use crate::schema::{IndexRecordOption, TantivyDocument, Term};
-use crate::{FutureResult, Opstamp};
+use crate::schema::{Field, IndexRecordOption, TantivyDocument, Term};
+use crate::{FutureResult, IndexReader, Opstamp, Searcher};
 
 // Size of the margin for the `memory_arena`. A segment is closed when the remaining memory
 // in the `memory_arena` goes below MARGIN_IN_BYTES.
@@ -76,6 +79,9 @@ pub struct IndexWriter<D: Document = TantivyDocument> {
 
     stamper: Stamper,
     committed_opstamp: Opstamp,
+
+    // True iff operations have been run since the last commit or soft commit.
+    has_pending_operations: AtomicBool,
 }
 
 fn compute_deleted_bitset(
@@ -311,6 +317,7 @@ impl<D: Document> IndexWriter<D> {
 
             committed_opstamp: current_opstamp,
             stamper,
+            has_pending_operations: AtomicBool::new(false),
 
             worker_id: 0,
         };
@@ -501,6 +508,7 @@ impl<D: Document> IndexWriter<D> {
     pub fn delete_all_documents(&self) -> crate::Result<Opstamp> {
         // Delete segments
         self.segment_updater.remove_all_segments();
+        self.has_pending_operations.store(true, Ordering::Release);
         // Return new stamp - reverted stamp
         self.stamper.revert(self.committed_opstamp);
         Ok(self.committed_opstamp)
@@ -626,6 +634,7 @@ impl<D: Document> IndexWriter<D> {
             self.add_indexing_worker()?;
         }
 
+        self.has_pending_operations.store(false, Ordering::Release);
         let commit_opstamp = self.stamper.stamp();
         let prepared_commit = PreparedCommit::new(self, commit_opstamp);
         info!("Prepared commit {}", commit_opstamp);
@@ -649,6 +658,36 @@ impl<D: Document> IndexWriter<D> {
         self.prepare_commit()?.commit()
     }
 
+    /// Makes all of the pending changes searchable by the near-real-time readers of this
+    /// `IndexWriter`, see [`IndexWriter::nrt_reader()`], without committing them.
+    ///
+    /// Like `commit()`, a soft commit flushes the pending documents into new segments, but it
+    /// does not write and sync the `meta.json` file. It is therefore cheaper, and the changes
+    /// are not visible to the other readers.
+    ///
+    /// The durability guarantee is much weaker than the one of `commit()`: the soft committed
+    /// changes are lost on a crash, on `rollback()`, or if the `IndexWriter` is dropped, until
+    /// they are part of a subsequent `commit()`.
+    ///
+    /// Returns the `opstamp` of the last document that made it in the soft commit.
+    pub fn soft_commit(&mut self) -> crate::Result<Opstamp> {
+        self.prepare_commit()?.soft_commit()
+    }
+
+    /// Creates an [`IndexReader`] which, in addition to the committed changes, sees the
+    /// changes of the last [`IndexWriter::soft_commit()`] of this `IndexWriter`.
+    ///
+    /// As for other readers, [`IndexReader::reload()`] has to be called for the reader to
+    /// reflect a soft commit. Once the `IndexWriter` commits, the reader reflects the last commit
+    /// until the next soft commit. After a rollback, or once the `IndexWriter` is dropped, it
+    /// only reflects the commits.
+    pub fn nrt_reader(&self) -> crate::Result<IndexReader> {
+        self.index
+            .reader_builder()
+            .nrt_segment_metas(self.segment_updater.nrt_segment_metas().clone())
+            .try_into()
+    }
+
     pub(crate) fn segment_updater(&self) -> &SegmentUpdater {
         &self.segment_updater
     }
@@ -669,7 +708,9 @@ impl<D: Document> IndexWriter<D> {
             .unwrap_or_else(|_| self.stamper.stamp())
     }
 
-    /// Delete all documents matching a given query.
+    /// Delete all documents matching a given query, e.g. a
+    /// [`RangeQuery`](crate::query::RangeQuery) or a
+    /// [`BooleanQuery`](crate::query::BooleanQuery).
     /// Returns an `Err` if the query can't be executed.
     ///
     /// Delete operation only affects documents that
@@ -678,7 +719,6 @@ impl<D: Document> IndexWriter<D> {
     ///
     /// Like adds, the deletion itself will be visible
     /// only after calling `commit()`.
-    #[doc(hidden)]
     pub fn delete_query(&self, query: Box<dyn Query>) -> crate::Result<Opstamp> {
         let weight = query.weight(EnableScoring::disabled_from_schema(&self.index.schema()))?;
         let opstamp = self.stamper.stamp();
@@ -687,9 +727,24 @@ impl<D: Document> IndexWriter<D> {
             target: weight,
         };
         self.delete_queue.push(delete_operation);
+        self.has_pending_operations.store(true, Ordering::Release);
         Ok(opstamp)
     }
 
+    /// Replaces all documents containing a given term by a new document.
+    ///
+    /// This is a full replace: the previous documents are deleted, and the new document is
+    /// added as is, so it needs to contain all of its fields.
+    ///
+    /// The delete and the add are run as a single group of operations (see
+    /// [`IndexWriter::run()`]), so that they are committed together, and the new document is
+    /// not affected by the deletion even if it contains the term.
+    ///
+    /// Like adds and deletes, the update will be visible only after calling `commit()`.
+    pub fn update_document(&self, term: Term, document: D) -> crate::Result<Opstamp> {
+        self.run([UserOperation::Delete(term), UserOperation::Add(document)])
+    }
+
     /// Returns the opstamp of the last successful commit.
     ///
     /// This is, for instance, the opstamp the index will
@@ -710,6 +765,7 @@ impl<D: Document> IndexWriter<D> {
     /// document queue.
     pub fn add_document(&self, document: D) -> crate::Result<Opstamp> {
         let opstamp = self.stamper.stamp();
+        self.has_pending_operations.store(true, Ordering::Release);
         self.send_add_documents_batch(smallvec![AddOperation { opstamp, document }])?;
         Ok(opstamp)
     }
@@ -754,6 +810,7 @@ impl<D: Document> IndexWriter<D> {
             return Ok(self.stamper.stamp());
         }
         let (batch_opstamp, stamps) = self.get_batch_opstamps(count);
+        self.has_pending_operations.store(true, Ordering::Release);
 
         let mut adds = AddBatch::default();
 
@@ -788,9 +845,82 @@ impl<D: Document> IndexWriter<D> {
     }
 }
 
+impl IndexWriter<TantivyDocument> {
+    /// Partially updates all documents containing a given term.
+    ///
+    /// The stored version of the documents is fetched with the given `searcher`, the values of
+    /// the fields present in `fields` replace the stored ones, and the resulting documents are
+    /// reindexed with [`IndexWriter::update_document()`].
+    ///
+    /// The deletion applies to all of the documents containing `term`, while only the documents
+    /// visible to the `searcher` can be re-added. To make sure that no document is lost, this
+    /// returns an `Err` if operations have been run since the last commit, or if the `searcher`
+    /// does not see the state of the last commit. Commit the pending changes and reload the
+    /// searcher before calling this method. The documents added concurrently from other threads
+    /// are not protected.
+    ///
+    /// Since the documents are reconstructed from the doc store, this requires all of the
+    /// fields of the schema to be stored, and returns an `Err` otherwise.
+    pub fn update_document_fields(
+        &self,
+        searcher: &Searcher,
+        term: Term,
+        fields: TantivyDocument,
+    ) -> crate::Result<Opstamp> {
+        let schema = self.index.schema();
+        if let Some((_, field_entry)) = schema
+            .fields()
+            .find(|(_, field_entry)| !field_entry.is_stored())
+        {
+            return Err(TantivyError::InvalidArgument(format!(
+                "Partial updates require all fields to be stored, but the field {:?} is not stored",
+                field_entry.name()
+            )));
+        }
+        if self.has_pending_operations.load(Ordering::Acquire) {
+            return Err(TantivyError::InvalidArgument(
+                "Partial updates require the pending operations to be committed first".to_string(),
+            ));
+        }
+        let segments: BTreeMap<SegmentId, Option<Opstamp>> = self
+            .segment_updater
+            .segment_metas()
+            .iter()
+            .map(|segment_meta| (segment_meta.id(), segment_meta.delete_opstamp()))
+            .collect();
+        if &segments != searcher.generation().segments() {
+            return Err(TantivyError::InvalidArgument(
+                "Partial updates require a searcher reloaded after the last commit".to_string(),
+            ));
+        }
+        let query = TermQuery::new(term.clone(), IndexRecordOption::Basic);
+        let doc_addresses = searcher.search(&query, &DocSetCollector)?;
+        let updated_fields: HashSet<Field> =
+            fields.field_values().map(|(field, _)| field).collect();
+
+        let mut user_operations = vec![UserOperation::Delete(term)];
+        for doc_address in doc_addresses {
+            let stored_doc: TantivyDocument = searcher.doc(doc_address)?;
+            let mut updated_doc = TantivyDocument::default();
+            for (field, value) in stored_doc.field_values() {
+                if !updated_fields.contains(&field) {
+                    updated_doc.add_field_value(field, value);
+                }
+            }
+            for (field, value) in fields.field_values() {
+                updated_doc.add_field_value(field, value);
+            }
+            user_operations.push(UserOperation::Add(updated_doc));
+        }
+        self.run(user_operations)
+    }
+}
+
 impl<D: Document> Drop for IndexWriter<D> {
     fn drop(&mut self) {
         self.segment_updater.kill();
+        // The soft committed changes are discarded.
+        self.segment_updater.nrt_segment_metas().set(None);
         self.drop_sender();
         for work in self.workers_join_handle.drain(..) {
             let _ = work.join();
@@ -808,20 +938,21 @@ mod tests {
     use proptest::prop_oneof;
 
     use super::super::operation::UserOperation;
-    use crate::collector::TopDocs;
+    use crate::collector::{Count, TopDocs};
     use crate::directory::error::LockError;
+    use crate::directory::RamDirectory;
     use crate::error::*;
     use crate::indexer::index_writer::MEMORY_BUDGET_NUM_BYTES_MIN;
     use crate::indexer::NoMergePolicy;
-    use crate::query::{BooleanQuery, Occur, Query, QueryParser, TermQuery};
+    use crate::query::{BooleanQuery, Occur, Query, QueryParser, RangeQuery, TermQuery};
     use crate::schema::{
         self, Facet, FacetOptions, IndexRecordOption, IpAddrOptions, NumericOptions, Schema,
         TextFieldIndexing, TextOptions, Value, FAST, INDEXED, STORED, STRING, TEXT,
     };
     use crate::store::DOCSTORE_CACHE_CAPACITY;
     use crate::{
-        DateTime, DocAddress, Index, IndexSettings, IndexSortByField, IndexWriter, Order,
-        ReloadPolicy, TantivyDocument, Term,
+        DateTime, DocAddress, Index, IndexReader, IndexSettings, IndexSortByField, IndexWriter,
+        Order, ReloadPolicy, TantivyDocument, Term,
     };
 
     const LOREM: &str = "Doc Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do \
@@ -1191,6 +1322,54 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_commit_payload_bytes_after_reopen() -> crate::Result<()> {
+        let mut schema_builder = schema::Schema::builder();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let directory = RamDirectory::create();
+        let index = Index::create(
+            directory.clone(),
+            schema_builder.build(),
+            IndexSettings::default(),
+        )?;
+        let offset: u64 = 1_234_567;
+        {
+            let mut index_writer: IndexWriter = index.writer_for_tests()?;
+            index_writer.add_document(doc!(text_field => "a"))?;
+            let mut prepared_commit = index_writer.prepare_commit()?;
+            prepared_commit.set_payload_bytes(&offset.to_le_bytes());
+            prepared_commit.commit()?;
+            index_writer.add_document(doc!(text_field => "b"))?;
+            // The aborted commit does not change the payload.
+            let mut prepared_commit = index_writer.prepare_commit()?;
+            prepared_commit.set_payload_bytes(&(offset + 1).to_le_bytes());
+            prepared_commit.abort()?;
+        }
+        let reopened_index = Index::open(directory)?;
+        let payload = reopened_index.load_metas()?.payload_bytes()?.unwrap();
+        assert_eq!(payload, offset.to_le_bytes());
+        assert_eq!(reopened_index.reader()?.searcher().num_docs(), 1);
+
+        // The string payload is not mistaken for a binary one, even if it is valid base64.
+        let mut index_writer: IndexWriter = reopened_index.writer_for_tests()?;
+        let mut prepared_commit = index_writer.prepare_commit()?;
+        prepared_commit.set_payload("AQID");
+        prepared_commit.commit()?;
+        let metas = reopened_index.load_metas()?;
+        assert_eq!(metas.payload.as_deref(), Some("AQID"));
+        assert!(metas.payload_bytes()?.is_none());
+
+        // Both payloads can be set on the same commit.
+        let mut prepared_commit = index_writer.prepare_commit()?;
+        prepared_commit.set_payload("offset");
+        prepared_commit.set_payload_bytes(&offset.to_le_bytes());
+        prepared_commit.commit()?;
+        let metas = reopened_index.load_metas()?;
+        assert_eq!(metas.payload.as_deref(), Some("offset"));
+        assert_eq!(metas.payload_bytes()?.unwrap(), offset.to_le_bytes());
+        Ok(())
+    }
+
     #[test]
     fn test_prepare_but_rollback() -> crate::Result<()> {
         let mut schema_builder = schema::Schema::builder();
@@ -1572,6 +1751,280 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_delete_query_numeric_range() -> crate::Result<()> {
+        let mut schema_builder = schema::Schema::builder();
+        let id_field = schema_builder.add_u64_field("id", INDEXED | FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let index_reader = index.reader()?;
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+
+        for id in 0u64..5u64 {
+            index_writer.add_document(doc!(id_field => id))?;
+        }
+        index_writer.commit()?;
+        for id in 5u64..10u64 {
+            index_writer.add_document(doc!(id_field => id))?;
+        }
+        let query = RangeQuery::new_u64("id".to_string(), 3..7);
+        index_writer.delete_query(Box::new(query))?;
+        // Documents added after the delete operation are not affected.
+        index_writer.add_document(doc!(id_field => 4u64))?;
+        index_writer.commit()?;
+        index_reader.reload()?;
+
+        let searcher = index_reader.searcher();
+        let mut alive_ids: Vec<u64> = searcher
+            .segment_readers()
+            .iter()
+            .flat_map(|segment_reader| {
+                let id_reader = segment_reader.fast_fields().u64("id").unwrap();
+                segment_reader
+                    .doc_ids_alive()
+                    .flat_map(|doc| id_reader.values_for_doc(doc))
+                    .collect::<Vec<u64>>()
+            })
+            .collect();
+        alive_ids.sort_unstable();
+        assert_eq!(&alive_ids[..], &[0, 1, 2, 4, 7, 8, 9]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_update_document() -> crate::Result<()> {
+        let mut schema_builder = schema::Schema::builder();
+        let id_field = schema_builder.add_text_field("id", STRING);
+        let status_field = schema_builder.add_text_field("status", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        let index_reader = index.reader()?;
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        let id_term = Term::from_field_text(id_field, "a");
+        let count_status = |status: &str| -> crate::Result<usize> {
+            let query = TermQuery::new(
+                Term::from_field_text(status_field, status),
+                IndexRecordOption::Basic,
+            );
+            index_reader.searcher().search(&query, &Count)
+        };
+
+        index_writer.add_document(doc!(id_field => "a", status_field => "committed"))?;
+        index_writer.commit()?;
+        // A document added in the same commit, before the update, is replaced too.
+        index_writer.add_document(doc!(id_field => "a", status_field => "uncommitted"))?;
+        index_writer.add_document(doc!(id_field => "b", status_field => "other"))?;
+        let add_opstamp =
+            index_writer.add_document(doc!(id_field => "a", status_field => "last"))?;
+        let update_opstamp = index_writer.update_document(
+            id_term.clone(),
+            doc!(id_field => "a", status_field => "updated"),
+        )?;
+        assert!(update_opstamp > add_opstamp);
+        index_writer.commit()?;
+        index_reader.reload()?;
+
+        let searcher = index_reader.searcher();
+        assert_eq!(searcher.num_docs(), 2);
+        assert_eq!(
+            searcher.search(&TermQuery::new(id_term, IndexRecordOption::Basic), &Count)?,
+            1
+        );
+        assert_eq!(count_status("committed")?, 0);
+        assert_eq!(count_status("uncommitted")?, 0);
+        assert_eq!(count_status("last")?, 0);
+        assert_eq!(count_status("other")?, 1);
+        assert_eq!(count_status("updated")?, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_update_document_fields() -> crate::Result<()> {
+        let mut schema_builder = schema::Schema::builder();
+        let id_field = schema_builder.add_text_field("id", STRING | STORED);
+        let title_field = schema_builder.add_text_field("title", TEXT | STORED);
+        let status_field = schema_builder.add_text_field("status", STRING | STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let index_reader = index.reader()?;
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        index_writer.add_document(doc!(
+            id_field => "a",
+            title_field => "the first document",
+            status_field => "draft",
+        ))?;
+        index_writer.add_document(doc!(
+            id_field => "b",
+            title_field => "the second document",
+            status_field => "draft",
+        ))?;
+        index_writer.commit()?;
+        index_reader.reload()?;
+
+        let id_term = Term::from_field_text(id_field, "a");
+        index_writer.update_document_fields(
+            &index_reader.searcher(),
+            id_term.clone(),
+            doc!(status_field => "published"),
+        )?;
+        index_writer.commit()?;
+        index_reader.reload()?;
+
+        let searcher = index_reader.searcher();
+        assert_eq!(searcher.num_docs(), 2);
+        let draft_query = TermQuery::new(
+            Term::from_field_text(status_field, "draft"),
+            IndexRecordOption::Basic,
+        );
+        assert_eq!(searcher.search(&draft_query, &Count)?, 1);
+        let top_docs = searcher.search(
+            &TermQuery::new(id_term, IndexRecordOption::Basic),
+            &TopDocs::with_limit(2),
+        )?;
+        assert_eq!(top_docs.len(), 1);
+        let doc: TantivyDocument = searcher.doc(top_docs[0].1)?;
+        assert_eq!(
+            doc.get_first(title_field).unwrap().as_str(),
+            Some("the first document")
+        );
+        assert_eq!(
+            doc.get_first(status_field).unwrap().as_str(),
+            Some("published")
+        );
+        // The updated document is reindexed.
+        let title_query = TermQuery::new(
+            Term::from_field_text(title_field, "first"),
+            IndexRecordOption::Basic,
+        );
+        assert_eq!(searcher.search(&title_query, &Count)?, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_update_document_fields_does_not_lose_documents() -> crate::Result<()> {
+        let mut schema_builder = schema::Schema::builder();
+        let id_field = schema_builder.add_text_field("id", STRING | STORED);
+        let status_field = schema_builder.add_text_field("status", STRING | STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let index_reader: IndexReader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::Manual)
+            .try_into()?;
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        index_writer.add_document(doc!(id_field => "a", status_field => "draft"))?;
+        index_writer.commit()?;
+        index_reader.reload()?;
+
+        let id_term = Term::from_field_text(id_field, "a");
+        // Not committed, so not visible to the searcher.
+        index_writer.add_document(doc!(id_field => "a", status_field => "pending"))?;
+        let res = index_writer.update_document_fields(
+            &index_reader.searcher(),
+            id_term.clone(),
+            doc!(status_field => "published"),
+        );
+        assert!(matches!(res, Err(TantivyError::InvalidArgument(_))));
+
+        // Committed, but the searcher has not been reloaded.
+        index_writer.commit()?;
+        let res = index_writer.update_document_fields(
+            &index_reader.searcher(),
+            id_term.clone(),
+            doc!(status_field => "published"),
+        );
+        assert!(matches!(res, Err(TantivyError::InvalidArgument(_))));
+
+        index_reader.reload()?;
+        index_writer.update_document_fields(
+            &index_reader.searcher(),
+            id_term,
+            doc!(status_field => "published"),
+        )?;
+        index_writer.commit()?;
+        index_reader.reload()?;
+
+        let searcher = index_reader.searcher();
+        assert_eq!(searcher.num_docs(), 2);
+        let published_query = TermQuery::new(
+            Term::from_field_text(status_field, "published"),
+            IndexRecordOption::Basic,
+        );
+        assert_eq!(searcher.search(&published_query, &Count)?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_update_document_fields_requires_stored_fields() -> crate::Result<()> {
+        let mut schema_builder = schema::Schema::builder();
+        let id_field = schema_builder.add_text_field("id", STRING | STORED);
+        let status_field = schema_builder.add_text_field("status", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        let index_writer: IndexWriter = index.writer_for_tests()?;
+        let searcher = index.reader()?.searcher();
+        let res = index_writer.update_document_fields(
+            &searcher,
+            Term::from_field_text(id_field, "a"),
+            doc!(status_field => "published"),
+        );
+        assert!(matches!(res, Err(TantivyError::InvalidArgument(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_soft_commit() -> crate::Result<()> {
+        let mut schema_builder = schema::Schema::builder();
+        let text_field = schema_builder.add_text_field("text", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text_field => "a"))?;
+        index_writer.commit()?;
+
+        let nrt_reader = index_writer.nrt_reader()?;
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::Manual)
+            .try_into()?;
+        let count = |reader: &IndexReader, text: &str| -> crate::Result<usize> {
+            let query = TermQuery::new(
+                Term::from_field_text(text_field, text),
+                IndexRecordOption::Basic,
+            );
+            reader.searcher().search(&query, &Count)
+        };
+
+        index_writer.add_document(doc!(text_field => "b"))?;
+        index_writer.soft_commit()?;
+        nrt_reader.reload()?;
+        reader.reload()?;
+        assert_eq!(nrt_reader.searcher().num_docs(), 2);
+        assert_eq!(count(&nrt_reader, "b")?, 1);
+        // The soft commit is neither persisted nor visible to other readers.
+        assert_eq!(reader.searcher().num_docs(), 1);
+        assert_eq!(index.searchable_segment_metas()?.len(), 1);
+
+        index_writer.delete_term(Term::from_field_text(text_field, "a"));
+        index_writer.soft_commit()?;
+        nrt_reader.reload()?;
+        assert_eq!(nrt_reader.searcher().num_docs(), 1);
+        assert_eq!(count(&nrt_reader, "a")?, 0);
+
+        // The soft committed changes are lost on rollback.
+        index_writer.rollback()?;
+        nrt_reader.reload()?;
+        assert_eq!(nrt_reader.searcher().num_docs(), 1);
+        assert_eq!(count(&nrt_reader, "a")?, 1);
+
+        // The rollback recreates the writer, the readers created before only reflect commits.
+        let nrt_reader = index_writer.nrt_reader()?;
+        index_writer.add_document(doc!(text_field => "c"))?;
+        index_writer.soft_commit()?;
+        nrt_reader.reload()?;
+        assert_eq!(count(&nrt_reader, "c")?, 1);
+        index_writer.commit()?;
+        nrt_reader.reload()?;
+        reader.reload()?;
+        assert_eq!(count(&nrt_reader, "c")?, 1);
+        assert_eq!(count(&reader, "c")?, 1);
+        Ok(())
+    }
+
     #[derive(Debug, Clone, Copy)]
     enum IndexingOp {
         AddDoc { id: u64 },
@@ -39,6 +39,8 @@ impl MergePolicy for NoMergePolicy {
 pub mod tests {
 
     use super::*;
+    use crate::schema::{Schema, TEXT};
+    use crate::{Index, IndexWriter};
 
     /// `MergePolicy` useful for test purposes.
     ///
@@ -60,4 +62,26 @@ pub mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_no_merge_policy_keeps_segments() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        index_writer.set_merge_policy(Box::new(NoMergePolicy));
+        for _ in 0..20 {
+            index_writer.add_document(doc!(text_field => "bulk"))?;
+            index_writer.commit()?;
+        }
+        assert_eq!(index.searchable_segment_ids()?.len(), 20);
+
+        // Once the bulk load is over, another merge policy can be installed.
+        index_writer.set_merge_policy(Box::new(MergeWheneverPossible));
+        index_writer.add_document(doc!(text_field => "incremental"))?;
+        index_writer.commit()?;
+        index_writer.wait_merging_threads()?;
+        assert_eq!(index.searchable_segment_ids()?.len(), 1);
+        Ok(())
+    }
 }
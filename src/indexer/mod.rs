@@ -144,6 +144,46 @@ mod tests_mmap {
             assert_eq!(num_docs, 256);
         }
     }
+    #[test]
+    fn test_json_field_nested_paths() {
+        let mut schema_builder = Schema::builder();
+        let json_field = schema_builder.add_json_field("meta", TEXT | FAST | STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests().unwrap();
+        let json = serde_json::json!({"author": {"name": "John Smith"}, "pages": 120});
+        index_writer.add_document(doc!(json_field=>json)).unwrap();
+        // The type of `pages` conflicts with the one of the first document.
+        let json = serde_json::json!({"author": {"name": "Jane Smith"}, "pages": "many"});
+        index_writer.add_document(doc!(json_field=>json)).unwrap();
+        index_writer.commit().unwrap();
+        let searcher = index.reader().unwrap().searcher();
+        let query_parser = QueryParser::for_index(&index, Vec::new());
+        let num_hits = |query: &str| {
+            let query = query_parser.parse_query(query).unwrap();
+            searcher.search(&query, &Count).unwrap()
+        };
+        assert_eq!(num_hits("meta.author.name:smith"), 2);
+        assert_eq!(num_hits("meta.author.name:john"), 1);
+        assert_eq!(num_hits("meta.author:smith"), 0);
+        assert_eq!(num_hits("meta.pages:120"), 1);
+        assert_eq!(num_hits("meta.pages:many"), 1);
+
+        // Each type of value gets its own column.
+        let fast_fields = searcher.segment_reader(0).fast_fields();
+        let pages_column = fast_fields
+            .column_opt::<i64>("meta.pages")
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            pages_column.values_for_doc(0).collect::<Vec<_>>(),
+            vec![120]
+        );
+        assert!(pages_column.values_for_doc(1).next().is_none());
+        let pages_str_column = fast_fields.str("meta.pages").unwrap().unwrap();
+        assert!(pages_str_column.term_ords(0).next().is_none());
+        assert!(pages_str_column.term_ords(1).eq([0]));
+    }
+
     #[test]
     fn test_json_field_null_byte() {
         // Test when field name contains a zero byte, which has special meaning in tantivy.
@@ -1,3 +1,6 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+
 use super::IndexWriter;
 use crate::schema::document::Document;
 use crate::{FutureResult, Opstamp, TantivyDocument};
@@ -6,6 +9,7 @@ use crate::{FutureResult, Opstamp, TantivyDocument};
 pub struct PreparedCommit<'a, D: Document = TantivyDocument> {
     index_writer: &'a mut IndexWriter<D>,
     payload: Option<String>,
+    binary_payload: Option<String>,
     opstamp: Opstamp,
 }
 
@@ -14,6 +18,7 @@ impl<'a, D: Document> PreparedCommit<'a, D> {
         Self {
             index_writer,
             payload: None,
+            binary_payload: None,
             opstamp,
         }
     }
@@ -28,6 +33,16 @@ impl<'a, D: Document> PreparedCommit<'a, D> {
         self.payload = Some(payload.to_string())
     }
 
+    /// Adds an arbitrary binary payload to the commit, e.g. the offset of the last
+    /// ingested message, to resume ingestion from there after a restart.
+    ///
+    /// The payload is stored base64 encoded in the index metadata, apart from the string
+    /// payload, and can be read back with
+    /// [`IndexMeta::payload_bytes()`](crate::IndexMeta::payload_bytes).
+    pub fn set_payload_bytes(&mut self, payload: &[u8]) {
+        self.binary_payload = Some(BASE64.encode(payload))
+    }
+
     /// Rollbacks any change.
     pub fn abort(self) -> crate::Result<Opstamp> {
         self.index_writer.rollback()
@@ -39,6 +54,16 @@ impl<'a, D: Document> PreparedCommit<'a, D> {
         self.commit_future().wait()
     }
 
+    /// Proceeds to a soft commit.
+    /// See [`IndexWriter::soft_commit()`].
+    pub fn soft_commit(self) -> crate::Result<Opstamp> {
+        info!("soft committing {}", self.opstamp);
+        self.index_writer
+            .segment_updater()
+            .schedule_soft_commit(self.opstamp)
+            .wait()
+    }
+
     /// Proceeds to commit.
     ///
     /// Unfortunately, contrary to what `PrepareCommit` may suggests,
@@ -46,8 +71,10 @@ impl<'a, D: Document> PreparedCommit<'a, D> {
     /// At this point deletes have not been flushed yet.
     pub fn commit_future(self) -> FutureResult<Opstamp> {
         info!("committing {}", self.opstamp);
-        self.index_writer
-            .segment_updater()
-            .schedule_commit(self.opstamp, self.payload)
+        self.index_writer.segment_updater().schedule_commit(
+            self.opstamp,
+            self.payload,
+            self.binary_payload,
+        )
     }
 }
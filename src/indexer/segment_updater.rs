@@ -248,6 +248,7 @@ pub fn merge_filtered_segments<T: Into<Box<dyn Directory>>>(
         schema: target_schema,
         opstamp: 0u64,
         payload: Some(stats),
+        binary_payload: None,
     };
 
     // save the meta.json
@@ -256,6 +257,26 @@ pub fn merge_filtered_segments<T: Into<Box<dyn Directory>>>(
     Ok(merged_index)
 }
 
+/// The segments made searchable by the last soft commit of an [`IndexWriter`], shared with its
+/// near-real-time readers.
+///
+/// It is `None` when there is no soft commit more recent than the last commit, in which case the
+/// readers load the segments from `meta.json`.
+///
+/// [`IndexWriter`]: crate::IndexWriter
+#[derive(Clone, Default)]
+pub(crate) struct NrtSegmentMetas(Arc<RwLock<Option<Vec<SegmentMeta>>>>);
+
+impl NrtSegmentMetas {
+    pub fn get(&self) -> Option<Vec<SegmentMeta>> {
+        self.0.read().unwrap().clone()
+    }
+
+    pub fn set(&self, segment_metas: Option<Vec<SegmentMeta>>) {
+        *self.0.write().unwrap() = segment_metas;
+    }
+}
+
 pub(crate) struct InnerSegmentUpdater {
     // we keep a copy of the current active IndexMeta to
     // avoid loading the file every time we need it in the
@@ -273,6 +294,7 @@ pub(crate) struct InnerSegmentUpdater {
     killed: AtomicBool,
     stamper: Stamper,
     merge_operations: MergeOperationInventory,
+    nrt_segment_metas: NrtSegmentMetas,
 }
 
 impl SegmentUpdater {
@@ -312,6 +334,7 @@ impl SegmentUpdater {
             killed: AtomicBool::new(false),
             stamper,
             merge_operations: Default::default(),
+            nrt_segment_metas: Default::default(),
         })))
     }
 
@@ -355,6 +378,15 @@ impl SegmentUpdater {
         self.segment_manager.remove_all_segments();
     }
 
+    /// Returns the metas of all of the segments, committed or not.
+    pub(crate) fn segment_metas(&self) -> Vec<SegmentMeta> {
+        self.segment_manager
+            .segment_entries()
+            .into_iter()
+            .map(|segment_entry| segment_entry.meta().clone())
+            .collect()
+    }
+
     pub fn kill(&mut self) {
         self.killed.store(true, Ordering::Release);
     }
@@ -380,6 +412,7 @@ impl SegmentUpdater {
         &self,
         opstamp: Opstamp,
         commit_message: Option<String>,
+        binary_payload: Option<String>,
     ) -> crate::Result<()> {
         if self.is_alive() {
             let index = &self.index;
@@ -406,6 +439,7 @@ impl SegmentUpdater {
                 schema: index.schema(),
                 opstamp,
                 payload: commit_message,
+                binary_payload,
             };
             // TODO add context to the error.
             save_metas(&index_meta, directory.box_clone().borrow_mut())?;
@@ -438,18 +472,44 @@ impl SegmentUpdater {
         &self,
         opstamp: Opstamp,
         payload: Option<String>,
+        binary_payload: Option<String>,
     ) -> FutureResult<Opstamp> {
         let segment_updater: SegmentUpdater = self.clone();
         self.schedule_task(move || {
             let segment_entries = segment_updater.purge_deletes(opstamp)?;
             segment_updater.segment_manager.commit(segment_entries);
-            segment_updater.save_metas(opstamp, payload)?;
+            segment_updater.save_metas(opstamp, payload, binary_payload)?;
+            // The readers can load the segments from `meta.json` again.
+            segment_updater.nrt_segment_metas.set(None);
             let _ = garbage_collect_files(segment_updater.clone());
             segment_updater.consider_merge_options();
             Ok(opstamp)
         })
     }
 
+    /// Makes all the segments, with the deletes up to `opstamp` applied, searchable by
+    /// the near-real-time readers, without writing `meta.json`.
+    ///
+    /// The segments stay uncommitted, so that they would be lost on a rollback.
+    pub(crate) fn schedule_soft_commit(&self, opstamp: Opstamp) -> FutureResult<Opstamp> {
+        let segment_updater: SegmentUpdater = self.clone();
+        self.schedule_task(move || {
+            let segment_entries = segment_updater.purge_deletes(opstamp)?;
+            let mut segment_metas: Vec<SegmentMeta> = segment_entries
+                .iter()
+                .map(|segment_entry| segment_entry.meta().clone())
+                .collect();
+            // Same heuristic as in `save_metas`.
+            segment_metas.sort_by_key(|segment_meta| -(segment_meta.max_doc() as i32));
+            segment_updater.nrt_segment_metas.set(Some(segment_metas));
+            Ok(opstamp)
+        })
+    }
+
+    pub(crate) fn nrt_segment_metas(&self) -> &NrtSegmentMetas {
+        &self.nrt_segment_metas
+    }
+
     fn store_meta(&self, index_meta: &IndexMeta) {
         *self.active_index_meta.write().unwrap() = Arc::new(index_meta.clone());
     }
@@ -632,8 +692,11 @@ impl SegmentUpdater {
                     .end_merge(merge_operation.segment_ids(), after_merge_segment_entry)?;
 
                 if segments_status == SegmentsStatus::Committed {
-                    segment_updater
-                        .save_metas(previous_metas.opstamp, previous_metas.payload.clone())?;
+                    segment_updater.save_metas(
+                        previous_metas.opstamp,
+                        previous_metas.payload.clone(),
+                        previous_metas.binary_payload.clone(),
+                    )?;
                 }
 
                 segment_updater.consider_merge_options();
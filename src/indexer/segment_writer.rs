@@ -72,6 +72,7 @@ pub struct SegmentWriter {
     pub(crate) doc_opstamps: Vec<Opstamp>,
     per_field_text_analyzers: Vec<TextAnalyzer>,
     term_buffer: Term,
+    has_dense_vector_fields: bool,
     schema: Schema,
 }
 
@@ -114,6 +115,9 @@ impl SegmentWriter {
                 })
             })
             .collect::<Result<Vec<_>, _>>()?;
+        let has_dense_vector_fields = schema
+            .fields()
+            .any(|(_, field_entry)| matches!(field_entry.field_type(), FieldType::DenseVector(_)));
         Ok(Self {
             max_doc: 0,
             ctx: IndexingContext::new(table_size),
@@ -129,6 +133,7 @@ impl SegmentWriter {
             doc_opstamps: Vec::with_capacity(1_000),
             per_field_text_analyzers,
             term_buffer: Term::with_capacity(16),
+            has_dense_vector_fields,
             schema,
         })
     }
@@ -377,6 +382,8 @@ impl SegmentWriter {
                         self.fieldnorms_writer.record(doc_id, field, num_vals);
                     }
                 }
+                // Dense vectors are never indexed.
+                FieldType::DenseVector(_) => {}
             }
         }
         Ok(())
@@ -390,6 +397,7 @@ impl SegmentWriter {
         add_operation: AddOperation<D>,
     ) -> crate::Result<()> {
         let AddOperation { document, opstamp } = add_operation;
+        self.check_dense_vectors(&document)?;
         self.doc_opstamps.push(opstamp);
         self.fast_field_writers.add_document(&document)?;
         self.index_document(&document)?;
@@ -399,6 +407,31 @@ impl SegmentWriter {
         Ok(())
     }
 
+    /// Checks that the dense vectors of the document have the dimension declared in the schema.
+    ///
+    /// This is done before writing anything, so that an invalid document is not partially
+    /// added.
+    fn check_dense_vectors<D: Document>(&self, doc: &D) -> crate::Result<()> {
+        if !self.has_dense_vector_fields {
+            return Ok(());
+        }
+        for (field, value) in doc.iter_fields_and_values() {
+            let field_entry = self.schema.get_field_entry(field);
+            let FieldType::DenseVector(dense_vector_options) = field_entry.field_type() else {
+                continue;
+            };
+            let value = value as D::Value<'_>;
+            let dimension = dense_vector_options.dimension();
+            if value.as_bytes().map(<[u8]>::len) != Some(dimension * 4) {
+                return Err(TantivyError::SchemaError(format!(
+                    "Expected a vector of dimension {dimension} for field {:?}",
+                    field_entry.name()
+                )));
+            }
+        }
+        Ok(())
+    }
+
     /// Max doc is
     /// - the number of documents in the segment assuming there is no deletes
     /// - the maximum document id (including deleted documents) + 1
@@ -506,7 +539,7 @@ mod tests {
     use crate::store::{Compressor, StoreReader, StoreWriter};
     use crate::time::format_description::well_known::Rfc3339;
     use crate::time::OffsetDateTime;
-    use crate::tokenizer::{PreTokenizedString, Token};
+    use crate::tokenizer::{PreTokenizedString, Token, TokenType};
     use crate::{
         DateTime, Directory, DocAddress, DocSet, Index, IndexWriter, TantivyDocument, Term,
         TERMINATED,
@@ -537,6 +570,9 @@ mod tests {
                 position: 0,
                 text: String::from("A"),
                 position_length: 1,
+                keyword: false,
+                token_type: TokenType::default(),
+                trailing_punctuation: false,
             }],
         };
 
@@ -983,6 +1019,9 @@ mod tests {
                 position: 0,
                 text: "rollercoaster".to_string(),
                 position_length: 2,
+                keyword: false,
+                token_type: TokenType::default(),
+                trailing_punctuation: false,
             }],
         };
         doc.add_pre_tokenized_text(text, tokens.clone());
@@ -1023,6 +1062,9 @@ mod tests {
                     position: 0,
                     text: "long_token".to_string(),
                     position_length: 3,
+                    keyword: false,
+                    token_type: TokenType::default(),
+                    trailing_punctuation: false,
                 },
                 Token {
                     offset_from: 0,
@@ -1030,6 +1072,9 @@ mod tests {
                     position: 1,
                     text: "short".to_string(),
                     position_length: 1,
+                    keyword: false,
+                    token_type: TokenType::default(),
+                    trailing_punctuation: false,
                 },
             ],
         };
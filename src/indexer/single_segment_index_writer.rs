@@ -48,6 +48,7 @@ impl<D: Document> SingleSegmentIndexWriter<D> {
             schema: index.schema(),
             opstamp: 0,
             payload: None,
+            binary_payload: None,
         };
         save_metas(&index_meta, index.directory())?;
         index.directory().sync_directory()?;
@@ -206,6 +206,7 @@ mod reader;
 
 pub use self::reader::{IndexReader, IndexReaderBuilder, ReloadPolicy, Warmer};
 pub mod snippet;
+pub mod suggester;
 
 mod docset;
 use std::fmt;
@@ -766,6 +767,103 @@ pub mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_indexed_bool() -> crate::Result<()> {
+        use crate::collector::{Count, TopDocs};
+        use crate::query::QueryParser;
+
+        let mut schema_builder = Schema::builder();
+        let title_field = schema_builder.add_text_field("title", STRING | STORED);
+        let active_field = schema_builder.add_bool_field("active", INDEXED | FAST | STORED);
+        let schema = schema_builder.build();
+
+        let index = Index::create_in_ram(schema.clone());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title_field => "a", active_field => true))?;
+        index_writer.add_document(doc!(title_field => "b", active_field => false))?;
+        index_writer.add_document(doc!(title_field => "c", active_field => true))?;
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+
+        let query_parser = QueryParser::for_index(&index, vec![title_field]);
+        let active_query = query_parser.parse_query("active:true")?;
+        assert_eq!(searcher.search(&active_query, &Count)?, 2);
+        let inactive_query = query_parser.parse_query("active:false")?;
+        let top_docs = searcher.search(&inactive_query, &TopDocs::with_limit(10))?;
+        assert_eq!(top_docs.len(), 1);
+
+        let doc: TantivyDocument = searcher.doc(top_docs[0].1)?;
+        assert_eq!(doc.to_json(&schema), r#"{"active":[false],"title":["b"]}"#);
+
+        let active_column = searcher.segment_reader(0).fast_fields().bool("active")?;
+        let active_values: Vec<Option<bool>> =
+            (0..3).map(|doc_id| active_column.first(doc_id)).collect();
+        assert_eq!(active_values, vec![Some(true), Some(false), Some(true)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_terms_with_prefix() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        index_writer.set_merge_policy(Box::new(NoMergePolicy));
+        index_writer.add_document(doc!(text_field => "apple apply ape"))?;
+        index_writer.add_document(doc!(text_field => "apple"))?;
+        index_writer.commit()?;
+        index_writer.add_document(doc!(text_field => "application apple apps"))?;
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+        assert_eq!(
+            searcher.terms_with_prefix(text_field, b"app", 10)?,
+            vec![
+                (b"apple".to_vec(), 3),
+                (b"application".to_vec(), 1),
+                (b"apply".to_vec(), 1),
+                (b"apps".to_vec(), 1),
+            ]
+        );
+        assert_eq!(
+            searcher.terms_with_prefix(text_field, b"app", 2)?,
+            vec![(b"apple".to_vec(), 3), (b"application".to_vec(), 1)]
+        );
+        assert!(searcher.terms_with_prefix(text_field, b"b", 10)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_terms_matching_regex() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        index_writer.set_merge_policy(Box::new(NoMergePolicy));
+        index_writer.add_document(doc!(text_field => "japan japon china"))?;
+        index_writer.add_document(doc!(text_field => "japan jupiter"))?;
+        index_writer.commit()?;
+        index_writer.add_document(doc!(text_field => "japan jap japanese"))?;
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+        assert_eq!(
+            searcher.terms_matching_regex(text_field, "jap[ao]n", 10)?,
+            vec![(b"japan".to_vec(), 3), (b"japon".to_vec(), 1)]
+        );
+        assert_eq!(
+            searcher.terms_matching_regex(text_field, "j.*", 3)?,
+            vec![
+                (b"jap".to_vec(), 1),
+                (b"japan".to_vec(), 3),
+                (b"japanese".to_vec(), 1),
+            ]
+        );
+        assert!(searcher.terms_matching_regex(text_field, "x.*", 10)?.is_empty());
+        assert!(searcher.terms_matching_regex(text_field, "(", 10).is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_indexedfield_not_in_documents() -> crate::Result<()> {
         let mut schema_builder = Schema::builder();
@@ -66,11 +66,17 @@ impl SegmentPostings {
     pub fn create_from_docs(docs: &[u32]) -> SegmentPostings {
         use crate::directory::FileSlice;
         use crate::postings::serializer::PostingsSerializer;
+        use crate::query::Bm25Params;
         use crate::schema::IndexRecordOption;
         let mut buffer = Vec::new();
         {
-            let mut postings_serializer =
-                PostingsSerializer::new(&mut buffer, 0.0, IndexRecordOption::Basic, None);
+            let mut postings_serializer = PostingsSerializer::new(
+                &mut buffer,
+                0.0,
+                Bm25Params::default(),
+                IndexRecordOption::Basic,
+                None,
+            );
             postings_serializer.new_term(docs.len() as u32, false);
             for &doc in docs {
                 postings_serializer.write_doc(doc, 1u32);
@@ -98,6 +104,7 @@ impl SegmentPostings {
         use crate::directory::FileSlice;
         use crate::fieldnorm::FieldNormReader;
         use crate::postings::serializer::PostingsSerializer;
+        use crate::query::Bm25Params;
         use crate::schema::IndexRecordOption;
         use crate::Score;
         let mut buffer: Vec<u8> = Vec::new();
@@ -117,6 +124,7 @@ impl SegmentPostings {
         let mut postings_serializer = PostingsSerializer::new(
             &mut buffer,
             average_field_norm,
+            Bm25Params::default(),
             IndexRecordOption::WithFreqs,
             fieldnorm_reader,
         );
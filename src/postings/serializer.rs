@@ -10,7 +10,7 @@ use crate::index::Segment;
 use crate::positions::PositionSerializer;
 use crate::postings::compression::{BlockEncoder, VIntEncoder, COMPRESSION_BLOCK_SIZE};
 use crate::postings::skip::SkipSerializer;
-use crate::query::Bm25Weight;
+use crate::query::{Bm25Params, Bm25Weight};
 use crate::schema::{Field, FieldEntry, FieldType, IndexRecordOption, Schema};
 use crate::termdict::TermDictionaryBuilder;
 use crate::{DocId, Score};
@@ -131,6 +131,7 @@ impl<'a> FieldSerializer<'a> {
         let postings_serializer = PostingsSerializer::new(
             postings_write,
             average_fieldnorm,
+            field_type.bm25_params(),
             index_record_option,
             fieldnorm_reader,
         );
@@ -307,6 +308,8 @@ pub struct PostingsSerializer<W: Write> {
     bm25_weight: Option<Bm25Weight>,
     avg_fieldnorm: Score, /* Average number of term in the field for that segment.
                            * this value is used to compute the block wand information. */
+    // Must be the parameters used at search time, for the block wand information to be exact.
+    bm25_params: Bm25Params,
     term_has_freq: bool,
 }
 
@@ -314,6 +317,7 @@ impl<W: Write> PostingsSerializer<W> {
     pub fn new(
         write: W,
         avg_fieldnorm: Score,
+        bm25_params: Bm25Params,
         mode: IndexRecordOption,
         fieldnorm_reader: Option<FieldNormReader>,
     ) -> PostingsSerializer<W> {
@@ -332,6 +336,7 @@ impl<W: Write> PostingsSerializer<W> {
             fieldnorm_reader,
             bm25_weight: None,
             avg_fieldnorm,
+            bm25_params,
             term_has_freq: false,
         }
     }
@@ -355,10 +360,11 @@ impl<W: Write> PostingsSerializer<W> {
             return;
         }
 
-        self.bm25_weight = Some(Bm25Weight::for_one_term_without_explain(
+        self.bm25_weight = Some(Bm25Weight::for_one_term_without_explain_with_params(
             term_doc_freq as u64,
             num_docs_in_segment,
             self.avg_fieldnorm,
+            self.bm25_params,
         ));
     }
 
@@ -160,6 +160,19 @@ impl SkipReader {
     // The block max score is available for all full bitpacked block,
     // but no available for the last VInt encoded incomplete block.
     pub fn block_max_score(&self, bm25_weight: &Bm25Weight) -> Option<Score> {
+        if bm25_weight.has_custom_similarity() {
+            // The block max information was computed with BM25, but the bit width of the term
+            // frequencies of the block still bounds them.
+            return match self.block_info {
+                BlockInfo::BitPacked { tf_num_bits, .. } => {
+                    // The term frequencies are encoded minus one.
+                    let max_term_freq =
+                        1u32.checked_shl(u32::from(tf_num_bits)).unwrap_or(u32::MAX);
+                    Some(bm25_weight.max_score_for_term_freq(max_term_freq))
+                }
+                BlockInfo::VInt { .. } => None,
+            };
+        }
         match self.block_info {
             BlockInfo::BitPacked {
                 block_wand_fieldnorm_id,
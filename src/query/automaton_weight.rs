@@ -6,11 +6,15 @@ use tantivy_fst::Automaton;
 
 use super::phrase_prefix_query::prefix_end;
 use crate::index::SegmentReader;
-use crate::query::{BitSetDocSet, ConstScorer, Explanation, Scorer, Weight};
+use crate::query::{check_search_deadline, BitSetDocSet, ConstScorer, Explanation, Scorer, Weight};
 use crate::schema::{Field, IndexRecordOption};
 use crate::termdict::{TermDictionary, TermStreamer};
 use crate::{DocId, Score, TantivyError};
 
+/// Number of terms expanded by an [`AutomatonWeight`] between two checks of the deadline of
+/// the search, if any.
+const DEADLINE_CHECK_INTERVAL: u32 = 64;
+
 /// A weight struct for Fuzzy Term and Regex Queries
 pub struct AutomatonWeight<A> {
     field: Field,
@@ -19,6 +23,7 @@ pub struct AutomatonWeight<A> {
     // We apply additional filtering based on the given JSON path, when searching within the term
     // dictionary. This prevents terms from unrelated paths from matching the search criteria.
     json_path_bytes: Option<Box<[u8]>>,
+    max_expansions: Option<u32>,
 }
 
 impl<A> AutomatonWeight<A>
@@ -32,6 +37,7 @@ where
             field,
             automaton: automaton.into(),
             json_path_bytes: None,
+            max_expansions: None,
         }
     }
 
@@ -45,9 +51,17 @@ where
             field,
             automaton: automaton.into(),
             json_path_bytes: Some(json_path_bytes.to_vec().into_boxed_slice()),
+            max_expansions: None,
         }
     }
 
+    /// Limits the number of matching terms considered in each segment.
+    ///
+    /// The terms are visited in the term dictionary order, and the following ones are ignored.
+    pub fn set_max_expansions(&mut self, max_expansions: u32) {
+        self.max_expansions = Some(max_expansions);
+    }
+
     fn automaton_stream<'a>(
         &'a self,
         term_dict: &'a TermDictionary,
@@ -77,7 +91,13 @@ where
         let inverted_index = reader.inverted_index(self.field)?;
         let term_dict = inverted_index.terms();
         let mut term_stream = self.automaton_stream(term_dict)?;
-        while term_stream.advance() {
+        let max_expansions = self.max_expansions.unwrap_or(u32::MAX);
+        let mut num_expansions = 0u32;
+        while num_expansions < max_expansions && term_stream.advance() {
+            num_expansions += 1;
+            if num_expansions % DEADLINE_CHECK_INTERVAL == 0 {
+                check_search_deadline()?;
+            }
             let term_info = term_stream.value();
             let mut block_segment_postings = inverted_index
                 .read_block_postings_from_terminfo(term_info, IndexRecordOption::Basic)?;
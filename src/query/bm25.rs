@@ -1,12 +1,14 @@
+use std::sync::Arc;
+
 use serde::{Deserialize, Serialize};
 
 use crate::fieldnorm::FieldNormReader;
-use crate::query::Explanation;
+use crate::query::{Explanation, Similarity};
 use crate::schema::Field;
 use crate::{Score, Searcher, Term};
 
-const K1: Score = 1.2;
-const B: Score = 0.75;
+const DEFAULT_K1: Score = 1.2;
+const DEFAULT_B: Score = 0.75;
 
 /// An interface to compute the statistics needed in BM25 scoring.
 ///
@@ -55,32 +57,72 @@ pub(crate) fn idf(doc_freq: u64, doc_count: u64) -> Score {
     (1.0 + x).ln()
 }
 
-fn cached_tf_component(fieldnorm: u32, average_fieldnorm: Score) -> Score {
-    K1 * (1.0 - B + B * fieldnorm as Score / average_fieldnorm)
+fn cached_tf_component(fieldnorm: u32, average_fieldnorm: Score, params: Bm25Params) -> Score {
+    params.k1 * (1.0 - params.b + params.b * fieldnorm as Score / average_fieldnorm)
 }
 
-fn compute_tf_cache(average_fieldnorm: Score) -> [Score; 256] {
+fn compute_tf_cache(average_fieldnorm: Score, params: Bm25Params) -> [Score; 256] {
     let mut cache: [Score; 256] = [0.0; 256];
     for (fieldnorm_id, cache_mut) in cache.iter_mut().enumerate() {
         let fieldnorm = FieldNormReader::id_to_fieldnorm(fieldnorm_id as u8);
-        *cache_mut = cached_tf_component(fieldnorm, average_fieldnorm);
+        *cache_mut = cached_tf_component(fieldnorm, average_fieldnorm, params);
     }
     cache
 }
 
-#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+/// The free parameters of BM25.
+///
+/// They can be set for each text field, see
+/// [`TextFieldIndexing::set_bm25_params`](crate::schema::TextFieldIndexing::set_bm25_params).
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Bm25Params {
-    pub idf: Score,
-    pub avg_fieldnorm: Score,
+    /// Term frequency saturation: the higher `k1`, the longer the score keeps increasing with
+    /// the number of occurrences of a term. Defaults to 1.2.
+    pub k1: Score,
+    /// Length normalization, between 0 and 1: with `b = 0`, the length of the field does not
+    /// impact the score, with `b = 1`, the term frequency is fully normalized by the length of
+    /// the field. Defaults to 0.75.
+    pub b: Score,
+}
+
+impl Default for Bm25Params {
+    fn default() -> Bm25Params {
+        Bm25Params {
+            k1: DEFAULT_K1,
+            b: DEFAULT_B,
+        }
+    }
+}
+
+// Bitwise comparison, so that the schema can implement `Eq`.
+impl PartialEq for Bm25Params {
+    fn eq(&self, other: &Bm25Params) -> bool {
+        self.k1.to_bits() == other.k1.to_bits() && self.b.to_bits() == other.b.to_bits()
+    }
+}
+
+impl Eq for Bm25Params {}
+
+// A similarity other than BM25, with the weight of the term computed by it.
+#[derive(Clone)]
+struct CustomSimilarity {
+    similarity: Arc<dyn Similarity>,
+    term_weight: Score,
 }
 
 /// A struct used for computing BM25 scores.
+///
+/// It can also delegate the scoring to a custom [`Similarity`], see
+/// [`Bm25Weight::for_terms_with_similarity`].
 #[derive(Clone)]
 pub struct Bm25Weight {
     idf_explain: Option<Explanation>,
     weight: Score,
     cache: [Score; 256],
     average_fieldnorm: Score,
+    params: Bm25Params,
+    custom_similarity: Option<CustomSimilarity>,
 }
 
 impl Bm25Weight {
@@ -91,13 +133,62 @@ impl Bm25Weight {
             weight: self.weight * boost,
             cache: self.cache,
             average_fieldnorm: self.average_fieldnorm,
+            params: self.params,
+            custom_similarity: self.custom_similarity.as_ref().map(|custom_similarity| {
+                CustomSimilarity {
+                    similarity: custom_similarity.similarity.clone(),
+                    term_weight: custom_similarity.term_weight * boost,
+                }
+            }),
         }
     }
 
+    /// Construct a weight for a phrase of terms, scoring the documents with the given
+    /// [`Similarity`].
+    ///
+    /// The BM25 parameters are only used if `similarity` is a
+    /// [`Bm25Similarity`](crate::query::Bm25Similarity).
+    pub fn for_terms_with_similarity(
+        statistics: &dyn Bm25StatisticsProvider,
+        terms: &[Term],
+        params: Bm25Params,
+        similarity: &Arc<dyn Similarity>,
+    ) -> crate::Result<Bm25Weight> {
+        if similarity.is_bm25() {
+            return Bm25Weight::for_terms_with_params(statistics, terms, params);
+        }
+        assert!(!terms.is_empty(), "Similarity requires at least one term");
+        let field = terms[0].field();
+        let total_num_tokens = statistics.total_num_tokens(field)?;
+        let total_num_docs = statistics.total_num_docs()?;
+        let average_fieldnorm = total_num_tokens as Score / total_num_docs as Score;
+        let doc_freqs = terms
+            .iter()
+            .map(|term| statistics.doc_freq(term))
+            .collect::<crate::Result<Vec<u64>>>()?;
+        let term_weight_explain = similarity.term_weight(&doc_freqs, total_num_docs);
+        let term_weight = term_weight_explain.value();
+        let mut bm25_weight = Bm25Weight::new(term_weight_explain, average_fieldnorm, params);
+        bm25_weight.custom_similarity = Some(CustomSimilarity {
+            similarity: similarity.clone(),
+            term_weight,
+        });
+        Ok(bm25_weight)
+    }
+
     /// Construct a [Bm25Weight] for a phrase of terms.
     pub fn for_terms(
         statistics: &dyn Bm25StatisticsProvider,
         terms: &[Term],
+    ) -> crate::Result<Bm25Weight> {
+        Bm25Weight::for_terms_with_params(statistics, terms, Bm25Params::default())
+    }
+
+    /// Construct a [Bm25Weight] for a phrase of terms, with the given BM25 parameters.
+    pub fn for_terms_with_params(
+        statistics: &dyn Bm25StatisticsProvider,
+        terms: &[Term],
+        params: Bm25Params,
     ) -> crate::Result<Bm25Weight> {
         assert!(!terms.is_empty(), "Bm25 requires at least one term");
         let field = terms[0].field();
@@ -115,10 +206,11 @@ impl Bm25Weight {
 
         if terms.len() == 1 {
             let term_doc_freq = statistics.doc_freq(&terms[0])?;
-            Ok(Bm25Weight::for_one_term(
+            Ok(Bm25Weight::for_one_term_with_params(
                 term_doc_freq,
                 total_num_docs,
                 average_fieldnorm,
+                params,
             ))
         } else {
             let mut idf_sum: Score = 0.0;
@@ -127,7 +219,7 @@ impl Bm25Weight {
                 idf_sum += idf(term_doc_freq, total_num_docs);
             }
             let idf_explain = Explanation::new("idf", idf_sum);
-            Ok(Bm25Weight::new(idf_explain, average_fieldnorm))
+            Ok(Bm25Weight::new(idf_explain, average_fieldnorm, params))
         }
     }
 
@@ -136,6 +228,21 @@ impl Bm25Weight {
         term_doc_freq: u64,
         total_num_docs: u64,
         avg_fieldnorm: Score,
+    ) -> Bm25Weight {
+        Bm25Weight::for_one_term_with_params(
+            term_doc_freq,
+            total_num_docs,
+            avg_fieldnorm,
+            Bm25Params::default(),
+        )
+    }
+
+    /// Construct a [Bm25Weight] for a single term, with the given BM25 parameters.
+    pub fn for_one_term_with_params(
+        term_doc_freq: u64,
+        total_num_docs: u64,
+        avg_fieldnorm: Score,
+        params: Bm25Params,
     ) -> Bm25Weight {
         let idf = idf(term_doc_freq, total_num_docs);
         let mut idf_explain =
@@ -145,7 +252,7 @@ impl Bm25Weight {
             term_doc_freq as Score,
         );
         idf_explain.add_const("N, total number of docs", total_num_docs as Score);
-        Bm25Weight::new(idf_explain, avg_fieldnorm)
+        Bm25Weight::new(idf_explain, avg_fieldnorm, params)
     }
     /// Construct a [Bm25Weight] for a single term.
     /// This method does not carry the [Explanation] for the idf.
@@ -153,41 +260,100 @@ impl Bm25Weight {
         term_doc_freq: u64,
         total_num_docs: u64,
         avg_fieldnorm: Score,
+    ) -> Bm25Weight {
+        Bm25Weight::for_one_term_without_explain_with_params(
+            term_doc_freq,
+            total_num_docs,
+            avg_fieldnorm,
+            Bm25Params::default(),
+        )
+    }
+
+    /// Construct a [Bm25Weight] for a single term, with the given BM25 parameters.
+    /// This method does not carry the [Explanation] for the idf.
+    pub fn for_one_term_without_explain_with_params(
+        term_doc_freq: u64,
+        total_num_docs: u64,
+        avg_fieldnorm: Score,
+        params: Bm25Params,
     ) -> Bm25Weight {
         let idf = idf(term_doc_freq, total_num_docs);
-        Bm25Weight::new_without_explain(idf, avg_fieldnorm)
+        Bm25Weight::new_without_explain(idf, avg_fieldnorm, params)
     }
 
-    pub(crate) fn new(idf_explain: Explanation, average_fieldnorm: Score) -> Bm25Weight {
-        let weight = idf_explain.value() * (1.0 + K1);
+    pub(crate) fn new(
+        idf_explain: Explanation,
+        average_fieldnorm: Score,
+        params: Bm25Params,
+    ) -> Bm25Weight {
+        let weight = idf_explain.value() * (1.0 + params.k1);
         Bm25Weight {
             idf_explain: Some(idf_explain),
             weight,
-            cache: compute_tf_cache(average_fieldnorm),
+            cache: compute_tf_cache(average_fieldnorm, params),
             average_fieldnorm,
+            params,
+            custom_similarity: None,
         }
     }
-    pub(crate) fn new_without_explain(idf: f32, average_fieldnorm: Score) -> Bm25Weight {
-        let weight = idf * (1.0 + K1);
+    pub(crate) fn new_without_explain(
+        idf: f32,
+        average_fieldnorm: Score,
+        params: Bm25Params,
+    ) -> Bm25Weight {
+        let weight = idf * (1.0 + params.k1);
         Bm25Weight {
             idf_explain: None,
             weight,
-            cache: compute_tf_cache(average_fieldnorm),
+            cache: compute_tf_cache(average_fieldnorm, params),
             average_fieldnorm,
+            params,
+            custom_similarity: None,
         }
     }
 
+    /// Returns true if the documents are scored by a custom [`Similarity`].
+    ///
+    /// The block-max scores stored in the index are then meaningless.
+    pub(crate) fn has_custom_similarity(&self) -> bool {
+        self.custom_similarity.is_some()
+    }
+
     /// Compute the BM25 score of a single document.
     #[inline]
     pub fn score(&self, fieldnorm_id: u8, term_freq: u32) -> Score {
+        if let Some(custom_similarity) = &self.custom_similarity {
+            return custom_similarity.similarity.score(
+                custom_similarity.term_weight,
+                term_freq,
+                FieldNormReader::id_to_fieldnorm(fieldnorm_id),
+                self.average_fieldnorm,
+            );
+        }
         self.weight * self.tf_factor(fieldnorm_id, term_freq)
     }
 
     /// Compute the maximum possible BM25 score given this weight.
     pub fn max_score(&self) -> Score {
+        if self.custom_similarity.is_some() {
+            return self.max_score_for_term_freq(u32::MAX);
+        }
         self.score(255u8, 2_013_265_944)
     }
 
+    /// Compute the maximum possible score of the documents in which the term appears at most
+    /// `max_term_freq` times.
+    pub(crate) fn max_score_for_term_freq(&self, max_term_freq: u32) -> Score {
+        if let Some(custom_similarity) = &self.custom_similarity {
+            return custom_similarity.similarity.max_score(
+                custom_similarity.term_weight,
+                max_term_freq,
+                self.average_fieldnorm,
+            );
+        }
+        self.max_score()
+    }
+
     #[inline]
     pub(crate) fn tf_factor(&self, fieldnorm_id: u8, term_freq: u32) -> Score {
         let term_freq = term_freq as Score;
@@ -199,6 +365,18 @@ impl Bm25Weight {
     pub fn explain(&self, fieldnorm_id: u8, term_freq: u32) -> Explanation {
         // The explain format is directly copied from Lucene's.
         // (So, Kudos to Lucene)
+        if let Some(custom_similarity) = &self.custom_similarity {
+            let mut explanation = custom_similarity.similarity.explain(
+                custom_similarity.term_weight,
+                term_freq,
+                FieldNormReader::id_to_fieldnorm(fieldnorm_id),
+                self.average_fieldnorm,
+            );
+            if let Some(term_weight_explain) = &self.idf_explain {
+                explanation.add_detail(term_weight_explain.clone());
+            }
+            return explanation;
+        }
         let score = self.score(fieldnorm_id, term_freq);
 
         let norm = self.cache[fieldnorm_id as usize];
@@ -211,8 +389,8 @@ impl Bm25Weight {
         );
 
         tf_explanation.add_const("freq, occurrences of term within document", term_freq);
-        tf_explanation.add_const("k1, term saturation parameter", K1);
-        tf_explanation.add_const("b, length normalization parameter", B);
+        tf_explanation.add_const("k1, term saturation parameter", self.params.k1);
+        tf_explanation.add_const("b, length normalization parameter", self.params.b);
         tf_explanation.add_const(
             "dl, length of field",
             FieldNormReader::id_to_fieldnorm(fieldnorm_id) as Score,
@@ -220,7 +398,7 @@ impl Bm25Weight {
         tf_explanation.add_const("avgdl, average length of field", self.average_fieldnorm);
 
         let mut explanation = Explanation::new("TermQuery, product of...", score);
-        explanation.add_detail(Explanation::new("(K1+1)", K1 + 1.0));
+        explanation.add_detail(Explanation::new("(K1+1)", self.params.k1 + 1.0));
         if let Some(idf_explain) = &self.idf_explain {
             explanation.add_detail(idf_explain.clone());
         }
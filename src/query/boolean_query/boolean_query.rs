@@ -14,6 +14,9 @@ use crate::schema::{IndexRecordOption, Term};
 /// * match at least one of the sub queries associated
 /// with the `Must` or `Should` occurrence.
 ///
+/// With [`BooleanQuery::set_minimum_number_should_match`], documents are also required
+/// to match a minimum number of the sub queries associated with the `Should` occurrence.
+///
 ///
 /// You can combine other query types and their `Occur`ances into one `BooleanQuery`
 ///
@@ -124,15 +127,20 @@ use crate::schema::{IndexRecordOption, Term};
 #[derive(Debug)]
 pub struct BooleanQuery {
     subqueries: Vec<(Occur, Box<dyn Query>)>,
+    minimum_number_should_match: usize,
 }
 
 impl Clone for BooleanQuery {
     fn clone(&self) -> Self {
-        self.subqueries
+        let subqueries = self
+            .subqueries
             .iter()
             .map(|(occur, subquery)| (*occur, subquery.box_clone()))
-            .collect::<Vec<_>>()
-            .into()
+            .collect::<Vec<_>>();
+        BooleanQuery {
+            subqueries,
+            minimum_number_should_match: self.minimum_number_should_match,
+        }
     }
 }
 
@@ -149,8 +157,9 @@ impl Query for BooleanQuery {
             .iter()
             .map(|(occur, subquery)| Ok((*occur, subquery.weight(enable_scoring)?)))
             .collect::<crate::Result<_>>()?;
-        Ok(Box::new(BooleanWeight::new(
+        Ok(Box::new(BooleanWeight::with_minimum_number_should_match(
             sub_weights,
+            self.minimum_number_should_match,
             enable_scoring.is_scoring_enabled(),
             Box::new(SumWithCoordsCombiner::default),
         )))
@@ -166,7 +175,25 @@ impl Query for BooleanQuery {
 impl BooleanQuery {
     /// Creates a new boolean query.
     pub fn new(subqueries: Vec<(Occur, Box<dyn Query>)>) -> BooleanQuery {
-        BooleanQuery { subqueries }
+        BooleanQuery {
+            subqueries,
+            minimum_number_should_match: 0,
+        }
+    }
+
+    /// Requires the matching documents to match at least `minimum_number_should_match`
+    /// of the `Should` sub queries. The `Must` and `MustNot` sub queries are not affected.
+    ///
+    /// The default, 0, keeps the regular behavior: the `Should` sub queries are optional
+    /// if there are `Must` sub queries, and at least one of them must match otherwise.
+    pub fn set_minimum_number_should_match(&mut self, minimum_number_should_match: usize) {
+        self.minimum_number_should_match = minimum_number_should_match;
+    }
+
+    /// Minimum number of `Should` sub queries a document must match. See
+    /// [`BooleanQuery::set_minimum_number_should_match`].
+    pub fn minimum_number_should_match(&self) -> usize {
+        self.minimum_number_should_match
     }
 
     /// Returns the intersection of the queries.
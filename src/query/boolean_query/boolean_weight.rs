@@ -8,8 +8,8 @@ use crate::query::score_combiner::{DoNothingCombiner, ScoreCombiner};
 use crate::query::term_query::TermScorer;
 use crate::query::weight::{for_each_docset_buffered, for_each_pruning_scorer, for_each_scorer};
 use crate::query::{
-    intersect_scorers, EmptyScorer, Exclude, Explanation, Occur, RequiredOptionalScorer, Scorer,
-    Union, Weight,
+    intersect_scorers, Disjunction, EmptyScorer, Exclude, Explanation, Occur,
+    RequiredOptionalScorer, Scorer, Union, Weight,
 };
 use crate::{DocId, Score};
 
@@ -70,6 +70,7 @@ fn into_box_scorer<TScoreCombiner: ScoreCombiner>(
 /// Weight associated to the `BoolQuery`.
 pub struct BooleanWeight<TScoreCombiner: ScoreCombiner> {
     weights: Vec<(Occur, Box<dyn Weight>)>,
+    minimum_number_should_match: usize,
     scoring_enabled: bool,
     score_combiner_fn: Box<dyn Fn() -> TScoreCombiner + Sync + Send>,
 }
@@ -80,9 +81,26 @@ impl<TScoreCombiner: ScoreCombiner> BooleanWeight<TScoreCombiner> {
         weights: Vec<(Occur, Box<dyn Weight>)>,
         scoring_enabled: bool,
         score_combiner_fn: Box<dyn Fn() -> TScoreCombiner + Sync + Send + 'static>,
+    ) -> BooleanWeight<TScoreCombiner> {
+        BooleanWeight::with_minimum_number_should_match(
+            weights,
+            0,
+            scoring_enabled,
+            score_combiner_fn,
+        )
+    }
+
+    /// Creates a new boolean weight, requiring the documents to match at least
+    /// `minimum_number_should_match` of the `Should` weights.
+    pub fn with_minimum_number_should_match(
+        weights: Vec<(Occur, Box<dyn Weight>)>,
+        minimum_number_should_match: usize,
+        scoring_enabled: bool,
+        score_combiner_fn: Box<dyn Fn() -> TScoreCombiner + Sync + Send + 'static>,
     ) -> BooleanWeight<TScoreCombiner> {
         BooleanWeight {
             weights,
+            minimum_number_should_match,
             scoring_enabled,
             score_combiner_fn,
         }
@@ -112,9 +130,22 @@ impl<TScoreCombiner: ScoreCombiner> BooleanWeight<TScoreCombiner> {
     ) -> crate::Result<SpecializedScorer> {
         let mut per_occur_scorers = self.per_occur_scorers(reader, boost)?;
 
-        let should_scorer_opt: Option<SpecializedScorer> = per_occur_scorers
-            .remove(&Occur::Should)
-            .map(|scorers| scorer_union(scorers, &score_combiner_fn));
+        let should_scorers = per_occur_scorers.remove(&Occur::Should);
+        let num_should_scorers = should_scorers.as_ref().map(Vec::len).unwrap_or(0);
+        if self.minimum_number_should_match > num_should_scorers {
+            return Ok(SpecializedScorer::Other(Box::new(EmptyScorer)));
+        }
+        let should_scorer_opt: Option<SpecializedScorer> = if self.minimum_number_should_match > 1 {
+            should_scorers.map(|scorers| {
+                SpecializedScorer::Other(Box::new(Disjunction::new(
+                    scorers,
+                    self.minimum_number_should_match,
+                    score_combiner_fn(),
+                )))
+            })
+        } else {
+            should_scorers.map(|scorers| scorer_union(scorers, &score_combiner_fn))
+        };
         let exclude_scorer_opt: Option<Box<dyn Scorer>> = per_occur_scorers
             .remove(&Occur::MustNot)
             .map(|scorers| scorer_union(scorers, DoNothingCombiner::default))
@@ -127,6 +158,12 @@ impl<TScoreCombiner: ScoreCombiner> BooleanWeight<TScoreCombiner> {
             .map(intersect_scorers);
 
         let positive_scorer: SpecializedScorer = match (should_scorer_opt, must_scorer_opt) {
+            (Some(should_scorer), Some(must_scorer)) if self.minimum_number_should_match > 0 => {
+                SpecializedScorer::Other(intersect_scorers(vec![
+                    must_scorer,
+                    into_box_scorer(should_scorer, &score_combiner_fn),
+                ]))
+            }
             (Some(should_scorer), Some(must_scorer)) => {
                 if self.scoring_enabled {
                     SpecializedScorer::Other(Box::new(RequiredOptionalScorer::<
@@ -164,7 +201,7 @@ impl<TScoreCombiner: ScoreCombiner + Sync> Weight for BooleanWeight<TScoreCombin
     fn scorer(&self, reader: &SegmentReader, boost: Score) -> crate::Result<Box<dyn Scorer>> {
         if self.weights.is_empty() {
             Ok(Box::new(EmptyScorer))
-        } else if self.weights.len() == 1 {
+        } else if self.weights.len() == 1 && self.minimum_number_should_match == 0 {
             let &(occur, ref weight) = &self.weights[0];
             if occur == Occur::MustNot {
                 Ok(Box::new(EmptyScorer))
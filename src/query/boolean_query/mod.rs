@@ -81,6 +81,59 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    pub fn test_boolean_minimum_number_should_match() -> crate::Result<()> {
+        let (index, text_field) = aux_test_helper()?;
+        let searcher = index.reader()?.searcher();
+        let make_term_query = |text: &str| -> Box<dyn Query> {
+            Box::new(TermQuery::new(
+                Term::from_field_text(text_field, text),
+                IndexRecordOption::WithFreqs,
+            ))
+        };
+        let matching_docs = |query: &BooleanQuery| -> crate::Result<Vec<DocId>> {
+            let top_docs = searcher.search(query, &TopDocs::with_limit(10))?;
+            let mut docs: Vec<DocId> = top_docs
+                .into_iter()
+                .map(|(_, doc_address)| doc_address.doc_id)
+                .collect();
+            docs.sort_unstable();
+            assert_eq!(query.count(&searcher)?, docs.len());
+            Ok(docs)
+        };
+        let mut query = BooleanQuery::new(vec![
+            (Occur::Should, make_term_query("a")),
+            (Occur::Should, make_term_query("b")),
+            (Occur::Should, make_term_query("d")),
+        ]);
+        assert_eq!(matching_docs(&query)?, [0, 1, 2, 3, 4]);
+        query.set_minimum_number_should_match(2);
+        assert_eq!(matching_docs(&query)?, [0, 3]);
+        query.set_minimum_number_should_match(3);
+        assert_eq!(matching_docs(&query)?, [3]);
+        query.set_minimum_number_should_match(4);
+        assert!(matching_docs(&query)?.is_empty());
+
+        let mut query = BooleanQuery::new(vec![
+            (Occur::Must, make_term_query("d")),
+            (Occur::Should, make_term_query("a")),
+            (Occur::Should, make_term_query("b")),
+        ]);
+        assert_eq!(matching_docs(&query)?, [3, 4]);
+        query.set_minimum_number_should_match(1);
+        assert_eq!(matching_docs(&query)?, [3]);
+
+        let mut query = BooleanQuery::new(vec![
+            (Occur::MustNot, make_term_query("d")),
+            (Occur::Should, make_term_query("a")),
+            (Occur::Should, make_term_query("b")),
+            (Occur::Should, make_term_query("c")),
+        ]);
+        query.set_minimum_number_should_match(2);
+        assert_eq!(matching_docs(&query)?, [0, 1, 2]);
+        Ok(())
+    }
+
     #[test]
     pub fn test_boolean_reqopt() -> crate::Result<()> {
         let (index, text_field) = aux_test_helper()?;
@@ -10,6 +10,9 @@ use crate::{DocId, DocSet, Score, SegmentReader, Term};
 /// The document set matched by the `BoostQuery` is strictly the same as the underlying query.
 /// The score of each document, is the score of the underlying query multiplied by the `boost`
 /// factor.
+///
+/// Boost queries can be nested, in which case the boosts are multiplied. A boost of `1.0`
+/// leaves the scores and the explanation of the underlying query untouched.
 pub struct BoostQuery {
     query: Box<dyn Query>,
     boost: Score,
@@ -40,7 +43,7 @@ impl fmt::Debug for BoostQuery {
 impl Query for BoostQuery {
     fn weight(&self, enable_scoring: EnableScoring<'_>) -> crate::Result<Box<dyn Weight>> {
         let weight_without_boost = self.query.weight(enable_scoring)?;
-        let boosted_weight = if enable_scoring.is_scoring_enabled() {
+        let boosted_weight = if enable_scoring.is_scoring_enabled() && self.boost != 1.0 {
             Box::new(BoostWeight::new(weight_without_boost, self.boost))
         } else {
             weight_without_boost
@@ -135,9 +138,12 @@ impl<S: Scorer> Scorer for BoostScorer<S> {
 #[cfg(test)]
 mod tests {
     use super::BoostQuery;
-    use crate::query::{AllQuery, Query};
-    use crate::schema::Schema;
-    use crate::{DocAddress, Index, IndexWriter, TantivyDocument};
+    use crate::collector::TopDocs;
+    use crate::query::{AllQuery, BooleanQuery, Occur, Query, TermQuery};
+    use crate::schema::{IndexRecordOption, Schema, TEXT};
+    use crate::{
+        assert_nearly_equals, DocAddress, Index, IndexWriter, Score, TantivyDocument, Term,
+    };
 
     #[test]
     fn test_boost_query_explain() -> crate::Result<()> {
@@ -156,4 +162,50 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_boost_query_scores() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let body = schema_builder.add_text_field("body", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "tantivy", body => "search engine"))?;
+        index_writer.add_document(doc!(title => "search engine", body => "tantivy"))?;
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let term_query = |field, text| -> Box<dyn Query> {
+            Box::new(TermQuery::new(
+                Term::from_field_text(field, text),
+                IndexRecordOption::WithFreqs,
+            ))
+        };
+        let score = |query: &dyn Query| -> crate::Result<Score> {
+            let top_docs = searcher.search(query, &TopDocs::with_limit(1))?;
+            Ok(top_docs[0].0)
+        };
+        let title_query = term_query(title, "tantivy");
+        let unboosted_score = score(title_query.as_ref())?;
+
+        let boosted = BoostQuery::new(title_query.box_clone(), 1.0);
+        assert_eq!(score(&boosted)?, unboosted_score);
+        let explanation = boosted.explain(&searcher, DocAddress::new(0, 0u32))?;
+        assert!(!explanation.to_pretty_json().contains("Boost"));
+
+        let nested = BoostQuery::new(Box::new(BoostQuery::new(title_query.box_clone(), 2.0)), 3.0);
+        assert_nearly_equals!(score(&nested)?, unboosted_score * 6.0);
+
+        // The boosted title clause wins over the body clause.
+        let boolean_query = BooleanQuery::from(vec![
+            (
+                Occur::Should,
+                Box::new(BoostQuery::new(title_query, 3.0)) as Box<dyn Query>,
+            ),
+            (Occur::Should, term_query(body, "tantivy")),
+        ]);
+        let top_docs = searcher.search(&boolean_query, &TopDocs::with_limit(2))?;
+        assert_eq!(top_docs[0].1, DocAddress::new(0, 0u32));
+        assert_eq!(top_docs.len(), 2);
+        Ok(())
+    }
 }
@@ -1,6 +1,7 @@
 use std::fmt;
 
 use crate::docset::COLLECT_BLOCK_BUFFER_LEN;
+use crate::fastfield::AliveBitSet;
 use crate::query::{EnableScoring, Explanation, Query, Scorer, Weight};
 use crate::{DocId, DocSet, Score, SegmentReader, TantivyError, Term};
 
@@ -130,6 +131,14 @@ impl<TDocSet: DocSet> DocSet for ConstScorer<TDocSet> {
     fn size_hint(&self) -> u32 {
         self.docset.size_hint()
     }
+
+    fn count(&mut self, alive_bitset: &AliveBitSet) -> u32 {
+        self.docset.count(alive_bitset)
+    }
+
+    fn count_including_deleted(&mut self) -> u32 {
+        self.docset.count_including_deleted()
+    }
 }
 
 impl<TDocSet: DocSet + 'static> Scorer for ConstScorer<TDocSet> {
@@ -141,9 +150,10 @@ impl<TDocSet: DocSet + 'static> Scorer for ConstScorer<TDocSet> {
 #[cfg(test)]
 mod tests {
     use super::ConstScoreQuery;
-    use crate::query::{AllQuery, Query};
-    use crate::schema::Schema;
-    use crate::{DocAddress, Index, IndexWriter, TantivyDocument};
+    use crate::collector::{Count, TopDocs};
+    use crate::query::{AllQuery, DisjunctionMaxQuery, Query, TermQuery};
+    use crate::schema::{IndexRecordOption, Schema, TEXT};
+    use crate::{DocAddress, Index, IndexWriter, TantivyDocument, Term};
 
     #[test]
     fn test_const_score_query_explain() -> crate::Result<()> {
@@ -171,4 +181,42 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_const_score_query_scores() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text => "red"))?;
+        index_writer.add_document(doc!(text => "red red car with a red roof"))?;
+        index_writer.add_document(doc!(text => "blue car"))?;
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let term_query = |word| -> Box<dyn Query> {
+            Box::new(TermQuery::new(
+                Term::from_field_text(text, word),
+                IndexRecordOption::WithFreqs,
+            ))
+        };
+
+        let query = ConstScoreQuery::new(term_query("red"), 2.5);
+        assert_eq!(searcher.search(&query, &Count)?, 2);
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(10))?;
+        assert_eq!(top_docs.len(), 2);
+        assert_eq!(top_docs[0].0, 2.5);
+        assert_eq!(top_docs[1].0, 2.5);
+
+        // The filter contributes a flat amount to the disjunction.
+        let query = DisjunctionMaxQuery::new(vec![
+            Box::new(ConstScoreQuery::new(term_query("red"), 10.0)),
+            term_query("car"),
+        ]);
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(10))?;
+        assert_eq!(top_docs.len(), 3);
+        assert_eq!(top_docs[0].0, 10.0);
+        assert_eq!(top_docs[1].0, 10.0);
+        assert_eq!(top_docs[2].1, DocAddress::new(0, 2));
+        Ok(())
+    }
 }
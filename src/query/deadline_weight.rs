@@ -0,0 +1,304 @@
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::query::{Explanation, Scorer, Weight};
+use crate::{DocId, DocSet, Score, SegmentReader, TantivyError, TERMINATED};
+
+/// Number of documents visited by a [`DeadlineScorer`] between two checks of the deadline.
+///
+/// Reading the clock is not free, so it should not be done for every document.
+const DEADLINE_CHECK_INTERVAL: u32 = 4_096;
+
+thread_local! {
+    static SEARCH_DEADLINE: Cell<Option<Instant>> = Cell::new(None);
+}
+
+/// Sets the deadline of the search running on the current thread, until it is dropped.
+///
+/// The steps of a search that happen before any document is visited, e.g. the expansion of
+/// an automaton over the term dictionary, check it with [`check_search_deadline`].
+pub(crate) struct SearchDeadlineGuard {
+    previous_deadline: Option<Instant>,
+}
+
+impl SearchDeadlineGuard {
+    pub fn new(deadline: Instant) -> SearchDeadlineGuard {
+        let previous_deadline = SEARCH_DEADLINE.with(|cell| cell.replace(Some(deadline)));
+        SearchDeadlineGuard { previous_deadline }
+    }
+}
+
+impl Drop for SearchDeadlineGuard {
+    fn drop(&mut self) {
+        SEARCH_DEADLINE.with(|cell| cell.set(self.previous_deadline));
+    }
+}
+
+/// Returns a [`TantivyError::TimedOut`] error if the deadline of the search running on the
+/// current thread, if any, is exceeded.
+pub(crate) fn check_search_deadline() -> crate::Result<()> {
+    let deadline_opt = SEARCH_DEADLINE.with(Cell::get);
+    match deadline_opt {
+        Some(deadline) if Instant::now() >= deadline => Err(TantivyError::TimedOut),
+        _ => Ok(()),
+    }
+}
+
+/// A weight wrapping the weight of a query, whose scorers stop matching documents once a
+/// deadline is exceeded.
+///
+/// Since scorers cannot return errors, the caller is expected to check
+/// [`DeadlineWeight::is_timed_out`] after using the scorers.
+///
+/// Only the top level weight of a query should be wrapped, so that the sub-weights keep their
+/// optimizations. For the same reason, `for_each_pruning` is delegated to the wrapped weight,
+/// and the deadline is checked when a competitive document is found.
+pub(crate) struct DeadlineWeight {
+    weight: Box<dyn Weight>,
+    deadline: Instant,
+    timed_out: Arc<AtomicBool>,
+}
+
+impl DeadlineWeight {
+    pub fn new(weight: Box<dyn Weight>, deadline: Instant) -> DeadlineWeight {
+        DeadlineWeight {
+            weight,
+            deadline,
+            timed_out: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Returns true if the deadline was exceeded.
+    ///
+    /// Once it returns true, the results collected with the scorers of this weight are
+    /// incomplete.
+    pub fn is_timed_out(&self) -> bool {
+        if self.timed_out.load(Ordering::Relaxed) {
+            return true;
+        }
+        if Instant::now() >= self.deadline {
+            self.timed_out.store(true, Ordering::Relaxed);
+            return true;
+        }
+        false
+    }
+}
+
+impl Weight for DeadlineWeight {
+    fn scorer(&self, reader: &SegmentReader, boost: Score) -> crate::Result<Box<dyn Scorer>> {
+        let scorer = self.weight.scorer(reader, boost)?;
+        Ok(Box::new(DeadlineScorer {
+            underlying: scorer,
+            deadline: self.deadline,
+            timed_out: self.timed_out.clone(),
+            num_docs_before_check: DEADLINE_CHECK_INTERVAL,
+        }))
+    }
+
+    fn explain(&self, reader: &SegmentReader, doc: DocId) -> crate::Result<Explanation> {
+        self.weight.explain(reader, doc)
+    }
+
+    fn for_each_pruning(
+        &self,
+        threshold: Score,
+        reader: &SegmentReader,
+        callback: &mut dyn FnMut(DocId, Score) -> Score,
+    ) -> crate::Result<()> {
+        self.weight
+            .for_each_pruning(threshold, reader, &mut |doc, score| {
+                if self.is_timed_out() {
+                    // No document can beat this threshold, so the remaining ones are pruned.
+                    return Score::MAX;
+                }
+                callback(doc, score)
+            })
+    }
+}
+
+/// A scorer that acts as if it reached the end of its docset once the deadline is exceeded.
+///
+/// `fill_buffer` and `count` are deliberately not delegated, so that they go through
+/// `advance` and the deadline is checked.
+struct DeadlineScorer {
+    underlying: Box<dyn Scorer>,
+    deadline: Instant,
+    timed_out: Arc<AtomicBool>,
+    num_docs_before_check: u32,
+}
+
+impl DeadlineScorer {
+    fn check_deadline(&mut self) -> bool {
+        if self.timed_out.load(Ordering::Relaxed) {
+            return true;
+        }
+        self.num_docs_before_check -= 1;
+        if self.num_docs_before_check > 0 {
+            return false;
+        }
+        self.num_docs_before_check = DEADLINE_CHECK_INTERVAL;
+        if Instant::now() >= self.deadline {
+            self.timed_out.store(true, Ordering::Relaxed);
+            return true;
+        }
+        false
+    }
+}
+
+impl DocSet for DeadlineScorer {
+    fn advance(&mut self) -> DocId {
+        if self.check_deadline() {
+            return TERMINATED;
+        }
+        self.underlying.advance()
+    }
+
+    fn seek(&mut self, target: DocId) -> DocId {
+        if self.check_deadline() {
+            return TERMINATED;
+        }
+        self.underlying.seek(target)
+    }
+
+    fn doc(&self) -> DocId {
+        if self.timed_out.load(Ordering::Relaxed) {
+            return TERMINATED;
+        }
+        self.underlying.doc()
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.underlying.size_hint()
+    }
+}
+
+impl Scorer for DeadlineScorer {
+    fn score(&mut self) -> Score {
+        self.underlying.score()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use tantivy_fst::Automaton;
+
+    use super::{DeadlineWeight, DEADLINE_CHECK_INTERVAL};
+    use crate::collector::{Count, TopDocs};
+    use crate::query::{AllQuery, AutomatonWeight, EnableScoring, Query, TermQuery, Weight};
+    use crate::schema::{Field, IndexRecordOption, Schema, STRING, TEXT};
+    use crate::{DocSet, Index, IndexWriter, TantivyError, Term, TERMINATED};
+
+    fn test_index(num_docs: usize) -> crate::Result<Index> {
+        let mut schema_builder = Schema::builder();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        for _ in 0..num_docs {
+            index_writer.add_document(doc!(text_field => "hello"))?;
+        }
+        index_writer.commit()?;
+        Ok(index)
+    }
+
+    #[test]
+    fn test_deadline_scorer_stops_at_deadline() -> crate::Result<()> {
+        let index = test_index(10_000)?;
+        let searcher = index.reader()?.searcher();
+        let weight = AllQuery.weight(EnableScoring::disabled_from_searcher(&searcher))?;
+        let deadline_weight = DeadlineWeight::new(weight, Instant::now());
+        let mut scorer = deadline_weight.scorer(searcher.segment_reader(0), 1.0)?;
+        let mut num_docs = 0;
+        while scorer.doc() != TERMINATED {
+            num_docs += 1;
+            scorer.advance();
+        }
+        assert_eq!(num_docs, DEADLINE_CHECK_INTERVAL);
+        assert!(deadline_weight.is_timed_out());
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_with_deadline() -> crate::Result<()> {
+        let index = test_index(10_000)?;
+        let searcher = index.reader()?.searcher();
+        let far_deadline = Instant::now() + Duration::from_secs(3_600);
+        assert_eq!(
+            searcher.search_with_deadline(&AllQuery, &Count, far_deadline)?,
+            10_000
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_with_deadline_top_docs() -> crate::Result<()> {
+        let index = test_index(10_000)?;
+        let searcher = index.reader()?.searcher();
+        let text_field = searcher.schema().get_field("text").unwrap();
+        let query = TermQuery::new(
+            Term::from_field_text(text_field, "hello"),
+            IndexRecordOption::WithFreqs,
+        );
+        let far_deadline = Instant::now() + Duration::from_secs(3_600);
+        let top_docs = TopDocs::with_limit(10);
+        assert_eq!(
+            searcher.search_with_deadline(&query, &top_docs, far_deadline)?,
+            searcher.search(&query, &top_docs)?
+        );
+        Ok(())
+    }
+
+    /// An automaton matching all of the terms, slowly.
+    struct SlowAutomaton;
+
+    impl Automaton for SlowAutomaton {
+        type State = ();
+
+        fn start(&self) -> Self::State {}
+
+        fn is_match(&self, _state: &Self::State) -> bool {
+            std::thread::sleep(Duration::from_millis(1));
+            true
+        }
+
+        fn accept(&self, _state: &Self::State, _byte: u8) -> Self::State {}
+    }
+
+    /// A query expanding the `SlowAutomaton` over the term dictionary of a field.
+    #[derive(Clone, Debug)]
+    struct SlowQuery {
+        field: Field,
+    }
+
+    impl Query for SlowQuery {
+        fn weight(&self, _enable_scoring: EnableScoring<'_>) -> crate::Result<Box<dyn Weight>> {
+            Ok(Box::new(AutomatonWeight::new(self.field, SlowAutomaton)))
+        }
+    }
+
+    #[test]
+    fn test_search_with_deadline_interrupts_term_expansion() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let id_field = schema_builder.add_text_field("id", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        for i in 0..10_000 {
+            index_writer.add_document(doc!(id_field => format!("id{i:05}")))?;
+        }
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        // Expanding all of the terms takes at least 10 seconds.
+        let query = SlowQuery { field: id_field };
+        let start = Instant::now();
+        let deadline = start + Duration::from_millis(50);
+        assert!(matches!(
+            searcher.search_with_deadline(&query, &Count, deadline),
+            Err(TantivyError::TimedOut)
+        ));
+        assert!(start.elapsed() < Duration::from_secs(5));
+        Ok(())
+    }
+}
@@ -0,0 +1,218 @@
+use std::cmp::Ordering;
+use std::collections::binary_heap::PeekMut;
+use std::collections::BinaryHeap;
+
+use crate::docset::{DocSet, TERMINATED};
+use crate::query::score_combiner::ScoreCombiner;
+use crate::query::Scorer;
+use crate::{DocId, Score};
+
+// Wraps a scorer to order it by its current doc, in a min-heap.
+struct ScorerWrapper<TScorer> {
+    scorer: TScorer,
+    doc: DocId,
+}
+
+impl<TScorer: Scorer> ScorerWrapper<TScorer> {
+    fn new(scorer: TScorer) -> ScorerWrapper<TScorer> {
+        let doc = scorer.doc();
+        ScorerWrapper { scorer, doc }
+    }
+
+    fn advance(&mut self) -> DocId {
+        self.doc = self.scorer.advance();
+        self.doc
+    }
+
+    fn seek(&mut self, target: DocId) -> DocId {
+        self.doc = self.scorer.seek(target);
+        self.doc
+    }
+}
+
+impl<TScorer> PartialEq for ScorerWrapper<TScorer> {
+    fn eq(&self, other: &Self) -> bool {
+        self.doc == other.doc
+    }
+}
+
+impl<TScorer> Eq for ScorerWrapper<TScorer> {}
+
+impl<TScorer> PartialOrd for ScorerWrapper<TScorer> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<TScorer> Ord for ScorerWrapper<TScorer> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed, so that the `BinaryHeap` pops the lowest doc first.
+        other.doc.cmp(&self.doc)
+    }
+}
+
+/// Creates a `DocSet` that iterates through the documents matched by at least
+/// `minimum_matches_required` of the given scorers.
+///
+/// The score of a document is the combination of the scores of the scorers matching it.
+pub struct Disjunction<TScorer, TScoreCombiner> {
+    scorers: BinaryHeap<ScorerWrapper<TScorer>>,
+    minimum_matches_required: usize,
+    score_combiner: TScoreCombiner,
+    current_doc: DocId,
+    current_score: Score,
+}
+
+impl<TScorer: Scorer, TScoreCombiner: ScoreCombiner> Disjunction<TScorer, TScoreCombiner> {
+    /// Creates a new `Disjunction`.
+    ///
+    /// `minimum_matches_required` must be at least 1.
+    pub fn new<T: IntoIterator<Item = TScorer>>(
+        scorers: T,
+        minimum_matches_required: usize,
+        score_combiner: TScoreCombiner,
+    ) -> Disjunction<TScorer, TScoreCombiner> {
+        assert!(minimum_matches_required >= 1);
+        let scorers = scorers
+            .into_iter()
+            .filter(|scorer| scorer.doc() != TERMINATED)
+            .map(ScorerWrapper::new)
+            .collect();
+        let mut disjunction = Disjunction {
+            scorers,
+            minimum_matches_required,
+            score_combiner,
+            current_doc: 0,
+            current_score: 0.0,
+        };
+        disjunction.advance();
+        disjunction
+    }
+}
+
+impl<TScorer: Scorer, TScoreCombiner: ScoreCombiner> DocSet
+    for Disjunction<TScorer, TScoreCombiner>
+{
+    fn advance(&mut self) -> DocId {
+        let mut num_matches = 0;
+        while let Some(mut candidate) = self.scorers.pop() {
+            if candidate.doc != self.current_doc {
+                if num_matches >= self.minimum_matches_required {
+                    // `current_doc` is complete, `candidate` belongs to the next one.
+                    self.scorers.push(candidate);
+                    self.current_score = self.score_combiner.score();
+                    return self.current_doc;
+                }
+                num_matches = 0;
+                self.current_doc = candidate.doc;
+                self.score_combiner.clear();
+            }
+            num_matches += 1;
+            self.score_combiner.update(&mut candidate.scorer);
+            if candidate.advance() != TERMINATED {
+                self.scorers.push(candidate);
+            }
+        }
+        if num_matches < self.minimum_matches_required {
+            self.current_doc = TERMINATED;
+        }
+        self.current_score = self.score_combiner.score();
+        self.current_doc
+    }
+
+    fn seek(&mut self, target: DocId) -> DocId {
+        if self.current_doc >= target {
+            return self.current_doc;
+        }
+        while let Some(mut scorer) = self.scorers.peek_mut() {
+            if scorer.doc >= target {
+                break;
+            }
+            if scorer.seek(target) == TERMINATED {
+                PeekMut::pop(scorer);
+            }
+        }
+        // All the scorers are now past `current_doc`.
+        self.advance()
+    }
+
+    fn doc(&self) -> DocId {
+        self.current_doc
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.scorers
+            .iter()
+            .map(|scorer| scorer.scorer.size_hint())
+            .max()
+            .unwrap_or(0u32)
+    }
+}
+
+impl<TScorer: Scorer, TScoreCombiner: ScoreCombiner> Scorer
+    for Disjunction<TScorer, TScoreCombiner>
+{
+    fn score(&mut self) -> Score {
+        self.current_score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Disjunction;
+    use crate::docset::{DocSet, TERMINATED};
+    use crate::postings::tests::test_skip_against_unoptimized;
+    use crate::query::score_combiner::SumCombiner;
+    use crate::query::{ConstScorer, Scorer, VecDocSet};
+    use crate::DocId;
+
+    fn disjunction(
+        docs: &[&[DocId]],
+        minimum_matches_required: usize,
+    ) -> Disjunction<ConstScorer<VecDocSet>, SumCombiner> {
+        let scorers = docs
+            .iter()
+            .map(|docs| ConstScorer::from(VecDocSet::from(docs.to_vec())));
+        Disjunction::new(scorers, minimum_matches_required, SumCombiner::default())
+    }
+
+    const DOCS: [&[DocId]; 3] = [&[1, 2, 5, 8, 10], &[2, 3, 5, 9, 10], &[1, 3, 4, 5, 11]];
+
+    #[test]
+    fn test_disjunction() {
+        for (minimum_matches_required, expected) in [
+            (1, vec![1, 2, 3, 4, 5, 8, 9, 10, 11]),
+            (2, vec![1, 2, 3, 5, 10]),
+            (3, vec![5]),
+            (4, vec![]),
+        ] {
+            let mut disjunction = disjunction(&DOCS, minimum_matches_required);
+            let mut docs = vec![];
+            while disjunction.doc() != TERMINATED {
+                docs.push(disjunction.doc());
+                disjunction.advance();
+            }
+            assert_eq!(docs, expected);
+        }
+    }
+
+    #[test]
+    fn test_disjunction_score() {
+        let mut disjunction = disjunction(&DOCS, 2);
+        assert_eq!(disjunction.doc(), 1);
+        assert_eq!(disjunction.score(), 2.0);
+        assert_eq!(disjunction.seek(5), 5);
+        assert_eq!(disjunction.score(), 3.0);
+        assert_eq!(disjunction.advance(), 10);
+        assert_eq!(disjunction.score(), 2.0);
+        assert_eq!(disjunction.advance(), TERMINATED);
+    }
+
+    #[test]
+    fn test_disjunction_skip() {
+        test_skip_against_unoptimized(
+            || Box::new(disjunction(&DOCS, 2)),
+            vec![0, 1, 2, 3, 4, 6, 10, 11],
+        );
+    }
+}
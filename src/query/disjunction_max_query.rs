@@ -128,3 +128,79 @@ impl DisjunctionMaxQuery {
         DisjunctionMaxQuery::with_tie_breaker(disjuncts, 0.0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::DisjunctionMaxQuery;
+    use crate::collector::TopDocs;
+    use crate::query::{BooleanQuery, Query, QueryClone, TermQuery};
+    use crate::schema::{IndexRecordOption, Schema, TEXT};
+    use crate::{assert_nearly_equals, DocId, Index, IndexWriter, Score, Searcher, Term};
+
+    fn scores(searcher: &Searcher, query: &dyn Query) -> crate::Result<HashMap<DocId, Score>> {
+        let top_docs = searcher.search(query, &TopDocs::with_limit(10))?;
+        Ok(top_docs
+            .into_iter()
+            .map(|(score, doc_address)| (doc_address.doc_id, score))
+            .collect())
+    }
+
+    #[test]
+    fn test_disjunction_max_query_vs_boolean_query() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let body = schema_builder.add_text_field("body", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        // Matches both fields.
+        index_writer.add_document(doc!(
+            title => "rust",
+            body => "a guide to rust"
+        ))?;
+        // Only matches the title.
+        index_writer.add_document(doc!(title => "rust", body => "a guide"))?;
+        // Only matches the body.
+        index_writer.add_document(doc!(title => "guide", body => "rust"))?;
+        index_writer.add_document(doc!(title => "python", body => "a guide to python"))?;
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+
+        let title_query = TermQuery::new(
+            Term::from_field_text(title, "rust"),
+            IndexRecordOption::WithFreqs,
+        );
+        let body_query = TermQuery::new(
+            Term::from_field_text(body, "rust"),
+            IndexRecordOption::WithFreqs,
+        );
+        let title_scores = scores(&searcher, &title_query)?;
+        let body_scores = scores(&searcher, &body_query)?;
+        let boolean_scores = scores(
+            &searcher,
+            &BooleanQuery::union(vec![title_query.box_clone(), body_query.box_clone()]),
+        )?;
+        for tie_breaker in [0.0, 0.3, 1.0] {
+            let dismax_query = DisjunctionMaxQuery::with_tie_breaker(
+                vec![title_query.box_clone(), body_query.box_clone()],
+                tie_breaker,
+            );
+            let dismax_scores = scores(&searcher, &dismax_query)?;
+            assert_eq!(dismax_scores.len(), 3);
+            for (doc, dismax_score) in dismax_scores {
+                let title_score = title_scores.get(&doc).copied().unwrap_or(0.0);
+                let body_score = body_scores.get(&doc).copied().unwrap_or(0.0);
+                let max_score = title_score.max(body_score);
+                let min_score = title_score.min(body_score);
+                assert_nearly_equals!(dismax_score, max_score + tie_breaker * min_score);
+                if tie_breaker == 1.0 {
+                    assert_nearly_equals!(dismax_score, boolean_scores[&doc]);
+                }
+            }
+        }
+        // The boolean query rewards matching both fields.
+        assert!(boolean_scores[&0] > boolean_scores[&1]);
+        Ok(())
+    }
+}
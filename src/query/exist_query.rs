@@ -1,16 +1,22 @@
 use core::fmt::Debug;
 
 use columnar::{ColumnIndex, DynamicColumn};
+use common::BitSet;
 
-use super::{ConstScorer, EmptyScorer};
+use super::{BitSetDocSet, ConstScorer, EmptyScorer};
 use crate::docset::{DocSet, TERMINATED};
 use crate::index::SegmentReader;
 use crate::query::explanation::does_not_match;
 use crate::query::{EnableScoring, Explanation, Query, Scorer, Weight};
+use crate::schema::{Field, FieldType, IndexRecordOption, Term};
 use crate::{DocId, Score, TantivyError};
 
 /// Query that matches all documents with a non-null value in the specified field.
 ///
+/// On fast fields, a document matches if it has at least one value in the columns of the
+/// field. On indexed fields which are not fast, a document matches if at least one term
+/// of the field has been indexed for it.
+///
 /// All of the matched documents get the score 1.0.
 #[derive(Clone, Debug)]
 pub struct ExistsQuery {
@@ -22,7 +28,7 @@ impl ExistsQuery {
     ///
     /// This query matches all documents with at least one non-null value in the specified field.
     /// This constructor never fails, but executing the search with this query will return an
-    /// error if the specified field doesn't exists or is not a fast field.
+    /// error if the specified field doesn't exists or is neither a fast field nor indexed.
     pub fn new_exists_query(field: String) -> ExistsQuery {
         ExistsQuery { field_name: field }
     }
@@ -31,19 +37,31 @@ impl ExistsQuery {
 impl Query for ExistsQuery {
     fn weight(&self, enable_scoring: EnableScoring) -> crate::Result<Box<dyn Weight>> {
         let schema = enable_scoring.schema();
-        let Some((field, _path)) = schema.find_field(&self.field_name) else {
+        let Some((field, path)) = schema.find_field(&self.field_name) else {
             return Err(TantivyError::FieldNotFound(self.field_name.clone()));
         };
         let field_type = schema.get_field_entry(field).field_type();
-        if !field_type.is_fast() {
+        if field_type.is_fast() {
+            return Ok(Box::new(ExistsWeight {
+                field_name: self.field_name.clone(),
+            }));
+        }
+        if !field_type.is_indexed() {
             return Err(TantivyError::SchemaError(format!(
-                "Field {} is not a fast field.",
+                "Field {} is neither a fast field nor an indexed field.",
                 self.field_name
             )));
         }
-        Ok(Box::new(ExistsWeight {
-            field_name: self.field_name.clone(),
-        }))
+        // The terms of a JSON path all start with the path, followed by the end of path marker.
+        let term_prefix = match field_type {
+            FieldType::JsonObject(json_options) if !path.is_empty() => {
+                Term::from_field_json_path(field, path, json_options.is_expand_dots_enabled())
+                    .serialized_value_bytes()
+                    .to_vec()
+            }
+            _ => Vec::new(),
+        };
+        Ok(Box::new(ExistsInvertedIndexWeight { field, term_prefix }))
     }
 }
 
@@ -84,6 +102,52 @@ impl Weight for ExistsWeight {
     }
 }
 
+/// Weight associated with the `ExistsQuery` query, on indexed fields which are not fast.
+///
+/// The matched documents are collected from the postings of all of the terms of the field.
+pub struct ExistsInvertedIndexWeight {
+    field: Field,
+    term_prefix: Vec<u8>,
+}
+
+impl Weight for ExistsInvertedIndexWeight {
+    fn scorer(&self, reader: &SegmentReader, boost: Score) -> crate::Result<Box<dyn Scorer>> {
+        let max_doc = reader.max_doc();
+        let mut doc_bitset = BitSet::with_max_value(max_doc);
+
+        let inverted_index = reader.inverted_index(self.field)?;
+        let mut term_stream = inverted_index
+            .terms()
+            .prefix_range(&self.term_prefix)
+            .into_stream()?;
+        while term_stream.advance() {
+            let term_info = term_stream.value();
+            let mut block_segment_postings = inverted_index
+                .read_block_postings_from_terminfo(term_info, IndexRecordOption::Basic)?;
+            loop {
+                let docs = block_segment_postings.docs();
+                if docs.is_empty() {
+                    break;
+                }
+                for &doc in docs {
+                    doc_bitset.insert(doc);
+                }
+                block_segment_postings.advance();
+            }
+        }
+        let doc_bitset = BitSetDocSet::from(doc_bitset);
+        Ok(Box::new(ConstScorer::new(doc_bitset, boost)))
+    }
+
+    fn explain(&self, reader: &SegmentReader, doc: DocId) -> crate::Result<Explanation> {
+        let mut scorer = self.scorer(reader, 1.0)?;
+        if scorer.seek(doc) != doc {
+            return Err(does_not_match(doc));
+        }
+        Ok(Explanation::new("ExistsQuery", 1.0))
+    }
+}
+
 pub(crate) struct ExistsDocSet {
     columns: Vec<DynamicColumn>,
     doc: DocId,
@@ -147,8 +211,8 @@ mod tests {
 
     use crate::collector::Count;
     use crate::query::exist_query::ExistsQuery;
-    use crate::query::{BooleanQuery, RangeQuery};
-    use crate::schema::{Facet, FacetOptions, Schema, FAST, INDEXED, STRING, TEXT};
+    use crate::query::{AllQuery, BooleanQuery, Occur, Query, RangeQuery};
+    use crate::schema::{Facet, FacetOptions, Schema, FAST, INDEXED, STORED, STRING, TEXT};
     use crate::{Index, Searcher};
 
     #[test]
@@ -296,17 +360,72 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_exists_query_indexed_fields() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let category = schema_builder.add_text_field("category", STRING);
+        let json = schema_builder.add_json_field("json", TEXT);
+        let schema = schema_builder.build();
+
+        let index = Index::create_in_ram(schema);
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            for i in 0u64..100u64 {
+                let json_value = if i % 4 == 0 {
+                    json!({ "a": i })
+                } else {
+                    json!({ "b": "text" })
+                };
+                if i % 3 == 0 {
+                    index_writer.add_document(doc!(
+                        title => format!("title {i}"),
+                        category => "fiction",
+                        json => json_value,
+                    ))?;
+                } else {
+                    index_writer.add_document(doc!(
+                        title => format!("title {i}"),
+                        json => json_value,
+                    ))?;
+                }
+            }
+            index_writer.commit()?;
+        }
+        let reader = index.reader()?;
+        let searcher = reader.searcher();
+
+        assert_eq!(count_existing_fields(&searcher, "title")?, 100);
+        assert_eq!(count_existing_fields(&searcher, "category")?, 34);
+        assert_eq!(count_existing_fields(&searcher, "json")?, 100);
+        assert_eq!(count_existing_fields(&searcher, "json.a")?, 25);
+        assert_eq!(count_existing_fields(&searcher, "json.b")?, 75);
+        assert_eq!(count_existing_fields(&searcher, "json.c")?, 0);
+
+        // Documents missing the field.
+        let missing_category = BooleanQuery::new(vec![
+            (Occur::Must, Box::new(AllQuery) as Box<dyn Query>),
+            (
+                Occur::MustNot,
+                Box::new(ExistsQuery::new_exists_query("category".to_string())),
+            ),
+        ]);
+        assert_eq!(searcher.search(&missing_category, &Count)?, 66);
+
+        Ok(())
+    }
+
     #[test]
     fn test_exists_query_unsupported_types() -> crate::Result<()> {
         let mut schema_builder = Schema::builder();
-        let not_fast = schema_builder.add_text_field("not_fast", TEXT);
+        let stored_only = schema_builder.add_text_field("stored_only", STORED);
         let schema = schema_builder.build();
 
         let index = Index::create_in_ram(schema);
         {
             let mut index_writer = index.writer_for_tests()?;
             index_writer.add_document(doc!(
-                not_fast => "slow",
+                stored_only => "hidden",
             ))?;
             index_writer.commit()?;
         }
@@ -316,12 +435,12 @@ mod tests {
         assert_eq!(
             searcher
                 .search(
-                    &ExistsQuery::new_exists_query("not_fast".to_string()),
+                    &ExistsQuery::new_exists_query("stored_only".to_string()),
                     &Count
                 )
                 .unwrap_err()
                 .to_string(),
-            "Schema error: 'Field not_fast is not a fast field.'"
+            "Schema error: 'Field stored_only is neither a fast field nor an indexed field.'"
         );
 
         assert_eq!(
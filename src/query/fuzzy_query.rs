@@ -31,6 +31,65 @@ impl Automaton for DfaWrapper {
     }
 }
 
+#[derive(Clone, Copy)]
+pub(crate) enum PrefixedDfaState {
+    // Number of bytes of the prefix matched so far.
+    Prefix(usize),
+    Dfa(u32),
+    Sink,
+}
+
+// Matches the terms starting with `prefix`, followed by a suffix accepted by the DFA.
+pub(crate) struct PrefixedDfaWrapper {
+    prefix: Vec<u8>,
+    dfa: DfaWrapper,
+}
+
+impl Automaton for PrefixedDfaWrapper {
+    type State = PrefixedDfaState;
+
+    fn start(&self) -> Self::State {
+        if self.prefix.is_empty() {
+            PrefixedDfaState::Dfa(self.dfa.start())
+        } else {
+            PrefixedDfaState::Prefix(0)
+        }
+    }
+
+    fn is_match(&self, state: &Self::State) -> bool {
+        match state {
+            PrefixedDfaState::Dfa(dfa_state) => self.dfa.is_match(dfa_state),
+            PrefixedDfaState::Prefix(_) | PrefixedDfaState::Sink => false,
+        }
+    }
+
+    fn can_match(&self, state: &Self::State) -> bool {
+        match state {
+            PrefixedDfaState::Prefix(_) => true,
+            PrefixedDfaState::Dfa(dfa_state) => self.dfa.can_match(dfa_state),
+            PrefixedDfaState::Sink => false,
+        }
+    }
+
+    fn accept(&self, state: &Self::State, byte: u8) -> Self::State {
+        match *state {
+            PrefixedDfaState::Prefix(num_matched) => {
+                if self.prefix[num_matched] != byte {
+                    PrefixedDfaState::Sink
+                } else if num_matched + 1 == self.prefix.len() {
+                    PrefixedDfaState::Dfa(self.dfa.start())
+                } else {
+                    PrefixedDfaState::Prefix(num_matched + 1)
+                }
+            }
+            PrefixedDfaState::Dfa(dfa_state) => {
+                PrefixedDfaState::Dfa(self.dfa.accept(&dfa_state, byte))
+            }
+            PrefixedDfaState::Sink => PrefixedDfaState::Sink,
+        }
+    }
+}
+
 /// A Fuzzy Query matches all of the documents
 /// containing a specific term that is within
 /// Levenshtein distance
@@ -86,6 +145,8 @@ pub struct FuzzyTermQuery {
     transposition_cost_one: bool,
     /// is a starts with query
     prefix: bool,
+    /// Number of leading characters that must match exactly
+    prefix_length: u8,
 }
 
 impl FuzzyTermQuery {
@@ -96,6 +157,7 @@ impl FuzzyTermQuery {
             distance,
             transposition_cost_one,
             prefix: false,
+            prefix_length: 0,
         }
     }
 
@@ -106,10 +168,20 @@ impl FuzzyTermQuery {
             distance,
             transposition_cost_one,
             prefix: true,
+            prefix_length: 0,
         }
     }
 
-    fn specialized_weight(&self) -> crate::Result<AutomatonWeight<DfaWrapper>> {
+    /// Requires the first `prefix_length` characters of the matching terms to be equal to
+    /// the ones of the term. The edit distance only applies to the rest of the term.
+    ///
+    /// This significantly reduces the number of terms to visit in the term dictionary.
+    /// Defaults to 0.
+    pub fn set_prefix_length(&mut self, prefix_length: u8) {
+        self.prefix_length = prefix_length;
+    }
+
+    fn specialized_weight(&self) -> crate::Result<AutomatonWeight<PrefixedDfaWrapper>> {
         static AUTOMATON_BUILDER: [[OnceCell<LevenshteinAutomatonBuilder>; 2]; 3] = [
             [OnceCell::new(), OnceCell::new()],
             [OnceCell::new(), OnceCell::new()],
@@ -153,23 +225,30 @@ impl FuzzyTermQuery {
                 InvalidArgument("The fuzzy term query requires a string term.".to_string())
             })?
         };
-        let automaton = if self.prefix {
-            automaton_builder.build_prefix_dfa(term_text)
+        let prefix_len_bytes = term_text
+            .char_indices()
+            .nth(self.prefix_length as usize)
+            .map(|(offset, _)| offset)
+            .unwrap_or(term_text.len());
+        let (prefix, suffix) = term_text.split_at(prefix_len_bytes);
+        let dfa = if self.prefix {
+            automaton_builder.build_prefix_dfa(suffix)
         } else {
-            automaton_builder.build_dfa(term_text)
+            automaton_builder.build_dfa(suffix)
+        };
+        let automaton = PrefixedDfaWrapper {
+            prefix: prefix.as_bytes().to_vec(),
+            dfa: DfaWrapper(dfa),
         };
 
         if let Some((json_path_bytes, _)) = term_value.as_json() {
             Ok(AutomatonWeight::new_for_json_path(
                 self.term.field(),
-                DfaWrapper(automaton),
+                automaton,
                 json_path_bytes,
             ))
         } else {
-            Ok(AutomatonWeight::new(
-                self.term.field(),
-                DfaWrapper(automaton),
-            ))
+            Ok(AutomatonWeight::new(self.term.field(), automaton))
         }
     }
 }
@@ -343,4 +422,27 @@ mod test {
         }
         Ok(())
     }
+
+    #[test]
+    pub fn test_fuzzy_term_prefix_length() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let word_field = schema_builder.add_text_field("word", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        for word in ["sitting", "sitten", "kitchen", "mitten"] {
+            index_writer.add_document(doc!(word_field => word))?;
+        }
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let term_kitten = Term::from_field_text(word_field, "kitten");
+        let mut fuzzy_query = FuzzyTermQuery::new(term_kitten, 2, true);
+        // "sitting" is 3 edits away.
+        assert_eq!(searcher.search(&fuzzy_query, &Count)?, 3);
+        fuzzy_query.set_prefix_length(1);
+        // Only "kitchen" starts with a 'k'.
+        assert_eq!(searcher.search(&fuzzy_query, &Count)?, 1);
+        fuzzy_query.set_prefix_length(4);
+        assert_eq!(searcher.search(&fuzzy_query, &Count)?, 0);
+        Ok(())
+    }
 }
@@ -0,0 +1,293 @@
+use common::BitSet;
+
+use crate::index::SegmentReader;
+use crate::query::explanation::does_not_match;
+use crate::query::{BitSetDocSet, ConstScorer, EnableScoring, Explanation, Query, Scorer, Weight};
+use crate::schema::{is_valid_lat, is_valid_lon, Field, FieldType, GeoPoint, Schema};
+use crate::{DocId, DocSet, Score, TantivyError};
+
+/// Query matching the documents with a [`GeoPoint`] within a latitude/longitude rectangle.
+///
+/// The field must be a geo field, created with
+/// [`SchemaBuilder::add_geo_field`](crate::schema::SchemaBuilder::add_geo_field).
+/// The bounds are inclusive, up to the quantization of the geo points.
+///
+/// If `min_lon` is greater than `max_lon`, the rectangle crosses the antimeridian: it spans the
+/// longitudes from `min_lon` to 180°, and from -180° to `max_lon`.
+///
+/// All of the matched documents get the score 1.0.
+///
+/// The points are not indexed: the query scans the fast field column of every segment, so its
+/// cost is linear in the number of documents, whatever the size of the rectangle.
+///
+/// ```rust
+/// use tantivy::collector::Count;
+/// use tantivy::query::GeoBoundingBoxQuery;
+/// use tantivy::schema::{GeoPoint, Schema, FAST};
+/// use tantivy::{doc, Index, IndexWriter};
+///
+/// # fn test() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let location = schema_builder.add_geo_field("location", FAST);
+/// let index = Index::create_in_ram(schema_builder.build());
+/// let mut index_writer: IndexWriter = index.writer_with_num_threads(1, 20_000_000)?;
+/// index_writer.add_document(doc!(location => GeoPoint::new(48.8566, 2.3522)))?;
+/// index_writer.add_document(doc!(location => GeoPoint::new(35.6762, 139.6503)))?;
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// let query = GeoBoundingBoxQuery::new(location, 40.0, 55.0, -5.0, 10.0);
+/// assert_eq!(searcher.search(&query, &Count)?, 1);
+/// # Ok(())
+/// # }
+/// # assert!(test().is_ok());
+/// ```
+#[derive(Clone, Debug)]
+pub struct GeoBoundingBoxQuery {
+    field: Field,
+    bounding_box: BoundingBox,
+}
+
+impl GeoBoundingBoxQuery {
+    /// Creates a new `GeoBoundingBoxQuery`, with bounds in degrees.
+    ///
+    /// This constructor never fails, but executing the search with this query will return an
+    /// error if the bounds are not valid latitudes and longitudes, or if `min_lat` is greater
+    /// than `max_lat`.
+    pub fn new(
+        field: Field,
+        min_lat: f64,
+        max_lat: f64,
+        min_lon: f64,
+        max_lon: f64,
+    ) -> GeoBoundingBoxQuery {
+        GeoBoundingBoxQuery {
+            field,
+            bounding_box: BoundingBox {
+                min_lat,
+                max_lat,
+                min_lon,
+                max_lon,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct BoundingBox {
+    pub min_lat: f64,
+    pub max_lat: f64,
+    pub min_lon: f64,
+    pub max_lon: f64,
+}
+
+impl BoundingBox {
+    pub fn contains(&self, geo_point: GeoPoint) -> bool {
+        if geo_point.lat() < self.min_lat || geo_point.lat() > self.max_lat {
+            return false;
+        }
+        if self.min_lon <= self.max_lon {
+            self.min_lon <= geo_point.lon() && geo_point.lon() <= self.max_lon
+        } else {
+            // The bounding box crosses the antimeridian.
+            self.min_lon <= geo_point.lon() || geo_point.lon() <= self.max_lon
+        }
+    }
+}
+
+impl Query for GeoBoundingBoxQuery {
+    fn weight(&self, enable_scoring: EnableScoring<'_>) -> crate::Result<Box<dyn Weight>> {
+        let BoundingBox {
+            min_lat,
+            max_lat,
+            min_lon,
+            max_lon,
+        } = self.bounding_box;
+        if !is_valid_lat(min_lat) || !is_valid_lat(max_lat) || min_lat > max_lat {
+            return Err(TantivyError::InvalidArgument(format!(
+                "Invalid latitude range [{min_lat}, {max_lat}]"
+            )));
+        }
+        if !is_valid_lon(min_lon) || !is_valid_lon(max_lon) {
+            return Err(TantivyError::InvalidArgument(format!(
+                "Invalid longitude range [{min_lon}, {max_lon}]"
+            )));
+        }
+        Ok(Box::new(GeoBoundingBoxWeight {
+            field_name: geo_field_name(enable_scoring.schema(), self.field)?,
+            bounding_box: self.bounding_box,
+        }))
+    }
+}
+
+/// Returns the name of the field, or an error if it is not a geo field.
+pub(crate) fn geo_field_name(schema: &Schema, field: Field) -> crate::Result<String> {
+    let field_entry = schema.get_field_entry(field);
+    let is_geo = match field_entry.field_type() {
+        FieldType::U64(options) => options.is_geo(),
+        _ => false,
+    };
+    if !is_geo {
+        return Err(TantivyError::SchemaError(format!(
+            "Field {} is not a geo field.",
+            field_entry.name()
+        )));
+    }
+    Ok(field_entry.name().to_string())
+}
+
+/// Returns a scorer matching the documents with at least one geo point accepted by the
+/// predicate.
+pub(crate) fn geo_point_scorer(
+    reader: &SegmentReader,
+    field_name: &str,
+    boost: Score,
+    predicate: impl Fn(GeoPoint) -> bool,
+) -> crate::Result<Box<dyn Scorer>> {
+    let column = reader.fast_fields().u64(field_name)?;
+    let max_doc = reader.max_doc();
+    let mut doc_bitset = BitSet::with_max_value(max_doc);
+    for doc in 0..max_doc {
+        if column
+            .values_for_doc(doc)
+            .any(|val| predicate(GeoPoint::from_u64(val)))
+        {
+            doc_bitset.insert(doc);
+        }
+    }
+    let doc_bitset = BitSetDocSet::from(doc_bitset);
+    Ok(Box::new(ConstScorer::new(doc_bitset, boost)))
+}
+
+/// Weight associated with the `GeoBoundingBoxQuery` query.
+struct GeoBoundingBoxWeight {
+    field_name: String,
+    bounding_box: BoundingBox,
+}
+
+impl Weight for GeoBoundingBoxWeight {
+    fn scorer(&self, reader: &SegmentReader, boost: Score) -> crate::Result<Box<dyn Scorer>> {
+        geo_point_scorer(reader, &self.field_name, boost, |geo_point| {
+            self.bounding_box.contains(geo_point)
+        })
+    }
+
+    fn explain(&self, reader: &SegmentReader, doc: DocId) -> crate::Result<Explanation> {
+        let mut scorer = self.scorer(reader, 1.0)?;
+        if scorer.seek(doc) != doc {
+            return Err(does_not_match(doc));
+        }
+        Ok(Explanation::new("GeoBoundingBoxQuery", 1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GeoBoundingBoxQuery;
+    use crate::collector::DocSetCollector;
+    use crate::schema::{GeoPoint, Schema, Value, FAST, STORED, TEXT};
+    use crate::{Index, IndexWriter, Searcher, TantivyDocument};
+
+    const CITIES: [(&str, f64, f64); 8] = [
+        ("paris", 48.8566, 2.3522),
+        ("london", 51.5074, -0.1278),
+        ("new york", 40.7128, -74.0060),
+        ("tokyo", 35.6762, 139.6503),
+        ("auckland", -36.8485, 174.7633),
+        ("suva", -18.1416, 178.4419),
+        ("apia", -13.8507, -171.7514),
+        ("honolulu", 21.3069, -157.8583),
+    ];
+
+    fn city_searcher() -> crate::Result<Searcher> {
+        let mut schema_builder = Schema::builder();
+        let name = schema_builder.add_text_field("name", TEXT | STORED);
+        let location = schema_builder.add_geo_field("location", STORED);
+        schema_builder.add_u64_field("count", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        for (city, lat, lon) in CITIES {
+            index_writer.add_document(doc!(name => city, location => GeoPoint::new(lat, lon)))?;
+        }
+        // A document without location.
+        index_writer.add_document(doc!(name => "nowhere"))?;
+        index_writer.commit()?;
+        Ok(index.reader()?.searcher())
+    }
+
+    fn matching_cities(searcher: &Searcher, query: &GeoBoundingBoxQuery) -> Vec<String> {
+        let name = searcher.schema().get_field("name").unwrap();
+        let mut cities: Vec<String> = searcher
+            .search(query, &DocSetCollector)
+            .unwrap()
+            .into_iter()
+            .map(|doc_address| {
+                let doc: TantivyDocument = searcher.doc(doc_address).unwrap();
+                doc.get_first(name).unwrap().as_str().unwrap().to_string()
+            })
+            .collect();
+        cities.sort();
+        cities
+    }
+
+    #[test]
+    fn test_geo_bounding_box_query() -> crate::Result<()> {
+        let searcher = city_searcher()?;
+        let location = searcher.schema().get_field("location").unwrap();
+        let europe = GeoBoundingBoxQuery::new(location, 45.0, 55.0, -5.0, 5.0);
+        assert_eq!(matching_cities(&searcher, &europe), vec!["london", "paris"]);
+        let northern = GeoBoundingBoxQuery::new(location, 0.0, 90.0, -180.0, 180.0);
+        assert_eq!(
+            matching_cities(&searcher, &northern),
+            vec!["honolulu", "london", "new york", "paris", "tokyo"]
+        );
+        let empty = GeoBoundingBoxQuery::new(location, -10.0, 10.0, -10.0, 10.0);
+        assert!(matching_cities(&searcher, &empty).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_geo_bounding_box_query_across_antimeridian() -> crate::Result<()> {
+        let searcher = city_searcher()?;
+        let location = searcher.schema().get_field("location").unwrap();
+        let pacific = GeoBoundingBoxQuery::new(location, -40.0, 0.0, 170.0, -170.0);
+        assert_eq!(
+            matching_cities(&searcher, &pacific),
+            vec!["apia", "auckland", "suva"]
+        );
+        // The same longitudes, without crossing the antimeridian.
+        let not_pacific = GeoBoundingBoxQuery::new(location, -40.0, 0.0, -170.0, 170.0);
+        assert!(matching_cities(&searcher, &not_pacific).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_geo_bounding_box_query_invalid() -> crate::Result<()> {
+        let searcher = city_searcher()?;
+        let location = searcher.schema().get_field("location").unwrap();
+        let name = searcher.schema().get_field("name").unwrap();
+        // A plain u64 fast field is not a geo field.
+        let count = searcher.schema().get_field("count").unwrap();
+        for query in [
+            GeoBoundingBoxQuery::new(location, -100.0, 0.0, 0.0, 10.0),
+            GeoBoundingBoxQuery::new(location, 10.0, 0.0, 0.0, 10.0),
+            GeoBoundingBoxQuery::new(location, 0.0, 10.0, 0.0, 190.0),
+            GeoBoundingBoxQuery::new(name, 0.0, 10.0, 0.0, 10.0),
+            GeoBoundingBoxQuery::new(count, 0.0, 10.0, 0.0, 10.0),
+        ] {
+            assert!(searcher.search(&query, &DocSetCollector).is_err());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_geo_field_stored_value() -> crate::Result<()> {
+        let searcher = city_searcher()?;
+        let location = searcher.schema().get_field("location").unwrap();
+        let doc: TantivyDocument = searcher.doc(crate::DocAddress::new(0, 0))?;
+        let geo_point = GeoPoint::from_u64(doc.get_first(location).unwrap().as_u64().unwrap());
+        assert!((geo_point.lat() - 48.8566).abs() < 1e-7);
+        assert!((geo_point.lon() - 2.3522).abs() < 1e-7);
+        Ok(())
+    }
+}
@@ -0,0 +1,327 @@
+use crate::index::SegmentReader;
+use crate::query::explanation::does_not_match;
+use crate::query::geo_bounding_box_query::{geo_field_name, geo_point_scorer, BoundingBox};
+use crate::query::{EnableScoring, Explanation, Query, Scorer, Weight};
+use crate::schema::{Field, GeoPoint, EARTH_RADIUS_METERS};
+use crate::{DocId, DocSet, Score, TantivyError};
+
+/// Query matching the documents with a [`GeoPoint`] within a given distance of a center.
+///
+/// The field must be a geo field, created with
+/// [`SchemaBuilder::add_geo_field`](crate::schema::SchemaBuilder::add_geo_field).
+/// Distances are great-circle distances, as computed by [`GeoPoint::distance`].
+///
+/// All of the matched documents get the score 1.0. Use
+/// [`TopDocs::order_by_geo_distance`](crate::collector::TopDocs::order_by_geo_distance) to
+/// sort them nearest-first.
+///
+/// Like [`GeoBoundingBoxQuery`](crate::query::GeoBoundingBoxQuery), the query scans the fast
+/// field column of every segment.
+///
+/// ```rust
+/// use tantivy::collector::Count;
+/// use tantivy::query::GeoDistanceQuery;
+/// use tantivy::schema::{GeoPoint, Schema, FAST};
+/// use tantivy::{doc, Index, IndexWriter};
+///
+/// # fn test() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let location = schema_builder.add_geo_field("location", FAST);
+/// let index = Index::create_in_ram(schema_builder.build());
+/// let mut index_writer: IndexWriter = index.writer_with_num_threads(1, 20_000_000)?;
+/// index_writer.add_document(doc!(location => GeoPoint::new(48.8566, 2.3522)))?;
+/// index_writer.add_document(doc!(location => GeoPoint::new(51.5074, -0.1278)))?;
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// let paris = GeoPoint::new(48.8566, 2.3522);
+/// let query = GeoDistanceQuery::new(location, paris, 100_000.0);
+/// assert_eq!(searcher.search(&query, &Count)?, 1);
+/// let query = GeoDistanceQuery::new(location, paris, 500_000.0);
+/// assert_eq!(searcher.search(&query, &Count)?, 2);
+/// # Ok(())
+/// # }
+/// # assert!(test().is_ok());
+/// ```
+#[derive(Clone, Debug)]
+pub struct GeoDistanceQuery {
+    field: Field,
+    center: GeoPoint,
+    radius_meters: f64,
+}
+
+impl GeoDistanceQuery {
+    /// Creates a new `GeoDistanceQuery`, matching the points at most `radius_meters` away from
+    /// `center`.
+    ///
+    /// This constructor never fails, but executing the search with this query will return an
+    /// error if the radius is negative or NaN.
+    pub fn new(field: Field, center: GeoPoint, radius_meters: f64) -> GeoDistanceQuery {
+        GeoDistanceQuery {
+            field,
+            center,
+            radius_meters,
+        }
+    }
+}
+
+impl Query for GeoDistanceQuery {
+    fn weight(&self, enable_scoring: EnableScoring<'_>) -> crate::Result<Box<dyn Weight>> {
+        if self.radius_meters.is_nan() || self.radius_meters < 0.0 {
+            return Err(TantivyError::InvalidArgument(format!(
+                "Invalid radius {}",
+                self.radius_meters
+            )));
+        }
+        Ok(Box::new(GeoDistanceWeight {
+            field_name: geo_field_name(enable_scoring.schema(), self.field)?,
+            center: self.center,
+            radius_meters: self.radius_meters,
+            bounding_box: bounding_box(self.center, self.radius_meters),
+        }))
+    }
+}
+
+/// Returns a bounding box containing all of the points within `radius_meters` of `center`.
+///
+/// It is used to discard most of the points without computing their distance.
+fn bounding_box(center: GeoPoint, radius_meters: f64) -> BoundingBox {
+    let angular_radius = radius_meters / EARTH_RADIUS_METERS;
+    let delta_lat = angular_radius.to_degrees();
+    let min_lat = center.lat() - delta_lat;
+    let max_lat = center.lat() + delta_lat;
+    let full_lon_range = BoundingBox {
+        min_lat: min_lat.max(-90.0),
+        max_lat: max_lat.min(90.0),
+        min_lon: -180.0,
+        max_lon: 180.0,
+    };
+    if min_lat <= -90.0 || max_lat >= 90.0 {
+        // The circle contains a pole.
+        return full_lon_range;
+    }
+    let sin_delta_lon = angular_radius.sin() / center.lat().to_radians().cos();
+    if sin_delta_lon >= 1.0 {
+        return full_lon_range;
+    }
+    let delta_lon = sin_delta_lon.asin().to_degrees();
+    let mut min_lon = center.lon() - delta_lon;
+    let mut max_lon = center.lon() + delta_lon;
+    // Past the antimeridian, `min_lon` ends up greater than `max_lon`.
+    if min_lon < -180.0 {
+        min_lon += 360.0;
+    }
+    if max_lon > 180.0 {
+        max_lon -= 360.0;
+    }
+    BoundingBox {
+        min_lat,
+        max_lat,
+        min_lon,
+        max_lon,
+    }
+}
+
+/// Weight associated with the `GeoDistanceQuery` query.
+struct GeoDistanceWeight {
+    field_name: String,
+    center: GeoPoint,
+    radius_meters: f64,
+    bounding_box: BoundingBox,
+}
+
+impl Weight for GeoDistanceWeight {
+    fn scorer(&self, reader: &SegmentReader, boost: Score) -> crate::Result<Box<dyn Scorer>> {
+        geo_point_scorer(reader, &self.field_name, boost, |geo_point| {
+            self.bounding_box.contains(geo_point)
+                && self.center.distance(&geo_point) <= self.radius_meters
+        })
+    }
+
+    fn explain(&self, reader: &SegmentReader, doc: DocId) -> crate::Result<Explanation> {
+        let mut scorer = self.scorer(reader, 1.0)?;
+        if scorer.seek(doc) != doc {
+            return Err(does_not_match(doc));
+        }
+        Ok(Explanation::new("GeoDistanceQuery", 1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bounding_box, GeoDistanceQuery};
+    use crate::collector::{DocSetCollector, TopDocs};
+    use crate::schema::{GeoPoint, Schema, Value, STORED, TEXT};
+    use crate::{Index, IndexWriter, Searcher, TantivyDocument};
+
+    fn geo_searcher(points: &[(&str, f64, f64)]) -> crate::Result<Searcher> {
+        let mut schema_builder = Schema::builder();
+        let name = schema_builder.add_text_field("name", TEXT | STORED);
+        let location = schema_builder.add_geo_field("location", STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        for &(point_name, lat, lon) in points {
+            index_writer
+                .add_document(doc!(name => point_name, location => GeoPoint::new(lat, lon)))?;
+        }
+        // A document without location.
+        index_writer.add_document(doc!(name => "nowhere"))?;
+        index_writer.commit()?;
+        Ok(index.reader()?.searcher())
+    }
+
+    fn name_of(searcher: &Searcher, doc_address: crate::DocAddress) -> String {
+        let name = searcher.schema().get_field("name").unwrap();
+        let doc: TantivyDocument = searcher.doc(doc_address).unwrap();
+        doc.get_first(name).unwrap().as_str().unwrap().to_string()
+    }
+
+    fn matching_names(searcher: &Searcher, center: GeoPoint, radius_meters: f64) -> Vec<String> {
+        let location = searcher.schema().get_field("location").unwrap();
+        let query = GeoDistanceQuery::new(location, center, radius_meters);
+        let mut names: Vec<String> = searcher
+            .search(&query, &DocSetCollector)
+            .unwrap()
+            .into_iter()
+            .map(|doc_address| name_of(searcher, doc_address))
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_geo_distance_query_near_equator() -> crate::Result<()> {
+        // 0.04° of longitude is about 4.45km on the equator.
+        let searcher = geo_searcher(&[
+            ("a", 0.0, 0.04),
+            ("b", 0.03, 0.03),
+            ("c", 0.05, 0.0),
+            ("d", -0.03, -0.035),
+        ])?;
+        let origin = GeoPoint::new(0.0, 0.0);
+        assert_eq!(matching_names(&searcher, origin, 5_000.0), vec!["a", "b"]);
+        assert_eq!(
+            matching_names(&searcher, origin, 4_000.0),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            matching_names(&searcher, origin, 6_000.0),
+            vec!["a", "b", "c", "d"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_geo_distance_query_near_poles() -> crate::Result<()> {
+        let searcher = geo_searcher(&[
+            ("a", 89.0, 0.0),
+            ("b", 89.0, 180.0),
+            ("c", 88.5, -90.0),
+            ("d", 87.0, 45.0),
+            ("e", -89.5, 10.0),
+        ])?;
+        // One degree of latitude is about 111.2km.
+        let north_pole = GeoPoint::new(90.0, 0.0);
+        assert_eq!(
+            matching_names(&searcher, north_pole, 200_000.0),
+            vec!["a", "b", "c"]
+        );
+        // The circle contains the pole without being centered on it.
+        assert_eq!(
+            matching_names(&searcher, GeoPoint::new(89.0, 0.0), 250_000.0),
+            vec!["a", "b", "c"]
+        );
+        assert_eq!(
+            matching_names(&searcher, GeoPoint::new(-90.0, 0.0), 100_000.0),
+            vec!["e"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_geo_distance_query_across_antimeridian() -> crate::Result<()> {
+        let searcher = geo_searcher(&[("a", 0.0, 179.8), ("b", 0.0, -179.8), ("c", 0.0, 179.0)])?;
+        let center = GeoPoint::new(0.0, 180.0);
+        assert_eq!(matching_names(&searcher, center, 50_000.0), vec!["a", "b"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_geo_distance_bounding_box() {
+        let equator_box = bounding_box(GeoPoint::new(0.0, 0.0), 111_195.08);
+        assert!((equator_box.max_lat - 1.0).abs() < 1e-6);
+        assert!((equator_box.max_lon - 1.0).abs() < 1e-6);
+        assert!((equator_box.min_lon + 1.0).abs() < 1e-6);
+        let polar_box = bounding_box(GeoPoint::new(89.5, 0.0), 111_195.08);
+        assert_eq!(polar_box.max_lat, 90.0);
+        assert_eq!((polar_box.min_lon, polar_box.max_lon), (-180.0, 180.0));
+        let pacific_box = bounding_box(GeoPoint::new(0.0, 179.5), 111_195.08);
+        assert!(pacific_box.min_lon > pacific_box.max_lon);
+        assert!(pacific_box.contains(GeoPoint::new(0.0, -179.9)));
+        assert!(!pacific_box.contains(GeoPoint::new(0.0, 0.0)));
+    }
+
+    #[test]
+    fn test_geo_distance_query_invalid() -> crate::Result<()> {
+        let searcher = geo_searcher(&[("a", 0.0, 0.0)])?;
+        let location = searcher.schema().get_field("location").unwrap();
+        let name = searcher.schema().get_field("name").unwrap();
+        let origin = GeoPoint::new(0.0, 0.0);
+        for query in [
+            GeoDistanceQuery::new(location, origin, -1.0),
+            GeoDistanceQuery::new(location, origin, f64::NAN),
+            GeoDistanceQuery::new(name, origin, 1.0),
+        ] {
+            assert!(searcher.search(&query, &DocSetCollector).is_err());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_order_by_geo_distance() -> crate::Result<()> {
+        let searcher = geo_searcher(&[
+            ("paris", 48.8566, 2.3522),
+            ("london", 51.5074, -0.1278),
+            ("new york", 40.7128, -74.0060),
+            ("brussels", 50.8503, 4.3517),
+        ])?;
+        let location = searcher.schema().get_field("location").unwrap();
+        let paris = GeoPoint::new(48.8566, 2.3522);
+        let query = GeoDistanceQuery::new(location, paris, 1_000_000.0);
+        let top_docs = searcher.search(
+            &query,
+            &TopDocs::with_limit(10).order_by_geo_distance("location", paris),
+        )?;
+        let names: Vec<String> = top_docs
+            .iter()
+            .map(|(_, doc_address)| name_of(&searcher, *doc_address))
+            .collect();
+        assert_eq!(names, vec!["paris", "brussels", "london"]);
+        assert!(top_docs[0].0 < 1.0);
+        // Paris to London is about 344km.
+        assert!((top_docs[2].0 - 343_557.0).abs() < 1_000.0);
+
+        // Documents without location come last.
+        let top_docs = searcher.search(
+            &crate::query::AllQuery,
+            &TopDocs::with_limit(10).order_by_geo_distance("location", paris),
+        )?;
+        let names: Vec<String> = top_docs
+            .iter()
+            .map(|(_, doc_address)| name_of(&searcher, *doc_address))
+            .collect();
+        assert_eq!(
+            names,
+            vec!["paris", "brussels", "london", "new york", "nowhere"]
+        );
+        assert_eq!(top_docs[4].0, f64::INFINITY);
+
+        assert!(searcher
+            .search(
+                &crate::query::AllQuery,
+                &TopDocs::with_limit(10).order_by_geo_distance("name", paris),
+            )
+            .is_err());
+        Ok(())
+    }
+}
@@ -5,12 +5,16 @@ mod bm25;
 mod boolean_query;
 mod boost_query;
 mod const_score_query;
+mod deadline_weight;
+mod disjunction;
 mod disjunction_max_query;
 mod empty_query;
 mod exclude;
 mod exist_query;
 mod explanation;
 mod fuzzy_query;
+mod geo_bounding_box_query;
+mod geo_distance_query;
 mod intersection;
 mod more_like_this;
 mod phrase_prefix_query;
@@ -22,8 +26,10 @@ mod regex_query;
 mod reqopt_scorer;
 mod scorer;
 mod set_query;
+mod similarity;
 mod term_query;
 mod union;
+mod vector_knn_query;
 mod weight;
 
 #[cfg(test)]
@@ -35,20 +41,27 @@ pub use query_grammar::Occur;
 pub use self::all_query::{AllQuery, AllScorer, AllWeight};
 pub use self::automaton_weight::AutomatonWeight;
 pub use self::bitset::BitSetDocSet;
-pub use self::bm25::{Bm25StatisticsProvider, Bm25Weight};
+pub use self::bm25::{Bm25Params, Bm25StatisticsProvider, Bm25Weight};
 pub use self::boolean_query::{BooleanQuery, BooleanWeight};
 pub use self::boost_query::{BoostQuery, BoostWeight};
 pub use self::const_score_query::{ConstScoreQuery, ConstScorer};
+pub(crate) use self::deadline_weight::{
+    check_search_deadline, DeadlineWeight, SearchDeadlineGuard,
+};
+pub use self::disjunction::Disjunction;
 pub use self::disjunction_max_query::DisjunctionMaxQuery;
 pub use self::empty_query::{EmptyQuery, EmptyScorer, EmptyWeight};
 pub use self::exclude::Exclude;
 pub use self::exist_query::ExistsQuery;
 pub use self::explanation::Explanation;
-#[cfg(test)]
 pub(crate) use self::fuzzy_query::DfaWrapper;
 pub use self::fuzzy_query::FuzzyTermQuery;
+pub(crate) use self::geo_bounding_box_query::geo_field_name;
+pub use self::geo_bounding_box_query::GeoBoundingBoxQuery;
+pub use self::geo_distance_query::GeoDistanceQuery;
 pub use self::intersection::{intersect_scorers, Intersection};
 pub use self::more_like_this::{MoreLikeThisQuery, MoreLikeThisQueryBuilder};
+pub(crate) use self::phrase_prefix_query::prefix_end;
 pub use self::phrase_prefix_query::PhrasePrefixQuery;
 pub use self::phrase_query::PhraseQuery;
 pub use self::query::{EnableScoring, Query, QueryClone};
@@ -61,17 +74,20 @@ pub use self::score_combiner::{
 };
 pub use self::scorer::Scorer;
 pub use self::set_query::TermSetQuery;
+pub use self::similarity::{Bm25Similarity, Similarity, TfIdfSimilarity};
 pub use self::term_query::TermQuery;
 pub use self::union::Union;
 #[cfg(test)]
 pub use self::vec_docset::VecDocSet;
+pub use self::vector_knn_query::{VectorKnnQuery, VectorMetric};
 pub use self::weight::Weight;
 
 #[cfg(test)]
 mod tests {
+    use crate::collector::TopDocs;
     use crate::query::QueryParser;
     use crate::schema::{Schema, TEXT};
-    use crate::{Index, Term};
+    use crate::{assert_nearly_equals, DocAddress, Index, IndexWriter, Term};
 
     #[test]
     fn test_query_terms() {
@@ -113,4 +129,40 @@ mod tests {
             assert_eq!(vec![(&term_a, false), (&term_b, false)], terms);
         }
     }
+
+    #[test]
+    fn test_searcher_explain() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title_field = schema_builder.add_text_field("title", TEXT);
+        let body_field = schema_builder.add_text_field("body", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title_field => "rust", body_field => "a b c"))?;
+        index_writer.add_document(doc!(title_field => "rust rust", body_field => "rust"))?;
+        index_writer.add_document(doc!(title_field => "lang", body_field => "the rust lang"))?;
+        index_writer.add_document(doc!(title_field => "other", body_field => "other"))?;
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let query_parser = QueryParser::for_index(&index, vec![title_field, body_field]);
+        for query_str in [
+            "title:rust",
+            "title:rust^2 body:rust",
+            "+body:rust -title:lang",
+        ] {
+            let query = query_parser.parse_query(query_str)?;
+            let top_docs = searcher.search(&query, &TopDocs::with_limit(10))?;
+            assert!(!top_docs.is_empty());
+            for (score, doc_address) in top_docs {
+                let explanation = searcher.explain(&query, doc_address)?;
+                assert_nearly_equals!(explanation.value(), score);
+            }
+        }
+        let query = query_parser.parse_query("title:rust")?;
+        let explanation = searcher.explain(&query, DocAddress::new(0, 0))?;
+        let explanation_json = explanation.to_pretty_json();
+        assert!(explanation_json.contains("idf"));
+        assert!(explanation_json.contains("freq"));
+        assert!(searcher.explain(&query, DocAddress::new(0, 3)).is_err());
+        Ok(())
+    }
 }
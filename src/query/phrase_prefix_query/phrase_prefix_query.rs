@@ -111,9 +111,12 @@ impl PhrasePrefixQuery {
         }
         let terms = self.phrase_terms();
         let bm25_weight_opt = match enable_scoring {
-            EnableScoring::Enabled { searcher, .. } => {
-                Some(Bm25Weight::for_terms(searcher, &terms)?)
-            }
+            EnableScoring::Enabled { searcher, .. } => Some(Bm25Weight::for_terms_with_similarity(
+                searcher,
+                &terms,
+                field_entry.field_type().bm25_params(),
+                searcher.similarity(),
+            )?),
             EnableScoring::Disabled { .. } => None,
         };
         let weight = PhrasePrefixWeight::new(
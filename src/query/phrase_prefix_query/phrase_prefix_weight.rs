@@ -157,11 +157,12 @@ impl Weight for PhrasePrefixWeight {
 
 #[cfg(test)]
 mod tests {
+    use crate::collector::TopDocs;
     use crate::docset::TERMINATED;
     use crate::index::Index;
     use crate::query::{EnableScoring, PhrasePrefixQuery, Query};
     use crate::schema::{Schema, TEXT};
-    use crate::{DocSet, IndexWriter, Term};
+    use crate::{DocId, DocSet, IndexWriter, Term};
 
     pub fn create_index(texts: &[&'static str]) -> crate::Result<Index> {
         let mut schema_builder = Schema::builder();
@@ -259,6 +260,37 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    pub fn test_phrase_prefix_positions() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text_field => "new york"))?;
+        index_writer.add_document(doc!(text_field => "new jersey york"))?;
+        index_writer.add_document(doc!(text_field => "york is new"))?;
+        index_writer.add_document(doc!(text_field => "the new yorker"))?;
+        // The values of a multivalued field are separated by a position gap.
+        index_writer.add_document(doc!(text_field => "new", text_field => "york"))?;
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let mut phrase_query = PhrasePrefixQuery::new(vec![
+            Term::from_field_text(text_field, "new"),
+            Term::from_field_text(text_field, "yo"),
+        ]);
+        let top_docs = searcher.search(&phrase_query, &TopDocs::with_limit(10))?;
+        let mut docs: Vec<DocId> = top_docs.iter().map(|(_, doc)| doc.doc_id).collect();
+        docs.sort_unstable();
+        assert_eq!(docs, [0, 3]);
+
+        // "york" comes before "yorker" in the term dictionary.
+        phrase_query.set_max_expansions(1);
+        let top_docs = searcher.search(&phrase_query, &TopDocs::with_limit(10))?;
+        assert_eq!(top_docs.len(), 1);
+        assert_eq!(top_docs[0].1.doc_id, 0);
+        Ok(())
+    }
+
     #[test]
     pub fn test_phrase_no_match() -> crate::Result<()> {
         let index = create_index(&["aa dd", "aa aa bb c dd aa bb cc aa dc", " aa bb cd"])?;
@@ -119,9 +119,14 @@ impl PhraseQuery {
         let terms = self.phrase_terms();
         let bm25_weight_opt = match enable_scoring {
             EnableScoring::Enabled {
+                searcher,
                 statistics_provider,
-                ..
-            } => Some(Bm25Weight::for_terms(statistics_provider, &terms)?),
+            } => Some(Bm25Weight::for_terms_with_similarity(
+                statistics_provider,
+                &terms,
+                field_entry.field_type().bm25_params(),
+                searcher.similarity(),
+            )?),
             EnableScoring::Disabled { .. } => None,
         };
         let mut weight = PhraseWeight::new(self.phrase_terms.clone(), bm25_weight_opt);
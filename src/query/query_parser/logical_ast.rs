@@ -8,6 +8,10 @@ use crate::Score;
 #[derive(Clone)]
 pub enum LogicalLiteral {
     Term(Term),
+    FuzzyTerm {
+        term: Term,
+        distance: u8,
+    },
     Phrase {
         terms: Vec<(usize, Term)>,
         slop: u32,
@@ -24,6 +28,9 @@ pub enum LogicalLiteral {
         value_type: Type,
         elements: Vec<Term>,
     },
+    Exists {
+        field_name: String,
+    },
     All,
 }
 
@@ -83,6 +90,9 @@ impl fmt::Debug for LogicalLiteral {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         match *self {
             LogicalLiteral::Term(ref term) => write!(formatter, "{term:?}"),
+            LogicalLiteral::FuzzyTerm { ref term, distance } => {
+                write!(formatter, "{term:?}~{distance}")
+            }
             LogicalLiteral::Phrase {
                 ref terms,
                 slop,
@@ -122,6 +132,7 @@ impl fmt::Debug for LogicalLiteral {
                 }
                 write!(formatter, "]")
             }
+            LogicalLiteral::Exists { ref field_name } => write!(formatter, "{field_name}:*"),
             LogicalLiteral::All => write!(formatter, "*"),
         }
     }
@@ -1,12 +1,11 @@
-use std::net::{AddrParseError, IpAddr};
+use std::net::{AddrParseError, IpAddr, Ipv6Addr};
 use std::num::{ParseFloatError, ParseIntError};
-use std::ops::Bound;
+use std::ops::{Bound, RangeInclusive};
 use std::str::{FromStr, ParseBoolError};
 
 use base64::engine::general_purpose::STANDARD as BASE64;
 use base64::Engine;
-use itertools::Itertools;
-use query_grammar::{UserInputAst, UserInputBound, UserInputLeaf, UserInputLiteral};
+use query_grammar::{Delimiter, UserInputAst, UserInputBound, UserInputLeaf, UserInputLiteral};
 use rustc_hash::FxHashMap;
 
 use super::logical_ast::*;
@@ -14,8 +13,8 @@ use crate::index::Index;
 use crate::json_utils::convert_to_fast_value_and_append_to_json_term;
 use crate::query::range_query::{is_type_valid_for_fastfield_range_query, RangeQuery};
 use crate::query::{
-    AllQuery, BooleanQuery, BoostQuery, EmptyQuery, FuzzyTermQuery, Occur, PhrasePrefixQuery,
-    PhraseQuery, Query, TermQuery, TermSetQuery,
+    AllQuery, BooleanQuery, BoostQuery, EmptyQuery, ExistsQuery, FuzzyTermQuery, Occur,
+    PhrasePrefixQuery, PhraseQuery, Query, TermQuery, TermSetQuery,
 };
 use crate::schema::{
     Facet, FacetParseError, Field, FieldType, IndexRecordOption, IntoIpv6Addr, JsonObjectOptions,
@@ -102,6 +101,12 @@ pub enum QueryParserError {
     /// The format for the ip field is invalid.
     #[error("The ip field is malformed: {0}")]
     IpFormatError(#[from] AddrParseError),
+    /// The prefix length of an ip CIDR block is invalid.
+    #[error("The ip CIDR block is malformed: {0:?}")]
+    IpCidrFormatError(String),
+    /// The fuzzy distance of a term, e.g. `search~3`, is above the maximum distance of 2.
+    #[error("The fuzzy distance {0} is too large, it must be at most 2")]
+    FuzzyDistanceTooLarge(u32),
 }
 
 /// Recursively remove empty clause from the AST
@@ -174,12 +179,21 @@ fn trim_ast(logical_ast: LogicalAst) -> Option<LogicalAst> {
 ///
 /// * set terms: Using the `IN` operator, a field can be matched against a set of literals, e.g.
 ///   `title: IN [a b cd]` will match documents where `title` is either `a`, `b` or `cd`, but do so
-///   more efficiently than the alternative query `title:a OR title:b OR title:c` does.
+///   more efficiently than the alternative query `title:a OR title:b OR title:c` does. The quoted
+///   members producing several tokens, e.g. `city: IN [paris "new york"]`, are searched as phrases.
 ///
 /// * date values: The query parser supports rfc3339 formatted dates. For example
 ///   `"2002-10-02T15:00:00.05Z"` or `some_date_field:[2002-10-02T15:00:00Z TO
 ///   2002-10-02T18:00:00Z}`
 ///
+/// * ip values: IPv4 addresses are mapped to IPv6 addresses. An ip field can also be matched
+///   against a CIDR block, e.g. `ip:10.0.0.0/24` is equivalent to `ip:[10.0.0.0 TO 10.0.0.255]`.
+///
+/// * exists terms: `title:*` will match the documents with at least one value in `title`, i.e. at
+///   least one indexed term, or a value in the fast field if `title` is fast. The documents missing
+///   a field can be matched by excluding it from the all docs query, e.g. `* -title:*` or `* AND
+///   NOT title:*`.
+///
 /// * all docs query: A plain `*` will match all documents in the index.
 ///
 /// Parts of the queries can be boosted by appending `^boostfactor`.
@@ -196,6 +210,11 @@ fn trim_ast(logical_ast: LogicalAst) -> Option<LogicalAst> {
 /// Phrase terms support the `~` slop operator which allows to set the phrase's matching
 /// distance in words. `"big wolf"~1` will return documents containing the phrase `"big bad wolf"`.
 ///
+/// On a word, the `~` operator produces a [`FuzzyTermQuery`] with the given Levenshtein
+/// distance, at most 2: `title:search~1` matches the documents containing `search` or `seach` in
+/// their title. It only applies to the text terms, other terms are matched exactly. A word
+/// producing several terms, e.g. `foo-bar~2`, is a phrase, and its `~` remains a slop.
+///
 /// Phrase terms also support the `*` prefix operator which switches the phrase's matching
 /// to consider all documents which contain the last term as a prefix, e.g. `"big bad wo"*` will
 /// match `"big bad wolf"`.
@@ -459,8 +478,12 @@ impl QueryParser {
                 Ok(Term::from_field_bool(field, val))
             }
             FieldType::Date(_) => {
+                // The range query truncates the date at the precision of the field.
                 let dt = OffsetDateTime::parse(phrase, &Rfc3339)?;
-                Ok(Term::from_field_date(field, DateTime::from_utc(dt)))
+                Ok(Term::from_field_date_untruncated(
+                    field,
+                    DateTime::from_utc(dt),
+                ))
             }
             FieldType::Str(ref str_options) => {
                 let option = str_options.get_indexing_options().ok_or_else(|| {
@@ -507,6 +530,9 @@ impl QueryParser {
                 let ip_v6 = IpAddr::from_str(phrase)?.into_ipv6_addr();
                 Ok(Term::from_field_ip_addr(field, ip_v6))
             }
+            FieldType::DenseVector(_) => Err(QueryParserError::FieldNotIndexed(
+                field_entry.name().to_string(),
+            )),
         }
     }
 
@@ -603,10 +629,22 @@ impl QueryParser {
                 Ok(vec![LogicalLiteral::Term(bytes_term)])
             }
             FieldType::IpAddr(_) => {
+                if let Some((ip_str, prefix_len_str)) = phrase.split_once('/') {
+                    let ip_range = parse_ip_cidr(ip_str, prefix_len_str)?;
+                    return Ok(vec![LogicalLiteral::Range {
+                        field: field_name.to_string(),
+                        value_type: Type::IpAddr,
+                        lower: Bound::Included(Term::from_field_ip_addr(field, *ip_range.start())),
+                        upper: Bound::Included(Term::from_field_ip_addr(field, *ip_range.end())),
+                    }]);
+                }
                 let ip_v6 = IpAddr::from_str(phrase)?.into_ipv6_addr();
                 let term = Term::from_field_ip_addr(field, ip_v6);
                 Ok(vec![LogicalLiteral::Term(term)])
             }
+            FieldType::DenseVector(_) => {
+                Err(QueryParserError::FieldNotIndexed(field_name.to_string()))
+            }
         }
     }
 
@@ -739,6 +777,9 @@ impl QueryParser {
     ) -> (Option<LogicalAst>, Vec<QueryParserError>) {
         match leaf {
             UserInputLeaf::Literal(literal) => {
+                // The `~` of a word producing a single text term is a fuzzy distance. For
+                // quoted phrases and words producing several terms, it remains a phrase slop.
+                let is_fuzzy_word = literal.delimiter == Delimiter::None && literal.slop > 0;
                 let term_phrases: Vec<(Field, &str, &str)> =
                     try_tuple!(self.compute_path_triplets_for_literal(&literal));
                 let mut asts: Vec<LogicalAst> = Vec::new();
@@ -757,7 +798,16 @@ impl QueryParser {
                             continue;
                         }
                     };
-                    for ast in unboosted_asts {
+                    for mut ast in unboosted_asts {
+                        if is_fuzzy_word {
+                            ast = match make_fuzzy(ast, literal.slop) {
+                                Ok(ast) => ast,
+                                Err(e) => {
+                                    errors.push(e);
+                                    continue;
+                                }
+                            };
+                        }
                         // Apply some field specific boost defined at the query parser level.
                         let boost = self.field_boost(field);
                         asts.push(LogicalAst::Leaf(Box::new(ast)).boost(boost));
@@ -834,23 +884,70 @@ impl QueryParser {
                     .ok_or_else(|| QueryParserError::FieldDoesNotExist(full_path.clone())));
                 let field_entry = self.schema.get_field_entry(field);
                 let value_type = field_entry.field_type().value_type();
-                let (elements, errors) = elements
-                    .into_iter()
-                    .map(|element| self.compute_boundary_term(field, json_path, &element))
-                    .partition_result();
-                let logical_ast = LogicalAst::Leaf(Box::new(LogicalLiteral::Set {
-                    elements,
-                    field,
-                    value_type,
+                let mut terms = Vec::new();
+                // The members producing several tokens, e.g. `"new york"`, are searched as
+                // phrases alongside the set of terms.
+                let mut phrases = Vec::new();
+                let mut errors = Vec::new();
+                for element in elements {
+                    if value_type == Type::Str {
+                        match self
+                            .compute_logical_ast_for_leaf(field, json_path, &element, 0, false)
+                        {
+                            Ok(literals) => {
+                                for literal in literals {
+                                    match literal {
+                                        LogicalLiteral::Term(term) => terms.push(term),
+                                        phrase => phrases.push(phrase),
+                                    }
+                                }
+                            }
+                            Err(err) => errors.push(err),
+                        }
+                    } else {
+                        match self.compute_boundary_term(field, json_path, &element) {
+                            Ok(term) => terms.push(truncate_indexed_date(term)),
+                            Err(err) => errors.push(err),
+                        }
+                    }
+                }
+                if phrases.is_empty() {
+                    let logical_ast = LogicalAst::Leaf(Box::new(LogicalLiteral::Set {
+                        elements: terms,
+                        field,
+                        value_type,
+                    }));
+                    return (Some(logical_ast), errors);
+                }
+                let mut clauses = Vec::new();
+                if !terms.is_empty() {
+                    let set_literal = LogicalLiteral::Set {
+                        elements: terms,
+                        field,
+                        value_type,
+                    };
+                    clauses.push((Occur::Should, set_literal.into()));
+                }
+                clauses.extend(
+                    phrases
+                        .into_iter()
+                        .map(|phrase| (Occur::Should, LogicalAst::from(phrase))),
+                );
+                (Some(LogicalAst::Clause(clauses)), errors)
+            }
+            UserInputLeaf::Exists { field: full_path } => {
+                let (field, _json_path) = try_tuple!(self
+                    .split_full_path(&full_path)
+                    .ok_or_else(|| QueryParserError::FieldDoesNotExist(full_path.clone())));
+                let field_type = self.schema.get_field_entry(field).field_type();
+                if !field_type.is_indexed() && !field_type.is_fast() {
+                    return (None, vec![QueryParserError::FieldNotIndexed(full_path)]);
+                }
+                let logical_ast = LogicalAst::Leaf(Box::new(LogicalLiteral::Exists {
+                    field_name: full_path,
                 }));
-                (Some(logical_ast), errors)
+                (Some(logical_ast), Vec::new())
             }
-            UserInputLeaf::Exists { .. } => (
-                None,
-                vec![QueryParserError::UnsupportedQuery(
-                    "Range query need to target a specific field.".to_string(),
-                )],
-            ),
         }
     }
 }
@@ -879,6 +976,22 @@ fn convert_literal_to_query(
                 Box::new(TermQuery::new(term, IndexRecordOption::WithFreqs))
             }
         }
+        LogicalLiteral::FuzzyTerm { term, distance } => {
+            // The other settings of a fuzzy field still apply.
+            let (prefix, transpose_cost_one) = fuzzy
+                .get(&term.field())
+                .map(|fuzzy| (fuzzy.prefix, fuzzy.transpose_cost_one))
+                .unwrap_or((false, true));
+            if prefix {
+                Box::new(FuzzyTermQuery::new_prefix(
+                    term,
+                    distance,
+                    transpose_cost_one,
+                ))
+            } else {
+                Box::new(FuzzyTermQuery::new(term, distance, transpose_cost_one))
+            }
+        }
         LogicalLiteral::Phrase {
             terms,
             slop,
@@ -899,10 +1012,71 @@ fn convert_literal_to_query(
             field, value_type, &lower, &upper,
         )),
         LogicalLiteral::Set { elements, .. } => Box::new(TermSetQuery::new(elements)),
+        LogicalLiteral::Exists { field_name } => {
+            Box::new(ExistsQuery::new_exists_query(field_name))
+        }
         LogicalLiteral::All => Box::new(AllQuery),
     }
 }
 
+/// Returns the range of the addresses of an ip CIDR block, e.g. `10.0.0.0/24`, given its
+/// address and its prefix length.
+///
+/// IPv4 addresses are mapped to IPv6 addresses, like in the ip fields.
+fn parse_ip_cidr(
+    ip_str: &str,
+    prefix_len_str: &str,
+) -> Result<RangeInclusive<Ipv6Addr>, QueryParserError> {
+    let ip_addr = IpAddr::from_str(ip_str)?;
+    let max_prefix_len: u32 = if ip_addr.is_ipv4() { 32 } else { 128 };
+    let prefix_len: u32 = prefix_len_str
+        .parse()
+        .ok()
+        .filter(|prefix_len| *prefix_len <= max_prefix_len)
+        .ok_or_else(|| QueryParserError::IpCidrFormatError(format!("{ip_str}/{prefix_len_str}")))?;
+    let host_mask = u128::MAX
+        .checked_shr(prefix_len + 128 - max_prefix_len)
+        .unwrap_or(0);
+    let ip_u128 = u128::from(ip_addr.into_ipv6_addr());
+    Ok(Ipv6Addr::from(ip_u128 & !host_mask)..=Ipv6Addr::from(ip_u128 | host_mask))
+}
+
+/// Turns a text term literal into a fuzzy term literal. The other literals, including phrases,
+/// are left unchanged.
+fn make_fuzzy(literal: LogicalLiteral, distance: u32) -> Result<LogicalLiteral, QueryParserError> {
+    let literal = match literal {
+        LogicalLiteral::Term(term) => {
+            let term_value = term.value();
+            let is_text = match term_value.typ() {
+                Type::Str => true,
+                Type::Json => term_value.json_path_type() == Some(Type::Str),
+                _ => false,
+            };
+            if !is_text {
+                LogicalLiteral::Term(term)
+            } else if distance > 2 {
+                return Err(QueryParserError::FuzzyDistanceTooLarge(distance));
+            } else {
+                LogicalLiteral::FuzzyTerm {
+                    term,
+                    distance: distance as u8,
+                }
+            }
+        }
+        literal => literal,
+    };
+    Ok(literal)
+}
+
+/// Truncates the value of a date boundary term at the precision of the inverted index, for the
+/// term set queries.
+fn truncate_indexed_date(term: Term) -> Term {
+    match term.value().as_date() {
+        Some(date) => Term::from_field_date(term.field(), date),
+        None => term,
+    }
+}
+
 fn generate_literals_for_str(
     field_name: &str,
     field: Field,
@@ -1032,6 +1206,7 @@ mod test {
 
     use super::super::logical_ast::*;
     use super::{QueryParser, QueryParserError};
+    use crate::collector::{Count, TopDocs};
     use crate::query::Query;
     use crate::schema::{
         FacetOptions, Field, IndexRecordOption, Schema, Term, TextFieldIndexing, TextOptions, FAST,
@@ -1040,7 +1215,7 @@ mod test {
     use crate::tokenizer::{
         LowerCaser, SimpleTokenizer, StopWordFilter, TextAnalyzer, TokenizerManager,
     };
-    use crate::Index;
+    use crate::{assert_nearly_equals, DocAddress, Index};
 
     fn make_schema() -> Schema {
         let mut schema_builder = Schema::builder();
@@ -1093,6 +1268,55 @@ mod test {
         make_query_parser_with_default_fields(&["title", "text"])
     }
 
+    #[test]
+    fn test_parse_ip_cidr() {
+        use std::net::Ipv6Addr;
+        use std::str::FromStr;
+
+        use super::parse_ip_cidr;
+
+        let ip = |ip_str: &str| Ipv6Addr::from_str(ip_str).unwrap();
+        assert_eq!(
+            parse_ip_cidr("10.0.0.0", "24").unwrap(),
+            ip("::ffff:10.0.0.0")..=ip("::ffff:10.0.0.255")
+        );
+        // The host bits are ignored.
+        assert_eq!(
+            parse_ip_cidr("10.0.0.17", "28").unwrap(),
+            ip("::ffff:10.0.0.16")..=ip("::ffff:10.0.0.31")
+        );
+        assert_eq!(
+            parse_ip_cidr("10.0.0.17", "32").unwrap(),
+            ip("::ffff:10.0.0.17")..=ip("::ffff:10.0.0.17")
+        );
+        assert_eq!(
+            parse_ip_cidr("0.0.0.0", "0").unwrap(),
+            ip("::ffff:0.0.0.0")..=ip("::ffff:255.255.255.255")
+        );
+        assert_eq!(
+            parse_ip_cidr("2001:db8::", "32").unwrap(),
+            ip("2001:db8::")..=ip("2001:db8:ffff:ffff:ffff:ffff:ffff:ffff")
+        );
+        assert_eq!(
+            parse_ip_cidr("::", "0").unwrap(),
+            ip("::")..=ip("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff")
+        );
+        assert_eq!(
+            parse_ip_cidr("10.0.0.0", "33"),
+            Err(QueryParserError::IpCidrFormatError(
+                "10.0.0.0/33".to_string()
+            ))
+        );
+        assert_matches!(
+            parse_ip_cidr("10.0.0.0", "abc"),
+            Err(QueryParserError::IpCidrFormatError(_))
+        );
+        assert_matches!(
+            parse_ip_cidr("10.0.0", "24"),
+            Err(QueryParserError::IpFormatError(_))
+        );
+    }
+
     fn parse_query_to_logical_ast(
         query: &str,
         default_conjunction: bool,
@@ -1160,6 +1384,58 @@ mod test {
         );
     }
 
+    #[test]
+    pub fn test_parse_query_default_fields_with_boost() {
+        let mut query_parser = make_query_parser();
+        let title_field = query_parser.schema.get_field("title").unwrap();
+        query_parser.set_field_boost(title_field, 3.0);
+        let query = query_parser.parse_query("rust").unwrap();
+        assert_eq!(
+            format!("{query:?}"),
+            "BooleanQuery { subqueries: [(Should, Boost(query=TermQuery(Term(field=0, type=Str, \
+             \"rust\")), boost=3)), (Should, TermQuery(Term(field=1, type=Str, \"rust\")))] }"
+        );
+        let logical_ast = query_parser
+            .parse_query_to_logical_ast("\"rust book\"")
+            .unwrap();
+        assert_eq!(
+            format!("{logical_ast:?}"),
+            r#"("[(0, Term(field=0, type=Str, "rust")), (1, Term(field=0, type=Str, "book"))]"^3 "[(0, Term(field=1, type=Str, "rust")), (1, Term(field=1, type=Str, "book"))]")"#
+        );
+    }
+
+    #[test]
+    pub fn test_default_fields_boost_ranking() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let body = schema_builder.add_text_field("body", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "rust guide", body => "systems programming"))?;
+        index_writer.add_document(doc!(title => "systems programming", body => "rust guide"))?;
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let mut query_parser = QueryParser::for_index(&index, vec![title, body]);
+        let search = |query_parser: &QueryParser, query: &str| {
+            let query = query_parser.parse_query(query).unwrap();
+            searcher.search(&query, &TopDocs::with_limit(2)).unwrap()
+        };
+        // The two documents are symmetric.
+        let top_docs = search(&query_parser, "rust");
+        assert_eq!(top_docs.len(), 2);
+        assert_nearly_equals!(top_docs[0].0, top_docs[1].0);
+
+        query_parser.set_field_boost(title, 3.0);
+        for query in ["rust", "\"rust guide\""] {
+            let top_docs = search(&query_parser, query);
+            assert_eq!(top_docs.len(), 2);
+            // The title hit comes first.
+            assert_eq!(top_docs[0].1, DocAddress::new(0, 0));
+            assert_nearly_equals!(top_docs[0].0, 3.0 * top_docs[1].0);
+        }
+        Ok(())
+    }
+
     #[test]
     pub fn test_parse_nonindexed_field_yields_error() {
         let query_parser = make_query_parser();
@@ -1191,6 +1467,25 @@ mod test {
         );
     }
 
+    #[test]
+    pub fn test_parse_query_field_alias() {
+        let mut schema_builder = Schema::builder();
+        schema_builder.add_text_field("headline", TEXT);
+        schema_builder.add_alias("title", "headline");
+        let query_parser = QueryParser::new(
+            schema_builder.build(),
+            Vec::new(),
+            TokenizerManager::default(),
+        );
+        let alias_query = query_parser.parse_query("title:hello").unwrap();
+        let field_query = query_parser.parse_query("headline:hello").unwrap();
+        assert_eq!(format!("{alias_query:?}"), format!("{field_query:?}"));
+        assert_eq!(
+            format!("{alias_query:?}"),
+            r#"TermQuery(Term(field=0, type=Str, "hello"))"#
+        );
+    }
+
     #[test]
     pub fn test_parse_query_untokenized() {
         test_parse_query_to_logical_ast_helper(
@@ -1802,6 +2097,42 @@ mod test {
             r#""[(0, Term(field=0, type=Str, "a")), (1, Term(field=0, type=Str, "b")), (2, Term(field=0, type=Str, "4"))]"~2"#,
             false,
         );
+        test_parse_query_to_logical_ast_helper(
+            "title:\"quick fox\"~3",
+            r#""[(0, Term(field=0, type=Str, "quick")), (1, Term(field=0, type=Str, "fox"))]"~3"#,
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "title:\"a b c\"~2",
+            r#""[(0, Term(field=0, type=Str, "a")), (1, Term(field=0, type=Str, "b")), (2, Term(field=0, type=Str, "c"))]"~2"#,
+            false,
+        );
+    }
+
+    #[test]
+    pub fn test_phrase_slop_search() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "the quick fox"))?;
+        index_writer.add_document(doc!(title => "the quick brown fox"))?;
+        index_writer.add_document(doc!(title => "the quick and lazy brown fox"))?;
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let query_parser = QueryParser::for_index(&index, vec![title]);
+        let count = |query: &str| {
+            let query = query_parser.parse_query(query).unwrap();
+            searcher.search(&query, &Count).unwrap()
+        };
+        assert_eq!(count("\"quick fox\""), 1);
+        assert_eq!(count("\"quick fox\"~0"), 1);
+        // One intervening word.
+        assert_eq!(count("\"quick fox\"~1"), 2);
+        assert_eq!(count("\"quick fox\"~2"), 2);
+        assert_eq!(count("\"quick fox\"~3"), 3);
+        Ok(())
     }
 
     #[test]
@@ -1846,6 +2177,64 @@ mod test {
         );
     }
 
+    #[test]
+    pub fn test_parse_query_exists() {
+        test_parse_query_to_logical_ast_helper("title:*", "title:*", false);
+        test_parse_query_to_logical_ast_helper("json.a:*", "json.a:*", false);
+        test_parse_query_to_logical_ast_helper("u64_ff:*", "u64_ff:*", false);
+        test_parse_query_to_logical_ast_helper("* -title:*", "(* -title:*)", false);
+        test_parse_query_to_logical_ast_helper(
+            "+title:* +text:a",
+            r#"(+title:* +Term(field=1, type=Str, "a"))"#,
+            false,
+        );
+        assert_matches!(
+            parse_query_to_logical_ast("notindexed_text:*", false),
+            Err(QueryParserError::FieldNotIndexed(_))
+        );
+        assert_matches!(
+            parse_query_to_logical_ast("missing:*", false),
+            Err(QueryParserError::FieldDoesNotExist(_))
+        );
+        assert_matches!(
+            parse_query_to_logical_ast("-title:*", false),
+            Err(QueryParserError::AllButQueryForbidden)
+        );
+    }
+
+    #[test]
+    pub fn test_exists_and_missing_search() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let category = schema_builder.add_text_field("category", STRING);
+        let rating = schema_builder.add_u64_field("rating", FAST);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "dune", category => "fiction", rating => 5u64))?;
+        index_writer.add_document(doc!(title => "cosmos", category => "science"))?;
+        index_writer.add_document(doc!(title => "notes", rating => 2u64))?;
+        index_writer.add_document(doc!(title => "untitled"))?;
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let query_parser = QueryParser::for_index(&index, vec![title]);
+        let count = |query: &str| {
+            let query = query_parser.parse_query(query).unwrap();
+            searcher.search(&query, &Count).unwrap()
+        };
+        // Indexed field.
+        assert_eq!(count("category:*"), 2);
+        assert_eq!(count("* -category:*"), 2);
+        assert_eq!(count("* AND NOT category:*"), 2);
+        // Fast field.
+        assert_eq!(count("rating:*"), 2);
+        assert_eq!(count("* -rating:*"), 2);
+        assert_eq!(count("category:* -rating:*"), 1);
+        assert_eq!(count("* -category:* -rating:*"), 1);
+        assert_eq!(count("title:*"), 4);
+        Ok(())
+    }
+
     #[test]
     pub fn test_term_set_query() {
         test_parse_query_to_logical_ast_helper(
@@ -1869,6 +2258,16 @@ mod test {
             r#"IN [Term(field=10, type=F64, 1.1), Term(field=10, type=F64, 2.2), Term(field=10, type=F64, -3.3)]"#,
             false,
         );
+        test_parse_query_to_logical_ast_helper(
+            r#"nottokenized: IN [active "on hold"]"#,
+            r#"IN [Term(field=7, type=Str, "active"), Term(field=7, type=Str, "on hold")]"#,
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            r#"title: IN [a "b c"]"#,
+            r#"(IN [Term(field=0, type=Str, "a")] "[(0, Term(field=0, type=Str, "b")), (1, Term(field=0, type=Str, "c"))]")"#,
+            false,
+        );
     }
 
     #[test]
@@ -1885,8 +2284,8 @@ mod test {
             assert_eq!(
                 format!("{query:?}"),
                 "BooleanQuery { subqueries: [(Should, FuzzyTermQuery { term: Term(field=0, \
-                 type=Str, \"abc\"), distance: 1, transposition_cost_one: true, prefix: false }), \
-                 (Should, TermQuery(Term(field=1, type=Str, \"abc\")))] }"
+                 type=Str, \"abc\"), distance: 1, transposition_cost_one: true, prefix: false, \
+                 prefix_length: 0 }), (Should, TermQuery(Term(field=1, type=Str, \"abc\")))] }"
             );
         }
 
@@ -1903,8 +2302,111 @@ mod test {
                 format!("{query:?}"),
                 "BooleanQuery { subqueries: [(Should, TermQuery(Term(field=0, type=Str, \
                  \"abc\"))), (Should, FuzzyTermQuery { term: Term(field=1, type=Str, \"abc\"), \
-                 distance: 2, transposition_cost_one: false, prefix: true })] }"
+                 distance: 2, transposition_cost_one: false, prefix: true, prefix_length: 0 })] }"
             );
         }
     }
+
+    #[test]
+    pub fn test_parse_query_fuzzy_operator() {
+        test_parse_query_to_logical_ast_helper(
+            "title:search~1",
+            r#"Term(field=0, type=Str, "search")~1"#,
+            false,
+        );
+        // Numbers are matched exactly.
+        test_parse_query_to_logical_ast_helper(
+            "unsigned:12~1",
+            "Term(field=3, type=U64, 12)",
+            false,
+        );
+        let query_parser = make_query_parser();
+        let query = query_parser.parse_query("title:search~1").unwrap();
+        assert_eq!(
+            format!("{query:?}"),
+            "FuzzyTermQuery { term: Term(field=0, type=Str, \"search\"), distance: 1, \
+             transposition_cost_one: true, prefix: false, prefix_length: 0 }"
+        );
+        assert!(matches!(
+            query_parser.parse_query("title:term~abc"),
+            Err(QueryParserError::SyntaxError(_))
+        ));
+        assert!(matches!(
+            query_parser.parse_query("title:search~3"),
+            Err(QueryParserError::FuzzyDistanceTooLarge(3))
+        ));
+    }
+
+    #[test]
+    pub fn test_parse_query_fuzzy_operator_on_phrases() {
+        // A word producing several terms is a phrase, and its `~` remains a slop.
+        test_parse_query_to_logical_ast_helper(
+            "title:foo-bar~2",
+            r#""[(0, Term(field=0, type=Str, "foo")), (1, Term(field=0, type=Str, "bar"))]"~2"#,
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "title:foo-bar~3",
+            r#""[(0, Term(field=0, type=Str, "foo")), (1, Term(field=0, type=Str, "bar"))]"~3"#,
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "title:\"a b\"~3 AND title:c~1",
+            r#"(+"[(0, Term(field=0, type=Str, "a")), (1, Term(field=0, type=Str, "b"))]"~3 +Term(field=0, type=Str, "c")~1)"#,
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "title:foo-bar~2 OR title:baz~2",
+            r#"("[(0, Term(field=0, type=Str, "foo")), (1, Term(field=0, type=Str, "bar"))]"~2 Term(field=0, type=Str, "baz")~2)"#,
+            false,
+        );
+        let query_parser = make_query_parser();
+        let query = query_parser.parse_query("title:foo-bar~2").unwrap();
+        assert_eq!(
+            format!("{query:?}"),
+            "PhraseQuery { field: Field(0), phrase_terms: [(0, Term(field=0, type=Str, \"foo\")), \
+             (1, Term(field=0, type=Str, \"bar\"))], slop: 2 }"
+        );
+    }
+
+    #[test]
+    pub fn test_parse_query_boost_operator() {
+        test_parse_query_to_logical_ast_helper(
+            "title:rust^2.5",
+            r#"Term(field=0, type=Str, "rust")^2.5"#,
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "title:\"a b\"^2",
+            r#""[(0, Term(field=0, type=Str, "a")), (1, Term(field=0, type=Str, "b"))]"^2"#,
+            false,
+        );
+        let query_parser = make_query_parser();
+        let query = query_parser.parse_query("title:rust^2.5").unwrap();
+        assert_eq!(
+            format!("{query:?}"),
+            r#"Boost(query=TermQuery(Term(field=0, type=Str, "rust")), boost=2.5)"#
+        );
+    }
+
+    #[test]
+    pub fn test_parse_query_fuzzy_and_boost_operators() {
+        test_parse_query_to_logical_ast_helper(
+            "title:rust~1^2",
+            r#"Term(field=0, type=Str, "rust")~1^2"#,
+            false,
+        );
+        test_parse_query_to_logical_ast_helper(
+            "title:a~1 AND text:b^2",
+            r#"(+Term(field=0, type=Str, "a")~1 +Term(field=1, type=Str, "b")^2)"#,
+            false,
+        );
+        let query_parser = make_query_parser();
+        let query = query_parser.parse_query("title:rust~1^2").unwrap();
+        assert_eq!(
+            format!("{query:?}"),
+            "Boost(query=FuzzyTermQuery { term: Term(field=0, type=Str, \"rust\"), distance: 1, \
+             transposition_cost_one: true, prefix: false, prefix_length: 0 }, boost=2)"
+        );
+    }
 }
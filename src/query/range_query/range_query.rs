@@ -2,7 +2,7 @@ use std::io;
 use std::net::Ipv6Addr;
 use std::ops::{Bound, Range};
 
-use columnar::MonotonicallyMappableToU128;
+use columnar::{MonotonicallyMappableToU128, MonotonicallyMappableToU64};
 use common::{BinarySerializable, BitSet};
 
 use super::map_bound;
@@ -13,7 +13,9 @@ use crate::query::explanation::does_not_match;
 use crate::query::range_query::range_query_ip_fastfield::IPFastFieldRangeWeight;
 use crate::query::range_query::{is_type_valid_for_fastfield_range_query, map_bound_res};
 use crate::query::{BitSetDocSet, ConstScorer, EnableScoring, Explanation, Query, Scorer, Weight};
-use crate::schema::{Field, IndexRecordOption, Term, Type};
+use crate::schema::{
+    DateTimePrecision, Field, FieldType, IndexRecordOption, Term, Type, DATE_TIME_PRECISION_INDEXED,
+};
 use crate::termdict::{TermDictionary, TermStreamer};
 use crate::{DateTime, DocId, Score};
 
@@ -238,6 +240,10 @@ impl RangeQuery {
     /// The two `Bound` arguments make it possible to create more complex
     /// ranges than semi-inclusive range.
     ///
+    /// The bounds are truncated at the precision of the field when the `Weight` object is
+    /// created, i.e. at the precision set in its [`DateOptions`](crate::schema::DateOptions) for
+    /// a fast field, and at [`DATE_TIME_PRECISION_INDEXED`] otherwise.
+    ///
     /// If the field is not of the type `date`, tantivy
     /// will panic when the `Weight` object is created.
     pub fn new_date_bounds(
@@ -245,11 +251,7 @@ impl RangeQuery {
         lower_bound: Bound<DateTime>,
         upper_bound: Bound<DateTime>,
     ) -> RangeQuery {
-        let make_term_val = |val: &DateTime| {
-            Term::from_field_date(Field::from_field_id(0), *val)
-                .serialized_value_bytes()
-                .to_owned()
-        };
+        let make_term_val = |val: &DateTime| val.to_u64().to_be_bytes().to_vec();
         RangeQuery {
             field,
             value_type: Type::Date,
@@ -324,7 +326,7 @@ pub(crate) fn maps_to_u64_fastfield(typ: Type) -> bool {
     match typ {
         Type::U64 | Type::I64 | Type::F64 | Type::Bool | Type::Date => true,
         Type::IpAddr => false,
-        Type::Str | Type::Facet | Type::Bytes | Type::Json => false,
+        Type::Str | Type::Facet | Type::Bytes | Type::Json | Type::DenseVector => false,
     }
 }
 
@@ -344,6 +346,20 @@ impl Query for RangeQuery {
             return Err(TantivyError::SchemaError(err_msg));
         }
 
+        let (lower_bound, upper_bound) = if let FieldType::Date(date_options) = field_type {
+            let precision = if field_type.is_fast() {
+                date_options.get_precision()
+            } else {
+                DATE_TIME_PRECISION_INDEXED
+            };
+            (
+                truncate_date_bound(&self.lower_bound, precision)?,
+                truncate_date_bound(&self.upper_bound, precision)?,
+            )
+        } else {
+            (self.lower_bound.clone(), self.upper_bound.clone())
+        };
+
         if field_type.is_fast() && is_type_valid_for_fastfield_range_query(self.value_type) {
             if field_type.is_ip_addr() {
                 let parse_ip_from_bytes = |data: &Vec<u8>| {
@@ -355,8 +371,8 @@ impl Query for RangeQuery {
                     let ip_u128 = u128::from_be_bytes(ip_u128_bytes);
                     crate::Result::<Ipv6Addr>::Ok(Ipv6Addr::from_u128(ip_u128))
                 };
-                let lower_bound = map_bound_res(&self.lower_bound, parse_ip_from_bytes)?;
-                let upper_bound = map_bound_res(&self.upper_bound, parse_ip_from_bytes)?;
+                let lower_bound = map_bound_res(&lower_bound, parse_ip_from_bytes)?;
+                let upper_bound = map_bound_res(&upper_bound, parse_ip_from_bytes)?;
                 Ok(Box::new(IPFastFieldRangeWeight::new(
                     self.field.to_string(),
                     lower_bound,
@@ -370,8 +386,8 @@ impl Query for RangeQuery {
                     u64::from_be(BinarySerializable::deserialize(&mut &data[..]).unwrap())
                 };
 
-                let lower_bound = map_bound(&self.lower_bound, parse_from_bytes);
-                let upper_bound = map_bound(&self.upper_bound, parse_from_bytes);
+                let lower_bound = map_bound(&lower_bound, parse_from_bytes);
+                let upper_bound = map_bound(&upper_bound, parse_from_bytes);
                 Ok(Box::new(FastFieldRangeWeight::new_u64_lenient(
                     self.field.to_string(),
                     lower_bound,
@@ -381,14 +397,28 @@ impl Query for RangeQuery {
         } else {
             Ok(Box::new(RangeWeight {
                 field: self.field.to_string(),
-                lower_bound: self.lower_bound.clone(),
-                upper_bound: self.upper_bound.clone(),
+                lower_bound,
+                upper_bound,
                 limit: self.limit,
             }))
         }
     }
 }
 
+/// Truncates the date of a bound at the given precision.
+fn truncate_date_bound(
+    bound: &Bound<Vec<u8>>,
+    precision: DateTimePrecision,
+) -> crate::Result<Bound<Vec<u8>>> {
+    map_bound_res(bound, |data: &Vec<u8>| {
+        let date_u64_bytes: [u8; 8] = data.as_slice().try_into().map_err(|_| {
+            crate::TantivyError::InvalidArgument("Expected 8 bytes for date".to_string())
+        })?;
+        let date = DateTime::from_u64(u64::from_be_bytes(date_u64_bytes));
+        crate::Result::<Vec<u8>>::Ok(date.truncate(precision).to_u64().to_be_bytes().to_vec())
+    })
+}
+
 pub struct RangeWeight {
     field: String,
     lower_bound: Bound<Vec<u8>>,
@@ -475,9 +505,10 @@ mod tests {
     use crate::indexer::NoMergePolicy;
     use crate::query::QueryParser;
     use crate::schema::{
-        Field, IntoIpv6Addr, Schema, TantivyDocument, FAST, INDEXED, STORED, TEXT,
+        DateOptions, DateTimePrecision, Field, IntoIpv6Addr, Schema, TantivyDocument, Value, FAST,
+        INDEXED, STORED, TEXT,
     };
-    use crate::{Index, IndexWriter};
+    use crate::{DateTime, DocAddress, Index, IndexWriter};
 
     #[test]
     fn test_range_query_simple() -> crate::Result<()> {
@@ -507,6 +538,86 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_range_query_date_sub_second_precision() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let date_options = DateOptions::from(INDEXED | STORED | FAST);
+        let micros_field = schema_builder.add_date_field(
+            "micros",
+            date_options
+                .clone()
+                .set_precision(DateTimePrecision::Microseconds),
+        );
+        let nanos_field = schema_builder.add_date_field(
+            "nanos",
+            date_options.set_precision(DateTimePrecision::Nanoseconds),
+        );
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        // 2023-11-14T22:13:20.000001Z and 2023-11-14T22:13:20.000002Z.
+        let first = DateTime::from_timestamp_micros(1_700_000_000_000_001);
+        let second = DateTime::from_timestamp_micros(1_700_000_000_000_002);
+        let nanos = DateTime::from_timestamp_nanos(1_700_000_000_000_000_001);
+        {
+            let mut index_writer: IndexWriter = index.writer_for_tests()?;
+            index_writer.add_document(doc!(micros_field => first, nanos_field => nanos))?;
+            index_writer.add_document(doc!(micros_field => second))?;
+            index_writer.commit()?;
+        }
+        let searcher = index.reader()?.searcher();
+        let count = |query: &RangeQuery| searcher.search(query, &Count).unwrap();
+        assert_eq!(
+            count(&RangeQuery::new_date("micros".to_string(), first..second)),
+            1
+        );
+        let after_second = DateTime::from_timestamp_micros(1_700_000_000_000_003);
+        assert_eq!(
+            count(&RangeQuery::new_date(
+                "micros".to_string(),
+                second..after_second
+            )),
+            1
+        );
+        assert_eq!(
+            count(&RangeQuery::new_date(
+                "micros".to_string(),
+                first..after_second
+            )),
+            2
+        );
+
+        let query_parser = QueryParser::for_index(&index, vec![]);
+        let count_parsed = |query: &str| {
+            let query = query_parser.parse_query(query).unwrap();
+            searcher.search(&query, &Count).unwrap()
+        };
+        assert_eq!(
+            count_parsed("micros:[2023-11-14T22:13:20.000001Z TO 2023-11-14T22:13:20.000002Z}"),
+            1
+        );
+        assert_eq!(count_parsed("micros:[2023-11-14T22:13:20.000002Z TO *]"), 1);
+        assert_eq!(
+            count_parsed("micros:[2023-11-14T22:13:20Z TO 2023-11-14T22:13:21Z}"),
+            2
+        );
+
+        // The fast fields keep their precision, the stored values are kept in microseconds.
+        let doc: TantivyDocument = searcher.doc(DocAddress::new(0, 0))?;
+        assert_eq!(
+            doc.get_first(micros_field)
+                .and_then(|val| val.as_datetime()),
+            Some(first)
+        );
+        assert_eq!(
+            doc.get_first(nanos_field).and_then(|val| val.as_datetime()),
+            Some(nanos.truncate(DateTimePrecision::Microseconds))
+        );
+        let fast_fields = searcher.segment_reader(0).fast_fields();
+        assert_eq!(fast_fields.date("micros")?.first(0), Some(first));
+        assert_eq!(fast_fields.date("nanos")?.first(0), Some(nanos));
+        Ok(())
+    }
+
     #[test]
     fn test_range_query_with_limit() -> crate::Result<()> {
         let mut schema_builder = Schema::builder();
@@ -785,6 +896,23 @@ mod tests {
         assert_eq!(get_num_hits(query_from_text("ip:[* TO 127.0.0.9]")), 0);
         assert_eq!(get_num_hits(query_from_text("ip:[* TO 127.0.0.10]")), 1000);
 
+        // CIDR blocks
+        assert_eq!(get_num_hits(query_from_text("ip:127.0.0.0/24")), 2000);
+        assert_eq!(get_num_hits(query_from_text("ip:127.0.0.16/28")), 1000);
+        assert_eq!(get_num_hits(query_from_text("ip:127.0.0.10/32")), 1000);
+        assert_eq!(get_num_hits(query_from_text("ip:127.0.1.0/24")), 0);
+
+        // IPv4 addresses are mapped to IPv6 addresses
+        assert_eq!(get_num_hits(query_from_text("ip:127.0.0.10")), 1000);
+        assert_eq!(
+            get_num_hits(query_from_text("ip:\"::ffff:127.0.0.10\"")),
+            1000
+        );
+        assert_eq!(
+            get_num_hits(query_from_text("ip:[::ffff:127.0.0.10 TO 127.0.0.15]")),
+            1000
+        );
+
         // Exclusive range
         assert_eq!(
             get_num_hits(query_from_text("ip:{127.0.0.1 TO 127.0.0.20}")),
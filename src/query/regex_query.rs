@@ -14,6 +14,10 @@ use crate::schema::Field;
 /// Wildcard queries (e.g. ho*se) can be achieved
 /// by converting them to their regex counterparts.
 ///
+/// The pattern must compile to a finite automaton: backreferences and
+/// look-around assertions are not supported. Patterns such as `.*` can match
+/// a large part of the term dictionary, see [`RegexQuery::set_max_expansions`].
+///
 /// ```rust
 /// use tantivy::collector::Count;
 /// use tantivy::query::RegexQuery;
@@ -57,6 +61,7 @@ use crate::schema::Field;
 pub struct RegexQuery {
     regex: Arc<Regex>,
     field: Field,
+    max_expansions: Option<u32>,
 }
 
 impl RegexQuery {
@@ -72,11 +77,24 @@ impl RegexQuery {
         RegexQuery {
             regex: regex.into(),
             field,
+            max_expansions: None,
         }
     }
 
+    /// Maximum number of matching terms the query expands to, in each segment.
+    ///
+    /// The terms are visited in lexicographic order, and the following ones are ignored.
+    /// By default, there is no limit.
+    pub fn set_max_expansions(&mut self, value: u32) {
+        self.max_expansions = Some(value);
+    }
+
     fn specialized_weight(&self) -> AutomatonWeight<Regex> {
-        AutomatonWeight::new(self.field, self.regex.clone())
+        let mut weight = AutomatonWeight::new(self.field, self.regex.clone());
+        if let Some(max_expansions) = self.max_expansions {
+            weight.set_max_expansions(max_expansions);
+        }
+        weight
     }
 }
 
@@ -93,8 +111,8 @@ mod test {
     use tantivy_fst::Regex;
 
     use super::RegexQuery;
-    use crate::collector::TopDocs;
-    use crate::schema::{Field, Schema, TEXT};
+    use crate::collector::{Count, TopDocs};
+    use crate::schema::{Field, Schema, STRING, TEXT};
     use crate::{assert_nearly_equals, Index, IndexReader, IndexWriter};
 
     fn build_test_index() -> crate::Result<(IndexReader, Field)> {
@@ -187,5 +205,35 @@ mod test {
             }
             res => panic!("unexpected result: {res:?}"),
         }
+        match RegexQuery::from_pattern(r"(a)\1", field) {
+            Err(crate::TantivyError::InvalidArgument(msg)) => {
+                assert!(msg.contains("backreferences are not supported"))
+            }
+            res => panic!("unexpected result: {res:?}"),
+        }
+    }
+
+    #[test]
+    pub fn test_regex_query_max_expansions() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let color_field = schema_builder.add_text_field("color", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        for color in ["color", "colour", "colossal", "cooler"] {
+            index_writer.add_document(doc!(color_field => color))?;
+        }
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+
+        let mut query = RegexQuery::from_pattern("col(o|ou)r", color_field)?;
+        assert_eq!(searcher.search(&query, &Count)?, 2);
+        query.set_max_expansions(1);
+        assert_eq!(searcher.search(&query, &Count)?, 1);
+
+        let mut query = RegexQuery::from_pattern(".*", color_field)?;
+        assert_eq!(searcher.search(&query, &Count)?, 4);
+        query.set_max_expansions(3);
+        assert_eq!(searcher.search(&query, &Count)?, 3);
+        Ok(())
     }
 }
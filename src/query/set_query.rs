@@ -9,6 +9,12 @@ use crate::schema::{Field, Schema};
 use crate::Term;
 
 /// A Term Set Query matches all of the documents containing any of the Term provided
+///
+/// The terms of each field are compiled into an FST, which is intersected with the term
+/// dictionary in a single pass. This is much cheaper than a `BooleanQuery` with one clause per
+/// term for large sets of terms, e.g. an allowlist of ids.
+///
+/// The query is a filter: all of the matching documents get a constant score of 1.
 #[derive(Debug, Clone)]
 pub struct TermSetQuery {
     terms_map: HashMap<Field, Vec<Term>>,
@@ -113,9 +119,9 @@ impl Automaton for SetDfaWrapper {
 
 #[cfg(test)]
 mod tests {
-    use crate::collector::TopDocs;
-    use crate::query::{QueryParser, TermSetQuery};
-    use crate::schema::{Schema, TEXT};
+    use crate::collector::{DocSetCollector, TopDocs};
+    use crate::query::{BooleanQuery, QueryParser, TermSetQuery};
+    use crate::schema::{Schema, STRING, TEXT};
     use crate::{assert_nearly_equals, Index, IndexWriter, Term};
 
     #[test]
@@ -227,6 +233,43 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_term_set_query_large_set_matches_boolean_query() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let id_field = schema_builder.add_text_field("id", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer: IndexWriter = index.writer_for_tests()?;
+            for id in 0..1_000 {
+                index_writer.add_document(doc!(id_field => format!("id{id}")))?;
+                if id % 250 == 0 {
+                    index_writer.commit()?;
+                }
+            }
+            index_writer.commit()?;
+        }
+        let searcher = index.reader()?.searcher();
+        // One id out of three, half of them missing from the index.
+        let terms: Vec<Term> = (0..2_000)
+            .step_by(3)
+            .map(|id| Term::from_field_text(id_field, &format!("id{id}")))
+            .collect();
+        let term_set_docs = searcher.search(&TermSetQuery::new(terms.clone()), &DocSetCollector)?;
+        assert_eq!(term_set_docs.len(), 334);
+        let boolean_docs = searcher.search(
+            &BooleanQuery::new_multiterms_query(terms.clone()),
+            &DocSetCollector,
+        )?;
+        assert_eq!(term_set_docs, boolean_docs);
+        // The scores are constant.
+        let top_docs = searcher.search(&TermSetQuery::new(terms), &TopDocs::with_limit(1_000))?;
+        assert_eq!(top_docs.len(), 334);
+        for (score, _) in top_docs {
+            assert_nearly_equals!(1.0, score);
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_term_set_query_parser() -> crate::Result<()> {
         let mut schema_builder = Schema::builder();
@@ -253,4 +296,42 @@ mod tests {
         assert_eq!(top_docs.len(), 2);
         Ok(())
     }
+
+    #[test]
+    fn test_term_set_query_parser_matches_or_query() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let status = schema_builder.add_text_field("status", STRING);
+        let city = schema_builder.add_text_field("city", TEXT);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        for (status_val, city_val) in [
+            ("active", "Paris"),
+            ("pending", "New York"),
+            ("closed", "York"),
+            ("on hold", "New Delhi"),
+            ("archived", "Amsterdam"),
+            ("active", "Lyon"),
+        ] {
+            index_writer.add_document(doc!(status => status_val, city => city_val))?;
+        }
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let query_parser = QueryParser::for_index(&index, vec![]);
+        let search = |query: &str| {
+            let query = query_parser.parse_query(query).unwrap();
+            searcher.search(&query, &DocSetCollector).unwrap()
+        };
+        let in_docs = search(r#"status: IN [active closed "on hold"]"#);
+        assert_eq!(in_docs.len(), 4);
+        assert_eq!(
+            in_docs,
+            search(r#"status:active OR status:closed OR status:"on hold""#)
+        );
+        // Members producing several tokens are phrases.
+        let in_docs = search(r#"city: IN [paris "new york"]"#);
+        assert_eq!(in_docs.len(), 2);
+        assert_eq!(in_docs, search(r#"city:paris OR city:"new york""#));
+        Ok(())
+    }
 }
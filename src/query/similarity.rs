@@ -0,0 +1,279 @@
+use crate::query::bm25::idf;
+use crate::query::{Bm25Params, Explanation};
+use crate::Score;
+
+/// A scoring model, computing the relevance of a document for a term, or a phrase.
+///
+/// The similarity used to score the documents can be set with
+/// [`Searcher::with_similarity`](crate::Searcher::with_similarity). It defaults to
+/// [`Bm25Similarity`].
+///
+/// The score of a document is computed in two steps:
+/// - [`Similarity::term_weight`] is called once per term, or phrase, of the query, from the
+///   statistics of the collection.
+/// - [`Similarity::score`] is then called for each matching document, with the weight computed
+///   above. It should be fast.
+pub trait Similarity: Send + Sync + 'static {
+    /// Computes the weight of a term, or of a phrase.
+    ///
+    /// `doc_freqs` holds the number of documents containing each of the terms,
+    /// and `total_num_docs` is the number of documents in the index.
+    fn term_weight(&self, doc_freqs: &[u64], total_num_docs: u64) -> Explanation;
+
+    /// Computes the score of a document.
+    ///
+    /// - `term_weight` is the value returned by [`Similarity::term_weight`], possibly boosted.
+    /// - `term_freq` is the number of occurrences of the term, or of the phrase, in the document.
+    /// - `fieldnorm` is the number of tokens of the field of the document.
+    /// - `average_fieldnorm` is the average number of tokens of the field across the index.
+    fn score(
+        &self,
+        term_weight: Score,
+        term_freq: u32,
+        fieldnorm: u32,
+        average_fieldnorm: Score,
+    ) -> Score;
+
+    /// Returns an upper bound of the scores that [`Similarity::score`] can return for the given
+    /// `term_weight`, and a `term_freq` of at most `max_term_freq`.
+    ///
+    /// `max_term_freq` is an upper bound of the term frequencies of a block of documents, read
+    /// from the index, or `u32::MAX` if none is known.
+    ///
+    /// It is used to skip the documents that cannot make it into the top results, so a loose
+    /// bound is correct but slower.
+    fn max_score(&self, term_weight: Score, max_term_freq: u32, average_fieldnorm: Score) -> Score;
+
+    /// Produces an [`Explanation`] of the score of a document.
+    fn explain(
+        &self,
+        term_weight: Score,
+        term_freq: u32,
+        fieldnorm: u32,
+        average_fieldnorm: Score,
+    ) -> Explanation {
+        let score = self.score(term_weight, term_freq, fieldnorm, average_fieldnorm);
+        let mut explanation = Explanation::new("score", score);
+        explanation.add_const("weight", term_weight);
+        explanation.add_const(
+            "freq, occurrences of term within document",
+            term_freq as Score,
+        );
+        explanation.add_const("dl, length of field", fieldnorm as Score);
+        explanation.add_const("avgdl, average length of field", average_fieldnorm);
+        explanation
+    }
+
+    /// Returns true if the documents should be scored with tantivy's built-in BM25
+    /// implementation, which uses the [`Bm25Params`] of the fields and the block-max
+    /// information stored in the index, instead of the methods above.
+    ///
+    /// Only [`Bm25Similarity`] returns true.
+    fn is_bm25(&self) -> bool {
+        false
+    }
+}
+
+/// The default [`Similarity`], Okapi BM25.
+///
+/// The parameters `k1` and `b` can be set for each field, see [`Bm25Params`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Bm25Similarity;
+
+impl Similarity for Bm25Similarity {
+    fn term_weight(&self, doc_freqs: &[u64], total_num_docs: u64) -> Explanation {
+        let idf_sum: Score = doc_freqs
+            .iter()
+            .map(|&doc_freq| idf(doc_freq, total_num_docs))
+            .sum();
+        Explanation::new("idf * (k1 + 1)", idf_sum * (1.0 + Bm25Params::default().k1))
+    }
+
+    fn score(
+        &self,
+        term_weight: Score,
+        term_freq: u32,
+        fieldnorm: u32,
+        average_fieldnorm: Score,
+    ) -> Score {
+        let params = Bm25Params::default();
+        let term_freq = term_freq as Score;
+        let norm = params.k1 * (1.0 - params.b + params.b * fieldnorm as Score / average_fieldnorm);
+        term_weight * term_freq / (term_freq + norm)
+    }
+
+    fn max_score(
+        &self,
+        term_weight: Score,
+        _max_term_freq: u32,
+        _average_fieldnorm: Score,
+    ) -> Score {
+        term_weight
+    }
+
+    fn is_bm25(&self) -> bool {
+        true
+    }
+}
+
+/// The classic TF-IDF [`Similarity`], as implemented by Lucene before BM25.
+///
+/// - the weight of a term is `idf²`, where `idf = 1 + ln(N / (n + 1))`. The idf of a phrase is the
+///   sum of the idfs of its terms.
+/// - the score of a document is `weight * sqrt(freq) / sqrt(dl)`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TfIdfSimilarity;
+
+impl TfIdfSimilarity {
+    fn idf(doc_freq: u64, total_num_docs: u64) -> Score {
+        1.0 + (total_num_docs as Score / (doc_freq as Score + 1.0)).ln()
+    }
+}
+
+impl Similarity for TfIdfSimilarity {
+    fn term_weight(&self, doc_freqs: &[u64], total_num_docs: u64) -> Explanation {
+        let idf: Score = doc_freqs
+            .iter()
+            .map(|&doc_freq| TfIdfSimilarity::idf(doc_freq, total_num_docs))
+            .sum();
+        let mut idf_explain = Explanation::new("idf, computed as 1 + log(N / (n + 1))", idf);
+        if let [doc_freq] = doc_freqs {
+            idf_explain.add_const("n, number of docs containing this term", *doc_freq as Score);
+        }
+        idf_explain.add_const("N, total number of docs", total_num_docs as Score);
+        let mut explanation = Explanation::new("idf^2", idf * idf);
+        explanation.add_detail(idf_explain);
+        explanation
+    }
+
+    fn score(
+        &self,
+        term_weight: Score,
+        term_freq: u32,
+        fieldnorm: u32,
+        _average_fieldnorm: Score,
+    ) -> Score {
+        term_weight * (term_freq as Score).sqrt() / (fieldnorm.max(1) as Score).sqrt()
+    }
+
+    fn max_score(
+        &self,
+        term_weight: Score,
+        max_term_freq: u32,
+        _average_fieldnorm: Score,
+    ) -> Score {
+        // The score decreases with the fieldnorm, which is at least 1. The fieldnorm cannot bound
+        // the term frequency, as fieldnorms are approximated, and fields without fieldnorms
+        // have a fieldnorm of 1.
+        term_weight * (max_term_freq as Score).sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bm25Similarity, Similarity, TfIdfSimilarity};
+    use crate::collector::TopDocs;
+    use crate::query::{Bm25Weight, QueryParser, TermQuery};
+    use crate::schema::{IndexRecordOption, Schema, TEXT};
+    use crate::{assert_nearly_equals, DocAddress, Index, IndexWriter, Score, Term};
+
+    #[test]
+    fn test_bm25_similarity_matches_bm25_weight() {
+        let bm25_weight = Bm25Weight::for_one_term(3, 10, 5.0);
+        let term_weight = Bm25Similarity.term_weight(&[3], 10).value();
+        for (fieldnorm_id, term_freq) in [(1u8, 1u32), (4, 2), (10, 7)] {
+            let fieldnorm = crate::fieldnorm::FieldNormReader::id_to_fieldnorm(fieldnorm_id);
+            assert_nearly_equals!(
+                Bm25Similarity.score(term_weight, term_freq, fieldnorm, 5.0),
+                bm25_weight.score(fieldnorm_id, term_freq)
+            );
+        }
+    }
+
+    fn search_scores(similarity: Option<Box<dyn Similarity>>) -> crate::Result<Vec<(Score, u32)>> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text => "hello hello hello world"))?;
+        index_writer.add_document(doc!(text => "hello"))?;
+        index_writer.add_document(doc!(text => "world"))?;
+        index_writer.commit()?;
+        let mut searcher = index.reader()?.searcher();
+        if let Some(similarity) = similarity {
+            searcher = searcher.with_similarity(similarity);
+        }
+        let query = TermQuery::new(
+            Term::from_field_text(text, "hello"),
+            IndexRecordOption::WithFreqs,
+        );
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(10))?;
+        Ok(top_docs
+            .into_iter()
+            .map(|(score, DocAddress { doc_id, .. })| (score, doc_id))
+            .collect())
+    }
+
+    #[test]
+    fn test_tf_idf_similarity() -> crate::Result<()> {
+        let scores = search_scores(Some(Box::new(TfIdfSimilarity)))?;
+        assert_eq!(scores.len(), 2);
+        // idf = 1 + ln(3 / 3) = 1
+        assert_eq!(scores[0].1, 1);
+        assert_nearly_equals!(scores[0].0, 1.0);
+        assert_eq!(scores[1].1, 0);
+        assert_nearly_equals!(scores[1].0, (3.0 as Score).sqrt() / 2.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_similarity_defaults_to_bm25() -> crate::Result<()> {
+        assert_eq!(
+            search_scores(None)?,
+            search_scores(Some(Box::new(Bm25Similarity)))?
+        );
+        assert_ne!(
+            search_scores(None)?,
+            search_scores(Some(Box::new(TfIdfSimilarity)))?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_tf_idf_max_score() {
+        let term_weight = 2.0;
+        for max_term_freq in [1u32, 4, 1_000] {
+            let max_score = TfIdfSimilarity.max_score(term_weight, max_term_freq, 10.0);
+            for term_freq in 1..=max_term_freq {
+                assert!(TfIdfSimilarity.score(term_weight, term_freq, 1, 10.0) <= max_score);
+            }
+        }
+        assert_nearly_equals!(TfIdfSimilarity.max_score(term_weight, 4, 10.0), 4.0);
+    }
+
+    #[test]
+    fn test_tf_idf_similarity_top_docs_pruning() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        for i in 0..1_000 {
+            let mut text_val = "hello ".repeat(i % 7 + 1);
+            if i % 3 == 0 {
+                text_val.push_str("world");
+            }
+            index_writer.add_document(doc!(text => text_val))?;
+        }
+        index_writer.commit()?;
+        let searcher = index
+            .reader()?
+            .searcher()
+            .with_similarity(Box::new(TfIdfSimilarity));
+        let query = QueryParser::for_index(&index, vec![text]).parse_query("hello world")?;
+        // Without pruning, as the limit is the number of matching documents.
+        let all_docs = searcher.search(&query, &TopDocs::with_limit(1_000))?;
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(10))?;
+        assert_eq!(top_docs, all_docs[..10]);
+        Ok(())
+    }
+}
@@ -10,8 +10,10 @@ mod tests {
     use crate::collector::TopDocs;
     use crate::docset::DocSet;
     use crate::postings::compression::COMPRESSION_BLOCK_SIZE;
-    use crate::query::{EnableScoring, Query, QueryParser, Scorer, TermQuery};
-    use crate::schema::{Field, IndexRecordOption, Schema, STRING, TEXT};
+    use crate::query::{Bm25Params, EnableScoring, Query, QueryParser, Scorer, TermQuery};
+    use crate::schema::{
+        Field, IndexRecordOption, Schema, TextFieldIndexing, TextOptions, STRING, TEXT,
+    };
     use crate::{assert_nearly_equals, DocAddress, Index, IndexWriter, Term, TERMINATED};
 
     #[test]
@@ -125,6 +127,55 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_term_query_bm25_params() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let body_field = schema_builder.add_text_field("body", TEXT);
+        let keywords_options = TextOptions::default().set_indexing_options(
+            TextFieldIndexing::default()
+                .set_index_option(IndexRecordOption::WithFreqs)
+                .set_bm25_params(Bm25Params { k1: 1.2, b: 0.0 }),
+        );
+        let keywords_field = schema_builder.add_text_field("keywords", keywords_options);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        index_writer.add_document(doc!(
+            body_field => "apple",
+            keywords_field => "apple",
+        ))?;
+        index_writer.add_document(doc!(
+            body_field => "apple banana cherry date",
+            keywords_field => "apple banana cherry date",
+        ))?;
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let scores = |field: Field| -> crate::Result<Vec<f32>> {
+            let term_query = TermQuery::new(
+                Term::from_field_text(field, "apple"),
+                IndexRecordOption::WithFreqs,
+            );
+            let top_docs = searcher.search(&term_query, &TopDocs::with_limit(2))?;
+            assert_eq!(top_docs.len(), 2);
+            Ok(top_docs.into_iter().map(|(score, _)| score).collect())
+        };
+        // The shorter document ranks first with the default length normalization.
+        let body_scores = scores(body_field)?;
+        assert!(body_scores[0] > body_scores[1]);
+        // Without length normalization, both documents get the same score.
+        let keywords_scores = scores(keywords_field)?;
+        assert_nearly_equals!(keywords_scores[0], keywords_scores[1]);
+        assert_eq!(
+            searcher
+                .schema()
+                .get_field_entry(keywords_field)
+                .field_type()
+                .bm25_params(),
+            Bm25Params { k1: 1.2, b: 0.0 }
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_term_query_count_when_there_are_deletes() -> crate::Result<()> {
         let mut schema_builder = Schema::builder();
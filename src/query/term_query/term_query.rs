@@ -1,7 +1,7 @@
 use std::fmt;
 
 use super::term_weight::TermWeight;
-use crate::query::bm25::Bm25Weight;
+use crate::query::bm25::{Bm25Params, Bm25Weight};
 use crate::query::{EnableScoring, Explanation, Query, Weight};
 use crate::schema::IndexRecordOption;
 use crate::Term;
@@ -97,12 +97,19 @@ impl TermQuery {
         }
         let bm25_weight = match enable_scoring {
             EnableScoring::Enabled {
+                searcher,
                 statistics_provider,
-                ..
-            } => Bm25Weight::for_terms(statistics_provider, &[self.term.clone()])?,
-            EnableScoring::Disabled { .. } => {
-                Bm25Weight::new(Explanation::new("<no score>", 1.0f32), 1.0f32)
-            }
+            } => Bm25Weight::for_terms_with_similarity(
+                statistics_provider,
+                &[self.term.clone()],
+                field_entry.field_type().bm25_params(),
+                searcher.similarity(),
+            )?,
+            EnableScoring::Disabled { .. } => Bm25Weight::new(
+                Explanation::new("<no score>", 1.0f32),
+                1.0f32,
+                Bm25Params::default(),
+            ),
         };
         let scoring_enabled = enable_scoring.is_scoring_enabled();
         let index_record_option = if scoring_enabled {
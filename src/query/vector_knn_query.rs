@@ -0,0 +1,478 @@
+use crate::collector::TopNComputer;
+use crate::index::SegmentReader;
+use crate::query::explanation::does_not_match;
+use crate::query::{EnableScoring, Explanation, Query, Scorer, Weight};
+use crate::schema::{decode_components, Field, FieldType};
+use crate::{DocId, DocSet, Score, TantivyError, TERMINATED};
+
+/// The metric used to compare the vectors of a [`VectorKnnQuery`].
+///
+/// The similarity of two vectors is turned into a score within `[0, 1]`, the most similar
+/// vectors getting the highest scores.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VectorMetric {
+    /// Cosine similarity, scored as `(1 + cosine) / 2`.
+    ///
+    /// A zero vector has a cosine similarity of 0 with any vector.
+    Cosine,
+    /// Euclidean distance, scored as `1 / (1 + distance²)`.
+    L2,
+}
+
+impl VectorMetric {
+    fn score(self, query_vector: &[f32], query_norm: f32, vector: &[f32]) -> Score {
+        match self {
+            VectorMetric::Cosine => {
+                let dot_product: f32 = query_vector
+                    .iter()
+                    .zip(vector)
+                    .map(|(left, right)| left * right)
+                    .sum();
+                let norm = vector.iter().map(|val| val * val).sum::<f32>().sqrt();
+                let cosine = if query_norm == 0.0 || norm == 0.0 {
+                    0.0
+                } else {
+                    dot_product / (query_norm * norm)
+                };
+                (1.0 + cosine) / 2.0
+            }
+            VectorMetric::L2 => {
+                let squared_distance: f32 = query_vector
+                    .iter()
+                    .zip(vector)
+                    .map(|(left, right)| (left - right) * (left - right))
+                    .sum();
+                1.0 / (1.0 + squared_distance)
+            }
+        }
+    }
+}
+
+/// Query matching the `k` documents whose vectors are the nearest to a query vector.
+///
+/// The field must be a dense vector field, created with
+/// [`SchemaBuilder::add_dense_vector_field`](crate::schema::SchemaBuilder::add_dense_vector_field).
+/// The search is exact: the vectors of all of the documents are compared to the query vector,
+/// or only those of the documents matching the filter, if any. A document with several vectors
+/// is scored with its most similar one.
+///
+/// The nearest documents are computed segment by segment, when the scorer of a segment is
+/// created: the query matches up to `k` documents in each segment, so that the `k` nearest
+/// documents of the index are the top `k` documents of the search, e.g. with
+/// [`TopDocs::with_limit(k)`](crate::collector::TopDocs::with_limit). The score of a document is
+/// the score of its [`VectorMetric`].
+///
+/// ```rust
+/// use tantivy::collector::TopDocs;
+/// use tantivy::query::{VectorKnnQuery, VectorMetric};
+/// use tantivy::schema::{DenseVector, Schema, STORED};
+/// use tantivy::{doc, DocAddress, Index, IndexWriter};
+///
+/// # fn test() -> tantivy::Result<()> {
+/// let mut schema_builder = Schema::builder();
+/// let embedding = schema_builder.add_dense_vector_field("embedding", 2, STORED);
+/// let index = Index::create_in_ram(schema_builder.build());
+/// let mut index_writer: IndexWriter = index.writer_with_num_threads(1, 20_000_000)?;
+/// index_writer.add_document(doc!(embedding => DenseVector::new(&[1.0, 0.0])))?;
+/// index_writer.add_document(doc!(embedding => DenseVector::new(&[0.0, 1.0])))?;
+/// index_writer.commit()?;
+///
+/// let searcher = index.reader()?.searcher();
+/// let query = VectorKnnQuery::new(embedding, vec![0.1, 0.9], 1, VectorMetric::Cosine);
+/// let top_docs = searcher.search(&query, &TopDocs::with_limit(1))?;
+/// assert_eq!(top_docs.len(), 1);
+/// assert_eq!(top_docs[0].1, DocAddress::new(0, 1));
+/// # Ok(())
+/// # }
+/// # assert!(test().is_ok());
+/// ```
+#[derive(Debug)]
+pub struct VectorKnnQuery {
+    field: Field,
+    query_vector: Vec<f32>,
+    k: usize,
+    metric: VectorMetric,
+    filter: Option<Box<dyn Query>>,
+}
+
+impl Clone for VectorKnnQuery {
+    fn clone(&self) -> Self {
+        VectorKnnQuery {
+            field: self.field,
+            query_vector: self.query_vector.clone(),
+            k: self.k,
+            metric: self.metric,
+            filter: self.filter.as_ref().map(|filter| filter.box_clone()),
+        }
+    }
+}
+
+impl VectorKnnQuery {
+    /// Creates a new `VectorKnnQuery`, matching the `k` documents whose vectors are the most
+    /// similar to `query_vector` according to `metric`.
+    ///
+    /// This constructor never fails, but executing the search with this query will return an
+    /// error if the dimension of `query_vector` is not the one of the field.
+    pub fn new(
+        field: Field,
+        query_vector: Vec<f32>,
+        k: usize,
+        metric: VectorMetric,
+    ) -> VectorKnnQuery {
+        VectorKnnQuery {
+            field,
+            query_vector,
+            k,
+            metric,
+            filter: None,
+        }
+    }
+
+    /// Restricts the candidates to the documents matching `filter`, e.g. a
+    /// [`BooleanQuery`](crate::query::BooleanQuery).
+    ///
+    /// The filter is applied before selecting the `k` nearest documents, so that up to `k`
+    /// documents are still matched.
+    #[must_use]
+    pub fn with_filter(mut self, filter: Box<dyn Query>) -> VectorKnnQuery {
+        self.filter = Some(filter);
+        self
+    }
+}
+
+impl Query for VectorKnnQuery {
+    fn weight(&self, enable_scoring: EnableScoring<'_>) -> crate::Result<Box<dyn Weight>> {
+        let schema = enable_scoring.schema();
+        let field_entry = schema.get_field_entry(self.field);
+        let FieldType::DenseVector(dense_vector_options) = field_entry.field_type() else {
+            return Err(TantivyError::SchemaError(format!(
+                "Field {:?} is not a dense vector field.",
+                field_entry.name()
+            )));
+        };
+        if self.query_vector.len() != dense_vector_options.dimension() {
+            return Err(TantivyError::InvalidArgument(format!(
+                "Expected a query vector of dimension {}, got {}",
+                dense_vector_options.dimension(),
+                self.query_vector.len()
+            )));
+        }
+        let filter_enable_scoring = match enable_scoring.searcher() {
+            Some(searcher) => EnableScoring::disabled_from_searcher(searcher),
+            None => EnableScoring::disabled_from_schema(schema),
+        };
+        let filter_weight = self
+            .filter
+            .as_ref()
+            .map(|filter| filter.weight(filter_enable_scoring))
+            .transpose()?;
+        Ok(Box::new(VectorKnnWeight {
+            field_name: field_entry.name().to_string(),
+            query_vector: self.query_vector.clone(),
+            k: self.k,
+            metric: self.metric,
+            filter_weight,
+        }))
+    }
+}
+
+/// Weight associated with the `VectorKnnQuery` query.
+struct VectorKnnWeight {
+    field_name: String,
+    query_vector: Vec<f32>,
+    k: usize,
+    metric: VectorMetric,
+    filter_weight: Option<Box<dyn Weight>>,
+}
+
+impl VectorKnnWeight {
+    /// Returns the `k` nearest documents of the segment, sorted by doc id.
+    fn nearest_docs(&self, reader: &SegmentReader) -> crate::Result<Vec<(DocId, Score)>> {
+        let Some(vector_column) = reader.fast_fields().bytes(&self.field_name)? else {
+            return Ok(Vec::new());
+        };
+        let query_norm = self
+            .query_vector
+            .iter()
+            .map(|val| val * val)
+            .sum::<f32>()
+            .sqrt();
+        // The bytes column deduplicates the vectors, so that each of them is only scored once.
+        let mut vector = Vec::with_capacity(self.query_vector.len());
+        let mut scores = Vec::with_capacity(vector_column.num_terms());
+        let mut vector_stream = vector_column.dictionary().stream()?;
+        while vector_stream.advance() {
+            decode_components(vector_stream.key(), &mut vector);
+            scores.push(self.metric.score(&self.query_vector, query_norm, &vector));
+        }
+        let mut top_k = TopNComputer::new(self.k);
+        let mut collect_doc = |doc: DocId| {
+            let best_score = vector_column
+                .term_ords(doc)
+                .map(|ord| scores[ord as usize])
+                .reduce(Score::max);
+            if let Some(score) = best_score {
+                top_k.push(score, doc);
+            }
+        };
+        if let Some(filter_weight) = self.filter_weight.as_ref() {
+            let mut filter_scorer = filter_weight.scorer(reader, 1.0)?;
+            let mut doc = filter_scorer.doc();
+            while doc != TERMINATED {
+                if !reader.is_deleted(doc) {
+                    collect_doc(doc);
+                }
+                doc = filter_scorer.advance();
+            }
+        } else {
+            for doc in reader.doc_ids_alive() {
+                collect_doc(doc);
+            }
+        }
+        let mut docs: Vec<(DocId, Score)> = top_k
+            .into_vec()
+            .into_iter()
+            .map(|scored_doc| (scored_doc.doc, scored_doc.feature))
+            .collect();
+        docs.sort_unstable_by_key(|(doc, _)| *doc);
+        Ok(docs)
+    }
+}
+
+impl Weight for VectorKnnWeight {
+    fn scorer(&self, reader: &SegmentReader, boost: Score) -> crate::Result<Box<dyn Scorer>> {
+        Ok(Box::new(VectorKnnScorer {
+            docs: self.nearest_docs(reader)?,
+            cursor: 0,
+            boost,
+        }))
+    }
+
+    fn explain(&self, reader: &SegmentReader, doc: DocId) -> crate::Result<Explanation> {
+        let mut scorer = self.scorer(reader, 1.0)?;
+        if scorer.seek(doc) != doc {
+            return Err(does_not_match(doc));
+        }
+        Ok(Explanation::new("VectorKnnQuery", scorer.score()))
+    }
+}
+
+/// Scorer over the nearest documents of a segment, sorted by doc id.
+struct VectorKnnScorer {
+    docs: Vec<(DocId, Score)>,
+    cursor: usize,
+    boost: Score,
+}
+
+impl DocSet for VectorKnnScorer {
+    fn advance(&mut self) -> DocId {
+        if self.cursor < self.docs.len() {
+            self.cursor += 1;
+        }
+        self.doc()
+    }
+
+    fn doc(&self) -> DocId {
+        self.docs
+            .get(self.cursor)
+            .map(|(doc, _)| *doc)
+            .unwrap_or(TERMINATED)
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.docs.len() as u32
+    }
+}
+
+impl Scorer for VectorKnnScorer {
+    fn score(&mut self) -> Score {
+        self.docs[self.cursor].1 * self.boost
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{VectorKnnQuery, VectorMetric};
+    use crate::collector::{Count, TopDocs};
+    use crate::query::{BooleanQuery, Occur, Query, TermQuery};
+    use crate::schema::{
+        DenseVector, IndexRecordOption, Schema, TantivyDocument, Value, STORED, STRING,
+    };
+    use crate::{assert_nearly_equals, Index, IndexWriter, Searcher, Term};
+
+    const VECTORS: [(&str, &str, [f32; 4]); 5] = [
+        ("a", "x", [1.0, 0.0, 0.0, 0.0]),
+        ("b", "y", [0.9, 0.1, 0.0, 0.0]),
+        ("c", "x", [0.0, 1.0, 0.0, 0.0]),
+        ("d", "y", [0.0, 0.0, 1.0, 0.0]),
+        ("e", "x", [0.5, 0.5, 0.5, 0.5]),
+    ];
+
+    fn vector_index() -> crate::Result<Index> {
+        let mut schema_builder = Schema::builder();
+        let name = schema_builder.add_text_field("name", STRING | STORED);
+        let category = schema_builder.add_text_field("category", STRING);
+        let embedding = schema_builder.add_dense_vector_field("embedding", 4, STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        for (i, (doc_name, doc_category, vector)) in VECTORS.into_iter().enumerate() {
+            index_writer.add_document(doc!(
+                name => doc_name,
+                category => doc_category,
+                embedding => DenseVector::new(&vector),
+            ))?;
+            // Spread the documents over two segments.
+            if i == 2 {
+                index_writer.commit()?;
+            }
+        }
+        // A document without vector.
+        index_writer.add_document(doc!(name => "f", category => "x"))?;
+        index_writer.commit()?;
+        Ok(index)
+    }
+
+    fn nearest_names(searcher: &Searcher, query: &VectorKnnQuery) -> crate::Result<Vec<String>> {
+        let name = searcher.schema().get_field("name").unwrap();
+        let top_docs = searcher.search(query, &TopDocs::with_limit(query.k))?;
+        Ok(top_docs
+            .into_iter()
+            .map(|(_, doc_address)| {
+                let doc: TantivyDocument = searcher.doc(doc_address).unwrap();
+                doc.get_first(name).unwrap().as_str().unwrap().to_string()
+            })
+            .collect())
+    }
+
+    #[test]
+    fn test_vector_knn_query_cosine() -> crate::Result<()> {
+        let searcher = vector_index()?.reader()?.searcher();
+        let embedding = searcher.schema().get_field("embedding").unwrap();
+        let query =
+            VectorKnnQuery::new(embedding, vec![1.0, 0.0, 0.0, 0.0], 2, VectorMetric::Cosine);
+        assert_eq!(nearest_names(&searcher, &query)?, vec!["a", "b"]);
+        // Up to `k` documents match in each of the two segments.
+        assert_eq!(searcher.search(&query, &Count)?, 4);
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(1))?;
+        assert_nearly_equals!(top_docs[0].0, 1.0);
+        // The norm of the query vector does not matter.
+        let query =
+            VectorKnnQuery::new(embedding, vec![0.0, 3.0, 0.0, 0.0], 3, VectorMetric::Cosine);
+        assert_eq!(nearest_names(&searcher, &query)?, vec!["c", "e", "b"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_vector_knn_query_l2() -> crate::Result<()> {
+        let searcher = vector_index()?.reader()?.searcher();
+        let embedding = searcher.schema().get_field("embedding").unwrap();
+        let query = VectorKnnQuery::new(embedding, vec![0.0, 0.9, 0.0, 0.0], 2, VectorMetric::L2);
+        assert_eq!(nearest_names(&searcher, &query)?, vec!["c", "e"]);
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(1))?;
+        assert_nearly_equals!(top_docs[0].0, 1.0 / 1.01);
+        // With k greater than the number of vectors, all of the documents with a vector match.
+        let query = VectorKnnQuery::new(embedding, vec![0.0, 0.9, 0.0, 0.0], 10, VectorMetric::L2);
+        assert_eq!(searcher.search(&query, &Count)?, 5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_vector_knn_query_with_filter() -> crate::Result<()> {
+        let index = vector_index()?;
+        let searcher = index.reader()?.searcher();
+        let embedding = searcher.schema().get_field("embedding").unwrap();
+        let category = searcher.schema().get_field("category").unwrap();
+        let in_category = |category_name: &str| -> Box<dyn Query> {
+            Box::new(TermQuery::new(
+                Term::from_field_text(category, category_name),
+                IndexRecordOption::Basic,
+            ))
+        };
+        let filter = BooleanQuery::new(vec![(Occur::Must, in_category("x"))]);
+        let query =
+            VectorKnnQuery::new(embedding, vec![1.0, 0.0, 0.0, 0.0], 2, VectorMetric::Cosine)
+                .with_filter(Box::new(filter));
+        assert_eq!(nearest_names(&searcher, &query)?, vec!["a", "e"]);
+
+        let filter = BooleanQuery::new(vec![(Occur::Must, in_category("y"))]);
+        let query =
+            VectorKnnQuery::new(embedding, vec![1.0, 0.0, 0.0, 0.0], 3, VectorMetric::Cosine)
+                .with_filter(Box::new(filter));
+        assert_eq!(nearest_names(&searcher, &query)?, vec!["b", "d"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_vector_knn_query_ignores_deleted_docs() -> crate::Result<()> {
+        let index = vector_index()?;
+        let name = index.schema().get_field("name").unwrap();
+        let embedding = index.schema().get_field("embedding").unwrap();
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        index_writer.delete_term(Term::from_field_text(name, "a"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let query =
+            VectorKnnQuery::new(embedding, vec![1.0, 0.0, 0.0, 0.0], 2, VectorMetric::Cosine);
+        assert_eq!(nearest_names(&searcher, &query)?, vec!["b", "e"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_vector_knn_query_invalid() -> crate::Result<()> {
+        let searcher = vector_index()?.reader()?.searcher();
+        let embedding = searcher.schema().get_field("embedding").unwrap();
+        let name = searcher.schema().get_field("name").unwrap();
+        let wrong_dimension = VectorKnnQuery::new(embedding, vec![1.0, 0.0], 2, VectorMetric::L2);
+        assert!(searcher.search(&wrong_dimension, &Count).is_err());
+        let not_a_vector_field =
+            VectorKnnQuery::new(name, vec![1.0, 0.0, 0.0, 0.0], 2, VectorMetric::L2);
+        assert!(searcher.search(&not_a_vector_field, &Count).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_vector_knn_query_delete_query() -> crate::Result<()> {
+        let index = vector_index()?;
+        let embedding = index.schema().get_field("embedding").unwrap();
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        // Without a searcher, the nearest documents of each segment are deleted.
+        let query =
+            VectorKnnQuery::new(embedding, vec![1.0, 0.0, 0.0, 0.0], 1, VectorMetric::Cosine);
+        index_writer.delete_query(Box::new(query))?;
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let query = VectorKnnQuery::new(
+            embedding,
+            vec![1.0, 0.0, 0.0, 0.0],
+            10,
+            VectorMetric::Cosine,
+        );
+        assert_eq!(searcher.search(&query, &Count)?, 3);
+        let query =
+            VectorKnnQuery::new(embedding, vec![1.0, 0.0, 0.0, 0.0], 1, VectorMetric::Cosine);
+        assert_eq!(nearest_names(&searcher, &query)?, vec!["b"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_dense_vector_field_stored_and_json() -> crate::Result<()> {
+        let searcher = vector_index()?.reader()?.searcher();
+        let schema = searcher.schema();
+        let embedding = schema.get_field("embedding").unwrap();
+        let name = schema.get_field("name").unwrap();
+        let query = TermQuery::new(Term::from_field_text(name, "c"), IndexRecordOption::Basic);
+        let (_, doc_address) = searcher.search(&query, &TopDocs::with_limit(1))?[0];
+        let doc: TantivyDocument = searcher.doc(doc_address)?;
+        let stored_vector =
+            DenseVector::from_bytes(doc.get_first(embedding).unwrap().as_bytes().unwrap()).unwrap();
+        assert_eq!(stored_vector.components(), vec![0.0, 1.0, 0.0, 0.0]);
+
+        let doc = TantivyDocument::parse_json(schema, r#"{"embedding": [0.5, 1, -2, 0]}"#)?;
+        let json_vector =
+            DenseVector::from_bytes(doc.get_first(embedding).unwrap().as_bytes().unwrap()).unwrap();
+        assert_eq!(json_vector.components(), vec![0.5, 1.0, -2.0, 0.0]);
+        assert!(TantivyDocument::parse_json(schema, r#"{"embedding": [0.5, 1]}"#).is_err());
+        assert!(TantivyDocument::parse_json(schema, r#"{"embedding": "abc"}"#).is_err());
+        Ok(())
+    }
+}
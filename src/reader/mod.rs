@@ -9,6 +9,7 @@ pub use warming::Warmer;
 use self::warming::WarmingState;
 use crate::core::searcher::{SearcherGeneration, SearcherInner};
 use crate::directory::{Directory, WatchCallback, WatchHandle, META_LOCK};
+use crate::indexer::segment_updater::NrtSegmentMetas;
 use crate::store::DOCSTORE_CACHE_CAPACITY;
 use crate::{Index, Inventory, Searcher, SegmentReader, TrackedObject};
 
@@ -44,6 +45,7 @@ pub struct IndexReaderBuilder {
     warmers: Vec<Weak<dyn Warmer>>,
     num_warming_threads: usize,
     doc_store_cache_num_blocks: usize,
+    nrt_segment_metas: Option<NrtSegmentMetas>,
 }
 
 impl IndexReaderBuilder {
@@ -55,6 +57,7 @@ impl IndexReaderBuilder {
             warmers: Vec::new(),
             num_warming_threads: 1,
             doc_store_cache_num_blocks: DOCSTORE_CACHE_CAPACITY,
+            nrt_segment_metas: None,
         }
     }
 
@@ -73,6 +76,7 @@ impl IndexReaderBuilder {
         let inner_reader = InnerIndexReader::new(
             self.doc_store_cache_num_blocks,
             self.index,
+            self.nrt_segment_metas,
             warming_state,
             searcher_generation_inventory,
         )?;
@@ -133,6 +137,18 @@ impl IndexReaderBuilder {
         self
     }
 
+    /// Makes the reader see the soft commits of an `IndexWriter`.
+    ///
+    /// See [`crate::IndexWriter::nrt_reader()`].
+    #[must_use]
+    pub(crate) fn nrt_segment_metas(
+        mut self,
+        nrt_segment_metas: NrtSegmentMetas,
+    ) -> IndexReaderBuilder {
+        self.nrt_segment_metas = Some(nrt_segment_metas);
+        self
+    }
+
     /// Sets the number of warming threads.
     ///
     /// This allows parallelizing warming work when there are multiple [`Warmer`] registered with
@@ -155,6 +171,7 @@ impl TryInto<IndexReader> for IndexReaderBuilder {
 struct InnerIndexReader {
     doc_store_cache_num_blocks: usize,
     index: Index,
+    nrt_segment_metas: Option<NrtSegmentMetas>,
     warming_state: WarmingState,
     searcher: arc_swap::ArcSwap<SearcherInner>,
     searcher_generation_counter: Arc<AtomicU64>,
@@ -165,6 +182,7 @@ impl InnerIndexReader {
     fn new(
         doc_store_cache_num_blocks: usize,
         index: Index,
+        nrt_segment_metas: Option<NrtSegmentMetas>,
         warming_state: WarmingState,
         // The searcher_generation_inventory is not used as source, but as target to track the
         // loaded segments.
@@ -174,6 +192,7 @@ impl InnerIndexReader {
 
         let searcher = Self::create_searcher(
             &index,
+            nrt_segment_metas.as_ref(),
             doc_store_cache_num_blocks,
             &warming_state,
             &searcher_generation_counter,
@@ -182,6 +201,7 @@ impl InnerIndexReader {
         Ok(InnerIndexReader {
             doc_store_cache_num_blocks,
             index,
+            nrt_segment_metas,
             warming_state,
             searcher: ArcSwap::from(searcher),
             searcher_generation_counter,
@@ -192,10 +212,21 @@ impl InnerIndexReader {
     ///
     /// This function acquires a lock to prevent GC from removing files
     /// as we are opening our index.
-    fn open_segment_readers(index: &Index) -> crate::Result<Vec<SegmentReader>> {
+    ///
+    /// The segments of the last soft commit are used if there is one.
+    fn open_segment_readers(
+        index: &Index,
+        nrt_segment_metas: Option<&NrtSegmentMetas>,
+    ) -> crate::Result<Vec<SegmentReader>> {
         // Prevents segment files from getting deleted while we are in the process of opening them
         let _meta_lock = index.directory().acquire_lock(&META_LOCK)?;
-        let searchable_segments = index.searchable_segments()?;
+        let searchable_segments = match nrt_segment_metas.and_then(NrtSegmentMetas::get) {
+            Some(segment_metas) => segment_metas
+                .into_iter()
+                .map(|segment_meta| index.segment(segment_meta))
+                .collect(),
+            None => index.searchable_segments()?,
+        };
         let segment_readers = searchable_segments
             .iter()
             .map(SegmentReader::open)
@@ -216,12 +247,13 @@ impl InnerIndexReader {
 
     fn create_searcher(
         index: &Index,
+        nrt_segment_metas: Option<&NrtSegmentMetas>,
         doc_store_cache_num_blocks: usize,
         warming_state: &WarmingState,
         searcher_generation_counter: &Arc<AtomicU64>,
         searcher_generation_inventory: &Inventory<SearcherGeneration>,
     ) -> crate::Result<Arc<SearcherInner>> {
-        let segment_readers = Self::open_segment_readers(index)?;
+        let segment_readers = Self::open_segment_readers(index, nrt_segment_metas)?;
         let searcher_generation = Self::track_segment_readers_in_inventory(
             &segment_readers,
             searcher_generation_counter,
@@ -244,6 +276,7 @@ impl InnerIndexReader {
     fn reload(&self) -> crate::Result<()> {
         let searcher = Self::create_searcher(
             &self.index,
+            self.nrt_segment_metas.as_ref(),
             self.doc_store_cache_num_blocks,
             &self.warming_state,
             &self.searcher_generation_counter,
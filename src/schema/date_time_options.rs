@@ -93,7 +93,8 @@ impl DateOptions {
     /// Indexed precision is always [`DATE_TIME_PRECISION_INDEXED`].
     ///
     /// Internal storage precision, used to optimize storage
-    /// compression on fast fields.
+    /// compression on fast fields. The range queries on a fast field run at this
+    /// precision. The stored values are kept at microsecond precision.
     pub fn set_precision(mut self, precision: DateTimePrecision) -> DateOptions {
         self.precision = precision;
         self
@@ -0,0 +1,109 @@
+use std::iter::Empty;
+
+use crate::schema::document::{ReferenceValue, ReferenceValueLeaf, Value};
+use crate::schema::OwnedValue;
+
+/// A vector of `f32`, as stored in the dense vector fields created with
+/// [`SchemaBuilder::add_dense_vector_field`](crate::schema::SchemaBuilder::add_dense_vector_field).
+///
+/// The vector is held in its bytes encoding, i.e. its components in little endian, which is
+/// also the encoding of the bytes values of the dense vector fields.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DenseVector {
+    bytes: Vec<u8>,
+}
+
+impl DenseVector {
+    /// Creates a new `DenseVector` from its components.
+    pub fn new(components: &[f32]) -> DenseVector {
+        let bytes = components
+            .iter()
+            .flat_map(|component| component.to_le_bytes())
+            .collect();
+        DenseVector { bytes }
+    }
+
+    /// Decodes a vector from its bytes encoding, e.g. a stored value of a dense vector field.
+    ///
+    /// Returns `None` if the number of bytes is not a multiple of 4.
+    pub fn from_bytes(bytes: &[u8]) -> Option<DenseVector> {
+        if bytes.len() % 4 != 0 {
+            return None;
+        }
+        Some(DenseVector {
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    /// Returns the number of components of the vector.
+    pub fn dimension(&self) -> usize {
+        self.bytes.len() / 4
+    }
+
+    /// Returns the components of the vector.
+    pub fn components(&self) -> Vec<f32> {
+        let mut components = Vec::with_capacity(self.dimension());
+        decode_components(&self.bytes, &mut components);
+        components
+    }
+
+    /// Returns the bytes encoding of the vector.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+/// Decodes the components of a vector from its bytes encoding into `output`.
+///
+/// `output` is cleared first.
+pub(crate) fn decode_components(bytes: &[u8], output: &mut Vec<f32>) {
+    output.clear();
+    output.extend(
+        bytes
+            .chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]])),
+    );
+}
+
+impl From<Vec<f32>> for DenseVector {
+    fn from(components: Vec<f32>) -> DenseVector {
+        DenseVector::new(&components)
+    }
+}
+
+impl<'a> From<&'a [f32]> for DenseVector {
+    fn from(components: &'a [f32]) -> DenseVector {
+        DenseVector::new(components)
+    }
+}
+
+impl From<DenseVector> for OwnedValue {
+    fn from(dense_vector: DenseVector) -> OwnedValue {
+        OwnedValue::Bytes(dense_vector.bytes)
+    }
+}
+
+impl<'a> Value<'a> for &'a DenseVector {
+    type ArrayIter = Empty<&'a DenseVector>;
+    type ObjectIter = Empty<(&'a str, &'a DenseVector)>;
+    #[inline]
+    fn as_value(&self) -> ReferenceValue<'a, Self> {
+        let dense_vector: &'a DenseVector = *self;
+        ReferenceValue::Leaf(ReferenceValueLeaf::Bytes(dense_vector.as_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DenseVector;
+
+    #[test]
+    fn test_dense_vector_bytes_roundtrip() {
+        let dense_vector = DenseVector::new(&[1.0, -0.5, 0.0, 3.25]);
+        assert_eq!(dense_vector.dimension(), 4);
+        assert_eq!(dense_vector.as_bytes().len(), 16);
+        let decoded = DenseVector::from_bytes(dense_vector.as_bytes()).unwrap();
+        assert_eq!(decoded.components(), vec![1.0, -0.5, 0.0, 3.25]);
+        assert!(DenseVector::from_bytes(&[0u8; 6]).is_none());
+    }
+}
@@ -0,0 +1,94 @@
+use std::ops::BitOr;
+
+use serde::{Deserialize, Serialize};
+
+use super::flags::{FastFlag, SchemaFlagList, StoredFlag};
+
+/// Define how a dense vector field should be handled by tantivy.
+///
+/// Dense vector fields are never indexed, and always fast: the vectors are stored in a
+/// columnar bytes fast field, to be scanned by the
+/// [`VectorKnnQuery`](crate::query::VectorKnnQuery).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DenseVectorOptions {
+    dimension: usize,
+    stored: bool,
+}
+
+impl DenseVectorOptions {
+    /// Returns the number of components of the vectors of the field.
+    #[inline]
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    /// Returns true if the value is stored.
+    #[inline]
+    pub fn is_stored(&self) -> bool {
+        self.stored
+    }
+
+    /// Set the number of components of the vectors of the field.
+    ///
+    /// Adding a document with a vector of another dimension returns an error.
+    #[must_use]
+    pub fn set_dimension(mut self, dimension: usize) -> DenseVectorOptions {
+        self.dimension = dimension;
+        self
+    }
+
+    /// Set the field as stored.
+    ///
+    /// Only the fields that are set as *stored* are
+    /// persisted into the Tantivy's store.
+    #[must_use]
+    pub fn set_stored(mut self) -> DenseVectorOptions {
+        self.stored = true;
+        self
+    }
+}
+
+impl<T: Into<DenseVectorOptions>> BitOr<T> for DenseVectorOptions {
+    type Output = DenseVectorOptions;
+
+    fn bitor(self, other: T) -> DenseVectorOptions {
+        let other = other.into();
+        DenseVectorOptions {
+            dimension: self.dimension.max(other.dimension),
+            stored: self.stored | other.stored,
+        }
+    }
+}
+
+impl From<()> for DenseVectorOptions {
+    fn from(_: ()) -> Self {
+        Self::default()
+    }
+}
+
+impl From<FastFlag> for DenseVectorOptions {
+    fn from(_: FastFlag) -> Self {
+        // Dense vector fields are always fast.
+        Self::default()
+    }
+}
+
+impl From<StoredFlag> for DenseVectorOptions {
+    fn from(_: StoredFlag) -> Self {
+        DenseVectorOptions {
+            dimension: 0,
+            stored: true,
+        }
+    }
+}
+
+impl<Head, Tail> From<SchemaFlagList<Head, Tail>> for DenseVectorOptions
+where
+    Head: Clone,
+    Tail: Clone,
+    Self: BitOr<Output = Self> + From<Head> + From<Tail>,
+{
+    fn from(head_tail: SchemaFlagList<Head, Tail>) -> Self {
+        Self::from(head_tail.head) | Self::from(head_tail.tail)
+    }
+}
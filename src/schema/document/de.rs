@@ -313,6 +313,24 @@ where R: Read
     fn is_complete(&self) -> bool {
         self.position >= self.length
     }
+
+    /// Advances to the next value of `field`, deserializing and dropping the values of the
+    /// other fields on the way.
+    ///
+    /// Returns `false` if the document has no more values for `field`. Otherwise, the reader is
+    /// left positioned on the type code of the value.
+    pub(crate) fn advance_to_field(&mut self, field: Field) -> Result<bool, DeserializeError> {
+        while !self.is_complete() {
+            let value_field = Field::deserialize(self.reader).map_err(DeserializeError::from)?;
+            self.position += 1;
+            if value_field == field {
+                return Ok(true);
+            }
+            let deserializer = BinaryValueDeserializer::from_reader(self.reader)?;
+            <OwnedValue as ValueDeserialize>::deserialize(deserializer)?;
+        }
+        Ok(false)
+    }
 }
 
 impl<'de, R> DocumentDeserializer<'de> for BinaryDocumentDeserializer<'de, R>
@@ -12,7 +12,7 @@ use crate::schema::document::{
     DeserializeError, Document, DocumentDeserialize, DocumentDeserializer,
 };
 use crate::schema::field_type::ValueParsingError;
-use crate::schema::{Facet, Field, NamedFieldDocument, OwnedValue, Schema};
+use crate::schema::{Facet, Field, FieldType, NamedFieldDocument, OwnedValue, Schema};
 use crate::tokenizer::PreTokenizedString;
 
 #[repr(packed)]
@@ -212,7 +212,10 @@ impl CompactDoc {
                 let field_entry = schema.get_field_entry(field);
                 let field_type = field_entry.field_type();
                 match json_value {
-                    serde_json::Value::Array(json_items) => {
+                    // The array of a dense vector field is a single value.
+                    serde_json::Value::Array(json_items)
+                        if !matches!(field_type, FieldType::DenseVector(_)) =>
+                    {
                         for json_item in json_items {
                             let value = field_type
                                 .value_from_json(json_item)
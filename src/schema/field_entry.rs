@@ -3,8 +3,8 @@ use serde::{Deserialize, Serialize};
 use super::ip_options::IpAddrOptions;
 use crate::schema::bytes_options::BytesOptions;
 use crate::schema::{
-    is_valid_field_name, DateOptions, FacetOptions, FieldType, JsonObjectOptions, NumericOptions,
-    TextOptions,
+    is_valid_field_name, DateOptions, DenseVectorOptions, FacetOptions, FieldType,
+    JsonObjectOptions, NumericOptions, TextOptions,
 };
 
 /// A `FieldEntry` represents a field and its configuration.
@@ -12,11 +12,14 @@ use crate::schema::{
 ///
 /// It consists of
 /// - a field name
+/// - the aliases of the field name
 /// - a field type, itself wrapping up options describing
 /// how the field should be indexed.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct FieldEntry {
     name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    aliases: Vec<String>,
     #[serde(flatten)]
     field_type: FieldType,
 }
@@ -27,6 +30,7 @@ impl FieldEntry {
         assert!(is_valid_field_name(&field_name));
         FieldEntry {
             name: field_name,
+            aliases: Vec::new(),
             field_type,
         }
     }
@@ -81,11 +85,31 @@ impl FieldEntry {
         Self::new(field_name, FieldType::JsonObject(json_object_options))
     }
 
+    /// Creates a field entry for a dense vector field
+    pub fn new_dense_vector(
+        field_name: String,
+        dense_vector_options: DenseVectorOptions,
+    ) -> FieldEntry {
+        Self::new(field_name, FieldType::DenseVector(dense_vector_options))
+    }
+
     /// Returns the name of the field
     pub fn name(&self) -> &str {
         &self.name
     }
 
+    /// Returns the aliases of the field name.
+    ///
+    /// See [`SchemaBuilder::add_alias`](crate::schema::SchemaBuilder::add_alias).
+    pub fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+
+    pub(crate) fn add_alias(&mut self, alias: String) {
+        assert!(is_valid_field_name(&alias));
+        self.aliases.push(alias);
+    }
+
     /// Returns the field type
     pub fn field_type(&self) -> &FieldType {
         &self.field_type
@@ -130,6 +154,7 @@ impl FieldEntry {
             FieldType::Bytes(ref options) => options.is_stored(),
             FieldType::JsonObject(ref options) => options.is_stored(),
             FieldType::IpAddr(ref options) => options.is_stored(),
+            FieldType::DenseVector(ref options) => options.is_stored(),
         }
     }
 }
@@ -10,11 +10,12 @@ use thiserror::Error;
 
 use super::ip_options::IpAddrOptions;
 use super::IntoIpv6Addr;
+use crate::query::Bm25Params;
 use crate::schema::bytes_options::BytesOptions;
 use crate::schema::facet_options::FacetOptions;
 use crate::schema::{
-    DateOptions, Facet, IndexRecordOption, JsonObjectOptions, NumericOptions, OwnedValue,
-    TextFieldIndexing, TextOptions,
+    DateOptions, DenseVector, DenseVectorOptions, Facet, IndexRecordOption, JsonObjectOptions,
+    NumericOptions, OwnedValue, TextFieldIndexing, TextOptions,
 };
 use crate::time::format_description::well_known::Rfc3339;
 use crate::time::OffsetDateTime;
@@ -71,6 +72,8 @@ pub enum Type {
     Json = b'j',
     /// IpAddr
     IpAddr = b'p',
+    /// `tantivy::schema::DenseVector`. Stored as bytes in the fast fields.
+    DenseVector = b'v',
 }
 
 impl From<ColumnType> for Type {
@@ -88,7 +91,7 @@ impl From<ColumnType> for Type {
     }
 }
 
-const ALL_TYPES: [Type; 10] = [
+const ALL_TYPES: [Type; 11] = [
     Type::Str,
     Type::U64,
     Type::I64,
@@ -99,6 +102,7 @@ const ALL_TYPES: [Type; 10] = [
     Type::Bytes,
     Type::Json,
     Type::IpAddr,
+    Type::DenseVector,
 ];
 
 impl Type {
@@ -127,6 +131,7 @@ impl Type {
             Type::Bytes => "Bytes",
             Type::Json => "Json",
             Type::IpAddr => "IpAddr",
+            Type::DenseVector => "DenseVector",
         }
     }
 
@@ -145,6 +150,7 @@ impl Type {
             b'b' => Some(Type::Bytes),
             b'j' => Some(Type::Json),
             b'p' => Some(Type::IpAddr),
+            b'v' => Some(Type::DenseVector),
             _ => None,
         }
     }
@@ -177,6 +183,8 @@ pub enum FieldType {
     JsonObject(JsonObjectOptions),
     /// IpAddr field
     IpAddr(IpAddrOptions),
+    /// Dense vector of `f32`, stored as a bytes fast field
+    DenseVector(DenseVectorOptions),
 }
 
 impl FieldType {
@@ -193,6 +201,7 @@ impl FieldType {
             FieldType::Bytes(_) => Type::Bytes,
             FieldType::JsonObject(_) => Type::Json,
             FieldType::IpAddr(_) => Type::IpAddr,
+            FieldType::DenseVector(_) => Type::DenseVector,
         }
     }
 
@@ -219,6 +228,7 @@ impl FieldType {
             FieldType::Bytes(ref bytes_options) => bytes_options.is_indexed(),
             FieldType::JsonObject(ref json_object_options) => json_object_options.is_indexed(),
             FieldType::IpAddr(ref ip_addr_options) => ip_addr_options.is_indexed(),
+            FieldType::DenseVector(_) => false,
         }
     }
 
@@ -243,6 +253,22 @@ impl FieldType {
         }
     }
 
+    /// Returns the BM25 parameters used to score the field.
+    ///
+    /// Only text and JSON fields can configure them, other fields use the default ones.
+    pub fn bm25_params(&self) -> Bm25Params {
+        let text_indexing = match self {
+            FieldType::Str(text_options) => text_options.get_indexing_options(),
+            FieldType::JsonObject(json_object_options) => {
+                json_object_options.get_text_indexing_options()
+            }
+            _ => None,
+        };
+        text_indexing
+            .map(TextFieldIndexing::bm25_params)
+            .unwrap_or_default()
+    }
+
     /// returns true if the field is fast.
     pub fn is_fast(&self) -> bool {
         match *self {
@@ -256,6 +282,7 @@ impl FieldType {
             FieldType::IpAddr(ref ip_addr_options) => ip_addr_options.is_fast(),
             FieldType::Facet(_) => true,
             FieldType::JsonObject(ref json_object_options) => json_object_options.is_fast(),
+            FieldType::DenseVector(_) => true,
         }
     }
 
@@ -275,6 +302,7 @@ impl FieldType {
             FieldType::Bytes(ref bytes_options) => bytes_options.fieldnorms(),
             FieldType::JsonObject(ref _json_object_options) => false,
             FieldType::IpAddr(ref ip_addr_options) => ip_addr_options.fieldnorms(),
+            FieldType::DenseVector(_) => false,
         }
     }
 
@@ -326,6 +354,7 @@ impl FieldType {
                     None
                 }
             }
+            FieldType::DenseVector(_) => None,
         }
     }
 
@@ -427,6 +456,10 @@ impl FieldType {
 
                         Ok(OwnedValue::IpAddr(ip_addr.into_ipv6_addr()))
                     }
+                    FieldType::DenseVector(_) => Err(ValueParsingError::TypeError {
+                        expected: "an array of numbers",
+                        json: JsonValue::String(field_text),
+                    }),
                 }
             }
             JsonValue::Number(field_val_num) => match self {
@@ -486,6 +519,39 @@ impl FieldType {
                     expected: "a string with an ip addr",
                     json: JsonValue::Number(field_val_num),
                 }),
+                FieldType::DenseVector(_) => Err(ValueParsingError::TypeError {
+                    expected: "an array of numbers",
+                    json: JsonValue::Number(field_val_num),
+                }),
+            },
+            JsonValue::Array(json_items) => match self {
+                FieldType::DenseVector(options) => {
+                    let components: Option<Vec<f32>> = json_items
+                        .iter()
+                        .map(|json_item| json_item.as_f64().map(|val| val as f32))
+                        .collect();
+                    let Some(components) = components else {
+                        return Err(ValueParsingError::TypeError {
+                            expected: "an array of numbers",
+                            json: JsonValue::Array(json_items),
+                        });
+                    };
+                    if components.len() != options.dimension() {
+                        return Err(ValueParsingError::ParseError {
+                            error: format!(
+                                "Expected a vector of dimension {}, got {}",
+                                options.dimension(),
+                                components.len()
+                            ),
+                            json: JsonValue::Array(json_items),
+                        });
+                    }
+                    Ok(DenseVector::new(&components).into())
+                }
+                _ => Err(ValueParsingError::TypeError {
+                    expected: self.value_type().name(),
+                    json: JsonValue::Array(json_items),
+                }),
             },
             JsonValue::Object(json_map) => match self {
                 FieldType::Str(_) => {
@@ -540,10 +606,6 @@ impl FieldType {
                     json: JsonValue::Null,
                 }),
             },
-            _ => Err(ValueParsingError::TypeError {
-                expected: self.value_type().name(),
-                json: json.clone(),
-            }),
         }
     }
 }
@@ -558,7 +620,7 @@ mod tests {
         Document, NumericOptions, OwnedValue, Schema, TextOptions, Type, COERCE, INDEXED,
     };
     use crate::time::{Date, Month, PrimitiveDateTime, Time};
-    use crate::tokenizer::{PreTokenizedString, Token};
+    use crate::tokenizer::{PreTokenizedString, Token, TokenType};
     use crate::{DateTime, TantivyDocument};
 
     #[test]
@@ -739,6 +801,9 @@ mod tests {
                     position: 0,
                     text: String::from("The"),
                     position_length: 1,
+                    keyword: false,
+                    token_type: TokenType::default(),
+                    trailing_punctuation: false,
                 },
                 Token {
                     offset_from: 4,
@@ -746,6 +811,9 @@ mod tests {
                     position: 1,
                     text: String::from("Old"),
                     position_length: 1,
+                    keyword: false,
+                    token_type: TokenType::default(),
+                    trailing_punctuation: false,
                 },
                 Token {
                     offset_from: 8,
@@ -753,6 +821,9 @@ mod tests {
                     position: 2,
                     text: String::from("Man"),
                     position_length: 1,
+                    keyword: false,
+                    token_type: TokenType::default(),
+                    trailing_punctuation: false,
                 },
             ],
         });
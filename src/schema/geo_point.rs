@@ -0,0 +1,194 @@
+use std::iter::Empty;
+
+use serde::{Deserialize, Serialize};
+
+use crate::schema::document::{ReferenceValue, ReferenceValueLeaf, Value};
+use crate::schema::OwnedValue;
+
+/// Mean radius of the earth, in meters, used to compute the distances between geo points.
+pub(crate) const EARTH_RADIUS_METERS: f64 = 6_371_008.8;
+
+/// A point on earth, defined by its latitude and longitude in degrees.
+///
+/// Geo points are indexed in the `u64` fast fields created with
+/// [`SchemaBuilder::add_geo_field`](crate::schema::SchemaBuilder::add_geo_field), as the Morton
+/// code (i.e. the interleaved bits) of their quantized latitude and longitude.
+/// The quantization step is below 10<sup>-7</sup> degrees, i.e. about a centimeter.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GeoPoint {
+    lat: f64,
+    lon: f64,
+}
+
+impl GeoPoint {
+    /// Creates a new `GeoPoint` from its latitude and longitude in degrees.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the latitude is not within `[-90, 90]` or the longitude is not within
+    /// `[-180, 180]`.
+    pub fn new(lat: f64, lon: f64) -> GeoPoint {
+        assert!(is_valid_lat(lat), "Invalid latitude {lat}");
+        assert!(is_valid_lon(lon), "Invalid longitude {lon}");
+        GeoPoint { lat, lon }
+    }
+
+    /// Returns the latitude, in degrees.
+    pub fn lat(&self) -> f64 {
+        self.lat
+    }
+
+    /// Returns the longitude, in degrees.
+    pub fn lon(&self) -> f64 {
+        self.lon
+    }
+
+    /// Returns the great-circle distance to another point in meters, computed with the
+    /// haversine formula on a spherical earth.
+    ///
+    /// The error compared to the actual ellipsoidal shape of the earth is below 0.5%.
+    pub fn distance(&self, other: &GeoPoint) -> f64 {
+        let lat1 = self.lat.to_radians();
+        let lat2 = other.lat.to_radians();
+        let half_delta_lat = (lat2 - lat1) / 2.0;
+        let half_delta_lon = (other.lon - self.lon).to_radians() / 2.0;
+        let haversine =
+            half_delta_lat.sin().powi(2) + lat1.cos() * lat2.cos() * half_delta_lon.sin().powi(2);
+        2.0 * EARTH_RADIUS_METERS * haversine.sqrt().min(1.0).asin()
+    }
+
+    /// Returns the `u64` encoding of the point, as stored in the geo fields.
+    pub fn to_u64(&self) -> u64 {
+        let lat_bits = quantize(self.lat, -90.0, 180.0);
+        let lon_bits = quantize(self.lon, -180.0, 360.0);
+        (spread_bits(lat_bits) << 1) | spread_bits(lon_bits)
+    }
+
+    /// Decodes a point from its `u64` encoding, as stored in the geo fields.
+    ///
+    /// The decoded point may differ from the encoded one by the quantization step.
+    pub fn from_u64(val: u64) -> GeoPoint {
+        let lat_bits = compact_bits(val >> 1);
+        let lon_bits = compact_bits(val);
+        GeoPoint {
+            lat: dequantize(lat_bits, -90.0, 180.0),
+            lon: dequantize(lon_bits, -180.0, 360.0),
+        }
+    }
+}
+
+pub(crate) fn is_valid_lat(lat: f64) -> bool {
+    (-90.0..=90.0).contains(&lat)
+}
+
+pub(crate) fn is_valid_lon(lon: f64) -> bool {
+    (-180.0..=180.0).contains(&lon)
+}
+
+fn quantize(val: f64, min: f64, len: f64) -> u32 {
+    ((val - min) / len * u32::MAX as f64).round() as u32
+}
+
+fn dequantize(val: u32, min: f64, len: f64) -> f64 {
+    min + val as f64 / u32::MAX as f64 * len
+}
+
+/// Spreads the 32 bits of `val` over the even bits of a `u64`.
+fn spread_bits(val: u32) -> u64 {
+    let mut val = val as u64;
+    val = (val | (val << 16)) & 0x0000_FFFF_0000_FFFF;
+    val = (val | (val << 8)) & 0x00FF_00FF_00FF_00FF;
+    val = (val | (val << 4)) & 0x0F0F_0F0F_0F0F_0F0F;
+    val = (val | (val << 2)) & 0x3333_3333_3333_3333;
+    (val | (val << 1)) & 0x5555_5555_5555_5555
+}
+
+/// Inverse of `spread_bits`: gathers the even bits of `val`.
+fn compact_bits(val: u64) -> u32 {
+    let mut val = val & 0x5555_5555_5555_5555;
+    val = (val | (val >> 1)) & 0x3333_3333_3333_3333;
+    val = (val | (val >> 2)) & 0x0F0F_0F0F_0F0F_0F0F;
+    val = (val | (val >> 4)) & 0x00FF_00FF_00FF_00FF;
+    val = (val | (val >> 8)) & 0x0000_FFFF_0000_FFFF;
+    (val | (val >> 16)) as u32
+}
+
+impl From<GeoPoint> for OwnedValue {
+    fn from(geo_point: GeoPoint) -> OwnedValue {
+        OwnedValue::U64(geo_point.to_u64())
+    }
+}
+
+impl<'a> Value<'a> for &'a GeoPoint {
+    type ArrayIter = Empty<&'a GeoPoint>;
+    type ObjectIter = Empty<(&'a str, &'a GeoPoint)>;
+    #[inline]
+    fn as_value(&self) -> ReferenceValue<'a, Self> {
+        ReferenceValue::Leaf(ReferenceValueLeaf::U64(self.to_u64()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GeoPoint;
+
+    #[test]
+    fn test_geo_point_u64_roundtrip() {
+        for (lat, lon) in [
+            (0.0, 0.0),
+            (48.8566, 2.3522),
+            (-36.8485, 174.7633),
+            (90.0, 180.0),
+            (-90.0, -180.0),
+        ] {
+            let decoded = GeoPoint::from_u64(GeoPoint::new(lat, lon).to_u64());
+            assert!((decoded.lat() - lat).abs() < 1e-7);
+            assert!((decoded.lon() - lon).abs() < 1e-7);
+        }
+    }
+
+    #[test]
+    fn test_geo_point_encoding_interleaves_bits() {
+        assert_eq!(GeoPoint::new(-90.0, -180.0).to_u64(), 0);
+        assert_eq!(GeoPoint::new(90.0, 180.0).to_u64(), u64::MAX);
+        assert_eq!(GeoPoint::new(-90.0, 180.0).to_u64(), 0x5555_5555_5555_5555);
+        assert_eq!(GeoPoint::new(90.0, -180.0).to_u64(), 0xAAAA_AAAA_AAAA_AAAA);
+    }
+
+    #[test]
+    fn test_geo_point_distance_near_equator() {
+        let origin = GeoPoint::new(0.0, 0.0);
+        // One degree along the equator or a meridian.
+        assert!((origin.distance(&GeoPoint::new(0.0, 1.0)) - 111_195.08).abs() < 1.0);
+        assert!((origin.distance(&GeoPoint::new(1.0, 0.0)) - 111_195.08).abs() < 1.0);
+        assert!((origin.distance(&GeoPoint::new(0.0, 0.001)) - 111.195).abs() < 0.01);
+        assert_eq!(origin.distance(&origin), 0.0);
+        // Across the antimeridian.
+        let distance = GeoPoint::new(0.0, 179.5).distance(&GeoPoint::new(0.0, -179.5));
+        assert!((distance - 111_195.08).abs() < 1.0);
+        // Antipodes.
+        let distance = origin.distance(&GeoPoint::new(0.0, 180.0));
+        assert!((distance - std::f64::consts::PI * 6_371_008.8).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_geo_point_distance_at_poles() {
+        let north_pole = GeoPoint::new(90.0, 0.0);
+        // All of the longitudes are the same point at the poles.
+        assert!(north_pole.distance(&GeoPoint::new(90.0, 120.0)) < 1e-6);
+        assert!((north_pole.distance(&GeoPoint::new(89.0, -75.0)) - 111_195.08).abs() < 1.0);
+        // Over the pole.
+        let distance = GeoPoint::new(89.0, 0.0).distance(&GeoPoint::new(89.0, 180.0));
+        assert!((distance - 222_390.16).abs() < 1.0);
+        let south_pole = GeoPoint::new(-90.0, 0.0);
+        assert!(
+            (north_pole.distance(&south_pole) - std::f64::consts::PI * 6_371_008.8).abs() < 1.0
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid latitude")]
+    fn test_geo_point_invalid_lat() {
+        GeoPoint::new(91.0, 0.0);
+    }
+}
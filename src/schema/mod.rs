@@ -117,8 +117,11 @@ mod field_type;
 
 mod bytes_options;
 mod date_time_options;
+mod dense_vector;
+mod dense_vector_options;
 mod field;
 mod flags;
+mod geo_point;
 mod index_record_option;
 mod ip_options;
 mod json_object_options;
@@ -130,6 +133,9 @@ use columnar::ColumnType;
 
 pub use self::bytes_options::BytesOptions;
 pub use self::date_time_options::{DateOptions, DateTimePrecision, DATE_TIME_PRECISION_INDEXED};
+pub(crate) use self::dense_vector::decode_components;
+pub use self::dense_vector::DenseVector;
+pub use self::dense_vector_options::DenseVectorOptions;
 pub use self::document::{DocParsingError, Document, OwnedValue, TantivyDocument, Value};
 pub(crate) use self::facet::FACET_SEP_BYTE;
 pub use self::facet::{Facet, FacetParseError};
@@ -138,6 +144,8 @@ pub use self::field::Field;
 pub use self::field_entry::FieldEntry;
 pub use self::field_type::{FieldType, Type};
 pub use self::flags::{COERCE, FAST, INDEXED, STORED};
+pub use self::geo_point::GeoPoint;
+pub(crate) use self::geo_point::{is_valid_lat, is_valid_lon, EARTH_RADIUS_METERS};
 pub use self::index_record_option::IndexRecordOption;
 pub use self::ip_options::{IntoIpv6Addr, IpAddrOptions};
 pub use self::json_object_options::JsonObjectOptions;
@@ -167,6 +175,7 @@ pub(crate) fn value_type_to_column_type(typ: Type) -> Option<ColumnType> {
         Type::Facet => Some(ColumnType::Str),
         Type::Bytes => Some(ColumnType::Bytes),
         Type::IpAddr => Some(ColumnType::IpAddr),
+        Type::DenseVector => Some(ColumnType::Bytes),
         Type::Json => None,
     }
 }
@@ -16,6 +16,8 @@ pub struct NumericOptions {
     stored: bool,
     #[serde(skip_serializing_if = "is_false")]
     coerce: bool,
+    #[serde(skip_serializing_if = "is_false")]
+    geo: bool,
 }
 
 fn is_false(val: &bool) -> bool {
@@ -37,6 +39,8 @@ struct NumericOptionsDeser {
     stored: bool,
     #[serde(default)]
     coerce: bool,
+    #[serde(default)]
+    geo: bool,
 }
 
 impl From<NumericOptionsDeser> for NumericOptions {
@@ -47,6 +51,7 @@ impl From<NumericOptionsDeser> for NumericOptions {
             fast: deser.fast,
             stored: deser.stored,
             coerce: deser.coerce,
+            geo: deser.geo,
         }
     }
 }
@@ -82,6 +87,12 @@ impl NumericOptions {
         self.coerce
     }
 
+    /// Returns true iff the field holds [`GeoPoint`](crate::schema::GeoPoint)s.
+    #[inline]
+    pub fn is_geo(&self) -> bool {
+        self.geo
+    }
+
     /// Try to coerce values if they are not a number. Defaults to false.
     #[must_use]
     pub fn set_coerce(mut self) -> Self {
@@ -129,6 +140,17 @@ impl NumericOptions {
         self.fast = true;
         self
     }
+
+    /// Set the field as a geo field.
+    ///
+    /// This is done by
+    /// [`SchemaBuilder::add_geo_field`](crate::schema::SchemaBuilder::add_geo_field)
+    /// and is required by the geo queries.
+    #[must_use]
+    pub(crate) fn set_geo(mut self) -> NumericOptions {
+        self.geo = true;
+        self
+    }
 }
 
 impl From<()> for NumericOptions {
@@ -145,6 +167,7 @@ impl From<CoerceFlag> for NumericOptions {
             stored: false,
             fast: false,
             coerce: true,
+            geo: false,
         }
     }
 }
@@ -157,6 +180,7 @@ impl From<FastFlag> for NumericOptions {
             stored: false,
             fast: true,
             coerce: false,
+            geo: false,
         }
     }
 }
@@ -169,6 +193,7 @@ impl From<StoredFlag> for NumericOptions {
             stored: true,
             fast: false,
             coerce: false,
+            geo: false,
         }
     }
 }
@@ -181,6 +206,7 @@ impl From<IndexedFlag> for NumericOptions {
             stored: false,
             fast: false,
             coerce: false,
+            geo: false,
         }
     }
 }
@@ -196,6 +222,7 @@ impl<T: Into<NumericOptions>> BitOr<T> for NumericOptions {
             stored: self.stored | other.stored,
             fast: self.fast | other.fast,
             coerce: self.coerce | other.coerce,
+            geo: self.geo | other.geo,
         }
     }
 }
@@ -230,6 +257,7 @@ mod tests {
                 fast: false,
                 stored: false,
                 coerce: false,
+                geo: false,
             }
         );
     }
@@ -249,6 +277,7 @@ mod tests {
                 fast: false,
                 stored: false,
                 coerce: false,
+                geo: false,
             }
         );
     }
@@ -269,6 +298,7 @@ mod tests {
                 fast: false,
                 stored: false,
                 coerce: false,
+                geo: false,
             }
         );
     }
@@ -290,6 +320,7 @@ mod tests {
                 fast: false,
                 stored: false,
                 coerce: false,
+                geo: false,
             }
         );
     }
@@ -312,6 +343,7 @@ mod tests {
                 fast: false,
                 stored: false,
                 coerce: true,
+                geo: false,
             }
         );
     }
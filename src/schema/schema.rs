@@ -123,6 +123,26 @@ impl SchemaBuilder {
         self.add_field(field_entry)
     }
 
+    /// Adds a geo point field.
+    /// Returns the associated field handle.
+    ///
+    /// A geo field is a `u64` fast field holding [`GeoPoint`](crate::schema::GeoPoint)s in
+    /// their `u64` encoding. It is always fast, so that it can be queried with a
+    /// [`GeoBoundingBoxQuery`](crate::query::GeoBoundingBoxQuery), and it is marked as geo
+    /// in its options (see [`NumericOptions::is_geo`]), so that the geo queries reject the
+    /// other `u64` fields.
+    ///
+    /// # Panics
+    ///
+    /// Panics when field already exists.
+    pub fn add_geo_field<T: Into<NumericOptions>>(
+        &mut self,
+        field_name_str: &str,
+        field_options: T,
+    ) -> Field {
+        self.add_u64_field(field_name_str, field_options.into().set_fast().set_geo())
+    }
+
     /// Adds a ip field.
     /// Returns the associated field handle.
     ///
@@ -184,6 +204,32 @@ impl SchemaBuilder {
         self.add_field(field_entry)
     }
 
+    /// Adds a dense vector field to the schema.
+    ///
+    /// A dense vector field holds [`DenseVector`]s with `dimension` components, e.g. embeddings
+    /// for semantic search. It is not searchable, but it is always fast, so that it can be
+    /// queried with a [`VectorKnnQuery`](crate::query::VectorKnnQuery).
+    ///
+    /// # Panics
+    ///
+    /// Panics when field already exists, or if `dimension` is 0.
+    pub fn add_dense_vector_field<T: Into<DenseVectorOptions>>(
+        &mut self,
+        field_name: &str,
+        dimension: usize,
+        field_options: T,
+    ) -> Field {
+        assert!(
+            dimension > 0,
+            "The dimension of a dense vector field must be positive"
+        );
+        let field_entry = FieldEntry::new_dense_vector(
+            field_name.to_string(),
+            field_options.into().set_dimension(dimension),
+        );
+        self.add_field(field_entry)
+    }
+
     /// Adds a json object field to the schema.
     pub fn add_json_field<T: Into<JsonObjectOptions>>(
         &mut self,
@@ -195,16 +241,44 @@ impl SchemaBuilder {
     }
 
     /// Adds a field entry to the schema in build.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the field name, or one of its aliases, is already a field name or an alias.
     pub fn add_field(&mut self, field_entry: FieldEntry) -> Field {
         let field = Field::from_field_id(self.fields.len() as u32);
-        let field_name = field_entry.name().to_string();
-        if let Some(_previous_value) = self.fields_map.insert(field_name, field) {
-            panic!("Field already exists in schema {}", field_entry.name());
-        };
+        let field_names = std::iter::once(field_entry.name())
+            .chain(field_entry.aliases().iter().map(String::as_str));
+        for field_name in field_names {
+            if let Some(_previous_value) = self.fields_map.insert(field_name.to_string(), field) {
+                panic!("Field already exists in schema {field_name}");
+            };
+        }
         self.fields.push(field_entry);
         field
     }
 
+    /// Adds an alias to a field of the schema in build.
+    ///
+    /// The alias resolves to the field in [`Schema::get_field`] and [`Schema::find_field`],
+    /// which means it can be used in queries and in the documents parsed from JSON, e.g. to keep
+    /// the old name of a renamed field working. The alias is persisted with the schema.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the field does not exist, or when the alias is already a field name or an
+    /// alias.
+    pub fn add_alias(&mut self, alias: &str, field_name: &str) {
+        let field = *self
+            .fields_map
+            .get(field_name)
+            .unwrap_or_else(|| panic!("Field does not exist in schema {field_name}"));
+        if let Some(_previous_value) = self.fields_map.insert(alias.to_string(), field) {
+            panic!("Field already exists in schema {alias}");
+        };
+        self.fields[field.field_id() as usize].add_alias(alias.to_string());
+    }
+
     /// Finalize the creation of a `Schema`
     /// This will consume your `SchemaBuilder`
     pub fn build(self) -> Schema {
@@ -431,6 +505,64 @@ mod tests {
         assert_eq!(&super::locate_splitting_dots(r"a\..b.c"), &[3, 5]);
     }
 
+    #[test]
+    fn test_schema_alias() {
+        let mut schema_builder = Schema::builder();
+        let headline_field = schema_builder.add_text_field("headline", TEXT | STORED);
+        let attributes_field = schema_builder.add_json_field("attributes", STORED);
+        schema_builder.add_alias("title", "headline");
+        schema_builder.add_alias("attrs", "attributes");
+        let schema = schema_builder.build();
+        assert_eq!(schema.get_field("title").unwrap(), headline_field);
+        assert_eq!(schema.get_field("headline").unwrap(), headline_field);
+        assert_eq!(schema.get_field_name(headline_field), "headline");
+        assert_eq!(
+            schema.get_field_entry(headline_field).aliases(),
+            &["title".to_string()]
+        );
+        assert_eq!(
+            schema.find_field("attrs.color"),
+            Some((attributes_field, "color"))
+        );
+
+        let doc = TantivyDocument::parse_json(&schema, r#"{"title": "hello"}"#).unwrap();
+        assert_eq!(doc.to_json(&schema), r#"{"headline":["hello"]}"#);
+
+        // The aliases are persisted with the schema.
+        let schema_json = serde_json::to_string(&schema).unwrap();
+        let deserialized_schema: Schema = serde_json::from_str(&schema_json).unwrap();
+        assert_eq!(deserialized_schema, schema);
+        assert_eq!(
+            deserialized_schema.get_field("title").unwrap(),
+            headline_field
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Field already exists in schema title")]
+    fn test_schema_alias_of_existing_field_panics() {
+        let mut schema_builder = Schema::builder();
+        schema_builder.add_text_field("headline", TEXT);
+        schema_builder.add_text_field("title", TEXT);
+        schema_builder.add_alias("title", "headline");
+    }
+
+    #[test]
+    #[should_panic(expected = "Field already exists in schema title")]
+    fn test_schema_field_named_like_alias_panics() {
+        let mut schema_builder = Schema::builder();
+        schema_builder.add_text_field("headline", TEXT);
+        schema_builder.add_alias("title", "headline");
+        schema_builder.add_text_field("title", TEXT);
+    }
+
+    #[test]
+    #[should_panic(expected = "Field does not exist in schema headline")]
+    fn test_schema_alias_of_unknown_field_panics() {
+        let mut schema_builder = Schema::builder();
+        schema_builder.add_alias("title", "headline");
+    }
+
     #[test]
     pub fn is_indexed_test() {
         let mut schema_builder = Schema::builder();
@@ -123,6 +123,14 @@ impl Term {
         Term::from_fast_value(field, &val.truncate(DATE_TIME_PRECISION_INDEXED))
     }
 
+    /// Builds a term given a field, and a `DateTime` value, without truncating the value.
+    ///
+    /// Such terms are only used as the bounds of range queries, which truncate them at the
+    /// precision of the field.
+    pub(crate) fn from_field_date_untruncated(field: Field, val: DateTime) -> Term {
+        Term::from_fast_value(field, &val)
+    }
+
     /// Creates a `Term` given a facet.
     pub fn from_facet(field: Field, facet: &Facet) -> Term {
         let facet_encoded_str = facet.encoded_str();
@@ -505,7 +513,7 @@ where B: AsRef<[u8]>
             Type::Facet => {
                 write_opt(f, self.as_facet())?;
             }
-            Type::Bytes => {
+            Type::Bytes | Type::DenseVector => {
                 write_opt(f, self.as_bytes())?;
             }
             Type::Json => {
@@ -4,6 +4,7 @@ use std::ops::BitOr;
 use serde::{Deserialize, Serialize};
 
 use super::flags::{CoerceFlag, FastFlag};
+use crate::query::Bm25Params;
 use crate::schema::flags::{SchemaFlagList, StoredFlag};
 use crate::schema::IndexRecordOption;
 
@@ -194,6 +195,7 @@ impl TokenizerName {
 /// - The name of the `Tokenizer` that should be used to process the field.
 /// - Flag indicating, if fieldnorms should be stored (See [fieldnorm](crate::fieldnorm)). Defaults
 ///   to `true`.
+/// - The [`Bm25Params`] used to score the field. Defaults to the usual `k1 = 1.2` and `b = 0.75`.
 #[derive(Clone, PartialEq, Debug, Eq, Serialize, Deserialize)]
 pub struct TextFieldIndexing {
     #[serde(default)]
@@ -202,6 +204,9 @@ pub struct TextFieldIndexing {
     fieldnorms: bool,
     #[serde(default)]
     tokenizer: TokenizerName,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bm25: Option<Bm25Params>,
 }
 
 pub(crate) fn default_fieldnorms() -> bool {
@@ -214,6 +219,7 @@ impl Default for TextFieldIndexing {
             tokenizer: TokenizerName::default(),
             record: IndexRecordOption::default(),
             fieldnorms: default_fieldnorms(),
+            bm25: None,
         }
     }
 }
@@ -258,6 +264,21 @@ impl TextFieldIndexing {
     pub fn index_option(&self) -> IndexRecordOption {
         self.record
     }
+
+    /// Sets the BM25 parameters used to score the field.
+    ///
+    /// e.g. a `b` of 0 removes the length normalization, so that the matches in a long
+    /// value are not penalized.
+    #[must_use]
+    pub fn set_bm25_params(mut self, bm25_params: Bm25Params) -> TextFieldIndexing {
+        self.bm25 = Some(bm25_params);
+        self
+    }
+
+    /// Returns the BM25 parameters used to score the field.
+    pub fn bm25_params(&self) -> Bm25Params {
+        self.bm25.unwrap_or_default()
+    }
 }
 
 /// The field will be untokenized and indexed.
@@ -266,6 +287,7 @@ pub const STRING: TextOptions = TextOptions {
         tokenizer: TokenizerName::from_static(NO_TOKENIZER_NAME),
         fieldnorms: true,
         record: IndexRecordOption::Basic,
+        bm25: None,
     }),
     stored: false,
     fast: FastFieldTextOptions::IsEnabled(false),
@@ -278,6 +300,7 @@ pub const TEXT: TextOptions = TextOptions {
         tokenizer: TokenizerName::from_static(DEFAULT_TOKENIZER_NAME),
         fieldnorms: true,
         record: IndexRecordOption::WithFreqsAndPositions,
+        bm25: None,
     }),
     stored: false,
     coerce: false,
@@ -53,6 +53,9 @@
 //!
 //! SnippetGenerator needs to be created from the `Searcher` and the query, and the field on which
 //! the `SnippetGenerator` should generate the snippets.
+//!
+//! [`MultiFieldSnippetGenerator`] generates the snippets of several fields at once, e.g. a title
+//! and a body, and can pick the best one among them.
 
 use std::cmp::Ordering;
 use std::collections::{BTreeMap, BTreeSet};
@@ -115,18 +118,22 @@ impl FragmentCandidate {
 pub struct Snippet {
     fragment: String,
     highlighted: Vec<Range<usize>>,
+    score: Score,
     snippet_prefix: String,
     snippet_postfix: String,
+    html_escape: bool,
 }
 
 impl Snippet {
     /// Create a new `Snippet`.
-    fn new(fragment: &str, highlighted: Vec<Range<usize>>) -> Self {
+    fn new(fragment: &str, highlighted: Vec<Range<usize>>, score: Score) -> Self {
         Self {
             fragment: fragment.to_string(),
             highlighted,
+            score,
             snippet_prefix: DEFAULT_SNIPPET_PREFIX.to_string(),
             snippet_postfix: DEFAULT_SNIPPET_POSTFIX.to_string(),
+            html_escape: true,
         }
     }
 
@@ -135,8 +142,10 @@ impl Snippet {
         Snippet {
             fragment: String::new(),
             highlighted: Vec::new(),
+            score: 0.0,
             snippet_prefix: String::new(),
             snippet_postfix: String::new(),
+            html_escape: true,
         }
     }
 
@@ -146,23 +155,32 @@ impl Snippet {
     }
 
     /// Returns a highlighted html from the `Snippet`.
+    ///
+    /// Unless disabled with [`Snippet::set_html_escape`], the text of the fragment is escaped
+    /// before the highlight prefix and postfix are inserted, so that they are kept as is.
     pub fn to_html(&self) -> String {
         let mut html = String::new();
         let mut start_from: usize = 0;
 
         for item in collapse_overlapped_ranges(&self.highlighted) {
-            html.push_str(&encode_minimal(&self.fragment[start_from..item.start]));
+            self.push_text(&mut html, &self.fragment[start_from..item.start]);
             html.push_str(&self.snippet_prefix);
-            html.push_str(&encode_minimal(&self.fragment[item.clone()]));
+            self.push_text(&mut html, &self.fragment[item.clone()]);
             html.push_str(&self.snippet_postfix);
             start_from = item.end;
         }
-        html.push_str(&encode_minimal(
-            &self.fragment[start_from..self.fragment.len()],
-        ));
+        self.push_text(&mut html, &self.fragment[start_from..self.fragment.len()]);
         html
     }
 
+    fn push_text(&self, html: &mut String, text: &str) {
+        if self.html_escape {
+            html.push_str(&encode_minimal(text));
+        } else {
+            html.push_str(text);
+        }
+    }
+
     /// Returns the fragment of text used in the  snippet.
     pub fn fragment(&self) -> &str {
         &self.fragment
@@ -173,11 +191,25 @@ impl Snippet {
         &self.highlighted
     }
 
+    /// Returns the score of the fragment of the `Snippet`, i.e. the sum of the scores of its
+    /// highlighted terms. The score of an empty snippet is 0.
+    pub fn score(&self) -> Score {
+        self.score
+    }
+
     /// Sets highlighted prefix and postfix.
     pub fn set_snippet_prefix_postfix(&mut self, prefix: &str, postfix: &str) {
         self.snippet_prefix = prefix.to_string();
         self.snippet_postfix = postfix.to_string()
     }
+
+    /// Sets whether the text of the fragment should be html escaped by [`Snippet::to_html`].
+    /// Default is `true`.
+    ///
+    /// Disabling it is only safe if the text does not come from untrusted sources.
+    pub fn set_html_escape(&mut self, html_escape: bool) {
+        self.html_escape = html_escape;
+    }
 }
 
 /// Returns a non-empty list of "good" fragments.
@@ -249,7 +281,7 @@ fn select_best_fragment_combination(fragments: &[FragmentCandidate], text: &str)
             .iter()
             .map(|item| item.start - fragment.start_offset..item.end - fragment.start_offset)
             .collect();
-        Snippet::new(fragment_text, highlighted)
+        Snippet::new(fragment_text, highlighted, fragment.score)
     } else {
         // When there are no fragments to chose from,
         // for now create an empty snippet.
@@ -350,6 +382,9 @@ pub struct SnippetGenerator {
     tokenizer: TextAnalyzer,
     field: Field,
     max_num_chars: usize,
+    snippet_prefix: String,
+    snippet_postfix: String,
+    html_escape: bool,
 }
 
 impl SnippetGenerator {
@@ -365,6 +400,9 @@ impl SnippetGenerator {
             tokenizer,
             field,
             max_num_chars,
+            snippet_prefix: DEFAULT_SNIPPET_PREFIX.to_string(),
+            snippet_postfix: DEFAULT_SNIPPET_POSTFIX.to_string(),
+            html_escape: true,
         }
     }
     /// Creates a new snippet generator
@@ -394,12 +432,12 @@ impl SnippetGenerator {
             }
         }
         let tokenizer = searcher.index().tokenizer_for_field(field)?;
-        Ok(SnippetGenerator {
+        Ok(SnippetGenerator::new(
             terms_text,
             tokenizer,
             field,
-            max_num_chars: DEFAULT_MAX_NUM_CHARS,
-        })
+            DEFAULT_MAX_NUM_CHARS,
+        ))
     }
 
     /// Sets a maximum number of chars. Default is 150.
@@ -407,6 +445,26 @@ impl SnippetGenerator {
         self.max_num_chars = max_num_chars;
     }
 
+    /// Sets the markup wrapping the highlighted terms of the generated snippets.
+    /// Default is `<b>` and `</b>`.
+    pub fn set_highlight_tags(&mut self, open: &str, close: &str) {
+        self.snippet_prefix = open.to_string();
+        self.snippet_postfix = close.to_string();
+    }
+
+    /// Sets whether the text of the generated snippets should be html escaped.
+    /// Default is `true`.
+    ///
+    /// See [`Snippet::set_html_escape`].
+    pub fn set_html_escape(&mut self, html_escape: bool) {
+        self.html_escape = html_escape;
+    }
+
+    /// Returns the field on which the snippets are generated.
+    pub fn field(&self) -> Field {
+        self.field
+    }
+
     #[cfg(test)]
     pub fn terms_text(&self) -> &BTreeMap<String, Score> {
         &self.terms_text
@@ -441,7 +499,109 @@ impl SnippetGenerator {
             &self.terms_text,
             self.max_num_chars,
         );
-        select_best_fragment_combination(&fragment_candidates[..], text)
+        let mut snippet = select_best_fragment_combination(&fragment_candidates[..], text);
+        if !snippet.is_empty() {
+            snippet.set_snippet_prefix_postfix(&self.snippet_prefix, &self.snippet_postfix);
+        }
+        snippet.set_html_escape(self.html_escape);
+        snippet
+    }
+}
+
+/// `MultiFieldSnippetGenerator`
+///
+/// Generates snippets for several fields of a document, e.g. a title and a body, using one
+/// [`SnippetGenerator`] per field.
+///
+/// The scores of the terms only depend on their document frequency, so the scores of the
+/// snippets of the different fields are comparable, and can be used to pick the best snippet
+/// of a document with [`MultiFieldSnippetGenerator::best_snippet_from_doc`].
+pub struct MultiFieldSnippetGenerator {
+    snippet_generators: Vec<SnippetGenerator>,
+}
+
+impl MultiFieldSnippetGenerator {
+    /// Creates a new multi-field snippet generator, from the `Searcher`, the query and the
+    /// fields on which the snippets should be generated.
+    pub fn create(
+        searcher: &Searcher,
+        query: &dyn Query,
+        fields: &[Field],
+    ) -> crate::Result<MultiFieldSnippetGenerator> {
+        let snippet_generators = fields
+            .iter()
+            .map(|&field| SnippetGenerator::create(searcher, query, field))
+            .collect::<crate::Result<Vec<SnippetGenerator>>>()?;
+        Ok(MultiFieldSnippetGenerator { snippet_generators })
+    }
+
+    /// Sets a maximum number of chars for the snippets of all the fields. Default is 150.
+    pub fn set_max_num_chars(&mut self, max_num_chars: usize) {
+        for snippet_generator in &mut self.snippet_generators {
+            snippet_generator.set_max_num_chars(max_num_chars);
+        }
+    }
+
+    /// Sets a maximum number of chars for the snippets of the given field.
+    ///
+    /// This has no effect if the field is not one of the fields of the generator.
+    pub fn set_max_num_chars_for_field(&mut self, field: Field, max_num_chars: usize) {
+        for snippet_generator in &mut self.snippet_generators {
+            if snippet_generator.field() == field {
+                snippet_generator.set_max_num_chars(max_num_chars);
+            }
+        }
+    }
+
+    /// Sets the markup wrapping the highlighted terms of the snippets of all the fields.
+    /// Default is `<b>` and `</b>`.
+    pub fn set_highlight_tags(&mut self, open: &str, close: &str) {
+        for snippet_generator in &mut self.snippet_generators {
+            snippet_generator.set_highlight_tags(open, close);
+        }
+    }
+
+    /// Sets whether the text of the snippets of all the fields should be html escaped.
+    /// Default is `true`.
+    pub fn set_html_escape(&mut self, html_escape: bool) {
+        for snippet_generator in &mut self.snippet_generators {
+            snippet_generator.set_html_escape(html_escape);
+        }
+    }
+
+    /// Generates a snippet for each field of the generator, in the order the fields were given.
+    ///
+    /// The snippet of a field without any match is empty.
+    pub fn snippets_from_doc<D: Document>(&self, doc: &D) -> Vec<(Field, Snippet)> {
+        self.snippet_generators
+            .iter()
+            .map(|snippet_generator| {
+                (
+                    snippet_generator.field(),
+                    snippet_generator.snippet_from_doc(doc),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the snippet with the highest score among the fields of the generator, or `None`
+    /// if the terms of the query do not appear in any of them.
+    ///
+    /// Ties are broken in favor of the field given first.
+    pub fn best_snippet_from_doc<D: Document>(&self, doc: &D) -> Option<(Field, Snippet)> {
+        let mut best_snippet_opt: Option<(Field, Snippet)> = None;
+        for (field, snippet) in self.snippets_from_doc(doc) {
+            if snippet.is_empty() {
+                continue;
+            }
+            if let Some((_, best_snippet)) = &best_snippet_opt {
+                if snippet.score() <= best_snippet.score() {
+                    continue;
+                }
+            }
+            best_snippet_opt = Some((field, snippet));
+        }
+        best_snippet_opt
     }
 }
 
@@ -454,7 +614,7 @@ mod tests {
     use super::{collapse_overlapped_ranges, search_fragments, select_best_fragment_combination};
     use crate::query::QueryParser;
     use crate::schema::{IndexRecordOption, Schema, TextFieldIndexing, TextOptions, TEXT};
-    use crate::snippet::SnippetGenerator;
+    use crate::snippet::{MultiFieldSnippetGenerator, SnippetGenerator};
     use crate::tokenizer::{NgramTokenizer, SimpleTokenizer};
     use crate::Index;
 
@@ -741,6 +901,92 @@ Survey in 2016, 2017, and 2018."#;
         Ok(())
     }
 
+    #[test]
+    fn test_multi_field_snippet_generator() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title_field = schema_builder.add_text_field("title", TEXT);
+        let body_field = schema_builder.add_text_field("body", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let doc = doc!(
+            title_field => "The Rust programming language",
+            body_field => TEST_TEXT
+        );
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            index_writer.add_document(doc.clone())?;
+            index_writer.commit()?;
+        }
+        let searcher = index.reader()?.searcher();
+        let query_parser = QueryParser::for_index(&index, vec![title_field, body_field]);
+        let query = query_parser.parse_query("mozilla")?;
+        let mut snippet_generator =
+            MultiFieldSnippetGenerator::create(&searcher, &*query, &[title_field, body_field])?;
+        snippet_generator.set_max_num_chars(100);
+        snippet_generator.set_max_num_chars_for_field(body_field, 30);
+
+        // The term only appears in the body.
+        let snippets = snippet_generator.snippets_from_doc(&doc);
+        assert_eq!(snippets.len(), 2);
+        assert_eq!(snippets[0].0, title_field);
+        assert!(snippets[0].1.is_empty());
+        assert_eq!(snippets[1].0, body_field);
+        assert_eq!(
+            snippets[1].1.to_html(),
+            "language sponsored by\n<b>Mozilla</b>"
+        );
+
+        let (field, snippet) = snippet_generator.best_snippet_from_doc(&doc).unwrap();
+        assert_eq!(field, body_field);
+        assert_eq!(snippet.to_html(), "language sponsored by\n<b>Mozilla</b>");
+        assert_eq!(snippet.score(), 0.5);
+
+        // Both fields match, the snippet with the most matches wins.
+        let query = query_parser.parse_query("rust language")?;
+        let snippet_generator =
+            MultiFieldSnippetGenerator::create(&searcher, &*query, &[title_field, body_field])?;
+        let (field, snippet) = snippet_generator.best_snippet_from_doc(&doc).unwrap();
+        assert_eq!(field, body_field);
+        assert!(snippet.highlighted().len() > 2);
+
+        let query = query_parser.parse_query("servo")?;
+        let snippet_generator =
+            MultiFieldSnippetGenerator::create(&searcher, &*query, &[title_field])?;
+        assert!(snippet_generator.best_snippet_from_doc(&doc).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_snippet_generator_html_escape_and_highlight_tags() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let text = "hello <script>alert(1)</script> & rust";
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            index_writer.add_document(doc!(text_field => text))?;
+            index_writer.commit()?;
+        }
+        let searcher = index.reader()?.searcher();
+        let query_parser = QueryParser::for_index(&index, vec![text_field]);
+        let query = query_parser.parse_query("rust")?;
+        let mut snippet_generator = SnippetGenerator::create(&searcher, &*query, text_field)?;
+        assert_eq!(
+            snippet_generator.snippet(text).to_html(),
+            "hello &lt;script&gt;alert(1)&lt;/script&gt; &amp; <b>rust</b>"
+        );
+        snippet_generator.set_highlight_tags("<mark>", "</mark>");
+        assert_eq!(
+            snippet_generator.snippet(text).to_html(),
+            "hello &lt;script&gt;alert(1)&lt;/script&gt; &amp; <mark>rust</mark>"
+        );
+        snippet_generator.set_html_escape(false);
+        assert_eq!(
+            snippet_generator.snippet(text).to_html(),
+            "hello <script>alert(1)</script> & <mark>rust</mark>"
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_collapse_overlapped_ranges() {
         #![allow(clippy::single_range_in_vec_init)]
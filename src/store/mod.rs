@@ -214,6 +214,103 @@ pub mod tests {
         )
     }
 
+    #[test]
+    fn test_store_get_fields() -> crate::Result<()> {
+        let mut schema_builder = schema::Schema::builder();
+        let title_field = schema_builder.add_text_field("title", TEXT | STORED);
+        let url_field = schema_builder.add_text_field("url", STORED);
+        let body_field = schema_builder.add_text_field("body", TEXT | STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        {
+            let mut index_writer: IndexWriter = index.writer_for_tests()?;
+            for i in 0..10 {
+                index_writer.add_document(doc!(
+                    title_field => format!("Doc {i}"),
+                    body_field => LOREM,
+                    url_field => format!("https://example.com/{i}"),
+                    title_field => format!("Subtitle {i}"),
+                ))?;
+            }
+            index_writer.commit()?;
+        }
+        let searcher = index.reader()?.searcher();
+        for doc_id in 0..10 {
+            let doc_address = crate::DocAddress::new(0, doc_id);
+            let full_doc: TantivyDocument = searcher.doc(doc_address)?;
+            let partial_doc = searcher.doc_fields(doc_address, &[title_field, url_field])?;
+            assert!(partial_doc.get_first(body_field).is_none());
+            for field in [title_field, url_field] {
+                let partial_values: Vec<&str> = partial_doc
+                    .get_all(field)
+                    .flat_map(|value| value.as_str())
+                    .collect();
+                let full_values: Vec<&str> = full_doc
+                    .get_all(field)
+                    .flat_map(|value| value.as_str())
+                    .collect();
+                assert_eq!(partial_values, full_values);
+            }
+            assert_eq!(partial_doc.get_all(title_field).count(), 2);
+        }
+        let empty_doc = searcher.doc_fields(crate::DocAddress::new(0, 0), &[])?;
+        assert_eq!(empty_doc.field_values().count(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_store_get_field_bytes() -> crate::Result<()> {
+        use std::io::Read;
+
+        let mut schema_builder = schema::Schema::builder();
+        let id_field = schema_builder.add_u64_field("id", STORED);
+        let title_field = schema_builder.add_text_field("title", STORED);
+        let blob_field = schema_builder.add_bytes_field("blob", STORED);
+        let body_field = schema_builder.add_text_field("body", STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let body: String = LOREM.repeat(10_000);
+        assert!(body.len() > 3_000_000);
+        let blob: Vec<u8> = (0..1_000_000u32).map(|i| (i % 251) as u8).collect();
+        {
+            let mut index_writer: IndexWriter = index.writer_for_tests()?;
+            index_writer.add_document(doc!(
+                id_field => 1u64,
+                title_field => "a large document",
+                blob_field => blob.clone(),
+                body_field => body.clone(),
+            ))?;
+            index_writer.add_document(doc!(id_field => 2u64))?;
+            index_writer.commit()?;
+        }
+        let searcher = index.reader()?.searcher();
+        let doc_address = crate::DocAddress::new(0, 0);
+
+        let mut body_reader = searcher.doc_field_bytes(doc_address, body_field)?.unwrap();
+        let mut read_body = Vec::new();
+        let mut chunk = vec![0u8; 64 * 1024];
+        loop {
+            let num_bytes = body_reader.read(&mut chunk)?;
+            if num_bytes == 0 {
+                break;
+            }
+            read_body.extend_from_slice(&chunk[..num_bytes]);
+        }
+        assert_eq!(read_body, body.as_bytes());
+
+        let blob_bytes = searcher.doc_field_bytes(doc_address, blob_field)?.unwrap();
+        assert_eq!(blob_bytes.as_slice(), &blob[..]);
+        let title_bytes = searcher.doc_field_bytes(doc_address, title_field)?.unwrap();
+        assert_eq!(title_bytes.as_slice(), b"a large document");
+
+        assert!(searcher
+            .doc_field_bytes(crate::DocAddress::new(0, 1), body_field)?
+            .is_none());
+        assert!(matches!(
+            searcher.doc_field_bytes(doc_address, id_field),
+            Err(crate::TantivyError::InvalidArgument(_))
+        ));
+        Ok(())
+    }
+
     #[test]
     fn test_store_with_delete() -> crate::Result<()> {
         let mut schema_builder = schema::Schema::builder();
@@ -254,6 +351,63 @@ pub mod tests {
         Ok(())
     }
 
+    #[cfg(feature = "lz4-compression")]
+    #[cfg(feature = "zstd-compression")]
+    #[test]
+    fn test_store_size_per_compressor() -> crate::Result<()> {
+        let store_len = |compressor: Compressor| -> crate::Result<usize> {
+            test_store(compressor, BLOCK_SIZE, true)?;
+            let path = Path::new("store");
+            let directory = RamDirectory::create();
+            let store_wrt = directory.open_write(path)?;
+            write_lorem_ipsum_store(store_wrt, NUM_DOCS, compressor, BLOCK_SIZE, true);
+            Ok(directory.open_read(path)?.len())
+        };
+        let none_len = store_len(Compressor::None)?;
+        let lz4_len = store_len(Compressor::Lz4)?;
+        let zstd_len = store_len(Compressor::Zstd(ZstdCompressor::default()))?;
+        let zstd_19_len = store_len(Compressor::Zstd(ZstdCompressor {
+            compression_level: Some(19),
+        }))?;
+        assert!(lz4_len * 2 < none_len);
+        assert!(zstd_len <= lz4_len);
+        assert!(zstd_19_len <= lz4_len);
+        Ok(())
+    }
+
+    #[cfg(feature = "zstd-compression")]
+    #[test]
+    fn test_store_compressor_is_persisted() -> crate::Result<()> {
+        let mut schema_builder = schema::Schema::builder();
+        let text_field = schema_builder.add_text_field("text_field", TEXT | STORED);
+        let directory = RamDirectory::create();
+        let settings = crate::IndexSettings {
+            docstore_compression: Compressor::Zstd(ZstdCompressor {
+                compression_level: Some(19),
+            }),
+            ..Default::default()
+        };
+        {
+            let index = Index::create(directory.clone(), schema_builder.build(), settings)?;
+            let mut index_writer: IndexWriter = index.writer_for_tests()?;
+            index_writer.add_document(doc!(text_field=> LOREM))?;
+            index_writer.commit()?;
+        }
+        let index = Index::open(directory)?;
+        assert_eq!(
+            index.settings().docstore_compression,
+            Compressor::Zstd(ZstdCompressor {
+                compression_level: Some(19),
+            })
+        );
+        let searcher = index.reader()?.searcher();
+        let store_reader = searcher.segment_reader(0).get_store_reader(10)?;
+        assert_eq!(store_reader.decompressor(), Decompressor::Zstd);
+        let doc = store_reader.get::<TantivyDocument>(0)?;
+        assert_eq!(doc.get_first(text_field).unwrap().as_str(), Some(LOREM));
+        Ok(())
+    }
+
     #[cfg(feature = "lz4-compression")]
     #[cfg(feature = "zstd-compression")]
     #[test]
@@ -401,4 +555,45 @@ mod bench {
         let store = StoreReader::open(store_file, 10).unwrap();
         b.iter(|| store.iter::<TantivyDocument>(None).collect::<Vec<_>>());
     }
+
+    #[bench]
+    fn bench_store_get_doc(b: &mut Bencher) {
+        let directory = RamDirectory::create();
+        let path = Path::new("store");
+        write_lorem_ipsum_store(
+            directory.open_write(path).unwrap(),
+            1_000,
+            Compressor::default(),
+            16_384,
+            true,
+        );
+        let store_file = directory.open_read(path).unwrap();
+        let store = StoreReader::open(store_file, 10).unwrap();
+        b.iter(|| {
+            (0..1_000)
+                .map(|doc_id| store.get::<TantivyDocument>(doc_id).unwrap())
+                .collect::<Vec<_>>()
+        });
+    }
+
+    #[bench]
+    fn bench_store_get_fields(b: &mut Bencher) {
+        let directory = RamDirectory::create();
+        let path = Path::new("store");
+        let schema = write_lorem_ipsum_store(
+            directory.open_write(path).unwrap(),
+            1_000,
+            Compressor::default(),
+            16_384,
+            true,
+        );
+        let title_field = schema.get_field("title").unwrap();
+        let store_file = directory.open_read(path).unwrap();
+        let store = StoreReader::open(store_file, 10).unwrap();
+        b.iter(|| {
+            (0..1_000)
+                .map(|doc_id| store.get_fields(doc_id, &[title_field]).unwrap())
+                .collect::<Vec<_>>()
+        });
+    }
 }
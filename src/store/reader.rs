@@ -5,7 +5,7 @@ use std::ops::{AddAssign, Range};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 
-use common::{BinarySerializable, OwnedBytes};
+use common::{BinarySerializable, OwnedBytes, VInt};
 use lru::LruCache;
 
 use super::footer::DocStoreFooter;
@@ -14,7 +14,10 @@ use super::Decompressor;
 use crate::directory::FileSlice;
 use crate::error::DataCorruption;
 use crate::fastfield::AliveBitSet;
-use crate::schema::document::{BinaryDocumentDeserializer, DocumentDeserialize};
+use crate::schema::document::{
+    type_codes, BinaryDocumentDeserializer, DocumentDeserialize, DocumentDeserializer,
+};
+use crate::schema::{Field, OwnedValue, TantivyDocument};
 use crate::space_usage::StoreSpaceUsage;
 use crate::store::index::Checkpoint;
 use crate::DocId;
@@ -208,6 +211,65 @@ impl StoreReader {
         D::deserialize(deserializer).map_err(crate::TantivyError::from)
     }
 
+    /// Reads the document `doc_id`, keeping only the values of the given fields.
+    ///
+    /// The block of the document still has to be decompressed, and its values decoded, but
+    /// the values of the other fields are dropped instead of being added to the document.
+    pub fn get_fields(&self, doc_id: DocId, fields: &[Field]) -> crate::Result<TantivyDocument> {
+        let mut doc_bytes = self.get_document_bytes(doc_id)?;
+        let mut deserializer = BinaryDocumentDeserializer::from_reader(&mut doc_bytes)
+            .map_err(crate::TantivyError::from)?;
+        let mut doc = TantivyDocument::default();
+        while let Some((field, value)) = deserializer
+            .next_field::<OwnedValue>()
+            .map_err(crate::TantivyError::from)?
+        {
+            if fields.contains(&field) {
+                doc.add_field_value(field, &value);
+            }
+        }
+        Ok(doc)
+    }
+
+    /// Returns the bytes of the first value of `field` in the document `doc_id`, without copying
+    /// them, or `None` if the document has no value for `field`.
+    ///
+    /// The value must be a text or a bytes value. In the case of a text value, the bytes are
+    /// its utf-8 encoding.
+    ///
+    /// The returned [`OwnedBytes`] implements [`io::Read`](std::io::Read), and points into the
+    /// decompressed block of the document. It does not borrow the `StoreReader`, but keeps the
+    /// whole block alive until it is dropped.
+    pub fn get_field_bytes(
+        &self,
+        doc_id: DocId,
+        field: Field,
+    ) -> crate::Result<Option<OwnedBytes>> {
+        let doc_bytes = self.get_document_bytes(doc_id)?;
+        let mut remaining: &[u8] = doc_bytes.as_slice();
+        let has_value = BinaryDocumentDeserializer::from_reader(&mut remaining)
+            .and_then(|mut deserializer| deserializer.advance_to_field(field))
+            .map_err(crate::TantivyError::from)?;
+        if !has_value {
+            return Ok(None);
+        }
+        let type_code = u8::deserialize(&mut remaining)?;
+        if type_code != type_codes::TEXT_CODE && type_code != type_codes::BYTES_CODE {
+            return Err(crate::TantivyError::InvalidArgument(format!(
+                "The value of the field {field:?} is neither a text nor a bytes value"
+            )));
+        }
+        let num_bytes = VInt::deserialize(&mut remaining)?.val() as usize;
+        if num_bytes > remaining.len() {
+            return Err(DataCorruption::comment_only(format!(
+                "The value of the field {field:?} of the document {doc_id} is truncated"
+            ))
+            .into());
+        }
+        let start = doc_bytes.len() - remaining.len();
+        Ok(Some(doc_bytes.slice(start..start + num_bytes)))
+    }
+
     /// Returns raw bytes of a given document.
     ///
     /// Calling `.get(doc)` is relatively costly as it requires
@@ -0,0 +1,225 @@
+//! [`Suggester`]
+//! Suggests corrections for misspelled terms, e.g. to offer a "did you mean" link when a query
+//! returns few or no results.
+//!
+//! The suggestions are the terms of the term dictionary of a field within a given Levenshtein
+//! distance of the misspelled term. They are ranked by edit distance, and then by document
+//! frequency.
+//!
+//! ## Example
+//!
+//! ```rust
+//! # use tantivy::schema::{Schema, TEXT};
+//! # use tantivy::{doc, Index, IndexWriter};
+//! use tantivy::suggester::Suggester;
+//!
+//! # fn main() -> tantivy::Result<()> {
+//! #    let mut schema_builder = Schema::builder();
+//! #    let text_field = schema_builder.add_text_field("text", TEXT);
+//! #    let index = Index::create_in_ram(schema_builder.build());
+//! #    let mut index_writer: IndexWriter = index.writer_with_num_threads(1, 20_000_000)?;
+//! #    index_writer.add_document(doc!(text_field => "I did not receive the parcel"))?;
+//! #    index_writer.commit()?;
+//! let suggester = Suggester::new(&index, text_field)?;
+//! let suggestions = suggester.suggest("recieve", 2, 3)?;
+//! assert_eq!(suggestions[0].term(), "receive");
+//! #    Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+
+use levenshtein_automata::{Distance, LevenshteinAutomatonBuilder};
+use once_cell::sync::OnceCell;
+
+use crate::query::DfaWrapper;
+use crate::schema::{Field, FieldType};
+use crate::{Index, ReloadPolicy, Searcher, TantivyError};
+
+/// A correction suggested by the [`Suggester`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Suggestion {
+    term: String,
+    distance: u8,
+    doc_freq: u64,
+}
+
+impl Suggestion {
+    /// Returns the suggested term.
+    pub fn term(&self) -> &str {
+        &self.term
+    }
+
+    /// Returns the Levenshtein distance between the suggested term and the misspelled term.
+    pub fn distance(&self) -> u8 {
+        self.distance
+    }
+
+    /// Returns the number of documents containing the suggested term.
+    ///
+    /// Deleted documents are included in this count.
+    pub fn doc_freq(&self) -> u64 {
+        self.doc_freq
+    }
+}
+
+/// `Suggester`
+///
+/// Suggests corrections for misspelled terms from the term dictionary of a text field.
+///
+/// The suggester works on the segments of the index at the time of its creation.
+pub struct Suggester {
+    searcher: Searcher,
+    field: Field,
+    automaton_builders: [OnceCell<LevenshteinAutomatonBuilder>; 3],
+}
+
+impl Suggester {
+    /// Creates a new suggester for the given text field of the index.
+    pub fn new(index: &Index, field: Field) -> crate::Result<Suggester> {
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::Manual)
+            .try_into()?;
+        Suggester::for_searcher(reader.searcher(), field)
+    }
+
+    /// Creates a new suggester for the given text field, working on the segments of the
+    /// searcher.
+    pub fn for_searcher(searcher: Searcher, field: Field) -> crate::Result<Suggester> {
+        let field_entry = searcher.schema().get_field_entry(field);
+        if !matches!(field_entry.field_type(), FieldType::Str(_)) {
+            return Err(TantivyError::InvalidArgument(format!(
+                "Suggestions require a text field. Field {:?} is not a text field.",
+                field_entry.name()
+            )));
+        }
+        Ok(Suggester {
+            searcher,
+            field,
+            automaton_builders: Default::default(),
+        })
+    }
+
+    /// Returns at most `top_n` terms within a Levenshtein distance of `max_edits` of `term`,
+    /// a transposition counting as a single edit.
+    ///
+    /// The terms are sorted by increasing edit distance, and then by decreasing document
+    /// frequency. If `term` itself is in the term dictionary, it is returned first, with a
+    /// distance of 0.
+    ///
+    /// `term` is looked up as is. It should be normalized the same way the indexed terms are,
+    /// e.g. lowercased.
+    ///
+    /// Returns an error if `max_edits` is greater than 2.
+    pub fn suggest(
+        &self,
+        term: &str,
+        max_edits: u8,
+        top_n: usize,
+    ) -> crate::Result<Vec<Suggestion>> {
+        let automaton_builder = self
+            .automaton_builders
+            .get(max_edits as usize)
+            .ok_or_else(|| {
+                TantivyError::InvalidArgument(format!(
+                    "Levenshtein distance of {max_edits} is not allowed. Choose a value less than \
+                     {}",
+                    self.automaton_builders.len()
+                ))
+            })?
+            .get_or_init(|| LevenshteinAutomatonBuilder::new(max_edits, true));
+
+        let mut doc_freqs: HashMap<Vec<u8>, u64> = HashMap::new();
+        for segment_reader in self.searcher.segment_readers() {
+            let inverted_index = segment_reader.inverted_index(self.field)?;
+            let automaton = DfaWrapper(automaton_builder.build_dfa(term));
+            let mut term_stream = inverted_index.terms().search(automaton).into_stream()?;
+            while term_stream.advance() {
+                let doc_freq = term_stream.value().doc_freq as u64;
+                *doc_freqs.entry(term_stream.key().to_vec()).or_default() += doc_freq;
+            }
+        }
+
+        let dfa = automaton_builder.build_dfa(term);
+        let mut suggestions: Vec<Suggestion> = doc_freqs
+            .into_iter()
+            .filter_map(|(term_bytes, doc_freq)| {
+                let Distance::Exact(distance) = dfa.eval(&term_bytes) else {
+                    return None;
+                };
+                let term = String::from_utf8(term_bytes).ok()?;
+                Some(Suggestion {
+                    term,
+                    distance,
+                    doc_freq,
+                })
+            })
+            .collect();
+        suggestions.sort_by(|left, right| {
+            left.distance
+                .cmp(&right.distance)
+                .then_with(|| right.doc_freq.cmp(&left.doc_freq))
+                .then_with(|| left.term.cmp(&right.term))
+        });
+        suggestions.truncate(top_n);
+        Ok(suggestions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Suggester;
+    use crate::schema::{Schema, INDEXED, TEXT};
+    use crate::{Index, IndexWriter};
+
+    #[test]
+    fn test_suggester() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer: IndexWriter = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text_field => "please receive my regards"))?;
+        index_writer.add_document(doc!(text_field => "we will receive the parcel"))?;
+        index_writer.commit()?;
+        index_writer.add_document(doc!(text_field => "receive"))?;
+        index_writer.add_document(doc!(text_field => "deceive receiver"))?;
+        index_writer.add_document(doc!(text_field => "deceive"))?;
+        index_writer.commit()?;
+
+        let suggester = Suggester::new(&index, text_field)?;
+        let suggestions = suggester.suggest("recieve", 2, 10)?;
+        let terms: Vec<(&str, u8, u64)> = suggestions
+            .iter()
+            .map(|suggestion| {
+                (
+                    suggestion.term(),
+                    suggestion.distance(),
+                    suggestion.doc_freq(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            terms,
+            vec![("receive", 1, 3), ("deceive", 2, 2), ("receiver", 2, 1)]
+        );
+
+        let suggestions = suggester.suggest("recieve", 1, 10)?;
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].term(), "receive");
+
+        assert_eq!(suggester.suggest("recieve", 2, 1)?.len(), 1);
+        assert_eq!(suggester.suggest("receive", 0, 10)?[0].distance(), 0);
+        assert!(suggester.suggest("zzzzzz", 2, 10)?.is_empty());
+        assert!(suggester.suggest("recieve", 3, 10).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_suggester_requires_text_field() {
+        let mut schema_builder = Schema::builder();
+        let id_field = schema_builder.add_u64_field("id", INDEXED);
+        let index = Index::create_in_ram(schema_builder.build());
+        assert!(Suggester::new(&index, id_field).is_err());
+    }
+}
@@ -42,10 +42,10 @@ use tantivy_fst::Automaton;
 
 use self::termdict::{
     TermDictionary as InnerTermDict, TermDictionaryBuilder as InnerTermDictBuilder,
-    TermStreamerBuilder,
 };
-pub use self::termdict::{TermMerger, TermStreamer};
+pub use self::termdict::{TermMerger, TermStreamer, TermStreamerBuilder};
 use crate::postings::TermInfo;
+use crate::query::prefix_end;
 
 #[repr(u32)]
 #[allow(dead_code)]
@@ -131,6 +131,20 @@ impl TermDictionary {
         self.0.range()
     }
 
+    /// Returns a range builder, to stream all of the terms starting with `prefix`.
+    ///
+    /// The range is bounded by `prefix` and the smallest key greater than all of the keys
+    /// starting with `prefix`, so only the matching part of the dictionary is visited.
+    pub fn prefix_range<K: AsRef<[u8]>>(&self, prefix: K) -> TermStreamerBuilder<'_> {
+        let prefix = prefix.as_ref();
+        let range = self.range().ge(prefix);
+        if let Some(prefix_end) = prefix_end(prefix) {
+            range.lt(prefix_end)
+        } else {
+            range
+        }
+    }
+
     /// A stream of all the sorted terms.
     pub fn stream(&self) -> io::Result<TermStreamer<'_>> {
         self.0.stream()
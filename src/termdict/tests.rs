@@ -224,6 +224,53 @@ fn test_stream_range() -> crate::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_stream_prefix_range() -> crate::Result<()> {
+    let terms: [&[u8]; 9] = [
+        b"ap",
+        b"apo",
+        b"app",
+        b"apple",
+        b"application",
+        b"apply",
+        b"apq",
+        b"b",
+        b"\xff\xff",
+    ];
+    let buffer: Vec<u8> = {
+        let mut term_dictionary_builder = TermDictionaryBuilder::create(vec![])?;
+        for (term_ord, term) in terms.iter().enumerate() {
+            term_dictionary_builder.insert(term, &make_term_info(term_ord as u64))?;
+        }
+        term_dictionary_builder.finish()?
+    };
+    let term_dictionary: TermDictionary = TermDictionary::open(FileSlice::from(buffer))?;
+    let prefixed_terms = |prefix: &[u8]| -> io::Result<Vec<(Vec<u8>, u32)>> {
+        let mut streamer = term_dictionary.prefix_range(prefix).into_stream()?;
+        let mut prefixed_terms = Vec::new();
+        while let Some((key, term_info)) = streamer.next() {
+            prefixed_terms.push((key.to_vec(), term_info.doc_freq));
+        }
+        Ok(prefixed_terms)
+    };
+    assert_eq!(
+        prefixed_terms(b"app")?,
+        vec![
+            (b"app".to_vec(), 2),
+            (b"apple".to_vec(), 3),
+            (b"application".to_vec(), 4),
+            (b"apply".to_vec(), 5),
+        ]
+    );
+    assert_eq!(prefixed_terms(b"appl")?.len(), 3);
+    assert_eq!(prefixed_terms(b"ap")?.len(), 7);
+    assert_eq!(prefixed_terms(b"")?.len(), terms.len());
+    assert!(prefixed_terms(b"c")?.is_empty());
+    // There is no upper bound for this prefix.
+    assert_eq!(prefixed_terms(b"\xff")?, vec![(b"\xff\xff".to_vec(), 8)]);
+    Ok(())
+}
+
 #[test]
 fn test_empty_string() -> crate::Result<()> {
     let buffer: Vec<u8> = {
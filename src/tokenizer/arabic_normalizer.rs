@@ -0,0 +1,157 @@
+//! # Example
+//! ```rust
+//! use tantivy::tokenizer::*;
+//!
+//! let mut tokenizer = TextAnalyzer::builder(WhitespaceTokenizer::default())
+//!   .filter(ArabicNormalizationFilter)
+//!   .build();
+//!
+//! // The vocalized and unvocalized spellings produce the same token.
+//! let mut stream = tokenizer.token_stream("مَدْرَسَةٌ مدرسة");
+//! assert_eq!(stream.next().unwrap().text, "مدرسه");
+//! assert_eq!(stream.next().unwrap().text, "مدرسه");
+//! assert!(stream.next().is_none());
+//! ```
+use std::mem;
+
+use super::{Token, TokenFilter, TokenStream, Tokenizer};
+
+/// `TokenFilter` that normalizes the orthographic variations of Arabic text.
+///
+/// It
+/// - removes the tashkeel (diacritics such as fatha, damma, kasra, shadda or sukun), the
+///   superscript alef and the tatweel,
+/// - folds the alef variants `أ`, `إ` and `آ` into `ا`,
+/// - maps the alef maksura `ى` to `ي`,
+/// - maps the taa marbuta `ة` to `ه`.
+///
+/// Offsets are not modified and still point to the original text.
+#[derive(Clone)]
+pub struct ArabicNormalizationFilter;
+
+impl TokenFilter for ArabicNormalizationFilter {
+    type Tokenizer<T: Tokenizer> = ArabicNormalizationFilterWrapper<T>;
+
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> ArabicNormalizationFilterWrapper<T> {
+        ArabicNormalizationFilterWrapper {
+            inner: tokenizer,
+            buffer: String::new(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ArabicNormalizationFilterWrapper<T> {
+    inner: T,
+    buffer: String,
+}
+
+impl<T: Tokenizer> Tokenizer for ArabicNormalizationFilterWrapper<T> {
+    type TokenStream<'a> = ArabicNormalizationFilterStream<'a, T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        self.buffer.clear();
+        ArabicNormalizationFilterStream {
+            tail: self.inner.token_stream(text),
+            buffer: &mut self.buffer,
+        }
+    }
+}
+
+pub struct ArabicNormalizationFilterStream<'a, T> {
+    buffer: &'a mut String,
+    tail: T,
+}
+
+// Returns the normalized form of an Arabic character,
+// or `None` if the character should be removed.
+fn normalize_arabic_char(c: char) -> Option<char> {
+    match c {
+        // tashkeel, from fathatan to sukun.
+        '\u{064B}'..='\u{0652}' => None,
+        // superscript alef
+        '\u{0670}' => None,
+        // tatweel
+        '\u{0640}' => None,
+        // alef with hamza above, alef with hamza below, alef with madda above
+        'أ' | 'إ' | 'آ' => Some('ا'),
+        // alef maksura
+        'ى' => Some('ي'),
+        // taa marbuta
+        'ة' => Some('ه'),
+        _ => Some(c),
+    }
+}
+
+fn normalize_arabic(text: &str, output: &mut String) {
+    output.clear();
+    output.extend(text.chars().filter_map(normalize_arabic_char));
+}
+
+impl<'a, T: TokenStream> TokenStream for ArabicNormalizationFilterStream<'a, T> {
+    fn advance(&mut self) -> bool {
+        if !self.tail.advance() {
+            return false;
+        }
+        if !self.tail.token().text.is_ascii() {
+            normalize_arabic(&self.tail.token().text, self.buffer);
+            mem::swap(&mut self.tail.token_mut().text, self.buffer);
+        }
+        true
+    }
+
+    fn token(&self) -> &Token {
+        self.tail.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.tail.token_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tokenizer::tests::assert_token;
+    use crate::tokenizer::{
+        ArabicNormalizationFilter, TextAnalyzer, Token, TokenizerManager, WhitespaceTokenizer,
+    };
+
+    #[test]
+    fn test_arabic_normalization() {
+        let tokens = token_stream_helper("مَدْرَسَةٌ مدرسة أَحْمَد إيمان مستشفى");
+        assert_eq!(tokens.len(), 5);
+        assert_token(&tokens[0], 0, "مدرسه", 0, 20);
+        assert_token(&tokens[1], 1, "مدرسه", 21, 31);
+        assert_token(&tokens[2], 2, "احمد", 32, 46);
+        assert_token(&tokens[3], 3, "ايمان", 47, 57);
+        assert_token(&tokens[4], 4, "مستشفي", 58, 70);
+    }
+
+    #[test]
+    fn test_arabic_stemmer_vocalized() {
+        let tokenizer_manager = TokenizerManager::default();
+        let mut ar_stem = tokenizer_manager.get("ar_stem").unwrap();
+        let mut stem = |text: &str| {
+            let mut tokens: Vec<String> = vec![];
+            ar_stem
+                .token_stream(text)
+                .process(&mut |token: &Token| tokens.push(token.text.clone()));
+            tokens
+        };
+        assert_eq!(stem("الْمَدْرَسَةُ"), stem("المدرسة"));
+        assert_eq!(stem("أَحْمَد"), stem("احمد"));
+    }
+
+    fn token_stream_helper(text: &str) -> Vec<Token> {
+        let mut a = TextAnalyzer::builder(WhitespaceTokenizer::default())
+            .filter(ArabicNormalizationFilter)
+            .build();
+        let mut token_stream = a.token_stream(text);
+        let mut tokens: Vec<Token> = vec![];
+        let mut add_token = |token: &Token| {
+            tokens.push(token.clone());
+        };
+        token_stream.process(&mut add_token);
+        tokens
+    }
+}
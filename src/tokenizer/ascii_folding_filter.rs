@@ -8,12 +8,39 @@ use super::{Token, TokenFilter, TokenStream, Tokenizer};
 #[derive(Clone)]
 pub struct AsciiFoldingFilter;
 
+impl AsciiFoldingFilter {
+    /// Creates an ascii folding [`TokenFilter`] which, if `preserve_original` is true,
+    /// emits the original token right after its folded version, at the same position.
+    ///
+    /// Tokens that are not modified by the folding are only emitted once.
+    pub fn preserve_original(preserve_original: bool) -> ConfigurableAsciiFoldingFilter {
+        ConfigurableAsciiFoldingFilter { preserve_original }
+    }
+}
+
 impl TokenFilter for AsciiFoldingFilter {
     type Tokenizer<T: Tokenizer> = AsciiFoldingFilterWrapper<T>;
 
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> AsciiFoldingFilterWrapper<T> {
+        ConfigurableAsciiFoldingFilter::default().transform(tokenizer)
+    }
+}
+
+/// Ascii folding token filter, with some extra options.
+///
+/// See [`AsciiFoldingFilter::preserve_original`].
+#[derive(Clone, Default)]
+pub struct ConfigurableAsciiFoldingFilter {
+    preserve_original: bool,
+}
+
+impl TokenFilter for ConfigurableAsciiFoldingFilter {
+    type Tokenizer<T: Tokenizer> = AsciiFoldingFilterWrapper<T>;
+
     fn transform<T: Tokenizer>(self, tokenizer: T) -> AsciiFoldingFilterWrapper<T> {
         AsciiFoldingFilterWrapper {
             tokenizer,
+            preserve_original: self.preserve_original,
             buffer: String::new(),
         }
     }
@@ -22,6 +49,7 @@ impl TokenFilter for AsciiFoldingFilter {
 #[derive(Clone)]
 pub struct AsciiFoldingFilterWrapper<T> {
     tokenizer: T,
+    preserve_original: bool,
     buffer: String,
 }
 
@@ -33,6 +61,8 @@ impl<T: Tokenizer> Tokenizer for AsciiFoldingFilterWrapper<T> {
         AsciiFoldingFilterTokenStream {
             buffer: &mut self.buffer,
             tail: self.tokenizer.token_stream(text),
+            preserve_original: self.preserve_original,
+            original_pending: false,
         }
     }
 }
@@ -40,16 +70,27 @@ impl<T: Tokenizer> Tokenizer for AsciiFoldingFilterWrapper<T> {
 pub struct AsciiFoldingFilterTokenStream<'a, T> {
     buffer: &'a mut String,
     tail: T,
+    preserve_original: bool,
+    // If true, `buffer` holds the original text of the current token,
+    // which should be emitted next.
+    original_pending: bool,
 }
 
 impl<'a, T: TokenStream> TokenStream for AsciiFoldingFilterTokenStream<'a, T> {
     fn advance(&mut self) -> bool {
+        if self.original_pending {
+            self.original_pending = false;
+            mem::swap(&mut self.tail.token_mut().text, self.buffer);
+            return true;
+        }
         if !self.tail.advance() {
             return false;
         }
         if !self.token_mut().text.is_ascii() {
             // ignore its already ascii
             to_ascii(&self.tail.token().text, self.buffer);
+            self.original_pending =
+                self.preserve_original && *self.buffer != self.tail.token().text;
             mem::swap(&mut self.tail.token_mut().text, self.buffer);
         }
         true
@@ -1564,7 +1605,10 @@ mod tests {
     use std::iter;
 
     use super::to_ascii;
-    use crate::tokenizer::{AsciiFoldingFilter, RawTokenizer, SimpleTokenizer, TextAnalyzer};
+    use crate::tokenizer::tests::assert_token;
+    use crate::tokenizer::{
+        AsciiFoldingFilter, RawTokenizer, SimpleTokenizer, TextAnalyzer, Token,
+    };
 
     #[test]
     fn test_ascii_folding() {
@@ -1578,6 +1622,25 @@ mod tests {
         assert_eq!(&folding_helper("Usagi"), &["Usagi"]);
     }
 
+    #[test]
+    fn test_ascii_folding_preserve_original() {
+        let mut tokens: Vec<Token> = Vec::new();
+        TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(AsciiFoldingFilter::preserve_original(true))
+            .build()
+            .token_stream("café Straße cafe 馬")
+            .process(&mut |token| {
+                tokens.push(token.clone());
+            });
+        assert_eq!(tokens.len(), 6);
+        assert_token(&tokens[0], 0, "cafe", 0, 5);
+        assert_token(&tokens[1], 0, "café", 0, 5);
+        assert_token(&tokens[2], 1, "Strasse", 6, 13);
+        assert_token(&tokens[3], 1, "Straße", 6, 13);
+        assert_token(&tokens[4], 2, "cafe", 14, 18);
+        assert_token(&tokens[5], 3, "馬", 19, 22);
+    }
+
     fn folding_helper(text: &str) -> Vec<String> {
         let mut tokens = Vec::new();
         TextAnalyzer::builder(SimpleTokenizer::default())
@@ -0,0 +1,194 @@
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+use super::simple_tokenizer::token_type_of;
+use super::{Token, TokenStream, TokenType, Tokenizer};
+
+/// Tokenize the text by emitting overlapping bigrams for the runs of CJK characters, and
+/// by splitting the rest of the text on whitespaces and punctuation.
+///
+/// Chinese, Japanese or Korean texts do not separate words with whitespaces. Indexing bigrams
+/// makes it possible to search for words without a dictionary, as a phrase query on their
+/// bigrams. A run made of a single CJK character is emitted as a unigram.
+///
+/// CJK characters are the Han ideographs, the Hiragana, the Katakana and the Hangul syllables.
+/// The bigrams and unigrams are emitted with the [`TokenType::Cjk`] type.
+///
+/// Example: `東京都 in Japan` is tokenized as follows:
+///
+/// | Term     | 東京 | 京都  | in    | Japan  |
+/// |----------|------|------|-------|--------|
+/// | Position | 0    | 1    | 2     | 3      |
+/// | Offsets  | 0,6  | 3,9  | 10,12 | 13,18  |
+#[derive(Clone, Default)]
+pub struct CjkTokenizer {
+    token: Token,
+}
+
+/// TokenStream produced by the `CjkTokenizer`.
+pub struct CjkTokenStream<'a> {
+    text: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+    // Offset of the last CJK char, if we are in a run of CJK chars,
+    // and whether a bigram was emitted for this run.
+    cjk_run: Option<(usize, bool)>,
+    token: &'a mut Token,
+}
+
+impl Tokenizer for CjkTokenizer {
+    type TokenStream<'a> = CjkTokenStream<'a>;
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> CjkTokenStream<'a> {
+        self.token.reset();
+        CjkTokenStream {
+            text,
+            chars: text.char_indices().peekable(),
+            cjk_run: None,
+            token: &mut self.token,
+        }
+    }
+}
+
+// Returns true if `c` belongs to a script written without whitespaces between words.
+pub(crate) fn is_cjk(c: char) -> bool {
+    matches!(c,
+        // Hiragana, Katakana
+        '\u{3040}'..='\u{30FF}'
+        // CJK Unified Ideographs Extension A
+        | '\u{3400}'..='\u{4DBF}'
+        // CJK Unified Ideographs
+        | '\u{4E00}'..='\u{9FFF}'
+        // Hangul Syllables
+        | '\u{AC00}'..='\u{D7AF}'
+        // CJK Compatibility Ideographs
+        | '\u{F900}'..='\u{FAFF}'
+        // Halfwidth Katakana
+        | '\u{FF66}'..='\u{FF9D}'
+        // CJK Unified Ideographs Extension B to F
+        | '\u{20000}'..='\u{2EBEF}'
+    )
+}
+
+impl<'a> CjkTokenStream<'a> {
+    fn emit(&mut self, offset_from: usize, offset_to: usize, token_type: TokenType) {
+        self.token.position = self.token.position.wrapping_add(1);
+        self.token.offset_from = offset_from;
+        self.token.offset_to = offset_to;
+        self.token.text.clear();
+        self.token.text.push_str(&self.text[offset_from..offset_to]);
+        self.token.token_type = token_type;
+    }
+
+    // Consumes the chars of the current non CJK word, and returns its end.
+    fn search_word_end(&mut self) -> usize {
+        while let Some(&(offset, c)) = self.chars.peek() {
+            if !c.is_alphanumeric() || is_cjk(c) {
+                return offset;
+            }
+            self.chars.next();
+        }
+        self.text.len()
+    }
+}
+
+impl<'a> TokenStream for CjkTokenStream<'a> {
+    fn advance(&mut self) -> bool {
+        loop {
+            let Some(&(offset, c)) = self.chars.peek() else {
+                // A run made of a single CJK char is emitted as a unigram.
+                if let Some((run_start, false)) = self.cjk_run.take() {
+                    self.emit(run_start, self.text.len(), TokenType::Cjk);
+                    return true;
+                }
+                return false;
+            };
+            if is_cjk(c) {
+                self.chars.next();
+                if let Some((bigram_start, _)) = self.cjk_run {
+                    self.cjk_run = Some((offset, true));
+                    self.emit(bigram_start, offset + c.len_utf8(), TokenType::Cjk);
+                    return true;
+                }
+                self.cjk_run = Some((offset, false));
+                continue;
+            }
+            if let Some((run_start, false)) = self.cjk_run.take() {
+                self.emit(run_start, offset, TokenType::Cjk);
+                return true;
+            }
+            self.chars.next();
+            if c.is_alphanumeric() {
+                let offset_to = self.search_word_end();
+                let token_type = token_type_of(&self.text[offset..offset_to]);
+                self.emit(offset, offset_to, token_type);
+                return true;
+            }
+        }
+    }
+
+    fn token(&self) -> &Token {
+        self.token
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.token
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tokenizer::tests::assert_token;
+    use crate::tokenizer::{CjkTokenizer, TextAnalyzer, Token, TokenType, TokenizerManager};
+
+    fn token_stream_helper(text: &str) -> Vec<Token> {
+        let mut a = TextAnalyzer::from(CjkTokenizer::default());
+        let mut token_stream = a.token_stream(text);
+        let mut tokens: Vec<Token> = vec![];
+        let mut add_token = |token: &Token| {
+            tokens.push(token.clone());
+        };
+        token_stream.process(&mut add_token);
+        tokens
+    }
+
+    #[test]
+    fn test_cjk_tokenizer_mixed_scripts() {
+        let tokens = token_stream_helper("Tokyo 東京都, is 日 big東京");
+        assert_eq!(tokens.len(), 7);
+        assert_token(&tokens[0], 0, "Tokyo", 0, 5);
+        assert_token(&tokens[1], 1, "東京", 6, 12);
+        assert_token(&tokens[2], 2, "京都", 9, 15);
+        assert_token(&tokens[3], 3, "is", 17, 19);
+        assert_token(&tokens[4], 4, "日", 20, 23);
+        assert_token(&tokens[5], 5, "big", 24, 27);
+        assert_token(&tokens[6], 6, "東京", 27, 33);
+        assert_eq!(tokens[0].token_type, TokenType::Alphabetic);
+        assert!(tokens[1..3]
+            .iter()
+            .all(|token| token.token_type == TokenType::Cjk));
+    }
+
+    #[test]
+    fn test_cjk_tokenizer_single_char_runs() {
+        let tokens = token_stream_helper("日");
+        assert_eq!(tokens.len(), 1);
+        assert_token(&tokens[0], 0, "日", 0, 3);
+
+        let tokens = token_stream_helper("a日b");
+        assert_eq!(tokens.len(), 3);
+        assert_token(&tokens[0], 0, "a", 0, 1);
+        assert_token(&tokens[1], 1, "日", 1, 4);
+        assert_token(&tokens[2], 2, "b", 4, 5);
+
+        assert!(token_stream_helper("").is_empty());
+    }
+
+    #[test]
+    fn test_cjk_tokenizer_registered() {
+        let tokenizer_manager = TokenizerManager::default();
+        let mut cjk = tokenizer_manager.get("cjk").unwrap();
+        let mut tokens: Vec<String> = vec![];
+        cjk.token_stream("TOKYO 東京")
+            .process(&mut |token: &Token| tokens.push(token.text.clone()));
+        assert_eq!(tokens, ["tokyo", "東京"]);
+    }
+}
@@ -0,0 +1,210 @@
+//! # Example
+//! ```rust
+//! use tantivy::tokenizer::*;
+//!
+//! let mut tokenizer = TextAnalyzer::builder(WhitespaceTokenizer::default())
+//!   .filter(DecimalDigitFilter)
+//!   .build();
+//!
+//! let mut stream = tokenizer.token_stream("١٩٠٦ ९८");
+//! assert_eq!(stream.next().unwrap().text, "1906");
+//! assert_eq!(stream.next().unwrap().text, "98");
+//! assert!(stream.next().is_none());
+//! ```
+use std::mem;
+
+use super::{Token, TokenFilter, TokenStream, Tokenizer};
+
+/// `TokenFilter` that replaces the decimal digits of any script, e.g. the Arabic-Indic `٣` or
+/// the Devanagari `३`, by the ASCII digits `0` to `9`.
+///
+/// Other characters are left untouched. Offsets are not modified and still point
+/// to the original text.
+#[derive(Clone)]
+pub struct DecimalDigitFilter;
+
+impl TokenFilter for DecimalDigitFilter {
+    type Tokenizer<T: Tokenizer> = DecimalDigitFilterWrapper<T>;
+
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> DecimalDigitFilterWrapper<T> {
+        DecimalDigitFilterWrapper {
+            inner: tokenizer,
+            buffer: String::new(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct DecimalDigitFilterWrapper<T> {
+    inner: T,
+    buffer: String,
+}
+
+impl<T: Tokenizer> Tokenizer for DecimalDigitFilterWrapper<T> {
+    type TokenStream<'a> = DecimalDigitFilterStream<'a, T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        self.buffer.clear();
+        DecimalDigitFilterStream {
+            tail: self.inner.token_stream(text),
+            buffer: &mut self.buffer,
+        }
+    }
+}
+
+pub struct DecimalDigitFilterStream<'a, T> {
+    buffer: &'a mut String,
+    tail: T,
+}
+
+// Digit zero of each of the non ASCII runs of decimal digits (general category `Nd`)
+// of Unicode 14. The digits one to nine always follow their zero.
+const DIGIT_ZEROS: [char; 65] = [
+    '\u{0660}',  // ARABIC-INDIC DIGIT ZERO
+    '\u{06F0}',  // EXTENDED ARABIC-INDIC DIGIT ZERO
+    '\u{07C0}',  // NKO DIGIT ZERO
+    '\u{0966}',  // DEVANAGARI DIGIT ZERO
+    '\u{09E6}',  // BENGALI DIGIT ZERO
+    '\u{0A66}',  // GURMUKHI DIGIT ZERO
+    '\u{0AE6}',  // GUJARATI DIGIT ZERO
+    '\u{0B66}',  // ORIYA DIGIT ZERO
+    '\u{0BE6}',  // TAMIL DIGIT ZERO
+    '\u{0C66}',  // TELUGU DIGIT ZERO
+    '\u{0CE6}',  // KANNADA DIGIT ZERO
+    '\u{0D66}',  // MALAYALAM DIGIT ZERO
+    '\u{0DE6}',  // SINHALA LITH DIGIT ZERO
+    '\u{0E50}',  // THAI DIGIT ZERO
+    '\u{0ED0}',  // LAO DIGIT ZERO
+    '\u{0F20}',  // TIBETAN DIGIT ZERO
+    '\u{1040}',  // MYANMAR DIGIT ZERO
+    '\u{1090}',  // MYANMAR SHAN DIGIT ZERO
+    '\u{17E0}',  // KHMER DIGIT ZERO
+    '\u{1810}',  // MONGOLIAN DIGIT ZERO
+    '\u{1946}',  // LIMBU DIGIT ZERO
+    '\u{19D0}',  // NEW TAI LUE DIGIT ZERO
+    '\u{1A80}',  // TAI THAM HORA DIGIT ZERO
+    '\u{1A90}',  // TAI THAM THAM DIGIT ZERO
+    '\u{1B50}',  // BALINESE DIGIT ZERO
+    '\u{1BB0}',  // SUNDANESE DIGIT ZERO
+    '\u{1C40}',  // LEPCHA DIGIT ZERO
+    '\u{1C50}',  // OL CHIKI DIGIT ZERO
+    '\u{A620}',  // VAI DIGIT ZERO
+    '\u{A8D0}',  // SAURASHTRA DIGIT ZERO
+    '\u{A900}',  // KAYAH LI DIGIT ZERO
+    '\u{A9D0}',  // JAVANESE DIGIT ZERO
+    '\u{A9F0}',  // MYANMAR TAI LAING DIGIT ZERO
+    '\u{AA50}',  // CHAM DIGIT ZERO
+    '\u{ABF0}',  // MEETEI MAYEK DIGIT ZERO
+    '\u{FF10}',  // FULLWIDTH DIGIT ZERO
+    '\u{104A0}', // OSMANYA DIGIT ZERO
+    '\u{10D30}', // HANIFI ROHINGYA DIGIT ZERO
+    '\u{11066}', // BRAHMI DIGIT ZERO
+    '\u{110F0}', // SORA SOMPENG DIGIT ZERO
+    '\u{11136}', // CHAKMA DIGIT ZERO
+    '\u{111D0}', // SHARADA DIGIT ZERO
+    '\u{112F0}', // KHUDAWADI DIGIT ZERO
+    '\u{11450}', // NEWA DIGIT ZERO
+    '\u{114D0}', // TIRHUTA DIGIT ZERO
+    '\u{11650}', // MODI DIGIT ZERO
+    '\u{116C0}', // TAKRI DIGIT ZERO
+    '\u{11730}', // AHOM DIGIT ZERO
+    '\u{118E0}', // WARANG CITI DIGIT ZERO
+    '\u{11950}', // DIVES AKURU DIGIT ZERO
+    '\u{11C50}', // BHAIKSUKI DIGIT ZERO
+    '\u{11D50}', // MASARAM GONDI DIGIT ZERO
+    '\u{11DA0}', // GUNJALA GONDI DIGIT ZERO
+    '\u{16A60}', // MRO DIGIT ZERO
+    '\u{16AC0}', // TANGSA DIGIT ZERO
+    '\u{16B50}', // PAHAWH HMONG DIGIT ZERO
+    '\u{1D7CE}', // MATHEMATICAL BOLD DIGIT ZERO
+    '\u{1D7D8}', // MATHEMATICAL DOUBLE-STRUCK DIGIT ZERO
+    '\u{1D7E2}', // MATHEMATICAL SANS-SERIF DIGIT ZERO
+    '\u{1D7EC}', // MATHEMATICAL SANS-SERIF BOLD DIGIT ZERO
+    '\u{1D7F6}', // MATHEMATICAL MONOSPACE DIGIT ZERO
+    '\u{1E140}', // NYIAKENG PUACHUE HMONG DIGIT ZERO
+    '\u{1E2F0}', // WANCHO DIGIT ZERO
+    '\u{1E950}', // ADLAM DIGIT ZERO
+    '\u{1FBF0}', // SEGMENTED DIGIT ZERO
+];
+
+// Returns the ASCII digit standing for `c`, if `c` is a non ASCII decimal digit.
+fn to_ascii_digit(c: char) -> Option<char> {
+    let num_zeros_before = DIGIT_ZEROS.partition_point(|&zero| zero <= c);
+    let zero = DIGIT_ZEROS[num_zeros_before.checked_sub(1)?];
+    let digit = c as u32 - zero as u32;
+    if digit < 10 {
+        char::from_digit(digit, 10)
+    } else {
+        None
+    }
+}
+
+fn fold_digits(text: &str, output: &mut String) {
+    output.clear();
+    output.extend(text.chars().map(|c| to_ascii_digit(c).unwrap_or(c)));
+}
+
+impl<'a, T: TokenStream> TokenStream for DecimalDigitFilterStream<'a, T> {
+    fn advance(&mut self) -> bool {
+        if !self.tail.advance() {
+            return false;
+        }
+        let text = &self.tail.token().text;
+        if text.chars().any(|c| to_ascii_digit(c).is_some()) {
+            fold_digits(text, self.buffer);
+            mem::swap(&mut self.tail.token_mut().text, self.buffer);
+        }
+        true
+    }
+
+    fn token(&self) -> &Token {
+        self.tail.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.tail.token_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::to_ascii_digit;
+    use crate::tokenizer::tests::assert_token;
+    use crate::tokenizer::{DecimalDigitFilter, TextAnalyzer, Token, WhitespaceTokenizer};
+
+    #[test]
+    fn test_decimal_digit_filter() {
+        let tokens = token_stream_helper("١٢٣ ४२ abc९ １０ 42");
+        assert_eq!(tokens.len(), 5);
+        assert_token(&tokens[0], 0, "123", 0, 6);
+        assert_token(&tokens[1], 1, "42", 7, 13);
+        assert_token(&tokens[2], 2, "abc9", 14, 20);
+        assert_token(&tokens[3], 3, "10", 21, 27);
+        assert_token(&tokens[4], 4, "42", 28, 30);
+    }
+
+    #[test]
+    fn test_to_ascii_digit() {
+        assert_eq!(to_ascii_digit('\u{0660}'), Some('0'));
+        assert_eq!(to_ascii_digit('\u{0669}'), Some('9'));
+        assert_eq!(to_ascii_digit('\u{1D7FF}'), Some('9'));
+        assert_eq!(to_ascii_digit('\u{066A}'), None);
+        assert_eq!(to_ascii_digit('5'), None);
+        assert_eq!(to_ascii_digit('a'), None);
+        // Digits that are not decimal are left untouched.
+        assert_eq!(to_ascii_digit('²'), None);
+    }
+
+    fn token_stream_helper(text: &str) -> Vec<Token> {
+        let mut a = TextAnalyzer::builder(WhitespaceTokenizer::default())
+            .filter(DecimalDigitFilter)
+            .build();
+        let mut token_stream = a.token_stream(text);
+        let mut tokens: Vec<Token> = vec![];
+        let mut add_token = |token: &Token| {
+            tokens.push(token.clone());
+        };
+        token_stream.process(&mut add_token);
+        tokens
+    }
+}
@@ -0,0 +1,215 @@
+//! # Example
+//! ```rust
+//! use tantivy::tokenizer::*;
+//!
+//! let mut tokenizer = TextAnalyzer::builder(SimpleTokenizer::default())
+//!   .filter(LowerCaser)
+//!   .filter(EdgeNgramFilter::new(1, 4).unwrap())
+//!   .build();
+//!
+//! let mut stream = tokenizer.token_stream("Search");
+//! assert_eq!(stream.next().unwrap().text, "s");
+//! assert_eq!(stream.next().unwrap().text, "se");
+//! assert_eq!(stream.next().unwrap().text, "sea");
+//! assert_eq!(stream.next().unwrap().text, "sear");
+//! assert!(stream.next().is_none());
+//! ```
+use std::mem;
+
+use super::{Token, TokenFilter, TokenStream, Tokenizer};
+use crate::TantivyError;
+
+/// `TokenFilter` that replaces each token by its prefixes (edge n-grams)
+/// of `min_gram` to `max_gram` characters.
+///
+/// This is typically used at indexing time to implement autocomplete,
+/// paired with a query-time analyzer that does not split the query into n-grams,
+/// e.g. `raw` or a `LowerCaser` based one.
+///
+/// All of the n-grams of a token are emitted at the position of the token,
+/// and keep its offsets.
+///
+/// Tokens shorter than `min_gram` are removed, unless
+/// [`set_keep_short_tokens`](EdgeNgramFilter::set_keep_short_tokens) is set.
+#[derive(Clone, Debug)]
+pub struct EdgeNgramFilter {
+    min_gram: usize,
+    max_gram: usize,
+    keep_short_tokens: bool,
+}
+
+impl EdgeNgramFilter {
+    /// Creates an `EdgeNgramFilter` emitting prefixes of `min_gram` to `max_gram` characters.
+    pub fn new(min_gram: usize, max_gram: usize) -> crate::Result<EdgeNgramFilter> {
+        if min_gram == 0 {
+            return Err(TantivyError::InvalidArgument(
+                "min_gram must be greater than 0".to_string(),
+            ));
+        }
+        if min_gram > max_gram {
+            return Err(TantivyError::InvalidArgument(
+                "min_gram must not be greater than max_gram".to_string(),
+            ));
+        }
+        Ok(EdgeNgramFilter {
+            min_gram,
+            max_gram,
+            keep_short_tokens: false,
+        })
+    }
+
+    /// If `keep_short_tokens` is true, tokens shorter than `min_gram` are emitted
+    /// unchanged instead of being removed.
+    pub fn set_keep_short_tokens(mut self, keep_short_tokens: bool) -> EdgeNgramFilter {
+        self.keep_short_tokens = keep_short_tokens;
+        self
+    }
+}
+
+impl TokenFilter for EdgeNgramFilter {
+    type Tokenizer<T: Tokenizer> = EdgeNgramFilterWrapper<T>;
+
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> EdgeNgramFilterWrapper<T> {
+        EdgeNgramFilterWrapper {
+            config: self,
+            inner: tokenizer,
+            original: String::new(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct EdgeNgramFilterWrapper<T> {
+    config: EdgeNgramFilter,
+    inner: T,
+    original: String,
+}
+
+impl<T: Tokenizer> Tokenizer for EdgeNgramFilterWrapper<T> {
+    type TokenStream<'a> = EdgeNgramFilterStream<'a, T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        self.original.clear();
+        EdgeNgramFilterStream {
+            config: &self.config,
+            tail: self.inner.token_stream(text),
+            original: &mut self.original,
+            gram_len: 0,
+            max_gram_len: 0,
+        }
+    }
+}
+
+pub struct EdgeNgramFilterStream<'a, T> {
+    config: &'a EdgeNgramFilter,
+    tail: T,
+    // Text of the token the n-grams are computed from.
+    original: &'a mut String,
+    // Length in chars of the last emitted n-gram.
+    gram_len: usize,
+    // Length in chars of the longest n-gram to emit for `original`.
+    max_gram_len: usize,
+}
+
+impl<'a, T: TokenStream> EdgeNgramFilterStream<'a, T> {
+    // Fetches the next token of the tail, and prepares its n-grams.
+    // Returns false if the tail is exhausted.
+    fn advance_tail(&mut self) -> bool {
+        while self.tail.advance() {
+            let num_chars = self.tail.token().text.chars().count();
+            if num_chars < self.config.min_gram {
+                if self.config.keep_short_tokens && num_chars > 0 {
+                    // The token is emitted unchanged.
+                    self.gram_len = 0;
+                    self.max_gram_len = 0;
+                    return true;
+                }
+                continue;
+            }
+            mem::swap(&mut self.tail.token_mut().text, self.original);
+            self.gram_len = self.config.min_gram - 1;
+            self.max_gram_len = num_chars.min(self.config.max_gram);
+            self.emit_next_gram();
+            return true;
+        }
+        false
+    }
+
+    fn emit_next_gram(&mut self) {
+        self.gram_len += 1;
+        let gram_end = self
+            .original
+            .char_indices()
+            .nth(self.gram_len)
+            .map(|(offset, _)| offset)
+            .unwrap_or(self.original.len());
+        let text = &mut self.tail.token_mut().text;
+        text.clear();
+        text.push_str(&self.original[..gram_end]);
+    }
+}
+
+impl<'a, T: TokenStream> TokenStream for EdgeNgramFilterStream<'a, T> {
+    fn advance(&mut self) -> bool {
+        if self.gram_len < self.max_gram_len {
+            self.emit_next_gram();
+            return true;
+        }
+        self.advance_tail()
+    }
+
+    fn token(&self) -> &Token {
+        self.tail.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.tail.token_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tokenizer::tests::assert_token;
+    use crate::tokenizer::{EdgeNgramFilter, SimpleTokenizer, TextAnalyzer, Token};
+
+    fn token_stream_helper(filter: EdgeNgramFilter, text: &str) -> Vec<Token> {
+        let mut a = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(filter)
+            .build();
+        let mut token_stream = a.token_stream(text);
+        let mut tokens: Vec<Token> = vec![];
+        let mut add_token = |token: &Token| {
+            tokens.push(token.clone());
+        };
+        token_stream.process(&mut add_token);
+        tokens
+    }
+
+    #[test]
+    fn test_edge_ngram_filter() {
+        let tokens = token_stream_helper(EdgeNgramFilter::new(2, 4).unwrap(), "a search été");
+        assert_eq!(tokens.len(), 5);
+        assert_token(&tokens[0], 1, "se", 2, 8);
+        assert_token(&tokens[1], 1, "sea", 2, 8);
+        assert_token(&tokens[2], 1, "sear", 2, 8);
+        assert_token(&tokens[3], 2, "ét", 9, 14);
+        assert_token(&tokens[4], 2, "été", 9, 14);
+    }
+
+    #[test]
+    fn test_edge_ngram_filter_keep_short_tokens() {
+        let filter = EdgeNgramFilter::new(2, 3)
+            .unwrap()
+            .set_keep_short_tokens(true);
+        let tokens = token_stream_helper(filter, "a be");
+        assert_eq!(tokens.len(), 2);
+        assert_token(&tokens[0], 0, "a", 0, 1);
+        assert_token(&tokens[1], 1, "be", 2, 4);
+    }
+
+    #[test]
+    fn test_edge_ngram_filter_invalid_interval() {
+        assert!(EdgeNgramFilter::new(0, 2).is_err());
+        assert!(EdgeNgramFilter::new(3, 2).is_err());
+    }
+}
@@ -0,0 +1,171 @@
+//! # Example
+//! ```rust
+//! use tantivy::tokenizer::*;
+//!
+//! let mut tokenizer = TextAnalyzer::builder(WhitespaceTokenizer::default())
+//!   .filter(ElisionFilter::french())
+//!   .build();
+//!
+//! let mut stream = tokenizer.token_stream("L'avion d’accord aujourd'hui");
+//! assert_eq!(stream.next().unwrap().text, "avion");
+//! assert_eq!(stream.next().unwrap().text, "accord");
+//! assert_eq!(stream.next().unwrap().text, "aujourd'hui");
+//! assert!(stream.next().is_none());
+//! ```
+use std::sync::Arc;
+
+use rustc_hash::FxHashSet;
+
+use super::{Token, TokenFilter, TokenStream, Tokenizer};
+
+/// Elided articles removed by [`ElisionFilter::french`].
+const FRENCH_ARTICLES: &[&str] = &[
+    "l", "m", "t", "qu", "n", "s", "j", "d", "c", "jusqu", "quoiqu", "lorsqu", "puisqu",
+];
+
+/// `TokenFilter` that removes elided articles, e.g. `l'` in `l'avion`.
+///
+/// The prefix of a token up to its first apostrophe (`'` or `’`) is removed if it
+/// matches one of the articles, compared case-insensitively.
+/// Offsets are not modified and still point to the original text.
+///
+/// This filter needs a tokenizer that does not split tokens on apostrophes,
+/// like the [`WhitespaceTokenizer`](super::WhitespaceTokenizer). The
+/// [`SimpleTokenizer`](super::SimpleTokenizer) on the other hand emits the article
+/// as a token of its own.
+#[derive(Clone)]
+pub struct ElisionFilter {
+    articles: Arc<FxHashSet<String>>,
+}
+
+impl ElisionFilter {
+    /// Creates an `ElisionFilter` given a list of articles.
+    pub fn from_articles<W: IntoIterator<Item = String>>(articles: W) -> ElisionFilter {
+        let articles = articles
+            .into_iter()
+            .map(|article| article.to_lowercase())
+            .collect();
+        ElisionFilter {
+            articles: Arc::new(articles),
+        }
+    }
+
+    /// Creates an `ElisionFilter` removing the French elided articles:
+    /// `l`, `m`, `t`, `qu`, `n`, `s`, `j`, `d`, `c`, `jusqu`, `quoiqu`, `lorsqu` and `puisqu`.
+    pub fn french() -> ElisionFilter {
+        ElisionFilter::from_articles(FRENCH_ARTICLES.iter().map(|&article| article.to_string()))
+    }
+}
+
+impl TokenFilter for ElisionFilter {
+    type Tokenizer<T: Tokenizer> = ElisionFilterWrapper<T>;
+
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> ElisionFilterWrapper<T> {
+        ElisionFilterWrapper {
+            articles: self.articles,
+            inner: tokenizer,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ElisionFilterWrapper<T> {
+    articles: Arc<FxHashSet<String>>,
+    inner: T,
+}
+
+impl<T: Tokenizer> Tokenizer for ElisionFilterWrapper<T> {
+    type TokenStream<'a> = ElisionFilterStream<'a, T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        ElisionFilterStream {
+            articles: &self.articles,
+            tail: self.inner.token_stream(text),
+        }
+    }
+}
+
+pub struct ElisionFilterStream<'a, T> {
+    articles: &'a FxHashSet<String>,
+    tail: T,
+}
+
+impl<'a, T: TokenStream> ElisionFilterStream<'a, T> {
+    // Returns the length of the elided article of `text`, apostrophe included,
+    // or `None` if it does not start with an article.
+    fn elision_len(&self, text: &str) -> Option<usize> {
+        let (apostrophe_pos, apostrophe) =
+            text.char_indices().find(|(_, c)| matches!(c, '\'' | '’'))?;
+        let prefix = &text[..apostrophe_pos];
+        let is_article = if prefix.chars().any(char::is_uppercase) {
+            self.articles.contains(&prefix.to_lowercase())
+        } else {
+            self.articles.contains(prefix)
+        };
+        is_article.then(|| apostrophe_pos + apostrophe.len_utf8())
+    }
+}
+
+impl<'a, T: TokenStream> TokenStream for ElisionFilterStream<'a, T> {
+    fn advance(&mut self) -> bool {
+        if !self.tail.advance() {
+            return false;
+        }
+        if let Some(elision_len) = self.elision_len(&self.tail.token().text) {
+            self.tail.token_mut().text.drain(..elision_len);
+        }
+        true
+    }
+
+    fn token(&self) -> &Token {
+        self.tail.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.tail.token_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tokenizer::tests::assert_token;
+    use crate::tokenizer::{ElisionFilter, TextAnalyzer, Token, WhitespaceTokenizer};
+
+    fn token_stream_helper(filter: ElisionFilter, text: &str) -> Vec<Token> {
+        let mut a = TextAnalyzer::builder(WhitespaceTokenizer::default())
+            .filter(filter)
+            .build();
+        let mut token_stream = a.token_stream(text);
+        let mut tokens: Vec<Token> = vec![];
+        let mut add_token = |token: &Token| {
+            tokens.push(token.clone());
+        };
+        token_stream.process(&mut add_token);
+        tokens
+    }
+
+    #[test]
+    fn test_elision_filter_french() {
+        let tokens = token_stream_helper(
+            ElisionFilter::french(),
+            "l'eau D'accord qu’il aujourd'hui 'quote",
+        );
+        assert_eq!(tokens.len(), 5);
+        assert_token(&tokens[0], 0, "eau", 0, 5);
+        assert_token(&tokens[1], 1, "accord", 6, 14);
+        assert_token(&tokens[2], 2, "il", 15, 22);
+        assert_token(&tokens[3], 3, "aujourd'hui", 23, 34);
+        assert_token(&tokens[4], 4, "'quote", 35, 41);
+    }
+
+    #[test]
+    fn test_elision_filter_custom_articles() {
+        let tokens = token_stream_helper(
+            ElisionFilter::from_articles(vec!["DELL".to_string()]),
+            "dell'arte l'arte",
+        );
+        assert_eq!(tokens.len(), 2);
+        assert_token(&tokens[0], 0, "arte", 0, 9);
+        assert_token(&tokens[1], 1, "l'arte", 10, 16);
+    }
+}
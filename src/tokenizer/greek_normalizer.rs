@@ -0,0 +1,175 @@
+//! # Example
+//! ```rust
+//! use tantivy::tokenizer::*;
+//!
+//! let mut tokenizer = TextAnalyzer::builder(WhitespaceTokenizer::default())
+//!   .filter(GreekNormalizationFilter)
+//!   .build();
+//!
+//! // The accented and capitalized spellings produce the same token.
+//! let mut stream = tokenizer.token_stream("Οδός ΟΔΟΣ οδοσ");
+//! assert_eq!(stream.next().unwrap().text, "οδοσ");
+//! assert_eq!(stream.next().unwrap().text, "οδοσ");
+//! assert_eq!(stream.next().unwrap().text, "οδοσ");
+//! assert!(stream.next().is_none());
+//! ```
+use std::mem;
+
+use super::{Token, TokenFilter, TokenStream, Tokenizer};
+
+/// `TokenFilter` that lowercases Greek text and normalizes its orthographic variations.
+///
+/// It
+/// - lowercases the tokens, `Σ` being always mapped to `σ`,
+/// - maps the final sigma `ς` to `σ`, so that a word gives the same token wherever the sigma
+///   stands,
+/// - removes the tonos and the dialytika, e.g. `ά` becomes `α` and `ϊ` becomes `ι`, whether they
+///   are precomposed or written as combining marks.
+///
+/// Polytonic diacritics are left untouched. Offsets are not modified and still point to the
+/// original text.
+///
+/// It replaces the [`LowerCaser`](super::LowerCaser) in front of a Greek
+/// [`Stemmer`](super::Stemmer).
+#[derive(Clone)]
+pub struct GreekNormalizationFilter;
+
+impl TokenFilter for GreekNormalizationFilter {
+    type Tokenizer<T: Tokenizer> = GreekNormalizationFilterWrapper<T>;
+
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> GreekNormalizationFilterWrapper<T> {
+        GreekNormalizationFilterWrapper {
+            inner: tokenizer,
+            buffer: String::new(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct GreekNormalizationFilterWrapper<T> {
+    inner: T,
+    buffer: String,
+}
+
+impl<T: Tokenizer> Tokenizer for GreekNormalizationFilterWrapper<T> {
+    type TokenStream<'a> = GreekNormalizationFilterStream<'a, T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        self.buffer.clear();
+        GreekNormalizationFilterStream {
+            tail: self.inner.token_stream(text),
+            buffer: &mut self.buffer,
+        }
+    }
+}
+
+pub struct GreekNormalizationFilterStream<'a, T> {
+    buffer: &'a mut String,
+    tail: T,
+}
+
+// Returns true if `c` belongs to the Greek and Coptic block.
+fn is_greek(c: char) -> bool {
+    ('\u{0370}'..='\u{03FF}').contains(&c)
+}
+
+// Returns the normalized form of a lowercased character.
+fn normalize_greek_char(c: char) -> char {
+    match c {
+        'ά' => 'α',
+        'έ' => 'ε',
+        'ή' => 'η',
+        'ί' | 'ϊ' | 'ΐ' => 'ι',
+        'ό' => 'ο',
+        'ύ' | 'ϋ' | 'ΰ' => 'υ',
+        'ώ' => 'ω',
+        'ς' => 'σ',
+        _ => c,
+    }
+}
+
+fn normalize_greek(text: &str, output: &mut String) {
+    output.clear();
+    let mut after_greek_char = false;
+    for c in text.chars() {
+        // combining acute accent and combining diaeresis, standing for the tonos and the
+        // dialytika in decomposed text.
+        if after_greek_char && (c == '\u{0301}' || c == '\u{0308}') {
+            continue;
+        }
+        output.extend(c.to_lowercase().map(normalize_greek_char));
+        after_greek_char = is_greek(c);
+    }
+}
+
+impl<'a, T: TokenStream> TokenStream for GreekNormalizationFilterStream<'a, T> {
+    fn advance(&mut self) -> bool {
+        if !self.tail.advance() {
+            return false;
+        }
+        if self.tail.token().text.is_ascii() {
+            self.tail.token_mut().text.make_ascii_lowercase();
+        } else {
+            normalize_greek(&self.tail.token().text, self.buffer);
+            mem::swap(&mut self.tail.token_mut().text, self.buffer);
+        }
+        true
+    }
+
+    fn token(&self) -> &Token {
+        self.tail.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.tail.token_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tokenizer::tests::assert_token;
+    use crate::tokenizer::{
+        GreekNormalizationFilter, TextAnalyzer, Token, TokenizerManager, WhitespaceTokenizer,
+    };
+
+    #[test]
+    fn test_greek_normalization() {
+        let tokens = token_stream_helper("Αθήνας ΑΘΗΝΑΣ προϋπόθεση ΐ Ω\u{301}ρα Tonos");
+        assert_eq!(tokens.len(), 6);
+        assert_token(&tokens[0], 0, "αθηνασ", 0, 12);
+        assert_token(&tokens[1], 1, "αθηνασ", 13, 25);
+        assert_token(&tokens[2], 2, "προυποθεση", 26, 46);
+        assert_token(&tokens[3], 3, "ι", 47, 49);
+        assert_token(&tokens[4], 4, "ωρα", 50, 58);
+        assert_token(&tokens[5], 5, "tonos", 59, 64);
+    }
+
+    #[test]
+    fn test_greek_stemmer_final_sigma() {
+        let tokenizer_manager = TokenizerManager::default();
+        let mut el_stem = tokenizer_manager.get("el_stem").unwrap();
+        let mut stem = |text: &str| {
+            let mut tokens: Vec<String> = vec![];
+            el_stem
+                .token_stream(text)
+                .process(&mut |token: &Token| tokens.push(token.text.clone()));
+            tokens
+        };
+        assert_eq!(stem("ΑΘΗΝΑΣ"), stem("Αθήνας"));
+        assert_eq!(stem("αθηνας"), stem("αθηνασ"));
+        assert_eq!(stem("ΔΡΟΜΟΣ"), stem("δρόμος"));
+    }
+
+    fn token_stream_helper(text: &str) -> Vec<Token> {
+        let mut a = TextAnalyzer::builder(WhitespaceTokenizer::default())
+            .filter(GreekNormalizationFilter)
+            .build();
+        let mut token_stream = a.token_stream(text);
+        let mut tokens: Vec<Token> = vec![];
+        let mut add_token = |token: &Token| {
+            tokens.push(token.clone());
+        };
+        token_stream.process(&mut add_token);
+        tokens
+    }
+}
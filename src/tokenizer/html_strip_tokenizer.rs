@@ -0,0 +1,316 @@
+//! # Example
+//! ```rust
+//! use tantivy::tokenizer::*;
+//!
+//! let mut tokenizer =
+//!     TextAnalyzer::builder(HtmlStripTokenizer::wrap(SimpleTokenizer::default())).build();
+//!
+//! let mut stream = tokenizer.token_stream("<p>Caf&#233; <b>cr&egrave;me</b></p>");
+//! let token = stream.next().unwrap();
+//! assert_eq!(token.text, "Café");
+//! // Offsets point to the original HTML.
+//! assert_eq!((token.offset_from, token.offset_to), (3, 12));
+//! // `&egrave;` is not decoded, only the most common named entities are.
+//! assert_eq!(stream.next().unwrap().text, "cr");
+//! ```
+use super::{Token, TokenStream, Tokenizer};
+
+/// Tokenizer wrapping another tokenizer, and feeding it the text content of an HTML document.
+///
+/// Tags and comments are removed, as well as the content of the `script` and `style` elements.
+/// Tags are replaced by a whitespace, so that they separate words.
+/// Decimal and hexadecimal character references, e.g. `&#233;` or `&#xE9;`, are decoded, as well
+/// as the named entities `&amp;`, `&lt;`, `&gt;`, `&quot;`, `&apos;` and `&nbsp;`.
+///
+/// The offsets of the emitted tokens point to the original HTML, so that snippets
+/// highlight the right part of the document.
+#[derive(Clone)]
+pub struct HtmlStripTokenizer<T> {
+    inner: T,
+    stripped: String,
+    segments: Vec<OffsetSegment>,
+}
+
+impl<T: Tokenizer> HtmlStripTokenizer<T> {
+    /// Wraps `inner`, feeding it the text stripped from its HTML markup.
+    pub fn wrap(inner: T) -> HtmlStripTokenizer<T> {
+        HtmlStripTokenizer {
+            inner,
+            stripped: String::new(),
+            segments: Vec::new(),
+        }
+    }
+}
+
+impl<T: Tokenizer> Tokenizer for HtmlStripTokenizer<T> {
+    type TokenStream<'a> = HtmlStripTokenStream<'a, T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        strip_html(text, &mut self.stripped, &mut self.segments);
+        HtmlStripTokenStream {
+            segments: &self.segments,
+            tail: self.inner.token_stream(&self.stripped),
+        }
+    }
+}
+
+pub struct HtmlStripTokenStream<'a, T> {
+    segments: &'a [OffsetSegment],
+    tail: T,
+}
+
+impl<'a, T: TokenStream> TokenStream for HtmlStripTokenStream<'a, T> {
+    fn advance(&mut self) -> bool {
+        if !self.tail.advance() {
+            return false;
+        }
+        let token = self.tail.token_mut();
+        let offset_from = original_offset_from(self.segments, token.offset_from);
+        let offset_to = original_offset_to(self.segments, token.offset_to);
+        token.offset_from = offset_from;
+        token.offset_to = offset_to;
+        true
+    }
+
+    fn token(&self) -> &Token {
+        self.tail.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.tail.token_mut()
+    }
+}
+
+/// Range of the stripped text, and the range of the original HTML it comes from.
+///
+/// Either the text was copied verbatim, and both ranges have the same length,
+/// or it is a single char replacing some markup, e.g. an entity or a tag.
+#[derive(Clone, Copy, Debug)]
+struct OffsetSegment {
+    stripped_from: usize,
+    stripped_to: usize,
+    original_from: usize,
+    original_to: usize,
+}
+
+impl OffsetSegment {
+    fn is_verbatim(&self) -> bool {
+        self.stripped_to - self.stripped_from == self.original_to - self.original_from
+    }
+}
+
+// Maps the offset of the first byte of a token in the stripped text to the original HTML.
+fn original_offset_from(segments: &[OffsetSegment], offset: usize) -> usize {
+    let num_segments_before = segments.partition_point(|segment| segment.stripped_from <= offset);
+    let Some(segment) = num_segments_before
+        .checked_sub(1)
+        .map(|segment_ord| segments[segment_ord])
+    else {
+        return 0;
+    };
+    if segment.is_verbatim() {
+        segment.original_from + (offset - segment.stripped_from)
+    } else if offset == segment.stripped_from {
+        segment.original_from
+    } else {
+        segment.original_to
+    }
+}
+
+// Maps the offset following the last byte of a token in the stripped text to the original HTML.
+fn original_offset_to(segments: &[OffsetSegment], offset: usize) -> usize {
+    let num_segments_before = segments.partition_point(|segment| segment.stripped_from < offset);
+    let Some(segment) = num_segments_before
+        .checked_sub(1)
+        .map(|segment_ord| segments[segment_ord])
+    else {
+        return original_offset_from(segments, offset);
+    };
+    if segment.is_verbatim() {
+        segment.original_from + (offset - segment.stripped_from)
+    } else {
+        segment.original_to
+    }
+}
+
+fn push_segment(
+    text: &str,
+    original_from: usize,
+    original_to: usize,
+    stripped: &mut String,
+    segments: &mut Vec<OffsetSegment>,
+) {
+    if text.is_empty() {
+        return;
+    }
+    let stripped_from = stripped.len();
+    stripped.push_str(text);
+    segments.push(OffsetSegment {
+        stripped_from,
+        stripped_to: stripped.len(),
+        original_from,
+        original_to,
+    });
+}
+
+// Writes the text content of `html` to `stripped`, and the mapping
+// of its offsets to the original `html` to `segments`.
+fn strip_html(html: &str, stripped: &mut String, segments: &mut Vec<OffsetSegment>) {
+    stripped.clear();
+    segments.clear();
+    let bytes = html.as_bytes();
+    let mut text_start = 0;
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let markup = match bytes[pos] {
+            b'<' => parse_tag(&html[pos..]).map(|tag_len| (tag_len, ' ')),
+            b'&' => parse_entity(&html[pos..]),
+            _ => None,
+        };
+        let Some((markup_len, replacement)) = markup else {
+            pos += 1;
+            continue;
+        };
+        push_segment(&html[text_start..pos], text_start, pos, stripped, segments);
+        let mut utf8_buffer = [0u8; 4];
+        push_segment(
+            replacement.encode_utf8(&mut utf8_buffer),
+            pos,
+            pos + markup_len,
+            stripped,
+            segments,
+        );
+        pos += markup_len;
+        text_start = pos;
+    }
+    push_segment(
+        &html[text_start..],
+        text_start,
+        html.len(),
+        stripped,
+        segments,
+    );
+}
+
+// Returns the length of the tag or comment `text` starts with,
+// including the content of the element for `script` and `style`.
+//
+// Returns `None` if `text` does not start with a tag,
+// in which case its `<` is regular text.
+fn parse_tag(text: &str) -> Option<usize> {
+    if text.starts_with("<!--") {
+        return Some(text.find("-->").map_or(text.len(), |end| end + 3));
+    }
+    let first_byte = *text.as_bytes().get(1)?;
+    if !(first_byte.is_ascii_alphabetic() || matches!(first_byte, b'/' | b'!' | b'?')) {
+        return None;
+    }
+    let tag_len = text.find('>')? + 1;
+    let name_len = text[1..]
+        .bytes()
+        .take_while(u8::is_ascii_alphanumeric)
+        .count();
+    let name = &text[1..1 + name_len];
+    if !(name.eq_ignore_ascii_case("script") || name.eq_ignore_ascii_case("style")) {
+        return Some(tag_len);
+    }
+    // The content of the element is dropped, up to and including its closing tag.
+    let mut search_from = tag_len;
+    while let Some(closing_pos) = text[search_from..].find("</").map(|pos| search_from + pos) {
+        let closing_name_end = closing_pos + 2 + name_len;
+        let closing_name = text.get(closing_pos + 2..closing_name_end);
+        if matches!(closing_name, Some(closing_name) if closing_name.eq_ignore_ascii_case(name)) {
+            return Some(
+                text[closing_name_end..]
+                    .find('>')
+                    .map_or(text.len(), |end| closing_name_end + end + 1),
+            );
+        }
+        search_from = closing_pos + 2;
+    }
+    Some(text.len())
+}
+
+// Longest entity we decode, `&#x10FFFF;`.
+const MAX_ENTITY_LEN: usize = 10;
+
+// Returns the length of the entity `text` starts with, and the char it stands for.
+//
+// Returns `None` if `text` does not start with an entity we can decode,
+// in which case its `&` is regular text.
+fn parse_entity(text: &str) -> Option<(usize, char)> {
+    let semicolon_pos = text.bytes().take(MAX_ENTITY_LEN).position(|b| b == b';')?;
+    let name = &text[1..semicolon_pos];
+    let decoded = if let Some(code) = name.strip_prefix('#') {
+        let code_point = if let Some(hex_code) = code.strip_prefix(['x', 'X']) {
+            u32::from_str_radix(hex_code, 16).ok()?
+        } else {
+            code.parse::<u32>().ok()?
+        };
+        char::from_u32(code_point)?
+    } else {
+        match name {
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "quot" => '"',
+            "apos" => '\'',
+            "nbsp" => '\u{00A0}',
+            _ => return None,
+        }
+    };
+    Some((semicolon_pos + 1, decoded))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::strip_html;
+    use crate::tokenizer::tests::assert_token;
+    use crate::tokenizer::{HtmlStripTokenizer, SimpleTokenizer, TextAnalyzer, Token};
+
+    fn token_stream_helper(text: &str) -> Vec<Token> {
+        let mut a =
+            TextAnalyzer::builder(HtmlStripTokenizer::wrap(SimpleTokenizer::default())).build();
+        let mut token_stream = a.token_stream(text);
+        let mut tokens: Vec<Token> = vec![];
+        let mut add_token = |token: &Token| {
+            tokens.push(token.clone());
+        };
+        token_stream.process(&mut add_token);
+        tokens
+    }
+
+    fn stripped_text(html: &str) -> String {
+        let mut stripped = String::new();
+        strip_html(html, &mut stripped, &mut Vec::new());
+        stripped
+    }
+
+    #[test]
+    fn test_html_strip_tokenizer() {
+        let html = "<html><head><style>p { color: red; }</style></head><body><p \
+                    class=\"x\">Caf&#233; &amp; <b>cr&#xE8;me</b></p><script>var x = \
+                    \"<p>\";</SCRIPT><!-- <p>comment</p> -->Fin</body></html>";
+        let tokens = token_stream_helper(html);
+        assert_eq!(tokens.len(), 3);
+        assert_token(&tokens[0], 0, "Café", 70, 79);
+        assert_token(&tokens[1], 1, "crème", 89, 99);
+        assert_token(&tokens[2], 2, "Fin", 161, 164);
+        assert_eq!(
+            &html[tokens[1].offset_from..tokens[1].offset_to],
+            "cr&#xE8;me"
+        );
+    }
+
+    #[test]
+    fn test_html_strip_regular_text() {
+        assert_eq!(stripped_text("AT&T a<b &foo; x"), "AT&T a<b &foo; x");
+        assert_eq!(stripped_text("a<br/>b&lt;c"), "a b<c");
+        assert_eq!(stripped_text("a<script>b"), "a ");
+        assert_eq!(stripped_text("é<i>è</i>"), "é è ");
+        let tokens = token_stream_helper("AT&T a<b");
+        assert_eq!(tokens.len(), 4);
+        assert_token(&tokens[0], 0, "AT", 0, 2);
+        assert_token(&tokens[3], 3, "b", 7, 8);
+    }
+}
@@ -0,0 +1,244 @@
+use std::io::BufRead;
+
+use regex::Regex;
+use rustc_hash::FxHashMap;
+
+use crate::TantivyError;
+
+type Flag = u64;
+
+/// How the flags are written in the `.aff` and `.dic` files, set by the `FLAG` option.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum FlagFormat {
+    // Each char is a flag, the default.
+    Char,
+    // Each pair of chars is a flag.
+    Long,
+    // Flags are comma separated numbers.
+    Num,
+}
+
+impl FlagFormat {
+    fn parse_flags(self, flags: &str) -> Vec<Flag> {
+        match self {
+            FlagFormat::Char => flags.chars().map(|c| c as Flag).collect(),
+            FlagFormat::Long => {
+                let chars: Vec<char> = flags.chars().collect();
+                chars
+                    .chunks(2)
+                    .map(|pair| pair.iter().fold(0, |flag, &c| flag * 0x11_0000 + c as Flag))
+                    .collect()
+            }
+            FlagFormat::Num => flags
+                .split(',')
+                .filter_map(|flag| flag.trim().parse().ok())
+                .collect(),
+        }
+    }
+}
+
+/// A `PFX` or `SFX` rule of the `.aff` file.
+struct AffixRule {
+    flag: Flag,
+    cross_product: bool,
+    // Removed from the stem when the affix is appended.
+    strip: String,
+    affix: String,
+    // Condition on the stem, `None` if any stem matches.
+    condition: Option<Regex>,
+}
+
+impl AffixRule {
+    // Returns the stem `word` derives from if the rule is a suffix rule that applies to it.
+    fn remove_suffix(&self, word: &str) -> Option<String> {
+        let root = word.strip_suffix(self.affix.as_str())?;
+        if root.is_empty() {
+            return None;
+        }
+        let stem = format!("{root}{}", self.strip);
+        self.matches_condition(&stem).then_some(stem)
+    }
+
+    // Returns the stem `word` derives from if the rule is a prefix rule that applies to it.
+    fn remove_prefix(&self, word: &str) -> Option<String> {
+        let root = word.strip_prefix(self.affix.as_str())?;
+        if root.is_empty() {
+            return None;
+        }
+        let stem = format!("{}{root}", self.strip);
+        self.matches_condition(&stem).then_some(stem)
+    }
+
+    fn matches_condition(&self, stem: &str) -> bool {
+        match &self.condition {
+            Some(condition) => condition.is_match(stem),
+            None => true,
+        }
+    }
+}
+
+/// The words of a Hunspell dictionary, with their affix flags, and the affix rules.
+pub(crate) struct HunspellDictionary {
+    words: FxHashMap<String, Vec<Flag>>,
+    prefixes: Vec<AffixRule>,
+    suffixes: Vec<AffixRule>,
+}
+
+fn invalid_line(file: &str, line: &str) -> TantivyError {
+    TantivyError::InvalidArgument(format!(
+        "Invalid line in the hunspell {file} file: `{line}`"
+    ))
+}
+
+// Hunspell uses `0` for an empty strip or affix.
+fn parse_affix_text(text: &str) -> String {
+    if text == "0" {
+        String::new()
+    } else {
+        text.to_lowercase()
+    }
+}
+
+impl HunspellDictionary {
+    /// Reads a dictionary from the content of its UTF-8 encoded `.aff` and `.dic` files.
+    ///
+    /// Words, affixes and conditions are lowercased.
+    pub(crate) fn from_readers<A: BufRead, D: BufRead>(
+        aff: A,
+        dic: D,
+    ) -> crate::Result<HunspellDictionary> {
+        let mut flag_format = FlagFormat::Char;
+        // Flag sets referred to by their 1-based index, set by the `AF` option.
+        let mut flag_aliases: Option<Vec<Vec<Flag>>> = None;
+        let mut cross_products: FxHashMap<(bool, Flag), bool> = FxHashMap::default();
+        let mut prefixes = Vec::new();
+        let mut suffixes = Vec::new();
+        for line in aff.lines() {
+            let line = line?;
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields[..] {
+                ["FLAG", "long", ..] => flag_format = FlagFormat::Long,
+                ["FLAG", "num", ..] => flag_format = FlagFormat::Num,
+                ["AF", flags, ..] => match &mut flag_aliases {
+                    Some(flag_aliases) => flag_aliases.push(flag_format.parse_flags(flags)),
+                    // The first `AF` line holds the number of aliases.
+                    None => flag_aliases = Some(Vec::new()),
+                },
+                // The header of a group of rules. A rule without a condition has 4 fields too.
+                [kind @ ("PFX" | "SFX"), flag, cross_product @ ("Y" | "N"), num_rules]
+                    if num_rules.parse::<usize>().is_ok() =>
+                {
+                    let Some(&flag) = flag_format.parse_flags(flag).first() else {
+                        return Err(invalid_line("aff", &line));
+                    };
+                    cross_products.insert((kind == "PFX", flag), cross_product == "Y");
+                }
+                [kind @ ("PFX" | "SFX"), flag, strip, affix, ref rest @ ..] => {
+                    // A missing condition accepts all of the words.
+                    let condition = rest.first().copied().unwrap_or(".");
+                    let is_prefix = kind == "PFX";
+                    let Some(&flag) = flag_format.parse_flags(flag).first() else {
+                        return Err(invalid_line("aff", &line));
+                    };
+                    // The flags following the affix allow to chain suffixes, which we ignore.
+                    let affix = affix.split('/').next().unwrap_or(affix);
+                    let condition = if condition == "." {
+                        None
+                    } else {
+                        let condition = condition.to_lowercase();
+                        let pattern = if is_prefix {
+                            format!("^{condition}")
+                        } else {
+                            format!("{condition}$")
+                        };
+                        Some(Regex::new(&pattern).map_err(|_| invalid_line("aff", &line))?)
+                    };
+                    let rule = AffixRule {
+                        flag,
+                        cross_product: cross_products
+                            .get(&(is_prefix, flag))
+                            .copied()
+                            .unwrap_or(false),
+                        strip: parse_affix_text(strip),
+                        affix: parse_affix_text(affix),
+                        condition,
+                    };
+                    if is_prefix {
+                        prefixes.push(rule);
+                    } else {
+                        suffixes.push(rule);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut words: FxHashMap<String, Vec<Flag>> = FxHashMap::default();
+        for (line_ord, line) in dic.lines().enumerate() {
+            let line = line?;
+            // Morphological fields follow the word, separated by a whitespace.
+            let Some(entry) = line.split_whitespace().next() else {
+                continue;
+            };
+            // The first line holds the number of words.
+            if line_ord == 0 && entry.parse::<usize>().is_ok() {
+                continue;
+            }
+            let (word, flags) = entry.split_once('/').unwrap_or((entry, ""));
+            let flags = match &flag_aliases {
+                Some(flag_aliases) if !flags.is_empty() => flags
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|alias| flag_aliases.get(alias.checked_sub(1)?))
+                    .cloned()
+                    .ok_or_else(|| invalid_line("dic", &line))?,
+                _ => flag_format.parse_flags(flags),
+            };
+            words.entry(word.to_lowercase()).or_default().extend(flags);
+        }
+        Ok(HunspellDictionary {
+            words,
+            prefixes,
+            suffixes,
+        })
+    }
+
+    fn has_flag(&self, word: &str, flag: Flag) -> bool {
+        matches!(self.words.get(word), Some(flags) if flags.contains(&flag))
+    }
+
+    /// Returns the stem of `word`, if it is an inflected form of a word of the dictionary.
+    ///
+    /// Returns `None` if `word` is unknown, or is a stem already.
+    pub(crate) fn stem(&self, word: &str) -> Option<String> {
+        if self.words.contains_key(word) {
+            return None;
+        }
+        for suffix in &self.suffixes {
+            if let Some(stem) = suffix.remove_suffix(word) {
+                if self.has_flag(&stem, suffix.flag) {
+                    return Some(stem);
+                }
+            }
+        }
+        for prefix in &self.prefixes {
+            let Some(without_prefix) = prefix.remove_prefix(word) else {
+                continue;
+            };
+            if self.has_flag(&without_prefix, prefix.flag) {
+                return Some(without_prefix);
+            }
+            if !prefix.cross_product {
+                continue;
+            }
+            for suffix in self.suffixes.iter().filter(|suffix| suffix.cross_product) {
+                if let Some(stem) = suffix.remove_suffix(&without_prefix) {
+                    if self.has_flag(&stem, suffix.flag) && self.has_flag(&stem, prefix.flag) {
+                        return Some(stem);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
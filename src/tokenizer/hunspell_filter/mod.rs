@@ -0,0 +1,281 @@
+//! # Example
+//! ```rust
+//! use tantivy::tokenizer::*;
+//!
+//! let aff = "SFX S Y 1\nSFX S 0 s .\n";
+//! let dic = "2\ncat/S\ndog/S\n";
+//! let hunspell_filter = HunspellFilter::from_readers(aff.as_bytes(), dic.as_bytes()).unwrap();
+//! let mut tokenizer = TextAnalyzer::builder(SimpleTokenizer::default())
+//!   .filter(LowerCaser)
+//!   .filter(hunspell_filter)
+//!   .build();
+//!
+//! let mut stream = tokenizer.token_stream("Cats chase dogs");
+//! assert_eq!(stream.next().unwrap().text, "cat");
+//! assert_eq!(stream.next().unwrap().text, "chase");
+//! assert_eq!(stream.next().unwrap().text, "dog");
+//! assert!(stream.next().is_none());
+//! ```
+mod dictionary;
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::mem;
+use std::path::Path;
+use std::sync::Arc;
+
+use self::dictionary::HunspellDictionary;
+use super::{Token, TokenFilter, TokenStream, Tokenizer};
+
+/// `TokenFilter` that replaces the tokens by their stem, as given by a
+/// [Hunspell](https://hunspell.github.io/) dictionary.
+///
+/// The dictionary is made of an affix file (`.aff`), describing how words are inflected, and of
+/// a dictionary file (`.dic`), listing the stems and the affixes they accept. A token is
+/// replaced by the first stem it can be derived from, and is left untouched if the
+/// dictionary does not know it. Tokens marked as keywords, e.g. by a
+/// [`KeywordMarkerFilter`](super::KeywordMarkerFilter), are left untouched as well.
+///
+/// Only the prefix and suffix rules of the affix file are supported: compounding, chained
+/// affixes and the other options are ignored. The files must be UTF-8 encoded.
+///
+/// The words of the dictionary are lowercased, so the tokens are expected to be lowercased
+/// beforehand. The dictionary is shared by the clones of the filter.
+#[derive(Clone)]
+pub struct HunspellFilter {
+    dictionary: Arc<HunspellDictionary>,
+}
+
+impl HunspellFilter {
+    /// Creates a `HunspellFilter` from the paths of the `.aff` and `.dic` files.
+    pub fn from_files<A: AsRef<Path>, D: AsRef<Path>>(
+        aff_path: A,
+        dic_path: D,
+    ) -> crate::Result<HunspellFilter> {
+        let aff = BufReader::new(File::open(aff_path)?);
+        let dic = BufReader::new(File::open(dic_path)?);
+        HunspellFilter::from_readers(aff, dic)
+    }
+
+    /// Creates a `HunspellFilter` from the content of the `.aff` and `.dic` files.
+    pub fn from_readers<A: BufRead, D: BufRead>(aff: A, dic: D) -> crate::Result<HunspellFilter> {
+        let dictionary = HunspellDictionary::from_readers(aff, dic)?;
+        Ok(HunspellFilter {
+            dictionary: Arc::new(dictionary),
+        })
+    }
+}
+
+impl TokenFilter for HunspellFilter {
+    type Tokenizer<T: Tokenizer> = HunspellFilterWrapper<T>;
+
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> HunspellFilterWrapper<T> {
+        HunspellFilterWrapper {
+            dictionary: self.dictionary,
+            inner: tokenizer,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct HunspellFilterWrapper<T> {
+    dictionary: Arc<HunspellDictionary>,
+    inner: T,
+}
+
+impl<T: Tokenizer> Tokenizer for HunspellFilterWrapper<T> {
+    type TokenStream<'a> = HunspellFilterStream<'a, T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        HunspellFilterStream {
+            dictionary: &self.dictionary,
+            tail: self.inner.token_stream(text),
+        }
+    }
+}
+
+pub struct HunspellFilterStream<'a, T> {
+    dictionary: &'a HunspellDictionary,
+    tail: T,
+}
+
+impl<'a, T: TokenStream> TokenStream for HunspellFilterStream<'a, T> {
+    fn advance(&mut self) -> bool {
+        if !self.tail.advance() {
+            return false;
+        }
+        let token = self.tail.token_mut();
+        if token.keyword {
+            return true;
+        }
+        if let Some(mut stem) = self.dictionary.stem(&token.text) {
+            mem::swap(&mut token.text, &mut stem);
+        }
+        true
+    }
+
+    fn token(&self) -> &Token {
+        self.tail.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.tail.token_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::sync::Arc;
+
+    use crate::tokenizer::tests::assert_token;
+    use crate::tokenizer::{
+        HunspellFilter, KeywordMarkerFilter, LowerCaser, SimpleTokenizer, TextAnalyzer, Token,
+    };
+
+    const TEST_AFF: &str = "\
+# Affix rules of the test dictionary
+SET UTF-8
+
+SFX S Y 1
+SFX S 0 s .
+
+SFX E Y 2
+SFX E 0 en [^e]
+SFX E 0 n e
+
+SFX U Y 1
+SFX U aus äuser aus
+
+SFX T Y 1
+SFX T en t en
+
+PFX G Y 1
+PFX G 0 ge .
+";
+
+    const TEST_DIC: &str = "\
+4
+Haus/SU
+Blume/E
+Frau/E
+machen/GT
+";
+
+    fn test_filter() -> HunspellFilter {
+        HunspellFilter::from_readers(TEST_AFF.as_bytes(), TEST_DIC.as_bytes()).unwrap()
+    }
+
+    fn token_stream_helper(mut analyzer: TextAnalyzer, text: &str) -> Vec<Token> {
+        let mut token_stream = analyzer.token_stream(text);
+        let mut tokens: Vec<Token> = vec![];
+        let mut add_token = |token: &Token| {
+            tokens.push(token.clone());
+        };
+        token_stream.process(&mut add_token);
+        tokens
+    }
+
+    #[test]
+    fn test_hunspell_filter_inflected_forms() {
+        let analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(LowerCaser)
+            .filter(test_filter())
+            .build();
+        let tokens = token_stream_helper(analyzer, "Häuser Blumen Frauen Haus gemacht Hunde");
+        assert_eq!(tokens.len(), 6);
+        assert_token(&tokens[0], 0, "haus", 0, 7);
+        assert_token(&tokens[1], 1, "blume", 8, 14);
+        assert_token(&tokens[2], 2, "frau", 15, 21);
+        assert_token(&tokens[3], 3, "haus", 22, 26);
+        // Prefix and suffix combined.
+        assert_token(&tokens[4], 4, "machen", 27, 34);
+        // Unknown words are left untouched.
+        assert_token(&tokens[5], 5, "hunde", 35, 40);
+    }
+
+    #[test]
+    fn test_hunspell_filter_affix_flags() {
+        let analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(test_filter())
+            .build();
+        // `frau` does not accept the `S` suffix, and `blume` does not match the condition of
+        // the `en` suffix.
+        let tokens = token_stream_helper(analyzer, "fraus blumeen hauses");
+        let texts: Vec<&str> = tokens.iter().map(|token| token.text.as_str()).collect();
+        assert_eq!(texts, ["fraus", "blumeen", "hauses"]);
+    }
+
+    #[test]
+    fn test_hunspell_filter_keywords() {
+        let analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(KeywordMarkerFilter::protect(["blumen"]))
+            .filter(test_filter())
+            .build();
+        let tokens = token_stream_helper(analyzer, "blumen frauen");
+        assert_eq!(tokens.len(), 2);
+        assert_token(&tokens[0], 0, "blumen", 0, 6);
+        assert_token(&tokens[1], 1, "frau", 7, 13);
+    }
+
+    #[test]
+    fn test_hunspell_filter_flag_formats() {
+        let aff = "FLAG long\nAF 2\nAF Aa\nAF AaBb\nSFX Aa Y 1\nSFX Aa 0 s .\nSFX Bb Y 1\nSFX Bb \
+                   y ies [^aeiou]y\n";
+        let dic = "2\ncat/1\npony/2\n";
+        let analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(HunspellFilter::from_readers(aff.as_bytes(), dic.as_bytes()).unwrap())
+            .build();
+        let tokens = token_stream_helper(analyzer, "cats ponies ponys caties");
+        let texts: Vec<&str> = tokens.iter().map(|token| token.text.as_str()).collect();
+        assert_eq!(texts, ["cat", "pony", "pony", "caties"]);
+
+        let aff = "FLAG num\nSFX 101 Y 1\nSFX 101 0 ed .\n";
+        let dic = "1\nwalk/7,101\n";
+        let analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(HunspellFilter::from_readers(aff.as_bytes(), dic.as_bytes()).unwrap())
+            .build();
+        let tokens = token_stream_helper(analyzer, "walked");
+        assert_eq!(tokens[0].text, "walk");
+    }
+
+    #[test]
+    fn test_hunspell_filter_from_files() {
+        let directory = tempfile::TempDir::new().unwrap();
+        let aff_path = directory.path().join("de_test.aff");
+        let dic_path = directory.path().join("de_test.dic");
+        fs::write(&aff_path, TEST_AFF).unwrap();
+        fs::write(&dic_path, TEST_DIC).unwrap();
+        let filter = HunspellFilter::from_files(&aff_path, &dic_path).unwrap();
+        assert!(Arc::ptr_eq(&filter.dictionary, &filter.clone().dictionary));
+        let analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(filter)
+            .build();
+        let tokens = token_stream_helper(analyzer, "häuser");
+        assert_eq!(tokens.len(), 1);
+        assert_token(&tokens[0], 0, "haus", 0, 7);
+
+        assert!(
+            HunspellFilter::from_files(directory.path().join("missing.aff"), &dic_path).is_err()
+        );
+    }
+
+    #[test]
+    fn test_hunspell_filter_rule_without_condition() {
+        // The rule lines have 4 fields, like the headers.
+        let aff = "SFX A Y 2\nSFX A 0 s\nSFX A 0 es\nPFX B N 1\nPFX B 0 re\n";
+        let dic = "2\nbox/A\ndo/B\n";
+        let analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(HunspellFilter::from_readers(aff.as_bytes(), dic.as_bytes()).unwrap())
+            .build();
+        let tokens = token_stream_helper(analyzer, "boxes boxs redo");
+        let texts: Vec<&str> = tokens.iter().map(|token| token.text.as_str()).collect();
+        assert_eq!(texts, ["box", "box", "do"]);
+    }
+
+    #[test]
+    fn test_hunspell_filter_invalid_condition() {
+        let aff = "SFX S Y 1\nSFX S 0 s [a\n";
+        assert!(HunspellFilter::from_readers(aff.as_bytes(), "".as_bytes()).is_err());
+    }
+}
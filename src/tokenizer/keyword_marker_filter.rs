@@ -0,0 +1,161 @@
+//! # Example
+//! ```rust
+//! use tantivy::tokenizer::*;
+//!
+//! let mut tokenizer = TextAnalyzer::builder(SimpleTokenizer::default())
+//!   .filter(KeywordMarkerFilter::protect(["iOS", "Windows"]))
+//!   .filter(Stemmer::new(Language::English))
+//!   .build();
+//!
+//! let mut stream = tokenizer.token_stream("Windows running");
+//! assert_eq!(stream.next().unwrap().text, "Windows");
+//! assert_eq!(stream.next().unwrap().text, "run");
+//! assert!(stream.next().is_none());
+//! ```
+use std::sync::Arc;
+
+use rustc_hash::FxHashSet;
+
+use super::lower_caser::to_lowercase_unicode;
+use super::{Token, TokenFilter, TokenStream, Tokenizer};
+
+/// `TokenFilter` that marks the tokens matching a set of protected words as keywords,
+/// so that the following filters, e.g. the [`Stemmer`](super::Stemmer), leave them untouched.
+///
+/// Words are matched case-insensitively. The [`keyword`](Token::keyword) flag of the
+/// other tokens is set to false.
+#[derive(Clone)]
+pub struct KeywordMarkerFilter {
+    words: Arc<FxHashSet<String>>,
+}
+
+impl KeywordMarkerFilter {
+    /// Creates a `KeywordMarkerFilter` given a list of words to protect.
+    pub fn protect<W, S>(words: W) -> KeywordMarkerFilter
+    where
+        W: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut buffer = String::new();
+        let words = words
+            .into_iter()
+            .map(|word| {
+                to_lowercase_unicode(word.as_ref(), &mut buffer);
+                buffer.clone()
+            })
+            .collect();
+        KeywordMarkerFilter {
+            words: Arc::new(words),
+        }
+    }
+}
+
+impl TokenFilter for KeywordMarkerFilter {
+    type Tokenizer<T: Tokenizer> = KeywordMarkerFilterWrapper<T>;
+
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> KeywordMarkerFilterWrapper<T> {
+        KeywordMarkerFilterWrapper {
+            words: self.words,
+            inner: tokenizer,
+            buffer: String::new(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct KeywordMarkerFilterWrapper<T> {
+    words: Arc<FxHashSet<String>>,
+    inner: T,
+    buffer: String,
+}
+
+impl<T: Tokenizer> Tokenizer for KeywordMarkerFilterWrapper<T> {
+    type TokenStream<'a> = KeywordMarkerFilterStream<'a, T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        self.buffer.clear();
+        KeywordMarkerFilterStream {
+            words: &self.words,
+            tail: self.inner.token_stream(text),
+            buffer: &mut self.buffer,
+        }
+    }
+}
+
+pub struct KeywordMarkerFilterStream<'a, T> {
+    words: &'a FxHashSet<String>,
+    tail: T,
+    buffer: &'a mut String,
+}
+
+impl<'a, T: TokenStream> KeywordMarkerFilterStream<'a, T> {
+    fn is_protected(&mut self) -> bool {
+        let text = &self.tail.token().text;
+        if !text.chars().any(char::is_uppercase) {
+            return self.words.contains(text);
+        }
+        to_lowercase_unicode(text, self.buffer);
+        self.words.contains(self.buffer.as_str())
+    }
+}
+
+impl<'a, T: TokenStream> TokenStream for KeywordMarkerFilterStream<'a, T> {
+    fn advance(&mut self) -> bool {
+        if !self.tail.advance() {
+            return false;
+        }
+        // Tokenizers usually reuse the same token, so the flag
+        // has to be reset for the tokens that are not protected.
+        self.tail.token_mut().keyword = self.is_protected();
+        true
+    }
+
+    fn token(&self) -> &Token {
+        self.tail.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.tail.token_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tokenizer::tests::assert_token;
+    use crate::tokenizer::{
+        KeywordMarkerFilter, Language, LowerCaser, SimpleTokenizer, Stemmer, TextAnalyzer, Token,
+    };
+
+    #[test]
+    fn test_keyword_marker_filter() {
+        let mut analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(KeywordMarkerFilter::protect(["iOS", "Windows"]))
+            .filter(Stemmer::new(Language::English))
+            .build();
+        let mut tokens: Vec<Token> = vec![];
+        analyzer
+            .token_stream("iOS apps Windows running")
+            .process(&mut |token: &Token| tokens.push(token.clone()));
+        assert_eq!(tokens.len(), 4);
+        assert_token(&tokens[0], 0, "iOS", 0, 3);
+        assert_token(&tokens[1], 1, "app", 4, 8);
+        assert_token(&tokens[2], 2, "Windows", 9, 16);
+        assert_token(&tokens[3], 3, "run", 17, 24);
+        let keywords: Vec<bool> = tokens.iter().map(|token| token.keyword).collect();
+        assert_eq!(keywords, [true, false, true, false]);
+    }
+
+    #[test]
+    fn test_keyword_marker_filter_case_insensitive() {
+        let mut analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(LowerCaser)
+            .filter(KeywordMarkerFilter::protect(["Windows"]))
+            .filter(Stemmer::new(Language::English))
+            .build();
+        let mut tokens: Vec<String> = vec![];
+        analyzer
+            .token_stream("WINDOWS windows")
+            .process(&mut |token: &Token| tokens.push(token.text.clone()));
+        assert_eq!(tokens, ["windows", "windows"]);
+    }
+}
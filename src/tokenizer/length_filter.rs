@@ -0,0 +1,149 @@
+//! # Example
+//! ```rust
+//! use tantivy::tokenizer::*;
+//!
+//! let mut tokenizer = TextAnalyzer::builder(SimpleTokenizer::default())
+//!   .filter(LengthFilter::new(2, 5).unwrap())
+//!   .build();
+//!
+//! let mut stream = tokenizer.token_stream("a nice sunny day");
+//! // `a` is shorter than 2 characters and `sunny`
+//! // longer than 5 characters, they are filtered out.
+//! assert_eq!(stream.next().unwrap().text, "nice");
+//! assert_eq!(stream.next().unwrap().text, "day");
+//! assert!(stream.next().is_none());
+//! ```
+use std::ops::RangeInclusive;
+
+use super::{Token, TokenFilter, TokenStream, Tokenizer};
+use crate::TantivyError;
+
+/// `LengthFilter` removes tokens whose number of characters (Unicode scalar values)
+/// is not within an inclusive range.
+///
+/// The position of the remaining tokens is left untouched.
+#[derive(Clone)]
+pub struct LengthFilter {
+    length_range: RangeInclusive<usize>,
+}
+
+impl LengthFilter {
+    /// Creates a `LengthFilter` keeping the tokens having between `min` and `max` characters,
+    /// both included.
+    pub fn new(min: usize, max: usize) -> crate::Result<LengthFilter> {
+        if min > max {
+            return Err(TantivyError::InvalidArgument(
+                "min must not be greater than max".to_string(),
+            ));
+        }
+        Ok(LengthFilter {
+            length_range: min..=max,
+        })
+    }
+}
+
+impl<T> LengthFilterStream<T> {
+    fn predicate(&self, token: &Token) -> bool {
+        // A token has at least as many bytes as chars, and at most 4 times as many.
+        let num_bytes = token.text.len();
+        if num_bytes < *self.length_range.start() || num_bytes / 4 > *self.length_range.end() {
+            return false;
+        }
+        self.length_range.contains(&token.text.chars().count())
+    }
+}
+
+impl TokenFilter for LengthFilter {
+    type Tokenizer<T: Tokenizer> = LengthFilterWrapper<T>;
+
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> LengthFilterWrapper<T> {
+        LengthFilterWrapper {
+            length_range: self.length_range,
+            inner: tokenizer,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct LengthFilterWrapper<T: Tokenizer> {
+    length_range: RangeInclusive<usize>,
+    inner: T,
+}
+
+impl<T: Tokenizer> Tokenizer for LengthFilterWrapper<T> {
+    type TokenStream<'a> = LengthFilterStream<T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        LengthFilterStream {
+            length_range: self.length_range.clone(),
+            tail: self.inner.token_stream(text),
+        }
+    }
+}
+
+pub struct LengthFilterStream<T> {
+    length_range: RangeInclusive<usize>,
+    tail: T,
+}
+
+impl<T: TokenStream> TokenStream for LengthFilterStream<T> {
+    fn advance(&mut self) -> bool {
+        while self.tail.advance() {
+            if self.predicate(self.tail.token()) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn token(&self) -> &Token {
+        self.tail.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.tail.token_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tokenizer::tests::assert_token;
+    use crate::tokenizer::{LengthFilter, SimpleTokenizer, TextAnalyzer, Token};
+
+    #[test]
+    fn test_length_filter() {
+        let tokens = token_stream_helper(
+            LengthFilter::new(2, 3).unwrap(),
+            "a tantivy 東京 is déjà 東京都",
+        );
+        assert_eq!(tokens.len(), 3);
+        assert_token(&tokens[0], 2, "東京", 10, 16);
+        assert_token(&tokens[1], 3, "is", 17, 19);
+        assert_token(&tokens[2], 5, "東京都", 27, 36);
+    }
+
+    #[test]
+    fn test_length_filter_single_length() {
+        let tokens = token_stream_helper(LengthFilter::new(4, 4).unwrap(), "a tantivy déjà vu");
+        assert_eq!(tokens.len(), 1);
+        assert_token(&tokens[0], 2, "déjà", 10, 16);
+    }
+
+    #[test]
+    fn test_length_filter_invalid_range() {
+        assert!(LengthFilter::new(3, 2).is_err());
+    }
+
+    fn token_stream_helper(filter: LengthFilter, text: &str) -> Vec<Token> {
+        let mut a = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(filter)
+            .build();
+        let mut token_stream = a.token_stream(text);
+        let mut tokens: Vec<Token> = vec![];
+        let mut add_token = |token: &Token| {
+            tokens.push(token.clone());
+        };
+        token_stream.process(&mut add_token);
+        tokens
+    }
+}
@@ -0,0 +1,112 @@
+//! # Example
+//! ```rust
+//! use tantivy::tokenizer::*;
+//!
+//! let mut tokenizer = TextAnalyzer::builder(SimpleTokenizer::default())
+//!   .filter(LimitTokenCountFilter::new(2))
+//!   .build();
+//!
+//! let mut stream = tokenizer.token_stream("one two three four");
+//! assert_eq!(stream.next().unwrap().text, "one");
+//! assert_eq!(stream.next().unwrap().text, "two");
+//! assert!(stream.next().is_none());
+//! ```
+use super::{Token, TokenFilter, TokenStream, Tokenizer};
+
+/// `LimitTokenCountFilter` stops the token stream after a given number of tokens.
+///
+/// It bounds the indexing cost of very large texts. The limit applies to each text
+/// independently: every call to `token_stream` starts a new count.
+#[derive(Clone)]
+pub struct LimitTokenCountFilter {
+    max_tokens: usize,
+}
+
+impl LimitTokenCountFilter {
+    /// Creates a `LimitTokenCountFilter` emitting at most `max_tokens` tokens per text.
+    pub fn new(max_tokens: usize) -> LimitTokenCountFilter {
+        LimitTokenCountFilter { max_tokens }
+    }
+}
+
+impl TokenFilter for LimitTokenCountFilter {
+    type Tokenizer<T: Tokenizer> = LimitTokenCountFilterWrapper<T>;
+
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> LimitTokenCountFilterWrapper<T> {
+        LimitTokenCountFilterWrapper {
+            max_tokens: self.max_tokens,
+            inner: tokenizer,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct LimitTokenCountFilterWrapper<T> {
+    max_tokens: usize,
+    inner: T,
+}
+
+impl<T: Tokenizer> Tokenizer for LimitTokenCountFilterWrapper<T> {
+    type TokenStream<'a> = LimitTokenCountFilterStream<T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        LimitTokenCountFilterStream {
+            num_remaining_tokens: self.max_tokens,
+            tail: self.inner.token_stream(text),
+        }
+    }
+}
+
+pub struct LimitTokenCountFilterStream<T> {
+    num_remaining_tokens: usize,
+    tail: T,
+}
+
+impl<T: TokenStream> TokenStream for LimitTokenCountFilterStream<T> {
+    fn advance(&mut self) -> bool {
+        // The tail is not advanced past the limit.
+        if self.num_remaining_tokens == 0 || !self.tail.advance() {
+            return false;
+        }
+        self.num_remaining_tokens -= 1;
+        true
+    }
+
+    fn token(&self) -> &Token {
+        self.tail.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.tail.token_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tokenizer::tests::assert_token;
+    use crate::tokenizer::{LimitTokenCountFilter, SimpleTokenizer, TextAnalyzer};
+
+    #[test]
+    fn test_limit_token_count_filter() {
+        let mut analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(LimitTokenCountFilter::new(3))
+            .build();
+        let tokens = analyzer.analyze("a b c d e");
+        assert_eq!(tokens.len(), 3);
+        assert_token(&tokens[0], 0, "a", 0, 1);
+        assert_token(&tokens[1], 1, "b", 2, 3);
+        assert_token(&tokens[2], 2, "c", 4, 5);
+
+        // The count is reset for each text.
+        assert_eq!(analyzer.analyze("f g h i").len(), 3);
+        assert_eq!(analyzer.analyze("j k").len(), 2);
+    }
+
+    #[test]
+    fn test_limit_token_count_filter_zero() {
+        let mut analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(LimitTokenCountFilter::new(0))
+            .build();
+        assert!(analyzer.analyze("a b").is_empty());
+    }
+}
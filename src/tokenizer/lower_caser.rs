@@ -1,17 +1,65 @@
 use std::mem;
 
-use super::{Token, TokenFilter, TokenStream, Tokenizer};
+use super::{Language, Token, TokenFilter, TokenStream, Tokenizer};
 
 /// Token filter that lowercase terms.
 #[derive(Clone)]
 pub struct LowerCaser;
 
+impl LowerCaser {
+    /// Creates a lowercasing [`TokenFilter`] applying the casing rules of the given language.
+    ///
+    /// Currently, only [`Language::Turkish`] has specific rules: `I` is mapped to `ı`
+    /// and `İ` to `i`. Other languages behave like [`LowerCaser`].
+    pub fn with_language(language: Language) -> ConfigurableLowerCaser {
+        ConfigurableLowerCaser {
+            language: Some(language),
+            skip_keywords: false,
+        }
+    }
+
+    /// Creates a lowercasing [`TokenFilter`] leaving the tokens marked as keywords untouched.
+    ///
+    /// Combined with a [`KeywordMarkerFilter`](super::KeywordMarkerFilter), it keeps e.g.
+    /// acronyms uppercased in the index.
+    pub fn skip_keywords() -> ConfigurableLowerCaser {
+        ConfigurableLowerCaser::default().skip_keywords()
+    }
+}
+
 impl TokenFilter for LowerCaser {
     type Tokenizer<T: Tokenizer> = LowerCaserFilter<T>;
 
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> Self::Tokenizer<T> {
+        ConfigurableLowerCaser::default().transform(tokenizer)
+    }
+}
+
+/// Token filter that lowercase terms, with some extra options.
+///
+/// See [`LowerCaser::with_language`] and [`LowerCaser::skip_keywords`].
+#[derive(Clone, Default)]
+pub struct ConfigurableLowerCaser {
+    language: Option<Language>,
+    skip_keywords: bool,
+}
+
+impl ConfigurableLowerCaser {
+    /// Leaves the tokens marked as keywords untouched.
+    pub fn skip_keywords(mut self) -> ConfigurableLowerCaser {
+        self.skip_keywords = true;
+        self
+    }
+}
+
+impl TokenFilter for ConfigurableLowerCaser {
+    type Tokenizer<T: Tokenizer> = LowerCaserFilter<T>;
+
     fn transform<T: Tokenizer>(self, tokenizer: T) -> Self::Tokenizer<T> {
         LowerCaserFilter {
             tokenizer,
+            turkic: self.language == Some(Language::Turkish),
+            skip_keywords: self.skip_keywords,
             buffer: String::new(),
         }
     }
@@ -20,6 +68,8 @@ impl TokenFilter for LowerCaser {
 #[derive(Clone)]
 pub struct LowerCaserFilter<T> {
     tokenizer: T,
+    turkic: bool,
+    skip_keywords: bool,
     buffer: String,
 }
 
@@ -30,6 +80,8 @@ impl<T: Tokenizer> Tokenizer for LowerCaserFilter<T> {
         self.buffer.clear();
         LowerCaserTokenStream {
             tail: self.tokenizer.token_stream(text),
+            turkic: self.turkic,
+            skip_keywords: self.skip_keywords,
             buffer: &mut self.buffer,
         }
     }
@@ -37,11 +89,13 @@ impl<T: Tokenizer> Tokenizer for LowerCaserFilter<T> {
 
 pub struct LowerCaserTokenStream<'a, T> {
     buffer: &'a mut String,
+    turkic: bool,
+    skip_keywords: bool,
     tail: T,
 }
 
 // writes a lowercased version of text into output.
-fn to_lowercase_unicode(text: &str, output: &mut String) {
+pub(crate) fn to_lowercase_unicode(text: &str, output: &mut String) {
     output.clear();
     output.reserve(50);
     for c in text.chars() {
@@ -51,12 +105,33 @@ fn to_lowercase_unicode(text: &str, output: &mut String) {
     }
 }
 
+// writes a lowercased version of text into output, following the
+// Turkish and Azeri rules for the dotted and dotless i.
+fn to_lowercase_turkic(text: &str, output: &mut String) {
+    output.clear();
+    output.reserve(50);
+    for c in text.chars() {
+        match c {
+            'I' => output.push('ı'),
+            // The std would emit `i` followed by a combining dot above.
+            'İ' => output.push('i'),
+            _ => output.extend(c.to_lowercase()),
+        }
+    }
+}
+
 impl<'a, T: TokenStream> TokenStream for LowerCaserTokenStream<'a, T> {
     fn advance(&mut self) -> bool {
         if !self.tail.advance() {
             return false;
         }
-        if self.token_mut().text.is_ascii() {
+        if self.skip_keywords && self.tail.token().keyword {
+            return true;
+        }
+        if self.turkic {
+            to_lowercase_turkic(&self.tail.token().text, self.buffer);
+            mem::swap(&mut self.tail.token_mut().text, self.buffer);
+        } else if self.token_mut().text.is_ascii() {
             // fast track for ascii.
             self.token_mut().text.make_ascii_lowercase();
         } else {
@@ -78,7 +153,9 @@ impl<'a, T: TokenStream> TokenStream for LowerCaserTokenStream<'a, T> {
 #[cfg(test)]
 mod tests {
     use crate::tokenizer::tests::assert_token;
-    use crate::tokenizer::{LowerCaser, SimpleTokenizer, TextAnalyzer, Token};
+    use crate::tokenizer::{
+        KeywordMarkerFilter, Language, LowerCaser, SimpleTokenizer, TextAnalyzer, Token,
+    };
 
     #[test]
     fn test_to_lower_case() {
@@ -92,6 +169,40 @@ mod tests {
         assert_token(&tokens[1], 1, "текст", 15, 25);
     }
 
+    #[test]
+    fn test_to_lower_case_turkish() {
+        let mut analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(LowerCaser::with_language(Language::Turkish))
+            .build();
+        let mut tokens = vec![];
+        analyzer
+            .token_stream("İSTANBUL IŞIK Ilık")
+            .process(&mut |token: &Token| tokens.push(token.clone()));
+        assert_eq!(tokens.len(), 3);
+        assert_token(&tokens[0], 0, "istanbul", 0, 9);
+        assert_token(&tokens[1], 1, "ışık", 10, 15);
+        assert_token(&tokens[2], 2, "ılık", 16, 21);
+    }
+
+    #[test]
+    fn test_to_lower_case_skip_keywords() {
+        let mut analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(KeywordMarkerFilter::protect(["nasa"]))
+            .filter(LowerCaser::skip_keywords())
+            .build();
+        let tokens = analyzer.analyze("NASA Rockets");
+        assert_eq!(tokens.len(), 2);
+        assert_token(&tokens[0], 0, "NASA", 0, 4);
+        assert_token(&tokens[1], 1, "rockets", 5, 12);
+
+        // Keywords are lowercased by default.
+        let mut analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(KeywordMarkerFilter::protect(["nasa"]))
+            .filter(LowerCaser)
+            .build();
+        assert_eq!(analyzer.analyze("NASA")[0].text, "nasa");
+    }
+
     fn token_stream_helper(text: &str) -> Vec<Token> {
         let mut token_stream = TextAnalyzer::builder(SimpleTokenizer::default())
             .filter(LowerCaser)
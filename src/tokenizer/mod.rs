@@ -122,40 +122,88 @@
 //!     .register("custom_en", custom_en_tokenizer);
 //! ```
 mod alphanum_only;
+mod arabic_normalizer;
 mod ascii_folding_filter;
+mod cjk_tokenizer;
+mod decimal_digit_filter;
+mod edge_ngram_filter;
+mod elision_filter;
 mod empty_tokenizer;
 mod facet_tokenizer;
+mod greek_normalizer;
+mod html_strip_tokenizer;
+mod hunspell_filter;
+mod keyword_marker_filter;
+mod length_filter;
+mod limit_token_count_filter;
 mod lower_caser;
 mod ngram_tokenizer;
+mod path_tokenizer;
+mod pattern_replace_filter;
+mod phonetic_filter;
 mod raw_tokenizer;
 mod regex_tokenizer;
 mod remove_long;
+mod remove_short;
+mod reverse_filter;
+mod shingle_filter;
 mod simple_tokenizer;
 mod split_compound_words;
 mod stemmer;
 mod stop_word_filter;
+mod synonym_filter;
+mod text_analyzer_pool;
 mod tokenized_string;
 mod tokenizer;
+mod tokenizer_config;
 mod tokenizer_manager;
+mod trim_filter;
+mod type_filter;
+mod unicode_normalization_filter;
 mod whitespace_tokenizer;
 
-pub use tokenizer_api::{BoxTokenStream, Token, TokenFilter, TokenStream, Tokenizer};
+pub use tokenizer_api::{
+    BoxTokenStream, ClonedTokens, Token, TokenFilter, TokenStream, TokenType, Tokenizer,
+};
 
 pub use self::alphanum_only::AlphaNumOnlyFilter;
-pub use self::ascii_folding_filter::AsciiFoldingFilter;
+pub use self::arabic_normalizer::ArabicNormalizationFilter;
+pub use self::ascii_folding_filter::{AsciiFoldingFilter, ConfigurableAsciiFoldingFilter};
+pub use self::cjk_tokenizer::CjkTokenizer;
+pub use self::decimal_digit_filter::DecimalDigitFilter;
+pub use self::edge_ngram_filter::EdgeNgramFilter;
+pub use self::elision_filter::ElisionFilter;
 pub use self::facet_tokenizer::FacetTokenizer;
-pub use self::lower_caser::LowerCaser;
+pub use self::greek_normalizer::GreekNormalizationFilter;
+pub use self::html_strip_tokenizer::HtmlStripTokenizer;
+pub use self::hunspell_filter::HunspellFilter;
+pub use self::keyword_marker_filter::KeywordMarkerFilter;
+pub use self::length_filter::LengthFilter;
+pub use self::limit_token_count_filter::LimitTokenCountFilter;
+pub use self::lower_caser::{ConfigurableLowerCaser, LowerCaser};
 pub use self::ngram_tokenizer::NgramTokenizer;
+pub use self::path_tokenizer::PathTokenizer;
+pub use self::pattern_replace_filter::PatternReplaceFilter;
+pub use self::phonetic_filter::{PhoneticAlgorithm, PhoneticFilter};
 pub use self::raw_tokenizer::RawTokenizer;
 pub use self::regex_tokenizer::RegexTokenizer;
 pub use self::remove_long::RemoveLongFilter;
+pub use self::remove_short::RemoveShortFilter;
+pub use self::reverse_filter::ReverseFilter;
+pub use self::shingle_filter::ShingleFilter;
 pub use self::simple_tokenizer::{SimpleTokenStream, SimpleTokenizer};
 pub use self::split_compound_words::SplitCompoundWords;
 pub use self::stemmer::{Language, Stemmer};
 pub use self::stop_word_filter::StopWordFilter;
+pub use self::synonym_filter::SynonymFilter;
+pub use self::text_analyzer_pool::{PooledAnalyzer, TextAnalyzerPool};
 pub use self::tokenized_string::{PreTokenizedStream, PreTokenizedString};
 pub use self::tokenizer::{TextAnalyzer, TextAnalyzerBuilder};
+pub use self::tokenizer_config::{BaseTokenizerConfig, TokenFilterConfig, TokenizerConfig};
 pub use self::tokenizer_manager::TokenizerManager;
+pub use self::trim_filter::TrimFilter;
+pub use self::type_filter::TypeFilter;
+pub use self::unicode_normalization_filter::{NormalizationForm, UnicodeNormalizationFilter};
 pub use self::whitespace_tokenizer::WhitespaceTokenizer;
 
 /// Maximum authorized len (in bytes) for a token.
@@ -226,6 +274,30 @@ pub mod tests {
         assert_token(&tokens[3], 3, "payer", 17, 22);
     }
 
+    #[test]
+    fn test_tokenizer_manager_list_remove_contains() {
+        use super::{RawTokenizer, WhitespaceTokenizer};
+
+        let tokenizer_manager = TokenizerManager::new();
+        assert!(tokenizer_manager.list().is_empty());
+        tokenizer_manager.register("whitespace", WhitespaceTokenizer::default());
+        tokenizer_manager.register("raw", RawTokenizer::default());
+        assert_eq!(tokenizer_manager.list(), ["raw", "whitespace"]);
+        assert!(tokenizer_manager.contains("raw"));
+        assert!(!tokenizer_manager.contains("default"));
+
+        let mut raw_tokenizer = tokenizer_manager.remove("raw").unwrap();
+        let mut tokens: Vec<Token> = vec![];
+        raw_tokenizer
+            .token_stream("Hello world")
+            .process(&mut |token: &Token| tokens.push(token.clone()));
+        assert_eq!(tokens.len(), 1);
+        assert_token(&tokens[0], 0, "Hello world", 0, 11);
+        assert!(!tokenizer_manager.contains("raw"));
+        assert!(tokenizer_manager.remove("raw").is_none());
+        assert_eq!(tokenizer_manager.list(), ["whitespace"]);
+    }
+
     #[test]
     fn test_non_en_tokenizer() {
         let tokenizer_manager = TokenizerManager::default();
@@ -254,6 +326,79 @@ pub mod tests {
         assert_token(&tokens[2], 2, "φορολογουμεν", 37, 63);
     }
 
+    #[test]
+    fn test_scandinavian_tokenizers() {
+        let tokenizer_manager = TokenizerManager::default();
+        let stem = |tokenizer_name: &str, text: &str| -> Vec<String> {
+            let mut tokenizer = tokenizer_manager.get(tokenizer_name).unwrap();
+            let mut tokens: Vec<String> = vec![];
+            tokenizer
+                .token_stream(text)
+                .process(&mut |token: &Token| tokens.push(token.text.clone()));
+            tokens
+        };
+        for (tokenizer_name, upper, lower) in [
+            ("sv_stem", "BLÅBÄRSSYLTAN Öarna", "blåbärssyltan öarna"),
+            ("da_stem", "ÆBLERNE SØERNE", "æblerne søerne"),
+            ("no_stem", "BLÅBÆRENE BRØDENE", "blåbærene brødene"),
+        ] {
+            let upper_tokens = stem(tokenizer_name, upper);
+            let lower_tokens = stem(tokenizer_name, lower);
+            assert_eq!(upper_tokens.len(), 2);
+            assert_eq!(upper_tokens, lower_tokens);
+        }
+        assert_eq!(stem("da_stem", "Æblerne"), vec!["æbl".to_string()]);
+    }
+
+    #[test]
+    fn test_name_phonetic_tokenizer() {
+        let tokenizer_manager = TokenizerManager::default();
+        let mut name_phonetic = tokenizer_manager.get("name_phonetic").unwrap();
+        let mut codes = |text: &str| -> Vec<String> {
+            let mut tokens: Vec<String> = vec![];
+            name_phonetic
+                .token_stream(text)
+                .process(&mut |token: &Token| tokens.push(token.text.clone()));
+            tokens
+        };
+        let smith_codes = codes("Smith");
+        assert!(!smith_codes.is_empty());
+        assert_eq!(smith_codes, codes("Smyth"));
+        assert_eq!(codes("Müller"), codes("Muller"));
+    }
+
+    #[test]
+    fn test_finnish_tokenizer() {
+        let tokenizer_manager = TokenizerManager::default();
+        let mut fi_stem = tokenizer_manager.get("fi_stem").unwrap();
+        let mut tokens: Vec<String> = vec![];
+        fi_stem
+            .token_stream("Talossa, taloon ja TALOT")
+            .process(&mut |token: &Token| tokens.push(token.text.clone()));
+        assert_eq!(tokens, ["talo", "talo", "ja", "talo"]);
+    }
+
+    #[test]
+    fn test_french_tokenizer() {
+        let tokenizer_manager = TokenizerManager::default();
+        let mut fr_stem_elision = tokenizer_manager.get("fr_stem_elision").unwrap();
+        let mut tokens: Vec<Token> = vec![];
+        fr_stem_elision
+            .token_stream("L'avion d’accord")
+            .process(&mut |token: &Token| tokens.push(token.clone()));
+        assert_eq!(tokens.len(), 2);
+        assert_token(&tokens[0], 0, "avion", 0, 7);
+        assert_token(&tokens[1], 1, "accord", 8, 18);
+
+        // `fr_stem` is unchanged, so that the existing indices keep matching.
+        let mut fr_stem = tokenizer_manager.get("fr_stem").unwrap();
+        let mut texts: Vec<String> = vec![];
+        fr_stem
+            .token_stream("L'avion")
+            .process(&mut |token: &Token| texts.push(token.text.clone()));
+        assert_eq!(texts, ["l", "avion"]);
+    }
+
     #[test]
     fn test_tokenizer_empty() {
         let tokenizer_manager = TokenizerManager::default();
@@ -3,7 +3,8 @@ use crate::TantivyError;
 
 /// Tokenize the text by splitting words into n-grams of the given size(s)
 ///
-/// With this tokenizer, the `position` is always 0.
+/// With this tokenizer, the `position` is always 0, unless
+/// [`NgramTokenizer::set_increasing_positions`] is called.
 /// Beware however, in presence of multiple value for the same field,
 /// the position will be `POSITION_GAP * index of value`.
 ///
@@ -88,6 +89,8 @@ pub struct NgramTokenizer {
     max_gram: usize,
     /// if true, will only parse the leading edge of the input
     prefix_only: bool,
+    /// if true, the n-grams get increasing positions instead of 0
+    increasing_positions: bool,
     token: Token,
 }
 
@@ -112,10 +115,22 @@ impl NgramTokenizer {
             min_gram,
             max_gram,
             prefix_only,
+            increasing_positions: false,
             token: Token::default(),
         })
     }
 
+    /// If `increasing_positions` is true, the n-grams are given increasing positions, in the
+    /// order they are emitted, instead of all being at position 0.
+    ///
+    /// Phrase queries, and the positions stored in the index, can then tell the n-grams apart.
+    /// For instance, `hello` tokenized with `(min_gram: 2, max_gram: 3)` gives `he` at position
+    /// 0, `hel` at position 1, `el` at position 2, and so on.
+    pub fn set_increasing_positions(mut self, increasing_positions: bool) -> NgramTokenizer {
+        self.increasing_positions = increasing_positions;
+        self
+    }
+
     /// Create a `NGramTokenizer` which generates tokens for all inner ngrams.
     ///
     /// This is as opposed to only prefix ngrams    .
@@ -136,6 +151,8 @@ pub struct NgramTokenStream<'a> {
     ngram_charidx_iterator: StutteringIterator<CodepointFrontiers<'a>>,
     /// true if the NgramTokenStream is in prefix mode.
     prefix_only: bool,
+    /// true if the n-grams get increasing positions.
+    increasing_positions: bool,
     /// input
     text: &'a str,
     /// output
@@ -153,6 +170,7 @@ impl Tokenizer for NgramTokenizer {
                 self.max_gram,
             ),
             prefix_only: self.prefix_only,
+            increasing_positions: self.increasing_positions,
             text,
             token: &mut self.token,
         }
@@ -165,7 +183,11 @@ impl<'a> TokenStream for NgramTokenStream<'a> {
             if self.prefix_only && offset_from > 0 {
                 return false;
             }
-            self.token.position = 0;
+            self.token.position = if self.increasing_positions {
+                self.token.position.wrapping_add(1)
+            } else {
+                0
+            };
             self.token.offset_from = offset_from;
             self.token.offset_to = offset_to;
             self.token.text.clear();
@@ -317,6 +339,7 @@ mod tests {
     use super::{utf8_codepoint_width, CodepointFrontiers, NgramTokenizer, StutteringIterator};
     use crate::tokenizer::tests::assert_token;
     use crate::tokenizer::{Token, TokenStream, Tokenizer};
+    use crate::TantivyError;
 
     fn test_helper<T: TokenStream>(mut tokenizer: T) -> Vec<Token> {
         let mut tokens: Vec<Token> = vec![];
@@ -389,6 +412,32 @@ mod tests {
         assert_token(&tokens[2], 0, "llo", 2, 5);
     }
 
+    #[test]
+    fn test_ngram_tokenizer_increasing_positions() {
+        let tokens = test_helper(
+            NgramTokenizer::all_ngrams(2, 3)
+                .unwrap()
+                .set_increasing_positions(true)
+                .token_stream("hello"),
+        );
+        assert_eq!(tokens.len(), 7);
+        assert_token(&tokens[0], 0, "he", 0, 2);
+        assert_token(&tokens[1], 1, "hel", 0, 3);
+        assert_token(&tokens[2], 2, "el", 1, 3);
+        assert_token(&tokens[6], 6, "lo", 3, 5);
+
+        let mut tokenizer = NgramTokenizer::prefix_only(2, 3)
+            .unwrap()
+            .set_increasing_positions(true);
+        let tokens = test_helper(tokenizer.token_stream("hello"));
+        assert_token(&tokens[1], 1, "hel", 0, 3);
+        // The positions start over for each text.
+        let tokens = test_helper(tokenizer.token_stream("world"));
+        assert_eq!(tokens.len(), 2);
+        assert_token(&tokens[0], 0, "wo", 0, 2);
+        assert_token(&tokens[1], 1, "wor", 0, 3);
+    }
+
     #[test]
     fn test_ngram_tokenizer_2_5_prefix() {
         let tokens = test_helper(
@@ -436,6 +485,22 @@ mod tests {
         assert_token(&tokens[3], 0, "hεllo", 0, 6);
     }
 
+    #[test]
+    fn test_ngram_multibyte_offsets_on_char_boundaries() {
+        let text = "日本🦀";
+        let tokens = test_helper(NgramTokenizer::all_ngrams(1, 2).unwrap().token_stream(text));
+        assert_eq!(tokens.len(), 5);
+        assert_token(&tokens[0], 0, "日", 0, 3);
+        assert_token(&tokens[1], 0, "日本", 0, 6);
+        assert_token(&tokens[2], 0, "本", 3, 6);
+        assert_token(&tokens[3], 0, "本🦀", 3, 10);
+        assert_token(&tokens[4], 0, "🦀", 6, 10);
+        for token in &tokens {
+            assert!(text.is_char_boundary(token.offset_from));
+            assert!(text.is_char_boundary(token.offset_to));
+        }
+    }
+
     #[test]
     fn test_ngram_empty() {
         let tokens = test_helper(NgramTokenizer::all_ngrams(1, 5).unwrap().token_stream(""));
@@ -460,6 +525,18 @@ mod tests {
         NgramTokenizer::all_ngrams(2, 1).unwrap();
     }
 
+    #[test]
+    fn test_invalid_interval_returns_error() {
+        assert!(matches!(
+            NgramTokenizer::new(3, 2, true),
+            Err(TantivyError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            NgramTokenizer::new(0, 2, false),
+            Err(TantivyError::InvalidArgument(_))
+        ));
+    }
+
     #[test]
     fn test_stutterring_iterator_empty() {
         let rg: Vec<usize> = vec![0];
@@ -0,0 +1,196 @@
+use super::{Token, TokenStream, Tokenizer};
+
+/// Tokenize a path by emitting all of its ancestors, e.g. to facet on directories.
+///
+/// For instance, `/usr/local/bin` emits the three following tokens:
+/// - `/usr`
+/// - `/usr/local`
+/// - `/usr/local/bin`
+///
+/// The delimiter defaults to `/`. The leading delimiters are part of the tokens, unless
+/// [`set_keep_leading_delimiter`](PathTokenizer::set_keep_leading_delimiter) is set to false.
+///
+/// In [reverse mode](PathTokenizer::set_reverse), the suffixes of the path are emitted
+/// instead, from the longest to the shortest. This suits hierarchies whose most general
+/// level comes last, e.g. the domain names: `mail.example.com` split on `.` emits
+/// `mail.example.com`, `example.com` and `com`.
+///
+/// Tokens occupy consecutive positions, and their offsets point to the original text.
+#[derive(Clone)]
+pub struct PathTokenizer {
+    delimiter: char,
+    keep_leading_delimiter: bool,
+    reverse: bool,
+    token: Token,
+}
+
+impl Default for PathTokenizer {
+    fn default() -> PathTokenizer {
+        PathTokenizer::new('/')
+    }
+}
+
+impl PathTokenizer {
+    /// Creates a `PathTokenizer` splitting paths on `delimiter`.
+    pub fn new(delimiter: char) -> PathTokenizer {
+        PathTokenizer {
+            delimiter,
+            keep_leading_delimiter: true,
+            reverse: false,
+            token: Token::default(),
+        }
+    }
+
+    /// If `keep_leading_delimiter` is false, the delimiters at the beginning of the text
+    /// are not part of the tokens, e.g. `/usr/local` emits `usr` and `usr/local`.
+    pub fn set_keep_leading_delimiter(mut self, keep_leading_delimiter: bool) -> PathTokenizer {
+        self.keep_leading_delimiter = keep_leading_delimiter;
+        self
+    }
+
+    /// If `reverse` is true, the suffixes of the path are emitted instead of its prefixes.
+    pub fn set_reverse(mut self, reverse: bool) -> PathTokenizer {
+        self.reverse = reverse;
+        self
+    }
+}
+
+impl Tokenizer for PathTokenizer {
+    type TokenStream<'a> = PathTokenStream<'a>;
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> PathTokenStream<'a> {
+        self.token.reset();
+        let mut token_stream = PathTokenStream {
+            text,
+            delimiter: self.delimiter,
+            reverse: self.reverse,
+            path_start: 0,
+            cursor: 0,
+            token: &mut self.token,
+        };
+        if !self.keep_leading_delimiter {
+            token_stream.path_start = token_stream.skip_delimiters(0);
+        }
+        token_stream
+    }
+}
+
+pub struct PathTokenStream<'a> {
+    text: &'a str,
+    delimiter: char,
+    reverse: bool,
+    // Start of the longest token.
+    path_start: usize,
+    // End of the last component emitted.
+    cursor: usize,
+    token: &'a mut Token,
+}
+
+impl<'a> PathTokenStream<'a> {
+    // Returns the offset of the first char following `offset` that is not a delimiter.
+    fn skip_delimiters(&self, offset: usize) -> usize {
+        self.text[offset..]
+            .find(|c: char| c != self.delimiter)
+            .map(|pos| offset + pos)
+            .unwrap_or(self.text.len())
+    }
+
+    // Returns the offset of the first delimiter following `offset`.
+    fn search_delimiter(&self, offset: usize) -> usize {
+        self.text[offset..]
+            .find(self.delimiter)
+            .map(|pos| offset + pos)
+            .unwrap_or(self.text.len())
+    }
+}
+
+impl<'a> TokenStream for PathTokenStream<'a> {
+    fn advance(&mut self) -> bool {
+        let component_start = self.skip_delimiters(self.cursor);
+        if component_start == self.text.len() {
+            return false;
+        }
+        let component_end = self.search_delimiter(component_start);
+        let (offset_from, offset_to) = if !self.reverse {
+            (self.path_start, component_end)
+        } else if self.cursor == 0 {
+            (self.path_start, self.text.len())
+        } else {
+            (component_start, self.text.len())
+        };
+        self.cursor = component_end;
+        self.token.position = self.token.position.wrapping_add(1);
+        self.token.offset_from = offset_from;
+        self.token.offset_to = offset_to;
+        self.token.text.clear();
+        self.token.text.push_str(&self.text[offset_from..offset_to]);
+        true
+    }
+
+    fn token(&self) -> &Token {
+        self.token
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.token
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tokenizer::tests::assert_token;
+    use crate::tokenizer::{PathTokenizer, TextAnalyzer, Token};
+
+    fn token_stream_helper(tokenizer: PathTokenizer, text: &str) -> Vec<Token> {
+        let mut a = TextAnalyzer::from(tokenizer);
+        let mut token_stream = a.token_stream(text);
+        let mut tokens: Vec<Token> = vec![];
+        let mut add_token = |token: &Token| {
+            tokens.push(token.clone());
+        };
+        token_stream.process(&mut add_token);
+        tokens
+    }
+
+    #[test]
+    fn test_path_tokenizer() {
+        let tokens = token_stream_helper(PathTokenizer::default(), "/a/b/c");
+        assert_eq!(tokens.len(), 3);
+        assert_token(&tokens[0], 0, "/a", 0, 2);
+        assert_token(&tokens[1], 1, "/a/b", 0, 4);
+        assert_token(&tokens[2], 2, "/a/b/c", 0, 6);
+
+        let tokens = token_stream_helper(PathTokenizer::default(), "/usr//local/");
+        assert_eq!(tokens.len(), 2);
+        assert_token(&tokens[0], 0, "/usr", 0, 4);
+        assert_token(&tokens[1], 1, "/usr//local", 0, 11);
+
+        assert!(token_stream_helper(PathTokenizer::default(), "").is_empty());
+        assert!(token_stream_helper(PathTokenizer::default(), "//").is_empty());
+    }
+
+    #[test]
+    fn test_path_tokenizer_no_leading_delimiter() {
+        let tokenizer = PathTokenizer::new('\\').set_keep_leading_delimiter(false);
+        let tokens = token_stream_helper(tokenizer, "\\a\\b\\c");
+        assert_eq!(tokens.len(), 3);
+        assert_token(&tokens[0], 0, "a", 1, 2);
+        assert_token(&tokens[1], 1, "a\\b", 1, 4);
+        assert_token(&tokens[2], 2, "a\\b\\c", 1, 6);
+    }
+
+    #[test]
+    fn test_path_tokenizer_reverse() {
+        let tokenizer = PathTokenizer::new('.').set_reverse(true);
+        let tokens = token_stream_helper(tokenizer, "mail.example.com");
+        assert_eq!(tokens.len(), 3);
+        assert_token(&tokens[0], 0, "mail.example.com", 0, 16);
+        assert_token(&tokens[1], 1, "example.com", 5, 16);
+        assert_token(&tokens[2], 2, "com", 13, 16);
+
+        let tokenizer = PathTokenizer::new('@').set_reverse(true);
+        let tokens = token_stream_helper(tokenizer, "john@example.com");
+        assert_eq!(tokens.len(), 2);
+        assert_token(&tokens[0], 0, "john@example.com", 0, 16);
+        assert_token(&tokens[1], 1, "example.com", 5, 16);
+    }
+}
@@ -0,0 +1,169 @@
+//! # Example
+//! ```rust
+//! use tantivy::tokenizer::*;
+//!
+//! let mut tokenizer = TextAnalyzer::builder(WhitespaceTokenizer::default())
+//!   .filter(PatternReplaceFilter::new(r"\.", "", true).unwrap())
+//!   .build();
+//!
+//! let mut stream = tokenizer.token_stream("u.s.a. ...");
+//! assert_eq!(stream.next().unwrap().text, "usa");
+//! // `...` is replaced by an empty text, and therefore removed.
+//! assert!(stream.next().is_none());
+//! ```
+use std::borrow::Cow;
+
+use regex::Regex;
+
+use super::{Token, TokenFilter, TokenStream, Tokenizer};
+use crate::TantivyError;
+
+/// `TokenFilter` that rewrites the text of tokens using a regex substitution.
+///
+/// The replacement can refer to the capture groups of the regex, e.g. `$1`,
+/// as described in [`Regex::replace`].
+///
+/// Tokens whose text is empty after the substitution are removed.
+/// Offsets are not modified and still point to the original text.
+#[derive(Clone)]
+pub struct PatternReplaceFilter {
+    regex: Regex,
+    replacement: String,
+    replace_all: bool,
+}
+
+impl PatternReplaceFilter {
+    /// Creates a `PatternReplaceFilter` replacing the matches of `regex_pattern` by `replacement`.
+    ///
+    /// If `replace_all` is false, only the first match of each token is replaced.
+    pub fn new(
+        regex_pattern: &str,
+        replacement: &str,
+        replace_all: bool,
+    ) -> crate::Result<PatternReplaceFilter> {
+        Regex::new(regex_pattern)
+            .map_err(|_| TantivyError::InvalidArgument(regex_pattern.to_owned()))
+            .map(|regex| PatternReplaceFilter {
+                regex,
+                replacement: replacement.to_string(),
+                replace_all,
+            })
+    }
+}
+
+impl TokenFilter for PatternReplaceFilter {
+    type Tokenizer<T: Tokenizer> = PatternReplaceFilterWrapper<T>;
+
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> PatternReplaceFilterWrapper<T> {
+        PatternReplaceFilterWrapper {
+            config: self,
+            inner: tokenizer,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct PatternReplaceFilterWrapper<T> {
+    config: PatternReplaceFilter,
+    inner: T,
+}
+
+impl<T: Tokenizer> Tokenizer for PatternReplaceFilterWrapper<T> {
+    type TokenStream<'a> = PatternReplaceFilterStream<'a, T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        PatternReplaceFilterStream {
+            config: &self.config,
+            tail: self.inner.token_stream(text),
+        }
+    }
+}
+
+pub struct PatternReplaceFilterStream<'a, T> {
+    config: &'a PatternReplaceFilter,
+    tail: T,
+}
+
+impl<'a, T: TokenStream> PatternReplaceFilterStream<'a, T> {
+    // Applies the substitution to the current token.
+    // Returns false if the resulting text is empty.
+    fn replace(&mut self) -> bool {
+        let config = self.config;
+        let text = &self.tail.token().text;
+        let replacement = config.replacement.as_str();
+        let replaced = if config.replace_all {
+            config.regex.replace_all(text, replacement)
+        } else {
+            config.regex.replace(text, replacement)
+        };
+        match replaced {
+            Cow::Borrowed(_) => !text.is_empty(),
+            Cow::Owned(replaced) => {
+                let is_empty = replaced.is_empty();
+                self.tail.token_mut().text = replaced;
+                !is_empty
+            }
+        }
+    }
+}
+
+impl<'a, T: TokenStream> TokenStream for PatternReplaceFilterStream<'a, T> {
+    fn advance(&mut self) -> bool {
+        while self.tail.advance() {
+            if self.replace() {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn token(&self) -> &Token {
+        self.tail.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.tail.token_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tokenizer::tests::assert_token;
+    use crate::tokenizer::{PatternReplaceFilter, TextAnalyzer, Token, WhitespaceTokenizer};
+
+    fn token_stream_helper(filter: PatternReplaceFilter, text: &str) -> Vec<Token> {
+        let mut a = TextAnalyzer::builder(WhitespaceTokenizer::default())
+            .filter(filter)
+            .build();
+        let mut token_stream = a.token_stream(text);
+        let mut tokens: Vec<Token> = vec![];
+        let mut add_token = |token: &Token| {
+            tokens.push(token.clone());
+        };
+        token_stream.process(&mut add_token);
+        tokens
+    }
+
+    #[test]
+    fn test_pattern_replace_filter_replace_all() {
+        let filter = PatternReplaceFilter::new(r"\.", "", true).unwrap();
+        let tokens = token_stream_helper(filter, "u.s.a. rocks ... now");
+        assert_eq!(tokens.len(), 3);
+        assert_token(&tokens[0], 0, "usa", 0, 6);
+        assert_token(&tokens[1], 1, "rocks", 7, 12);
+        assert_token(&tokens[2], 3, "now", 17, 20);
+    }
+
+    #[test]
+    fn test_pattern_replace_filter_first_match() {
+        let filter = PatternReplaceFilter::new(r"(\w+)-(\w+)", "$2-$1", false).unwrap();
+        let tokens = token_stream_helper(filter, "a-b-c-d");
+        assert_eq!(tokens.len(), 1);
+        assert_token(&tokens[0], 0, "b-a-c-d", 0, 7);
+    }
+
+    #[test]
+    fn test_pattern_replace_filter_invalid_regex() {
+        assert!(PatternReplaceFilter::new(r"\@(", "", true).is_err());
+    }
+}
@@ -0,0 +1,674 @@
+const MAX_CODE_LEN: usize = 4;
+
+/// Computes the primary and alternate Double Metaphone codes of a word,
+/// following the algorithm by Lawrence Philips. Codes are truncated to 4 characters.
+///
+/// Returns empty codes if the word does not contain any letter.
+pub(crate) fn double_metaphone(text: &str) -> (String, String) {
+    let word: Vec<char> = text
+        .chars()
+        .flat_map(char::to_uppercase)
+        .filter(|c| c.is_alphabetic())
+        .collect();
+    let mut encoder = DoubleMetaphone {
+        slavo_germanic: is_slavo_germanic(&word),
+        word,
+        primary: String::new(),
+        alternate: String::new(),
+    };
+    encoder.encode();
+    (encoder.primary, encoder.alternate)
+}
+
+fn is_slavo_germanic(word: &[char]) -> bool {
+    word.contains(&'W') || word.contains(&'K') || word.windows(2).any(|w| w == ['C', 'Z'])
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'A' | 'E' | 'I' | 'O' | 'U' | 'Y')
+}
+
+struct DoubleMetaphone {
+    word: Vec<char>,
+    slavo_germanic: bool,
+    primary: String,
+    alternate: String,
+}
+
+impl DoubleMetaphone {
+    fn len(&self) -> isize {
+        self.word.len() as isize
+    }
+
+    // Returns the char at `pos` or `'\0'` if `pos` is out of bounds.
+    fn char_at(&self, pos: isize) -> char {
+        if pos < 0 {
+            return '\0';
+        }
+        self.word.get(pos as usize).copied().unwrap_or('\0')
+    }
+
+    // Returns true if the `len` chars starting at `start` are equal to one of the `candidates`.
+    // All candidates are expected to have `len` chars.
+    fn contains(&self, start: isize, len: isize, candidates: &[&str]) -> bool {
+        if start < 0 || start + len > self.len() {
+            return false;
+        }
+        let region = &self.word[start as usize..(start + len) as usize];
+        candidates
+            .iter()
+            .any(|candidate| candidate.chars().eq(region.iter().copied()))
+    }
+
+    fn is_complete(&self) -> bool {
+        self.primary.len() >= MAX_CODE_LEN && self.alternate.len() >= MAX_CODE_LEN
+    }
+
+    fn append_primary(&mut self, code: &str) {
+        let remaining = MAX_CODE_LEN.saturating_sub(self.primary.len());
+        self.primary.extend(code.chars().take(remaining));
+    }
+
+    fn append_alternate(&mut self, code: &str) {
+        let remaining = MAX_CODE_LEN.saturating_sub(self.alternate.len());
+        self.alternate.extend(code.chars().take(remaining));
+    }
+
+    fn append(&mut self, code: &str) {
+        self.append_primary(code);
+        self.append_alternate(code);
+    }
+
+    fn append_both(&mut self, primary: &str, alternate: &str) {
+        self.append_primary(primary);
+        self.append_alternate(alternate);
+    }
+
+    // Skips the next char if it is equal to `c`.
+    fn skip_double(&self, pos: isize, c: char) -> isize {
+        if self.char_at(pos + 1) == c {
+            pos + 2
+        } else {
+            pos + 1
+        }
+    }
+
+    fn encode(&mut self) {
+        let mut pos: isize = if self.contains(0, 2, &["GN", "KN", "PN", "WR", "PS"]) {
+            1
+        } else {
+            0
+        };
+        while !self.is_complete() && pos < self.len() {
+            pos = match self.char_at(pos) {
+                'A' | 'E' | 'I' | 'O' | 'U' | 'Y' => {
+                    if pos == 0 {
+                        self.append("A");
+                    }
+                    pos + 1
+                }
+                'B' => {
+                    self.append("P");
+                    self.skip_double(pos, 'B')
+                }
+                'Ç' => {
+                    self.append("S");
+                    pos + 1
+                }
+                'C' => self.handle_c(pos),
+                'D' => self.handle_d(pos),
+                'F' => {
+                    self.append("F");
+                    self.skip_double(pos, 'F')
+                }
+                'G' => self.handle_g(pos),
+                'H' => self.handle_h(pos),
+                'J' => self.handle_j(pos),
+                'K' => {
+                    self.append("K");
+                    self.skip_double(pos, 'K')
+                }
+                'L' => self.handle_l(pos),
+                'M' => {
+                    self.append("M");
+                    if self.condition_m0(pos) {
+                        pos + 2
+                    } else {
+                        pos + 1
+                    }
+                }
+                'N' => {
+                    self.append("N");
+                    self.skip_double(pos, 'N')
+                }
+                'Ñ' => {
+                    self.append("N");
+                    pos + 1
+                }
+                'P' => self.handle_p(pos),
+                'Q' => {
+                    self.append("K");
+                    self.skip_double(pos, 'Q')
+                }
+                'R' => self.handle_r(pos),
+                'S' => self.handle_s(pos),
+                'T' => self.handle_t(pos),
+                'V' => {
+                    self.append("F");
+                    self.skip_double(pos, 'V')
+                }
+                'W' => self.handle_w(pos),
+                'X' => self.handle_x(pos),
+                'Z' => self.handle_z(pos),
+                _ => pos + 1,
+            };
+        }
+    }
+
+    fn handle_c(&mut self, pos: isize) -> isize {
+        if self.condition_c0(pos) {
+            self.append("K");
+            pos + 2
+        } else if pos == 0 && self.contains(pos, 6, &["CAESAR"]) {
+            self.append("S");
+            pos + 2
+        } else if self.contains(pos, 2, &["CH"]) {
+            self.handle_ch(pos)
+        } else if self.contains(pos, 2, &["CZ"]) && !self.contains(pos - 2, 4, &["WICZ"]) {
+            // "Czerny"
+            self.append_both("S", "X");
+            pos + 2
+        } else if self.contains(pos + 1, 3, &["CIA"]) {
+            // "focaccia"
+            self.append("X");
+            pos + 3
+        } else if self.contains(pos, 2, &["CC"]) && !(pos == 1 && self.char_at(0) == 'M') {
+            // double "cc" but not "McClelland"
+            self.handle_cc(pos)
+        } else if self.contains(pos, 2, &["CK", "CG", "CQ"]) {
+            self.append("K");
+            pos + 2
+        } else if self.contains(pos, 2, &["CI", "CE", "CY"]) {
+            // Italian vs. English
+            if self.contains(pos, 3, &["CIO", "CIE", "CIA"]) {
+                self.append_both("S", "X");
+            } else {
+                self.append("S");
+            }
+            pos + 2
+        } else {
+            self.append("K");
+            if self.contains(pos + 1, 2, &[" C", " Q", " G"]) {
+                // "Mac Caffrey", "Mac Gregor"
+                pos + 3
+            } else if self.contains(pos + 1, 1, &["C", "K", "Q"])
+                && !self.contains(pos + 1, 2, &["CE", "CI"])
+            {
+                pos + 2
+            } else {
+                pos + 1
+            }
+        }
+    }
+
+    fn handle_cc(&mut self, pos: isize) -> isize {
+        if self.contains(pos + 2, 1, &["I", "E", "H"]) && !self.contains(pos + 2, 2, &["HU"]) {
+            // "bellocchio" but not "bacchus"
+            if (pos == 1 && self.char_at(pos - 1) == 'A')
+                || self.contains(pos - 1, 5, &["UCCEE", "UCCES"])
+            {
+                // "accident", "accede", "succeed"
+                self.append("KS");
+            } else {
+                // "bacci", "bertucci", other Italian
+                self.append("X");
+            }
+            pos + 3
+        } else {
+            // Pierce's rule
+            self.append("K");
+            pos + 2
+        }
+    }
+
+    fn handle_ch(&mut self, pos: isize) -> isize {
+        if pos > 0 && self.contains(pos, 4, &["CHAE"]) {
+            // "Michael"
+            self.append_both("K", "X");
+        } else if self.condition_ch0(pos) || self.condition_ch1(pos) {
+            // Greek roots ("chemistry", "chorus", etc.), or germanic 'ch' for 'kh' sound.
+            self.append("K");
+        } else if pos > 0 {
+            if self.contains(0, 2, &["MC"]) {
+                self.append("K");
+            } else {
+                self.append_both("X", "K");
+            }
+        } else {
+            self.append("X");
+        }
+        pos + 2
+    }
+
+    fn handle_d(&mut self, pos: isize) -> isize {
+        if self.contains(pos, 2, &["DG"]) {
+            if self.contains(pos + 2, 1, &["I", "E", "Y"]) {
+                // "edge"
+                self.append("J");
+                pos + 3
+            } else {
+                // "Edgar"
+                self.append("TK");
+                pos + 2
+            }
+        } else if self.contains(pos, 2, &["DT", "DD"]) {
+            self.append("T");
+            pos + 2
+        } else {
+            self.append("T");
+            pos + 1
+        }
+    }
+
+    fn handle_g(&mut self, pos: isize) -> isize {
+        if self.char_at(pos + 1) == 'H' {
+            self.handle_gh(pos)
+        } else if self.char_at(pos + 1) == 'N' {
+            if pos == 1 && is_vowel(self.char_at(0)) && !self.slavo_germanic {
+                self.append_both("KN", "N");
+            } else if !self.contains(pos + 2, 2, &["EY"])
+                && self.char_at(pos + 1) != 'Y'
+                && !self.slavo_germanic
+            {
+                self.append_both("N", "KN");
+            } else {
+                self.append("KN");
+            }
+            pos + 2
+        } else if self.contains(pos + 1, 2, &["LI"]) && !self.slavo_germanic {
+            self.append_both("KL", "L");
+            pos + 2
+        } else if pos == 0
+            && (self.char_at(pos + 1) == 'Y'
+                || self.contains(
+                    pos + 1,
+                    2,
+                    &[
+                        "ES", "EP", "EB", "EL", "EY", "IB", "IL", "IN", "IE", "EI", "ER",
+                    ],
+                ))
+        {
+            // -ges-, -gep-, -gel-, -gie- at beginning
+            self.append_both("K", "J");
+            pos + 2
+        } else if (self.contains(pos + 1, 2, &["ER"]) || self.char_at(pos + 1) == 'Y')
+            && !self.contains(0, 6, &["DANGER", "RANGER", "MANGER"])
+            && !self.contains(pos - 1, 1, &["E", "I"])
+            && !self.contains(pos - 1, 3, &["RGY", "OGY"])
+        {
+            // -ger-, -gy-
+            self.append_both("K", "J");
+            pos + 2
+        } else if self.contains(pos + 1, 1, &["E", "I", "Y"])
+            || self.contains(pos - 1, 4, &["AGGI", "OGGI"])
+        {
+            // Italian "biaggi"
+            if self.contains(0, 4, &["VAN ", "VON "])
+                || self.contains(0, 3, &["SCH"])
+                || self.contains(pos + 1, 2, &["ET"])
+            {
+                // obvious germanic
+                self.append("K");
+            } else if self.contains(pos + 1, 3, &["IER"]) {
+                self.append("J");
+            } else {
+                self.append_both("J", "K");
+            }
+            pos + 2
+        } else {
+            self.append("K");
+            self.skip_double(pos, 'G')
+        }
+    }
+
+    fn handle_gh(&mut self, pos: isize) -> isize {
+        if pos > 0 && !is_vowel(self.char_at(pos - 1)) {
+            self.append("K");
+        } else if pos == 0 {
+            if self.char_at(pos + 2) == 'I' {
+                self.append("J");
+            } else {
+                self.append("K");
+            }
+        } else if (pos > 1 && self.contains(pos - 2, 1, &["B", "H", "D"]))
+            || (pos > 2 && self.contains(pos - 3, 1, &["B", "H", "D"]))
+            || (pos > 3 && self.contains(pos - 4, 1, &["B", "H"]))
+        {
+            // Parker's rule (with some further refinements), e.g. "hugh"
+        } else if pos > 2
+            && self.char_at(pos - 1) == 'U'
+            && self.contains(pos - 3, 1, &["C", "G", "L", "R", "T"])
+        {
+            // "laugh", "McLaughlin", "cough", "gough", "rough", "tough"
+            self.append("F");
+        } else if pos > 0 && self.char_at(pos - 1) != 'I' {
+            self.append("K");
+        }
+        pos + 2
+    }
+
+    fn handle_h(&mut self, pos: isize) -> isize {
+        // only keep if first & before vowel or between 2 vowels
+        if (pos == 0 || is_vowel(self.char_at(pos - 1))) && is_vowel(self.char_at(pos + 1)) {
+            self.append("H");
+            pos + 2
+        } else {
+            pos + 1
+        }
+    }
+
+    fn handle_j(&mut self, pos: isize) -> isize {
+        if self.contains(pos, 4, &["JOSE"]) || self.contains(0, 4, &["SAN "]) {
+            // obvious Spanish, "Jose", "San Jacinto"
+            if (pos == 0 && self.char_at(pos + 4) == ' ')
+                || self.len() == 4
+                || self.contains(0, 4, &["SAN "])
+            {
+                self.append("H");
+            } else {
+                self.append_both("J", "H");
+            }
+            return pos + 1;
+        }
+        if pos == 0 {
+            self.append_both("J", "A");
+        } else if is_vowel(self.char_at(pos - 1))
+            && !self.slavo_germanic
+            && matches!(self.char_at(pos + 1), 'A' | 'O')
+        {
+            self.append_both("J", "H");
+        } else if pos == self.len() - 1 {
+            // The reference implementation appends a space to the alternate code here,
+            // which is of no use in a token.
+            self.append_primary("J");
+        } else if !self.contains(pos + 1, 1, &["L", "T", "K", "S", "N", "M", "B", "Z"])
+            && !self.contains(pos - 1, 1, &["S", "K", "L"])
+        {
+            self.append("J");
+        }
+        self.skip_double(pos, 'J')
+    }
+
+    fn handle_l(&mut self, pos: isize) -> isize {
+        if self.char_at(pos + 1) == 'L' {
+            if self.condition_l0(pos) {
+                self.append_primary("L");
+            } else {
+                self.append("L");
+            }
+            pos + 2
+        } else {
+            self.append("L");
+            pos + 1
+        }
+    }
+
+    fn handle_p(&mut self, pos: isize) -> isize {
+        if self.char_at(pos + 1) == 'H' {
+            self.append("F");
+            pos + 2
+        } else {
+            self.append("P");
+            if self.contains(pos + 1, 1, &["P", "B"]) {
+                pos + 2
+            } else {
+                pos + 1
+            }
+        }
+    }
+
+    fn handle_r(&mut self, pos: isize) -> isize {
+        if pos == self.len() - 1
+            && !self.slavo_germanic
+            && self.contains(pos - 2, 2, &["IE"])
+            && !self.contains(pos - 4, 2, &["ME", "MA"])
+        {
+            // French, e.g. "Rogier"
+            self.append_alternate("R");
+        } else {
+            self.append("R");
+        }
+        self.skip_double(pos, 'R')
+    }
+
+    fn handle_s(&mut self, pos: isize) -> isize {
+        if self.contains(pos - 1, 3, &["ISL", "YSL"]) {
+            // "island", "isle", "carlisle", "carlysle"
+            pos + 1
+        } else if pos == 0 && self.contains(pos, 5, &["SUGAR"]) {
+            self.append_both("X", "S");
+            pos + 1
+        } else if self.contains(pos, 2, &["SH"]) {
+            if self.contains(pos + 1, 4, &["HEIM", "HOEK", "HOLM", "HOLZ"]) {
+                // germanic
+                self.append("S");
+            } else {
+                self.append("X");
+            }
+            pos + 2
+        } else if self.contains(pos, 3, &["SIO", "SIA"]) || self.contains(pos, 4, &["SIAN"]) {
+            // Italian and Armenian
+            if self.slavo_germanic {
+                self.append("S");
+            } else {
+                self.append_both("S", "X");
+            }
+            pos + 3
+        } else if (pos == 0 && self.contains(pos + 1, 1, &["M", "N", "L", "W"]))
+            || self.contains(pos + 1, 1, &["Z"])
+        {
+            // german & anglicisations, e.g. "smith" match "schmidt",
+            // "snider" match "schneider", -sz- in slavic languages.
+            self.append_both("S", "X");
+            self.skip_double(pos, 'Z')
+        } else if self.contains(pos, 2, &["SC"]) {
+            self.handle_sc(pos)
+        } else {
+            if pos == self.len() - 1 && self.contains(pos - 2, 2, &["AI", "OI"]) {
+                // French, e.g. "resnais", "artois"
+                self.append_alternate("S");
+            } else {
+                self.append("S");
+            }
+            if self.contains(pos + 1, 1, &["S", "Z"]) {
+                pos + 2
+            } else {
+                pos + 1
+            }
+        }
+    }
+
+    fn handle_sc(&mut self, pos: isize) -> isize {
+        if self.char_at(pos + 2) == 'H' {
+            // Schlesinger's rule
+            if self.contains(pos + 3, 2, &["OO", "ER", "EN", "UY", "ED", "EM"]) {
+                // Dutch origin, e.g. "school", "schooner"
+                if self.contains(pos + 3, 2, &["ER", "EN"]) {
+                    // "schermerhorn", "schenker"
+                    self.append_both("X", "SK");
+                } else {
+                    self.append("SK");
+                }
+            } else if pos == 0 && !is_vowel(self.char_at(3)) && self.char_at(3) != 'W' {
+                self.append_both("X", "S");
+            } else {
+                self.append("X");
+            }
+        } else if self.contains(pos + 2, 1, &["I", "E", "Y"]) {
+            self.append("S");
+        } else {
+            self.append("SK");
+        }
+        pos + 3
+    }
+
+    fn handle_t(&mut self, pos: isize) -> isize {
+        if self.contains(pos, 4, &["TION"]) || self.contains(pos, 3, &["TIA", "TCH"]) {
+            self.append("X");
+            pos + 3
+        } else if self.contains(pos, 2, &["TH"]) || self.contains(pos, 3, &["TTH"]) {
+            if self.contains(pos + 2, 2, &["OM", "AM"])
+                // special case "thomas", "thames" or germanic
+                || self.contains(0, 4, &["VAN ", "VON "])
+                || self.contains(0, 3, &["SCH"])
+            {
+                self.append("T");
+            } else {
+                self.append_both("0", "T");
+            }
+            pos + 2
+        } else {
+            self.append("T");
+            if self.contains(pos + 1, 1, &["T", "D"]) {
+                pos + 2
+            } else {
+                pos + 1
+            }
+        }
+    }
+
+    fn handle_w(&mut self, pos: isize) -> isize {
+        if self.contains(pos, 2, &["WR"]) {
+            // can also be in middle of word
+            self.append("R");
+            pos + 2
+        } else if pos == 0 && (is_vowel(self.char_at(pos + 1)) || self.contains(pos, 2, &["WH"])) {
+            if is_vowel(self.char_at(pos + 1)) {
+                // "Wasserman" should match "Vasserman"
+                self.append_both("A", "F");
+            } else {
+                // need "Uomo" to match "Womo"
+                self.append("A");
+            }
+            pos + 1
+        } else if (pos == self.len() - 1 && is_vowel(self.char_at(pos - 1)))
+            || self.contains(pos - 1, 5, &["EWSKI", "EWSKY", "OWSKI", "OWSKY"])
+            || self.contains(0, 3, &["SCH"])
+        {
+            // "Arnow" should match "Arnoff"
+            self.append_alternate("F");
+            pos + 1
+        } else if self.contains(pos, 4, &["WICZ", "WITZ"]) {
+            // Polish, e.g. "filipowicz"
+            self.append_both("TS", "FX");
+            pos + 4
+        } else {
+            pos + 1
+        }
+    }
+
+    fn handle_x(&mut self, pos: isize) -> isize {
+        if pos == 0 {
+            self.append("S");
+            return pos + 1;
+        }
+        let french_ending = pos == self.len() - 1
+            && (self.contains(pos - 3, 3, &["IAU", "EAU"])
+                || self.contains(pos - 2, 2, &["AU", "OU"]));
+        if !french_ending {
+            // not French, e.g. "breaux"
+            self.append("KS");
+        }
+        if self.contains(pos + 1, 1, &["C", "X"]) {
+            pos + 2
+        } else {
+            pos + 1
+        }
+    }
+
+    fn handle_z(&mut self, pos: isize) -> isize {
+        if self.char_at(pos + 1) == 'H' {
+            // Chinese pinyin, e.g. "zhao"
+            self.append("J");
+            return pos + 2;
+        }
+        if self.contains(pos + 1, 2, &["ZO", "ZI", "ZA"])
+            || (self.slavo_germanic && pos > 0 && self.char_at(pos - 1) != 'T')
+        {
+            self.append_both("S", "TS");
+        } else {
+            self.append("S");
+        }
+        self.skip_double(pos, 'Z')
+    }
+
+    fn condition_c0(&self, pos: isize) -> bool {
+        if self.contains(pos, 4, &["CHIA"]) {
+            return true;
+        }
+        if pos <= 1 || is_vowel(self.char_at(pos - 2)) || !self.contains(pos - 1, 3, &["ACH"]) {
+            return false;
+        }
+        let c = self.char_at(pos + 2);
+        (c != 'I' && c != 'E') || self.contains(pos - 2, 6, &["BACHER", "MACHER"])
+    }
+
+    fn condition_ch0(&self, pos: isize) -> bool {
+        pos == 0
+            && (self.contains(pos + 1, 5, &["HARAC", "HARIS"])
+                || self.contains(pos + 1, 3, &["HOR", "HYM", "HIA", "HEM"]))
+            && !self.contains(0, 5, &["CHORE"])
+    }
+
+    fn condition_ch1(&self, pos: isize) -> bool {
+        self.contains(0, 4, &["VAN ", "VON "])
+            || self.contains(0, 3, &["SCH"])
+            || self.contains(pos - 2, 6, &["ORCHES", "ARCHIT", "ORCHID"])
+            || self.contains(pos + 2, 1, &["T", "S"])
+            || ((self.contains(pos - 1, 1, &["A", "O", "U", "E"]) || pos == 0)
+                && (self.contains(
+                    pos + 2,
+                    1,
+                    &["L", "R", "N", "M", "B", "H", "F", "V", "W", " "],
+                ) || pos + 1 == self.len() - 1))
+    }
+
+    fn condition_l0(&self, pos: isize) -> bool {
+        if pos == self.len() - 3 && self.contains(pos - 1, 4, &["ILLO", "ILLA", "ALLE"]) {
+            return true;
+        }
+        (self.contains(self.len() - 2, 2, &["AS", "OS"])
+            || self.contains(self.len() - 1, 1, &["A", "O"]))
+            && self.contains(pos - 1, 4, &["ALLE"])
+    }
+
+    fn condition_m0(&self, pos: isize) -> bool {
+        if self.char_at(pos + 1) == 'M' {
+            return true;
+        }
+        self.contains(pos - 1, 3, &["UMB"])
+            && (pos + 1 == self.len() - 1 || self.contains(pos + 2, 2, &["ER"]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::double_metaphone;
+
+    fn codes(text: &str) -> (String, String) {
+        double_metaphone(text)
+    }
+
+    #[test]
+    fn test_double_metaphone() {
+        assert_eq!(codes("Smith"), ("SM0".to_string(), "XMT".to_string()));
+        assert_eq!(codes("Smyth"), ("SM0".to_string(), "XMT".to_string()));
+        assert_eq!(codes("Schmidt"), ("XMT".to_string(), "SMT".to_string()));
+        assert_eq!(codes("Thomas"), ("TMS".to_string(), "TMS".to_string()));
+        assert_eq!(codes("Knight"), ("NT".to_string(), "NT".to_string()));
+        assert_eq!(codes("Philip"), ("FLP".to_string(), "FLP".to_string()));
+        assert_eq!(codes("Jose"), ("HS".to_string(), "HS".to_string()));
+        assert_eq!(codes("Caesar"), ("SSR".to_string(), "SSR".to_string()));
+        assert_eq!(codes(""), (String::new(), String::new()));
+    }
+}
@@ -0,0 +1,185 @@
+use super::uppercase_letters;
+
+const MAX_CODE_LEN: usize = 4;
+
+fn is_vowel(word: &[char], pos: usize) -> bool {
+    matches!(word.get(pos), Some('A' | 'E' | 'I' | 'O' | 'U'))
+}
+
+fn is_front_vowel(word: &[char], pos: usize) -> bool {
+    matches!(word.get(pos), Some('E' | 'I' | 'Y'))
+}
+
+fn is_previous_char(word: &[char], pos: usize, c: char) -> bool {
+    pos > 0 && word.get(pos - 1) == Some(&c)
+}
+
+fn is_next_char(word: &[char], pos: usize, c: char) -> bool {
+    word.get(pos + 1) == Some(&c)
+}
+
+fn region_match(word: &[char], pos: usize, test: &str) -> bool {
+    let mut chars = word[pos..].iter();
+    test.chars().all(|c| chars.next() == Some(&c))
+}
+
+/// Computes the Metaphone code of a word, following the original algorithm
+/// by Lawrence Philips. Codes are truncated to 4 characters.
+///
+/// Non ascii letters are ignored. Returns an empty string if the word
+/// does not contain any ascii letter.
+pub(crate) fn metaphone(text: &str) -> String {
+    let mut word = uppercase_letters(text);
+    if word.len() <= 1 {
+        return word.into_iter().collect();
+    }
+    match (word[0], word[1]) {
+        // Initial silent letters.
+        ('K' | 'G' | 'P', 'N') | ('A', 'E') | ('W', 'R') => {
+            word.remove(0);
+        }
+        ('W', 'H') => {
+            word.remove(1);
+        }
+        ('X', _) => word[0] = 'S',
+        _ => {}
+    }
+    let word = &word[..];
+    let last = word.len() - 1;
+    let mut code = String::new();
+    let mut pos = 0;
+    while code.len() < MAX_CODE_LEN && pos < word.len() {
+        let c = word[pos];
+        // Double letters are coded once, except `CC`.
+        if c != 'C' && is_previous_char(word, pos, c) {
+            pos += 1;
+            continue;
+        }
+        match c {
+            'A' | 'E' | 'I' | 'O' | 'U' => {
+                if pos == 0 {
+                    code.push(c);
+                }
+            }
+            'B' => {
+                // Silent in a trailing `MB`, e.g. `dumb`.
+                if !(is_previous_char(word, pos, 'M') && pos == last) {
+                    code.push('B');
+                }
+            }
+            'C' => {
+                if is_previous_char(word, pos, 'S') && is_front_vowel(word, pos + 1) {
+                    // Silent in `SCI`, `SCE`, `SCY`.
+                } else if region_match(word, pos, "CIA") {
+                    code.push('X');
+                } else if is_front_vowel(word, pos + 1) {
+                    code.push('S');
+                } else if is_previous_char(word, pos, 'S') && is_next_char(word, pos, 'H') {
+                    code.push('K');
+                } else if is_next_char(word, pos, 'H') {
+                    if pos == 0 && is_vowel(word, 2) {
+                        code.push('K');
+                    } else {
+                        code.push('X');
+                    }
+                } else {
+                    code.push('K');
+                }
+            }
+            'D' => {
+                if is_next_char(word, pos, 'G') && is_front_vowel(word, pos + 2) {
+                    code.push('J');
+                    pos += 2;
+                } else {
+                    code.push('T');
+                }
+            }
+            'G' => {
+                let silent = if is_next_char(word, pos, 'H') {
+                    pos + 1 == last || !is_vowel(word, pos + 2)
+                } else {
+                    pos > 0 && (region_match(word, pos, "GN") || region_match(word, pos, "GNED"))
+                };
+                if !silent {
+                    if is_front_vowel(word, pos + 1) && !is_previous_char(word, pos, 'G') {
+                        code.push('J');
+                    } else {
+                        code.push('K');
+                    }
+                }
+            }
+            'H' => {
+                let after_varson = pos > 0 && matches!(word[pos - 1], 'C' | 'S' | 'P' | 'T' | 'G');
+                if pos != last && !after_varson && is_vowel(word, pos + 1) {
+                    code.push('H');
+                }
+            }
+            'F' | 'J' | 'L' | 'M' | 'N' | 'R' => code.push(c),
+            'K' => {
+                if !is_previous_char(word, pos, 'C') {
+                    code.push('K');
+                }
+            }
+            'P' => {
+                if is_next_char(word, pos, 'H') {
+                    code.push('F');
+                } else {
+                    code.push('P');
+                }
+            }
+            'Q' => code.push('K'),
+            'S' => {
+                if region_match(word, pos, "SH")
+                    || region_match(word, pos, "SIO")
+                    || region_match(word, pos, "SIA")
+                {
+                    code.push('X');
+                } else {
+                    code.push('S');
+                }
+            }
+            'T' => {
+                if region_match(word, pos, "TIA") || region_match(word, pos, "TIO") {
+                    code.push('X');
+                } else if region_match(word, pos, "TCH") {
+                    // Silent, the `CH` is coded instead.
+                } else if region_match(word, pos, "TH") {
+                    code.push('0');
+                } else {
+                    code.push('T');
+                }
+            }
+            'V' => code.push('F'),
+            'W' | 'Y' => {
+                if is_vowel(word, pos + 1) {
+                    code.push(c);
+                }
+            }
+            'X' => code.push_str("KS"),
+            'Z' => code.push('S'),
+            _ => {}
+        }
+        pos += 1;
+    }
+    code.truncate(MAX_CODE_LEN);
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::metaphone;
+
+    #[test]
+    fn test_metaphone() {
+        assert_eq!(metaphone("knight"), "NT");
+        assert_eq!(metaphone("dumb"), "TM");
+        assert_eq!(metaphone("Smith"), "SM0");
+        assert_eq!(metaphone("Smyth"), "SM0");
+        assert_eq!(metaphone("Wright"), "RT");
+        assert_eq!(metaphone("Xavier"), "SFR");
+        assert_eq!(metaphone("Philip"), "FLP");
+        assert_eq!(metaphone("school"), "SKL");
+        assert_eq!(metaphone("x"), "X");
+        assert_eq!(metaphone(""), "");
+    }
+}
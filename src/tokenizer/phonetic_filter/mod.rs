@@ -0,0 +1,258 @@
+//! # Example
+//! ```rust
+//! use tantivy::tokenizer::*;
+//!
+//! let mut tokenizer = TextAnalyzer::builder(SimpleTokenizer::default())
+//!   .filter(PhoneticFilter::soundex())
+//!   .build();
+//!
+//! let mut stream = tokenizer.token_stream("Robert Rupert");
+//! assert_eq!(stream.next().unwrap().text, "R163");
+//! assert_eq!(stream.next().unwrap().text, "R163");
+//! assert!(stream.next().is_none());
+//! ```
+mod double_metaphone;
+mod metaphone;
+mod soundex;
+
+use std::mem;
+
+use serde::{Deserialize, Serialize};
+
+use self::double_metaphone::double_metaphone;
+use self::metaphone::metaphone;
+use self::soundex::soundex;
+use super::{Token, TokenFilter, TokenStream, Tokenizer};
+
+// Returns the uppercased ascii letters of `text`, dropping any other character.
+fn uppercase_letters(text: &str) -> Vec<char> {
+    text.chars()
+        .filter(char::is_ascii_alphabetic)
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// The algorithm computing the phonetic codes of a [`PhoneticFilter`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PhoneticAlgorithm {
+    /// The American Soundex algorithm.
+    Soundex,
+    /// The original Metaphone algorithm.
+    Metaphone,
+    /// The Double Metaphone algorithm.
+    DoubleMetaphone,
+}
+
+impl PhoneticAlgorithm {
+    // Pushes the phonetic codes of `text` to `codes`, in reverse order of emission.
+    fn encode(self, text: &str, codes: &mut Vec<String>) {
+        match self {
+            PhoneticAlgorithm::Soundex => codes.push(soundex(text)),
+            PhoneticAlgorithm::Metaphone => codes.push(metaphone(text)),
+            PhoneticAlgorithm::DoubleMetaphone => {
+                let (primary, alternate) = double_metaphone(text);
+                if alternate != primary {
+                    codes.push(alternate);
+                }
+                codes.push(primary);
+            }
+        }
+        codes.retain(|code| !code.is_empty());
+    }
+}
+
+/// `TokenFilter` that replaces tokens by a phonetic code, so that words
+/// that sound alike, like the names `Robert` and `Rupert`, produce the same token.
+///
+/// The [Double Metaphone](PhoneticFilter::double_metaphone) algorithm can produce two codes for
+/// a given word. In that case, both codes are emitted at the position of the original token.
+///
+/// Tokens without any code, e.g. numbers, are left untouched.
+///
+/// Phonetic codes only make sense for the languages the algorithms were designed for,
+/// English first.
+#[derive(Clone, Debug)]
+pub struct PhoneticFilter {
+    algorithm: PhoneticAlgorithm,
+    inject: bool,
+}
+
+impl PhoneticFilter {
+    /// Creates a `PhoneticFilter` using the given algorithm.
+    pub fn new(algorithm: PhoneticAlgorithm) -> PhoneticFilter {
+        PhoneticFilter {
+            algorithm,
+            inject: false,
+        }
+    }
+
+    /// Creates a `PhoneticFilter` using the American Soundex algorithm.
+    pub fn soundex() -> PhoneticFilter {
+        PhoneticFilter::new(PhoneticAlgorithm::Soundex)
+    }
+
+    /// Creates a `PhoneticFilter` using the original Metaphone algorithm.
+    pub fn metaphone() -> PhoneticFilter {
+        PhoneticFilter::new(PhoneticAlgorithm::Metaphone)
+    }
+
+    /// Creates a `PhoneticFilter` using the Double Metaphone algorithm.
+    pub fn double_metaphone() -> PhoneticFilter {
+        PhoneticFilter::new(PhoneticAlgorithm::DoubleMetaphone)
+    }
+
+    /// If `inject` is true, the original token is kept, and the phonetic codes are emitted
+    /// right after it, at the same position.
+    ///
+    /// By default, the original token is replaced.
+    pub fn set_inject(mut self, inject: bool) -> PhoneticFilter {
+        self.inject = inject;
+        self
+    }
+}
+
+impl TokenFilter for PhoneticFilter {
+    type Tokenizer<T: Tokenizer> = PhoneticFilterWrapper<T>;
+
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> PhoneticFilterWrapper<T> {
+        PhoneticFilterWrapper {
+            algorithm: self.algorithm,
+            inject: self.inject,
+            inner: tokenizer,
+            pending_codes: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct PhoneticFilterWrapper<T> {
+    algorithm: PhoneticAlgorithm,
+    inject: bool,
+    inner: T,
+    pending_codes: Vec<String>,
+}
+
+impl<T: Tokenizer> Tokenizer for PhoneticFilterWrapper<T> {
+    type TokenStream<'a> = PhoneticFilterStream<'a, T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        self.pending_codes.clear();
+        PhoneticFilterStream {
+            algorithm: self.algorithm,
+            inject: self.inject,
+            tail: self.inner.token_stream(text),
+            pending_codes: &mut self.pending_codes,
+        }
+    }
+}
+
+pub struct PhoneticFilterStream<'a, T> {
+    algorithm: PhoneticAlgorithm,
+    inject: bool,
+    tail: T,
+    // Codes remaining to be emitted for the current token, in reverse order.
+    pending_codes: &'a mut Vec<String>,
+}
+
+impl<'a, T: TokenStream> PhoneticFilterStream<'a, T> {
+    // Replaces the text of the current token by the next pending code.
+    // Returns false if there are no pending codes.
+    fn emit_pending_code(&mut self) -> bool {
+        let Some(mut code) = self.pending_codes.pop() else {
+            return false;
+        };
+        mem::swap(&mut self.tail.token_mut().text, &mut code);
+        true
+    }
+}
+
+impl<'a, T: TokenStream> TokenStream for PhoneticFilterStream<'a, T> {
+    fn advance(&mut self) -> bool {
+        if self.emit_pending_code() {
+            return true;
+        }
+        if !self.tail.advance() {
+            return false;
+        }
+        self.algorithm
+            .encode(&self.tail.token().text, self.pending_codes);
+        if !self.inject {
+            self.emit_pending_code();
+        }
+        true
+    }
+
+    fn token(&self) -> &Token {
+        self.tail.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.tail.token_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tokenizer::tests::assert_token;
+    use crate::tokenizer::{PhoneticFilter, SimpleTokenizer, TextAnalyzer, Token};
+
+    fn token_stream_helper(filter: PhoneticFilter, text: &str) -> Vec<Token> {
+        let mut a = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(filter)
+            .build();
+        let mut token_stream = a.token_stream(text);
+        let mut tokens: Vec<Token> = vec![];
+        let mut add_token = |token: &Token| {
+            tokens.push(token.clone());
+        };
+        token_stream.process(&mut add_token);
+        tokens
+    }
+
+    #[test]
+    fn test_soundex_filter() {
+        let tokens = token_stream_helper(PhoneticFilter::soundex(), "Robert Rupert 42");
+        assert_eq!(tokens.len(), 3);
+        assert_token(&tokens[0], 0, "R163", 0, 6);
+        assert_token(&tokens[1], 1, "R163", 7, 13);
+        assert_token(&tokens[2], 2, "42", 14, 16);
+    }
+
+    #[test]
+    fn test_soundex_filter_inject() {
+        let tokens =
+            token_stream_helper(PhoneticFilter::soundex().set_inject(true), "Robert Rupert");
+        assert_eq!(tokens.len(), 4);
+        assert_token(&tokens[0], 0, "Robert", 0, 6);
+        assert_token(&tokens[1], 0, "R163", 0, 6);
+        assert_token(&tokens[2], 1, "Rupert", 7, 13);
+        assert_token(&tokens[3], 1, "R163", 7, 13);
+    }
+
+    #[test]
+    fn test_metaphone_filter() {
+        let tokens = token_stream_helper(PhoneticFilter::metaphone(), "Smith Smyth");
+        assert_eq!(tokens.len(), 2);
+        assert_token(&tokens[0], 0, "SM0", 0, 5);
+        assert_token(&tokens[1], 1, "SM0", 6, 11);
+    }
+
+    #[test]
+    fn test_double_metaphone_filter() {
+        let tokens = token_stream_helper(PhoneticFilter::double_metaphone(), "Thomas Schmidt");
+        assert_eq!(tokens.len(), 3);
+        assert_token(&tokens[0], 0, "TMS", 0, 6);
+        assert_token(&tokens[1], 1, "XMT", 7, 14);
+        assert_token(&tokens[2], 1, "SMT", 7, 14);
+
+        let tokens = token_stream_helper(
+            PhoneticFilter::double_metaphone().set_inject(true),
+            "Schmidt",
+        );
+        assert_eq!(tokens.len(), 3);
+        assert_token(&tokens[0], 0, "Schmidt", 0, 7);
+        assert_token(&tokens[1], 0, "XMT", 0, 7);
+        assert_token(&tokens[2], 0, "SMT", 0, 7);
+    }
+}
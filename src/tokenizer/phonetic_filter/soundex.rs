@@ -0,0 +1,64 @@
+// Returns the soundex digit of an uppercase ascii letter.
+//
+// Vowels (and `Y`) are coded as `0`: they separate consonants but are not part of the code.
+// `H` and `W` return `None`: they are ignored altogether.
+fn soundex_digit(c: u8) -> Option<u8> {
+    let digit = match c {
+        b'B' | b'F' | b'P' | b'V' => b'1',
+        b'C' | b'G' | b'J' | b'K' | b'Q' | b'S' | b'X' | b'Z' => b'2',
+        b'D' | b'T' => b'3',
+        b'L' => b'4',
+        b'M' | b'N' => b'5',
+        b'R' => b'6',
+        b'H' | b'W' => return None,
+        _ => b'0',
+    };
+    Some(digit)
+}
+
+/// Computes the American Soundex code of a word, e.g. `R163` for `Robert`.
+///
+/// Non ascii letters are ignored. Returns an empty string if the word
+/// does not contain any ascii letter.
+pub(crate) fn soundex(text: &str) -> String {
+    let mut letters = text
+        .bytes()
+        .filter(u8::is_ascii_alphabetic)
+        .map(|c| c.to_ascii_uppercase());
+    let Some(first_letter) = letters.next() else {
+        return String::new();
+    };
+    let mut code = vec![first_letter];
+    let mut previous_digit = soundex_digit(first_letter);
+    for letter in letters {
+        let Some(digit) = soundex_digit(letter) else {
+            continue;
+        };
+        if digit != b'0' && Some(digit) != previous_digit {
+            code.push(digit);
+            if code.len() == 4 {
+                break;
+            }
+        }
+        previous_digit = Some(digit);
+    }
+    code.resize(4, b'0');
+    String::from_utf8(code).expect("Soundex codes are ascii")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::soundex;
+
+    #[test]
+    fn test_soundex() {
+        assert_eq!(soundex("Robert"), "R163");
+        assert_eq!(soundex("Rupert"), "R163");
+        assert_eq!(soundex("Rubin"), "R150");
+        assert_eq!(soundex("Ashcraft"), "A261");
+        assert_eq!(soundex("Tymczak"), "T522");
+        assert_eq!(soundex("Pfister"), "P236");
+        assert_eq!(soundex("Lee"), "L000");
+        assert_eq!(soundex("42"), "");
+    }
+}
@@ -1,10 +1,15 @@
-use regex::Regex;
+use regex::{Match, Regex};
 
 use super::{Token, TokenStream, Tokenizer};
 use crate::TantivyError;
 
-/// Tokenize the text by using a regex pattern to split.
-/// Each match of the regex emits a distinct token, empty tokens will not be emitted. Anchors such
+/// Tokenize the text by using a regex pattern.
+///
+/// By default, or with [`RegexTokenizer::matches`], each match of the regex emits a distinct
+/// token. With [`RegexTokenizer::split`], the text is split on the matches of the regex, and the
+/// parts in between the matches are emitted as tokens instead.
+///
+/// In both cases, empty tokens will not be emitted and empty matches are ignored. Anchors such
 /// as `\A` will match the text from the part where the last token was emitted or the beginning of
 /// the complete text if no token was emitted yet.
 ///
@@ -48,19 +53,36 @@ use crate::TantivyError;
 #[derive(Clone)]
 pub struct RegexTokenizer {
     regex: Regex,
+    split: bool,
     token: Token,
 }
 
 impl RegexTokenizer {
-    /// Creates a new RegexTokenizer.
+    /// Creates a new RegexTokenizer emitting the matches of the regex as tokens.
     pub fn new(regex_pattern: &str) -> crate::Result<RegexTokenizer> {
         Regex::new(regex_pattern)
             .map_err(|_| TantivyError::InvalidArgument(regex_pattern.to_owned()))
             .map(|regex| Self {
                 regex,
+                split: false,
                 token: Token::default(),
             })
     }
+
+    /// Creates a new RegexTokenizer emitting the matches of the regex as tokens.
+    ///
+    /// This is the same as [`RegexTokenizer::new`].
+    pub fn matches(regex_pattern: &str) -> crate::Result<RegexTokenizer> {
+        Self::new(regex_pattern)
+    }
+
+    /// Creates a new RegexTokenizer splitting the text on the matches of the regex,
+    /// e.g. on a custom delimiter.
+    pub fn split(regex_pattern: &str) -> crate::Result<RegexTokenizer> {
+        let mut tokenizer = Self::new(regex_pattern)?;
+        tokenizer.split = true;
+        Ok(tokenizer)
+    }
 }
 
 impl Tokenizer for RegexTokenizer {
@@ -69,41 +91,84 @@ impl Tokenizer for RegexTokenizer {
         self.token.reset();
         RegexTokenStream {
             regex: self.regex.clone(),
+            split: self.split,
             text,
             token: &mut self.token,
             cursor: 0,
+            exhausted: false,
         }
     }
 }
 
 pub struct RegexTokenStream<'a> {
     regex: Regex,
+    split: bool,
     text: &'a str,
     token: &'a mut Token,
     cursor: usize,
+    // Only used in split mode: true once the part following the last match was consumed.
+    exhausted: bool,
 }
 
-impl<'a> TokenStream for RegexTokenStream<'a> {
-    fn advance(&mut self) -> bool {
-        let Some(regex_match) = self.regex.find(self.text) else {
-            return false;
-        };
-        if regex_match.as_str().is_empty() {
-            return false;
-        }
+impl<'a> RegexTokenStream<'a> {
+    // Returns the first non empty match of the regex in the remaining text.
+    fn find_non_empty(&self) -> Option<Match<'a>> {
+        self.regex
+            .find_iter(self.text)
+            .find(|regex_match| !regex_match.as_str().is_empty())
+    }
+
+    // Emits `self.text[start..end]` as a token, and moves the cursor to `next_start`.
+    fn emit(&mut self, start: usize, end: usize, next_start: usize) {
         self.token.text.clear();
-        self.token.text.push_str(regex_match.as_str());
+        self.token.text.push_str(&self.text[start..end]);
 
-        self.token.offset_from = self.cursor + regex_match.start();
-        self.cursor += regex_match.end();
-        self.token.offset_to = self.cursor;
+        self.token.offset_from = self.cursor + start;
+        self.token.offset_to = self.cursor + end;
+        self.cursor += next_start;
 
         self.token.position = self.token.position.wrapping_add(1);
 
-        self.text = &self.text[regex_match.end()..];
+        self.text = &self.text[next_start..];
+    }
+
+    fn advance_match(&mut self) -> bool {
+        let Some(regex_match) = self.find_non_empty() else {
+            return false;
+        };
+        self.emit(regex_match.start(), regex_match.end(), regex_match.end());
         true
     }
 
+    fn advance_split(&mut self) -> bool {
+        while !self.exhausted {
+            let (part_end, next_start) = match self.find_non_empty() {
+                Some(regex_match) => (regex_match.start(), regex_match.end()),
+                None => {
+                    self.exhausted = true;
+                    (self.text.len(), self.text.len())
+                }
+            };
+            if part_end > 0 {
+                self.emit(0, part_end, next_start);
+                return true;
+            }
+            self.cursor += next_start;
+            self.text = &self.text[next_start..];
+        }
+        false
+    }
+}
+
+impl<'a> TokenStream for RegexTokenStream<'a> {
+    fn advance(&mut self) -> bool {
+        if self.split {
+            self.advance_split()
+        } else {
+            self.advance_match()
+        }
+    }
+
     fn token(&self) -> &Token {
         self.token
     }
@@ -150,6 +215,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_regexp_tokenizer_empty_matches() {
+        let tokens = token_stream_helper("aa bb", r"\w*");
+        assert_eq!(tokens.len(), 2);
+        assert_token(&tokens[0], 0, "aa", 0, 2);
+        assert_token(&tokens[1], 1, "bb", 3, 5);
+    }
+
+    #[test]
+    fn test_regexp_tokenizer_split() {
+        let tokens = split_token_stream_helper("|2024-01-01||INFO|é|", r"\|");
+        assert_eq!(tokens.len(), 3);
+        assert_token(&tokens[0], 0, "2024-01-01", 1, 11);
+        assert_token(&tokens[1], 1, "INFO", 13, 17);
+        assert_token(&tokens[2], 2, "é", 18, 20);
+    }
+
+    #[test]
+    fn test_regexp_tokenizer_split_no_match() {
+        let tokens = split_token_stream_helper("aaa", r",\s*");
+        assert_eq!(tokens.len(), 1);
+        assert_token(&tokens[0], 0, "aaa", 0, 3);
+
+        let tokens = split_token_stream_helper("", r",\s*");
+        assert!(tokens.is_empty());
+    }
+
+    #[test]
+    fn test_regexp_tokenizer_split_empty_matches() {
+        let tokens = split_token_stream_helper("a, b", r",?\s*");
+        assert_eq!(tokens.len(), 2);
+        assert_token(&tokens[0], 0, "a", 0, 1);
+        assert_token(&tokens[1], 1, "b", 3, 4);
+    }
+
+    fn split_token_stream_helper(text: &str, pattern: &str) -> Vec<Token> {
+        let r = RegexTokenizer::split(pattern).unwrap();
+        let mut a = TextAnalyzer::from(r);
+        let mut tokens: Vec<Token> = vec![];
+        a.token_stream(text)
+            .process(&mut |token: &Token| tokens.push(token.clone()));
+        tokens
+    }
+
     fn token_stream_helper(text: &str, pattern: &str) -> Vec<Token> {
         let r = RegexTokenizer::new(pattern).unwrap();
         let mut a = TextAnalyzer::from(r);
@@ -15,25 +15,43 @@
 use super::{Token, TokenFilter, TokenStream, Tokenizer};
 
 /// `RemoveLongFilter` removes tokens that are longer
-/// than a given number of bytes (in UTF-8 representation).
+/// than a given number of bytes (in UTF-8 representation),
+/// or of chars with [`RemoveLongFilter::limit_chars`].
 ///
 /// It is especially useful when indexing unconstrained content.
 /// e.g. Mail containing base-64 encoded pictures etc.
+///
+/// Note that a limit in bytes is stricter for non-latin scripts: a CJK character
+/// takes 3 bytes.
 #[derive(Clone)]
 pub struct RemoveLongFilter {
     length_limit: usize,
+    count_chars: bool,
 }
 
 impl RemoveLongFilter {
     /// Creates a `RemoveLongFilter` given a limit in bytes of the UTF-8 representation.
     pub fn limit(length_limit: usize) -> RemoveLongFilter {
-        RemoveLongFilter { length_limit }
+        RemoveLongFilter {
+            length_limit,
+            count_chars: false,
+        }
+    }
+
+    /// Creates a `RemoveLongFilter` given a limit in number of chars (Unicode scalar values).
+    pub fn limit_chars(length_limit: usize) -> RemoveLongFilter {
+        RemoveLongFilter {
+            length_limit,
+            count_chars: true,
+        }
     }
 }
 
 impl<T> RemoveLongFilterStream<T> {
     fn predicate(&self, token: &Token) -> bool {
+        // A text has at most as many chars as bytes.
         token.text.len() < self.token_length_limit
+            || (self.count_chars && token.text.chars().count() < self.token_length_limit)
     }
 }
 
@@ -43,6 +61,7 @@ impl TokenFilter for RemoveLongFilter {
     fn transform<T: Tokenizer>(self, tokenizer: T) -> RemoveLongFilterWrapper<T> {
         RemoveLongFilterWrapper {
             length_limit: self.length_limit,
+            count_chars: self.count_chars,
             inner: tokenizer,
         }
     }
@@ -51,6 +70,7 @@ impl TokenFilter for RemoveLongFilter {
 #[derive(Clone)]
 pub struct RemoveLongFilterWrapper<T: Tokenizer> {
     length_limit: usize,
+    count_chars: bool,
     inner: T,
 }
 
@@ -60,6 +80,7 @@ impl<T: Tokenizer> Tokenizer for RemoveLongFilterWrapper<T> {
     fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
         RemoveLongFilterStream {
             token_length_limit: self.length_limit,
+            count_chars: self.count_chars,
             tail: self.inner.token_stream(text),
         }
     }
@@ -67,6 +88,7 @@ impl<T: Tokenizer> Tokenizer for RemoveLongFilterWrapper<T> {
 
 pub struct RemoveLongFilterStream<T> {
     token_length_limit: usize,
+    count_chars: bool,
     tail: T,
 }
 
@@ -102,6 +124,24 @@ mod tests {
         assert_token(&tokens[1], 2, "happy", 15, 20);
     }
 
+    #[test]
+    fn test_remove_long_chars() {
+        let cjk_word = "東京".repeat(10);
+        let text = format!("{cjk_word} {}", "a".repeat(40));
+        let mut analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(RemoveLongFilter::limit_chars(40))
+            .build();
+        let tokens = analyzer.analyze(&text);
+        assert_eq!(tokens.len(), 1);
+        assert_token(&tokens[0], 0, &cjk_word, 0, 60);
+
+        // The same token is over the limit in bytes.
+        let mut analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(RemoveLongFilter::limit(40))
+            .build();
+        assert!(analyzer.analyze(&cjk_word).is_empty());
+    }
+
     fn token_stream_helper(text: &str) -> Vec<Token> {
         let mut a = TextAnalyzer::builder(SimpleTokenizer::default())
             .filter(RemoveLongFilter::limit(6))
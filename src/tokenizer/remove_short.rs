@@ -0,0 +1,120 @@
+//! # Example
+//! ```rust
+//! use tantivy::tokenizer::*;
+//!
+//! let mut tokenizer = TextAnalyzer::builder(SimpleTokenizer::default())
+//!   .filter(RemoveShortFilter::limit(2))
+//!   .build();
+//!
+//! let mut stream = tokenizer.token_stream("a nice day");
+//! // because `a` is less than 2 characters, it is filtered
+//! // out of the token stream.
+//! assert_eq!(stream.next().unwrap().text, "nice");
+//! assert_eq!(stream.next().unwrap().text, "day");
+//! assert!(stream.next().is_none());
+//! ```
+use super::{Token, TokenFilter, TokenStream, Tokenizer};
+
+/// `RemoveShortFilter` removes tokens that are shorter
+/// than a given number of characters (Unicode scalar values).
+///
+/// The position of the remaining tokens is left untouched.
+#[derive(Clone)]
+pub struct RemoveShortFilter {
+    length_limit: usize,
+}
+
+impl RemoveShortFilter {
+    /// Creates a `RemoveShortFilter` given a minimum number of characters.
+    pub fn limit(length_limit: usize) -> RemoveShortFilter {
+        RemoveShortFilter { length_limit }
+    }
+}
+
+impl<T> RemoveShortFilterStream<T> {
+    fn predicate(&self, token: &Token) -> bool {
+        // A token has at least as many bytes as chars.
+        token.text.len() >= self.token_length_limit
+            && token.text.chars().count() >= self.token_length_limit
+    }
+}
+
+impl TokenFilter for RemoveShortFilter {
+    type Tokenizer<T: Tokenizer> = RemoveShortFilterWrapper<T>;
+
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> RemoveShortFilterWrapper<T> {
+        RemoveShortFilterWrapper {
+            length_limit: self.length_limit,
+            inner: tokenizer,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RemoveShortFilterWrapper<T: Tokenizer> {
+    length_limit: usize,
+    inner: T,
+}
+
+impl<T: Tokenizer> Tokenizer for RemoveShortFilterWrapper<T> {
+    type TokenStream<'a> = RemoveShortFilterStream<T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        RemoveShortFilterStream {
+            token_length_limit: self.length_limit,
+            tail: self.inner.token_stream(text),
+        }
+    }
+}
+
+pub struct RemoveShortFilterStream<T> {
+    token_length_limit: usize,
+    tail: T,
+}
+
+impl<T: TokenStream> TokenStream for RemoveShortFilterStream<T> {
+    fn advance(&mut self) -> bool {
+        while self.tail.advance() {
+            if self.predicate(self.tail.token()) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn token(&self) -> &Token {
+        self.tail.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.tail.token_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tokenizer::tests::assert_token;
+    use crate::tokenizer::{RemoveShortFilter, SimpleTokenizer, TextAnalyzer, Token};
+
+    #[test]
+    fn test_remove_short() {
+        let tokens = token_stream_helper("a tantivy é, is 東京 日");
+        assert_eq!(tokens.len(), 3);
+        assert_token(&tokens[0], 1, "tantivy", 2, 9);
+        assert_token(&tokens[1], 3, "is", 14, 16);
+        assert_token(&tokens[2], 4, "東京", 17, 23);
+    }
+
+    fn token_stream_helper(text: &str) -> Vec<Token> {
+        let mut a = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(RemoveShortFilter::limit(2))
+            .build();
+        let mut token_stream = a.token_stream(text);
+        let mut tokens: Vec<Token> = vec![];
+        let mut add_token = |token: &Token| {
+            tokens.push(token.clone());
+        };
+        token_stream.process(&mut add_token);
+        tokens
+    }
+}
@@ -0,0 +1,120 @@
+//! # Example
+//! ```rust
+//! use tantivy::tokenizer::*;
+//!
+//! let mut tokenizer = TextAnalyzer::builder(SimpleTokenizer::default())
+//!   .filter(ReverseFilter)
+//!   .build();
+//!
+//! let mut stream = tokenizer.token_stream("search engine");
+//! assert_eq!(stream.next().unwrap().text, "hcraes");
+//! assert_eq!(stream.next().unwrap().text, "enigne");
+//! assert!(stream.next().is_none());
+//! ```
+use std::mem;
+
+use super::{Token, TokenFilter, TokenStream, Tokenizer};
+
+/// `TokenFilter` that reverses the characters of the tokens.
+///
+/// Indexing the reversed tokens turns a suffix query into a prefix query on the reversed
+/// text, e.g. `*ing` can be searched as the prefix `gni`, possibly paired with an
+/// [`EdgeNgramFilter`](super::EdgeNgramFilter).
+///
+/// The text is reversed char by char, i.e. by Unicode scalar value. Grapheme clusters made
+/// of several chars are not preserved: a combining accent ends up before the letter it was
+/// applied to. Both the indexed text and the queries are affected the same way, so matching
+/// still works, but the reversed tokens should not be displayed.
+/// Offsets are not modified and still point to the original text.
+#[derive(Clone)]
+pub struct ReverseFilter;
+
+impl TokenFilter for ReverseFilter {
+    type Tokenizer<T: Tokenizer> = ReverseFilterWrapper<T>;
+
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> ReverseFilterWrapper<T> {
+        ReverseFilterWrapper {
+            inner: tokenizer,
+            buffer: String::new(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ReverseFilterWrapper<T> {
+    inner: T,
+    buffer: String,
+}
+
+impl<T: Tokenizer> Tokenizer for ReverseFilterWrapper<T> {
+    type TokenStream<'a> = ReverseFilterStream<'a, T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        self.buffer.clear();
+        ReverseFilterStream {
+            tail: self.inner.token_stream(text),
+            buffer: &mut self.buffer,
+        }
+    }
+}
+
+pub struct ReverseFilterStream<'a, T> {
+    buffer: &'a mut String,
+    tail: T,
+}
+
+impl<'a, T: TokenStream> TokenStream for ReverseFilterStream<'a, T> {
+    fn advance(&mut self) -> bool {
+        if !self.tail.advance() {
+            return false;
+        }
+        self.buffer.clear();
+        self.buffer.extend(self.tail.token().text.chars().rev());
+        mem::swap(&mut self.tail.token_mut().text, self.buffer);
+        true
+    }
+
+    fn token(&self) -> &Token {
+        self.tail.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.tail.token_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tokenizer::tests::assert_token;
+    use crate::tokenizer::{ReverseFilter, TextAnalyzer, Token, WhitespaceTokenizer};
+
+    #[test]
+    fn test_reverse_filter() {
+        let tokens = token_stream_helper("search 東京 été");
+        assert_eq!(tokens.len(), 3);
+        assert_token(&tokens[0], 0, "hcraes", 0, 6);
+        assert_token(&tokens[1], 1, "京東", 7, 13);
+        assert_token(&tokens[2], 2, "été", 14, 19);
+    }
+
+    #[test]
+    fn test_reverse_filter_combining_chars() {
+        // `e` followed by a combining acute accent.
+        let tokens = token_stream_helper("cafe\u{301}");
+        assert_eq!(tokens.len(), 1);
+        assert_token(&tokens[0], 0, "\u{301}efac", 0, 6);
+    }
+
+    fn token_stream_helper(text: &str) -> Vec<Token> {
+        let mut a = TextAnalyzer::builder(WhitespaceTokenizer::default())
+            .filter(ReverseFilter)
+            .build();
+        let mut token_stream = a.token_stream(text);
+        let mut tokens: Vec<Token> = vec![];
+        let mut add_token = |token: &Token| {
+            tokens.push(token.clone());
+        };
+        token_stream.process(&mut add_token);
+        tokens
+    }
+}
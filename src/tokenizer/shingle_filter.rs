@@ -0,0 +1,289 @@
+//! # Example
+//! ```rust
+//! use tantivy::tokenizer::*;
+//!
+//! let mut tokenizer = TextAnalyzer::builder(SimpleTokenizer::default())
+//!   .filter(ShingleFilter::new(2, 2, " ").unwrap())
+//!   .build();
+//!
+//! let mut stream = tokenizer.token_stream("the quick brown fox");
+//! assert_eq!(stream.next().unwrap().text, "the quick");
+//! assert_eq!(stream.next().unwrap().text, "quick brown");
+//! assert_eq!(stream.next().unwrap().text, "brown fox");
+//! assert!(stream.next().is_none());
+//! ```
+use std::collections::VecDeque;
+
+use super::{Token, TokenFilter, TokenStream, Tokenizer};
+use crate::TantivyError;
+
+/// `TokenFilter` that emits shingles, i.e. n-grams of consecutive tokens,
+/// made of `min_shingle` to `max_shingle` tokens joined by `token_separator`.
+///
+/// Indexing shingles makes phrase queries cheaper, and lets documents matching
+/// the words of the query next to each other rank higher.
+///
+/// A shingle is emitted at the position of its first token, with a `position_length`
+/// equal to the number of positions it spans. Its offsets range from the start of its first
+/// token to the end of its last token. The shingles starting at a given token are emitted in
+/// increasing length, right after the token itself if
+/// [`set_output_unigrams`](ShingleFilter::set_output_unigrams) is set.
+///
+/// Positions left empty by a preceding filter, e.g. the
+/// [`StopWordFilter`](super::StopWordFilter), are occupied by a filler token, `_` by default,
+/// so that no shingle joins tokens that were not next to each other. Shingles never start or
+/// end with a filler token.
+#[derive(Clone, Debug)]
+pub struct ShingleFilter {
+    min_shingle: usize,
+    max_shingle: usize,
+    token_separator: String,
+    filler_token: String,
+    output_unigrams: bool,
+}
+
+impl ShingleFilter {
+    /// Creates a `ShingleFilter` emitting shingles of `min_shingle` to `max_shingle` tokens,
+    /// joined by `token_separator`.
+    ///
+    /// `min_shingle` must be at least 2, unigrams are emitted with
+    /// [`set_output_unigrams`](ShingleFilter::set_output_unigrams).
+    pub fn new(
+        min_shingle: usize,
+        max_shingle: usize,
+        token_separator: &str,
+    ) -> crate::Result<ShingleFilter> {
+        if min_shingle < 2 {
+            return Err(TantivyError::InvalidArgument(
+                "min_shingle must be at least 2".to_string(),
+            ));
+        }
+        if min_shingle > max_shingle {
+            return Err(TantivyError::InvalidArgument(
+                "min_shingle must not be greater than max_shingle".to_string(),
+            ));
+        }
+        Ok(ShingleFilter {
+            min_shingle,
+            max_shingle,
+            token_separator: token_separator.to_string(),
+            filler_token: "_".to_string(),
+            output_unigrams: false,
+        })
+    }
+
+    /// If `output_unigrams` is true, the original tokens are emitted as well as the shingles.
+    pub fn set_output_unigrams(mut self, output_unigrams: bool) -> ShingleFilter {
+        self.output_unigrams = output_unigrams;
+        self
+    }
+
+    /// Sets the text standing for a missing token in shingles, `_` by default.
+    pub fn set_filler_token(mut self, filler_token: &str) -> ShingleFilter {
+        self.filler_token = filler_token.to_string();
+        self
+    }
+}
+
+impl TokenFilter for ShingleFilter {
+    type Tokenizer<T: Tokenizer> = ShingleFilterWrapper<T>;
+
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> ShingleFilterWrapper<T> {
+        ShingleFilterWrapper {
+            config: self,
+            inner: tokenizer,
+            window: VecDeque::new(),
+            parts: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ShingleFilterWrapper<T> {
+    config: ShingleFilter,
+    inner: T,
+    window: VecDeque<Option<Token>>,
+    parts: Vec<Token>,
+}
+
+impl<T: Tokenizer> Tokenizer for ShingleFilterWrapper<T> {
+    type TokenStream<'a> = ShingleFilterStream<'a, T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        self.window.clear();
+        self.parts.clear();
+        ShingleFilterStream {
+            config: &self.config,
+            tail: self.inner.token_stream(text),
+            window: &mut self.window,
+            parts: &mut self.parts,
+            tail_exhausted: false,
+        }
+    }
+}
+
+pub struct ShingleFilterStream<'a, T> {
+    config: &'a ShingleFilter,
+    tail: T,
+    // The upcoming tokens of the tail, `None` standing for a filler token.
+    window: &'a mut VecDeque<Option<Token>>,
+    // The tokens to emit for the last token popped from the window, in reverse order.
+    parts: &'a mut Vec<Token>,
+    tail_exhausted: bool,
+}
+
+impl<'a, T: TokenStream> ShingleFilterStream<'a, T> {
+    // Reads tokens from the tail, until the window holds enough tokens
+    // to build the longest shingle starting at its first token.
+    fn fill_window(&mut self) {
+        while !self.tail_exhausted && self.window.len() < self.config.max_shingle {
+            if !self.tail.advance() {
+                self.tail_exhausted = true;
+                return;
+            }
+            let token = self.tail.token();
+            let last_token_before = self.window.iter().rev().find_map(|token| token.as_ref());
+            if let Some(last_token) = last_token_before {
+                // A shingle can not span more than `max_shingle - 1` fillers.
+                let num_fillers = token
+                    .position
+                    .saturating_sub(last_token.position + 1)
+                    .min(self.config.max_shingle - 1);
+                self.window.extend((0..num_fillers).map(|_| None));
+            }
+            self.window.push_back(Some(token.clone()));
+        }
+    }
+
+    // Pops the first token of the window, and fills `self.parts`
+    // with the tokens to emit for it.
+    fn push_shingles(&mut self) {
+        let Some(Some(first_token)) = self.window.pop_front() else {
+            return;
+        };
+        if self.config.output_unigrams {
+            self.parts.push(first_token.clone());
+        }
+        let mut text = first_token.text.clone();
+        let following_tokens = self.window.iter().take(self.config.max_shingle - 1);
+        for (shingle_len, token) in (2..).zip(following_tokens) {
+            text.push_str(&self.config.token_separator);
+            let Some(token) = token else {
+                text.push_str(&self.config.filler_token);
+                continue;
+            };
+            text.push_str(&token.text);
+            if shingle_len >= self.config.min_shingle {
+                self.parts.push(Token {
+                    text: text.clone(),
+                    offset_to: token.offset_to,
+                    position_length: shingle_len,
+                    ..first_token
+                });
+            }
+        }
+        self.parts.reverse();
+    }
+}
+
+impl<'a, T: TokenStream> TokenStream for ShingleFilterStream<'a, T> {
+    fn advance(&mut self) -> bool {
+        self.parts.pop();
+        while self.parts.is_empty() {
+            self.fill_window();
+            if self.window.is_empty() {
+                return false;
+            }
+            self.push_shingles();
+        }
+        true
+    }
+
+    fn token(&self) -> &Token {
+        self.parts.last().unwrap_or_else(|| self.tail.token())
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.parts
+            .last_mut()
+            .unwrap_or_else(|| self.tail.token_mut())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tokenizer::tests::assert_token;
+    use crate::tokenizer::{ShingleFilter, SimpleTokenizer, StopWordFilter, TextAnalyzer, Token};
+
+    fn token_stream_helper(text: &str, filter: ShingleFilter) -> Vec<Token> {
+        let mut a = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(StopWordFilter::remove(vec![
+                "the".to_string(),
+                "a".to_string(),
+            ]))
+            .filter(filter)
+            .build();
+        let mut token_stream = a.token_stream(text);
+        let mut tokens: Vec<Token> = vec![];
+        let mut add_token = |token: &Token| {
+            tokens.push(token.clone());
+        };
+        token_stream.process(&mut add_token);
+        tokens
+    }
+
+    #[test]
+    fn test_shingle_filter_bigrams() {
+        let filter = ShingleFilter::new(2, 2, " ").unwrap();
+        let tokens = token_stream_helper("quick brown fox", filter);
+        assert_eq!(tokens.len(), 2);
+        assert_token(&tokens[0], 0, "quick brown", 0, 11);
+        assert_token(&tokens[1], 1, "brown fox", 6, 15);
+        assert!(tokens.iter().all(|token| token.position_length == 2));
+
+        let filter = ShingleFilter::new(2, 2, " ").unwrap();
+        assert!(token_stream_helper("fox", filter).is_empty());
+    }
+
+    #[test]
+    fn test_shingle_filter_output_unigrams() {
+        let filter = ShingleFilter::new(2, 3, "_")
+            .unwrap()
+            .set_output_unigrams(true);
+        let tokens = token_stream_helper("quick brown fox", filter);
+        assert_eq!(tokens.len(), 6);
+        assert_token(&tokens[0], 0, "quick", 0, 5);
+        assert_token(&tokens[1], 0, "quick_brown", 0, 11);
+        assert_token(&tokens[2], 0, "quick_brown_fox", 0, 15);
+        assert_eq!(tokens[2].position_length, 3);
+        assert_token(&tokens[3], 1, "brown", 6, 11);
+        assert_token(&tokens[4], 1, "brown_fox", 6, 15);
+        assert_token(&tokens[5], 2, "fox", 12, 15);
+        assert_eq!(tokens[5].position_length, 1);
+    }
+
+    #[test]
+    fn test_shingle_filter_stop_words_gap() {
+        let filter = ShingleFilter::new(2, 3, " ").unwrap();
+        let tokens = token_stream_helper("jumps over the lazy dog", filter);
+        assert_eq!(tokens.len(), 3);
+        assert_token(&tokens[0], 0, "jumps over", 0, 10);
+        assert_token(&tokens[1], 1, "over _ lazy", 6, 19);
+        assert_eq!(tokens[1].position_length, 3);
+        assert_token(&tokens[2], 3, "lazy dog", 15, 23);
+
+        // The filler token can be customized.
+        let filter = ShingleFilter::new(3, 3, " ")
+            .unwrap()
+            .set_filler_token("<stop>");
+        let tokens = token_stream_helper("over the lazy", filter);
+        assert_eq!(tokens.len(), 1);
+        assert_token(&tokens[0], 0, "over <stop> lazy", 0, 13);
+    }
+
+    #[test]
+    fn test_shingle_filter_invalid_sizes() {
+        assert!(ShingleFilter::new(1, 2, " ").is_err());
+        assert!(ShingleFilter::new(3, 2, " ").is_err());
+    }
+}
@@ -1,11 +1,52 @@
 use std::str::CharIndices;
 
-use super::{Token, TokenStream, Tokenizer};
+use super::cjk_tokenizer::is_cjk;
+use super::{Token, TokenStream, TokenType, Tokenizer};
 
 /// Tokenize the text by splitting on whitespaces and punctuation.
+///
+/// The [`TokenType`] of the tokens is set according to the characters they are made of.
+///
+/// By default, numbers are split on their separators too, e.g. `3.14` gives `3` and `14`.
+/// See [`SimpleTokenizer::keep_decimals`] and
+/// [`set_keep_thousands_separators`](SimpleTokenizer::set_keep_thousands_separators).
+/// Apostrophes split words too, unless
+/// [`set_keep_apostrophes`](SimpleTokenizer::set_keep_apostrophes) is called.
 #[derive(Clone, Default)]
 pub struct SimpleTokenizer {
     token: Token,
+    keep_decimals: bool,
+    keep_thousands_separators: bool,
+    keep_apostrophes: bool,
+}
+
+impl SimpleTokenizer {
+    /// Creates a `SimpleTokenizer` keeping the `.` between two digits inside the tokens,
+    /// so that `3.14` or `192.168.0.1` are emitted as a single token.
+    pub fn keep_decimals() -> SimpleTokenizer {
+        SimpleTokenizer {
+            keep_decimals: true,
+            ..SimpleTokenizer::default()
+        }
+    }
+
+    /// If `keep_thousands_separators` is true, the `,` between two digits is kept inside the
+    /// tokens, so that `1,000,000` is emitted as a single token.
+    pub fn set_keep_thousands_separators(
+        mut self,
+        keep_thousands_separators: bool,
+    ) -> SimpleTokenizer {
+        self.keep_thousands_separators = keep_thousands_separators;
+        self
+    }
+
+    /// If `keep_apostrophes` is true, the apostrophes (`'` or `’`) between two letters are kept
+    /// inside the tokens, so that `l'avion` is emitted as a single token. This is needed by the
+    /// [`ElisionFilter`](super::ElisionFilter).
+    pub fn set_keep_apostrophes(mut self, keep_apostrophes: bool) -> SimpleTokenizer {
+        self.keep_apostrophes = keep_apostrophes;
+        self
+    }
 }
 
 /// TokenStream produced by the `SimpleTokenizer`.
@@ -13,6 +54,9 @@ pub struct SimpleTokenStream<'a> {
     text: &'a str,
     chars: CharIndices<'a>,
     token: &'a mut Token,
+    keep_decimals: bool,
+    keep_thousands_separators: bool,
+    keep_apostrophes: bool,
 }
 
 impl Tokenizer for SimpleTokenizer {
@@ -23,18 +67,89 @@ impl Tokenizer for SimpleTokenizer {
             text,
             chars: text.char_indices(),
             token: &mut self.token,
+            keep_decimals: self.keep_decimals,
+            keep_thousands_separators: self.keep_thousands_separators,
+            keep_apostrophes: self.keep_apostrophes,
+        }
+    }
+}
+
+// Classifies `text` according to the characters it is made of.
+pub(crate) fn token_type_of(text: &str) -> TokenType {
+    let mut classifier = TokenTypeClassifier::default();
+    for c in text.chars() {
+        classifier.add_char(c);
+    }
+    classifier.token_type()
+}
+
+// Accumulates the kinds of the characters of a token, to compute its `TokenType`.
+#[derive(Default)]
+struct TokenTypeClassifier {
+    has_cjk: bool,
+    has_alphabetic: bool,
+    has_numeric: bool,
+}
+
+impl TokenTypeClassifier {
+    #[inline]
+    fn add_char(&mut self, c: char) {
+        if !c.is_ascii() && is_cjk(c) {
+            self.has_cjk = true;
+        } else if c.is_alphabetic() {
+            self.has_alphabetic = true;
+        } else if c.is_numeric() {
+            self.has_numeric = true;
+        }
+    }
+
+    fn token_type(&self) -> TokenType {
+        if self.has_cjk {
+            return TokenType::Cjk;
+        }
+        match (self.has_alphabetic, self.has_numeric) {
+            (true, false) => TokenType::Alphabetic,
+            (false, true) => TokenType::Numeric,
+            (true, true) => TokenType::Alphanumeric,
+            (false, false) => TokenType::Symbol,
         }
     }
 }
 
 impl<'a> SimpleTokenStream<'a> {
-    // search for the end of the current token.
-    fn search_token_end(&mut self) -> usize {
-        (&mut self.chars)
-            .filter(|(_, c)| !c.is_alphanumeric())
-            .map(|(offset, _)| offset)
-            .next()
-            .unwrap_or(self.text.len())
+    // Returns true if `c` is a separator kept inside numbers.
+    fn is_number_separator(&self, c: char) -> bool {
+        (c == '.' && self.keep_decimals) || (c == ',' && self.keep_thousands_separators)
+    }
+
+    // Returns true if `c` is a separator kept inside words.
+    fn is_word_separator(&self, c: char) -> bool {
+        (c == '\'' || c == '’') && self.keep_apostrophes
+    }
+
+    // search for the end of the current token, starting with `first_char`, and classifies the
+    // token while its characters are scanned.
+    fn search_token_end(&mut self, first_char: char) -> (usize, TokenType) {
+        let mut classifier = TokenTypeClassifier::default();
+        classifier.add_char(first_char);
+        let mut previous_char = first_char;
+        while let Some((offset, c)) = self.chars.next() {
+            if !c.is_alphanumeric() {
+                let next_char = self.chars.clone().next().map(|(_, next_char)| next_char);
+                let between_digits = previous_char.is_numeric()
+                    && matches!(next_char, Some(next_char) if next_char.is_numeric());
+                let between_letters = previous_char.is_alphabetic()
+                    && matches!(next_char, Some(next_char) if next_char.is_alphabetic());
+                if !(between_digits && self.is_number_separator(c))
+                    && !(between_letters && self.is_word_separator(c))
+                {
+                    return (offset, classifier.token_type());
+                }
+            }
+            classifier.add_char(c);
+            previous_char = c;
+        }
+        (self.text.len(), classifier.token_type())
     }
 }
 
@@ -44,10 +159,11 @@ impl<'a> TokenStream for SimpleTokenStream<'a> {
         self.token.position = self.token.position.wrapping_add(1);
         while let Some((offset_from, c)) = self.chars.next() {
             if c.is_alphanumeric() {
-                let offset_to = self.search_token_end();
+                let (offset_to, token_type) = self.search_token_end(c);
                 self.token.offset_from = offset_from;
                 self.token.offset_to = offset_to;
                 self.token.text.push_str(&self.text[offset_from..offset_to]);
+                self.token.token_type = token_type;
                 return true;
             }
         }
@@ -66,7 +182,7 @@ impl<'a> TokenStream for SimpleTokenStream<'a> {
 #[cfg(test)]
 mod tests {
     use crate::tokenizer::tests::assert_token;
-    use crate::tokenizer::{SimpleTokenizer, TextAnalyzer, Token};
+    use crate::tokenizer::{SimpleTokenizer, TextAnalyzer, Token, TokenType};
 
     #[test]
     fn test_simple_tokenizer() {
@@ -78,6 +194,71 @@ mod tests {
         assert_token(&tokens[3], 3, "payer", 17, 22);
     }
 
+    #[test]
+    fn test_simple_tokenizer_token_types() {
+        let tokens = token_stream_helper("Hello 1906 mp3 東京 ٣");
+        let token_types: Vec<TokenType> = tokens.iter().map(|token| token.token_type).collect();
+        assert_eq!(
+            token_types,
+            [
+                TokenType::Alphabetic,
+                TokenType::Numeric,
+                TokenType::Alphanumeric,
+                TokenType::Cjk,
+                TokenType::Numeric,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_simple_tokenizer_keep_decimals() {
+        let mut analyzer = TextAnalyzer::from(SimpleTokenizer::keep_decimals());
+        let tokens = analyzer.analyze("$3.14 for 192.168.0.1, 1,000,000 or 3.");
+        assert_eq!(tokens.len(), 8);
+        assert_token(&tokens[0], 0, "3.14", 1, 5);
+        assert_eq!(tokens[0].token_type, TokenType::Numeric);
+        assert_token(&tokens[2], 2, "192.168.0.1", 10, 21);
+        assert_token(&tokens[3], 3, "1", 23, 24);
+        assert_token(&tokens[4], 4, "000", 25, 28);
+        assert_token(&tokens[7], 7, "3", 36, 37);
+
+        let tokens = token_stream_helper("$3.14");
+        assert_eq!(tokens.len(), 2);
+        assert_token(&tokens[0], 0, "3", 1, 2);
+        assert_token(&tokens[1], 1, "14", 3, 5);
+    }
+
+    #[test]
+    fn test_simple_tokenizer_keep_thousands_separators() {
+        let mut analyzer =
+            TextAnalyzer::from(SimpleTokenizer::default().set_keep_thousands_separators(true));
+        let tokens = analyzer.analyze("1,000,000 $3.14 a,b");
+        assert_eq!(tokens.len(), 5);
+        assert_token(&tokens[0], 0, "1,000,000", 0, 9);
+        assert_token(&tokens[1], 1, "3", 11, 12);
+        assert_token(&tokens[4], 4, "b", 18, 19);
+
+        let mut analyzer = TextAnalyzer::from(
+            SimpleTokenizer::keep_decimals().set_keep_thousands_separators(true),
+        );
+        let tokens = analyzer.analyze("$1,000.50, 2");
+        assert_eq!(tokens.len(), 2);
+        assert_token(&tokens[0], 0, "1,000.50", 1, 9);
+        assert_token(&tokens[1], 1, "2", 11, 12);
+    }
+
+    #[test]
+    fn test_simple_tokenizer_keep_apostrophes() {
+        let mut analyzer =
+            TextAnalyzer::from(SimpleTokenizer::default().set_keep_apostrophes(true));
+        let tokens = analyzer.analyze("L'avion d’accord, 'quoted' l'");
+        assert_eq!(tokens.len(), 4);
+        assert_token(&tokens[0], 0, "L'avion", 0, 7);
+        assert_token(&tokens[1], 1, "d’accord", 8, 18);
+        assert_token(&tokens[2], 2, "quoted", 21, 27);
+        assert_token(&tokens[3], 3, "l", 29, 30);
+    }
+
     fn token_stream_helper(text: &str) -> Vec<Token> {
         let mut a = TextAnalyzer::from(SimpleTokenizer::default());
         let mut token_stream = a.token_stream(text);
@@ -1,9 +1,14 @@
 use std::borrow::Cow;
 use std::mem;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
 
+use lru::LruCache;
 use rust_stemmers::Algorithm;
+use rustc_hash::FxHashSet;
 use serde::{Deserialize, Serialize};
 
+use super::lower_caser::to_lowercase_unicode;
 use super::{Token, TokenFilter, TokenStream, Tokenizer};
 
 /// Available stemmer languages.
@@ -59,18 +64,57 @@ impl Language {
 /// `Stemmer` token filter. Several languages are supported, see [`Language`] for the available
 /// languages.
 /// Tokens are expected to be lowercased beforehand.
+///
+/// Tokens marked as keywords, e.g. by a [`KeywordMarkerFilter`](super::KeywordMarkerFilter),
+/// are left untouched, as well as the words excluded with [`Stemmer::with_exclusions`].
+///
+/// The stems of the most frequent words can be cached with [`Stemmer::set_cache_size`].
 #[derive(Clone)]
 pub struct Stemmer {
     stemmer_algorithm: Algorithm,
+    exclusions: Arc<FxHashSet<String>>,
+    cache_size: Option<NonZeroUsize>,
 }
 
 impl Stemmer {
     /// Creates a new `Stemmer` [`TokenFilter`] for a given language algorithm.
     pub fn new(language: Language) -> Stemmer {
+        Stemmer::with_exclusions(language, Vec::<String>::new())
+    }
+
+    /// Creates a new `Stemmer` [`TokenFilter`] for a given language algorithm,
+    /// leaving the given words untouched.
+    ///
+    /// Words are matched case-insensitively.
+    pub fn with_exclusions<W, S>(language: Language, words: W) -> Stemmer
+    where
+        W: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut buffer = String::new();
+        let exclusions = words
+            .into_iter()
+            .map(|word| {
+                to_lowercase_unicode(word.as_ref(), &mut buffer);
+                buffer.clone()
+            })
+            .collect();
         Stemmer {
             stemmer_algorithm: language.algorithm(),
+            exclusions: Arc::new(exclusions),
+            cache_size: None,
         }
     }
+
+    /// Keeps the stems of the `cache_size` most recently seen words in a LRU cache,
+    /// so that frequent words are not stemmed again and again.
+    ///
+    /// Each analyzer has its own cache, so that no lock is involved. A `cache_size` of 0,
+    /// the default, disables the cache.
+    pub fn set_cache_size(mut self, cache_size: usize) -> Stemmer {
+        self.cache_size = NonZeroUsize::new(cache_size);
+        self
+    }
 }
 
 impl Default for Stemmer {
@@ -86,42 +130,92 @@ impl TokenFilter for Stemmer {
     fn transform<T: Tokenizer>(self, tokenizer: T) -> StemmerFilter<T> {
         StemmerFilter {
             stemmer_algorithm: self.stemmer_algorithm,
+            exclusions: self.exclusions,
+            cache_size: self.cache_size,
+            cache: self.cache_size.map(LruCache::new),
             inner: tokenizer,
         }
     }
 }
 
-#[derive(Clone)]
 pub struct StemmerFilter<T> {
     stemmer_algorithm: Algorithm,
+    exclusions: Arc<FxHashSet<String>>,
+    cache_size: Option<NonZeroUsize>,
+    cache: Option<LruCache<String, String>>,
     inner: T,
 }
 
+impl<T: Clone> Clone for StemmerFilter<T> {
+    // The clones start with an empty cache.
+    fn clone(&self) -> Self {
+        StemmerFilter {
+            stemmer_algorithm: self.stemmer_algorithm,
+            exclusions: self.exclusions.clone(),
+            cache_size: self.cache_size,
+            cache: self.cache_size.map(LruCache::new),
+            inner: self.inner.clone(),
+        }
+    }
+}
+
 impl<T: Tokenizer> Tokenizer for StemmerFilter<T> {
-    type TokenStream<'a> = StemmerTokenStream<T::TokenStream<'a>>;
+    type TokenStream<'a> = StemmerTokenStream<'a, T::TokenStream<'a>>;
 
     fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
         let stemmer = rust_stemmers::Stemmer::create(self.stemmer_algorithm);
         StemmerTokenStream {
             tail: self.inner.token_stream(text),
             stemmer,
+            exclusions: &self.exclusions,
+            cache: self.cache.as_mut(),
             buffer: String::new(),
         }
     }
 }
 
-pub struct StemmerTokenStream<T> {
+pub struct StemmerTokenStream<'a, T> {
     tail: T,
     stemmer: rust_stemmers::Stemmer,
+    exclusions: &'a FxHashSet<String>,
+    cache: Option<&'a mut LruCache<String, String>>,
     buffer: String,
 }
 
-impl<T: TokenStream> TokenStream for StemmerTokenStream<T> {
+impl<'a, T: TokenStream> StemmerTokenStream<'a, T> {
+    fn is_excluded(&mut self) -> bool {
+        if self.exclusions.is_empty() {
+            return false;
+        }
+        let text = &self.tail.token().text;
+        if !text.chars().any(char::is_uppercase) {
+            return self.exclusions.contains(text);
+        }
+        to_lowercase_unicode(text, &mut self.buffer);
+        self.exclusions.contains(self.buffer.as_str())
+    }
+}
+
+impl<'a, T: TokenStream> TokenStream for StemmerTokenStream<'a, T> {
     fn advance(&mut self) -> bool {
         if !self.tail.advance() {
             return false;
         }
+        if self.tail.token().keyword || self.is_excluded() {
+            return true;
+        }
         let token = self.tail.token_mut();
+        if let Some(cache) = self.cache.as_deref_mut() {
+            if let Some(stemmed_str) = cache.get(token.text.as_str()) {
+                token.text.clear();
+                token.text.push_str(stemmed_str);
+            } else {
+                let stemmed_str = self.stemmer.stem(&token.text).into_owned();
+                let original_str = mem::replace(&mut token.text, stemmed_str.clone());
+                cache.put(original_str, stemmed_str);
+            }
+            return true;
+        }
         let stemmed_str = self.stemmer.stem(&token.text);
         match stemmed_str {
             Cow::Owned(stemmed_str) => token.text = stemmed_str,
@@ -142,3 +236,50 @@ impl<T: TokenStream> TokenStream for StemmerTokenStream<T> {
         self.tail.token_mut()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Language, Stemmer};
+    use crate::tokenizer::{SimpleTokenizer, TextAnalyzer};
+
+    fn stems(analyzer: &mut TextAnalyzer, text: &str) -> Vec<String> {
+        analyzer
+            .analyze(text)
+            .into_iter()
+            .map(|token| token.text)
+            .collect()
+    }
+
+    #[test]
+    fn test_stemmer_cache() {
+        let text = "running jumps running cats jumps running";
+        let mut analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(Stemmer::new(Language::English))
+            .build();
+        let expected = stems(&mut analyzer, text);
+        assert_eq!(expected, ["run", "jump", "run", "cat", "jump", "run"]);
+        for cache_size in [1, 2, 100] {
+            let mut cached_analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+                .filter(Stemmer::new(Language::English).set_cache_size(cache_size))
+                .build();
+            assert_eq!(stems(&mut cached_analyzer, text), expected);
+            // The second run hits the cache.
+            assert_eq!(stems(&mut cached_analyzer, text), expected);
+            assert_eq!(stems(&mut cached_analyzer.clone(), text), expected);
+        }
+    }
+
+    #[test]
+    fn test_stemmer_exclusions() {
+        let mut analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(Stemmer::with_exclusions(
+                Language::English,
+                ["news", "Apple"],
+            ))
+            .build();
+        assert_eq!(
+            stems(&mut analyzer, "news running apples Apple apple News"),
+            ["news", "run", "appl", "Apple", "apple", "News"]
+        );
+    }
+}
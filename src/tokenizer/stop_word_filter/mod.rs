@@ -6,7 +6,7 @@
 //!   .filter(StopWordFilter::remove(vec!["the".to_string(), "is".to_string()]))
 //!   .build();
 //!
-//! let mut stream = tokenizer.token_stream("the fox is crafty");
+//! let mut stream = tokenizer.token_stream("The fox is crafty");
 //! assert_eq!(stream.next().unwrap().text, "fox");
 //! assert_eq!(stream.next().unwrap().text, "crafty");
 //! assert!(stream.next().is_none());
@@ -15,15 +15,22 @@
 #[rustfmt::skip]
 mod stopwords;
 
+use std::io::{self, BufRead};
 use std::sync::Arc;
 
 use rustc_hash::FxHashSet;
 
+use super::lower_caser::to_lowercase_unicode;
 #[cfg(feature = "stopwords")]
 use super::Language;
 use super::{Token, TokenFilter, TokenStream, Tokenizer};
 
 /// `TokenFilter` that removes stop words from a token stream
+///
+/// Stop words are matched case-insensitively: a token is removed if its lowercased
+/// text is part of the lowercased list of stop words.
+/// The position of the remaining tokens is left untouched, so that phrase queries
+/// still account for the removed words.
 #[derive(Clone)]
 pub struct StopWordFilter {
     words: Arc<FxHashSet<String>>,
@@ -65,10 +72,39 @@ impl StopWordFilter {
 
     /// Creates a `StopWordFilter` given a list of words to remove
     pub fn remove<W: IntoIterator<Item = String>>(words: W) -> StopWordFilter {
+        let mut buffer = String::new();
+        let words = words
+            .into_iter()
+            .map(|word| {
+                if word.chars().any(char::is_uppercase) {
+                    to_lowercase_unicode(&word, &mut buffer);
+                    buffer.clone()
+                } else {
+                    word
+                }
+            })
+            .collect();
         StopWordFilter {
-            words: Arc::new(words.into_iter().collect()),
+            words: Arc::new(words),
         }
     }
+
+    /// Creates a `StopWordFilter` from a list of words read from `reader`.
+    ///
+    /// The list is expected to contain one word per line. Whitespaces surrounding a word
+    /// are trimmed, and blank lines as well as lines starting with `#` are ignored.
+    pub fn from_reader<R: BufRead>(reader: R) -> io::Result<StopWordFilter> {
+        let mut words = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            let word = line.trim();
+            if word.is_empty() || word.starts_with('#') {
+                continue;
+            }
+            words.push(word.to_string());
+        }
+        Ok(Self::remove(words))
+    }
 }
 
 impl TokenFilter for StopWordFilter {
@@ -95,6 +131,7 @@ impl<T: Tokenizer> Tokenizer for StopWordFilterWrapper<T> {
         StopWordFilterStream {
             words: self.words.clone(),
             tail: self.inner.token_stream(text),
+            buffer: String::new(),
         }
     }
 }
@@ -102,18 +139,24 @@ impl<T: Tokenizer> Tokenizer for StopWordFilterWrapper<T> {
 pub struct StopWordFilterStream<T> {
     words: Arc<FxHashSet<String>>,
     tail: T,
+    buffer: String,
 }
 
-impl<T> StopWordFilterStream<T> {
-    fn predicate(&self, token: &Token) -> bool {
-        !self.words.contains(&token.text)
+impl<T: TokenStream> StopWordFilterStream<T> {
+    fn predicate(&mut self) -> bool {
+        let text = &self.tail.token().text;
+        if !text.chars().any(char::is_uppercase) {
+            return !self.words.contains(text);
+        }
+        to_lowercase_unicode(text, &mut self.buffer);
+        !self.words.contains(&self.buffer)
     }
 }
 
 impl<T: TokenStream> TokenStream for StopWordFilterStream<T> {
     fn advance(&mut self) -> bool {
         while self.tail.advance() {
-            if self.predicate(self.tail.token()) {
+            if self.predicate() {
                 return true;
             }
         }
@@ -132,6 +175,8 @@ impl<T: TokenStream> TokenStream for StopWordFilterStream<T> {
 #[cfg(test)]
 mod tests {
     use crate::tokenizer::tests::assert_token;
+    #[cfg(feature = "stopwords")]
+    use crate::tokenizer::{Language, LowerCaser};
     use crate::tokenizer::{SimpleTokenizer, StopWordFilter, TextAnalyzer, Token};
 
     #[test]
@@ -145,6 +190,57 @@ mod tests {
         assert_token(&tokens[4], 9, "name", 29, 33);
     }
 
+    #[test]
+    fn test_stop_word_case_insensitive() {
+        let tokens = token_stream_helper("I Am a cat. As yet, I have no NAME.");
+        assert_eq!(tokens.len(), 5);
+        assert_token(&tokens[0], 3, "cat", 7, 10);
+        assert_token(&tokens[1], 5, "yet", 15, 18);
+        assert_token(&tokens[4], 9, "NAME", 30, 34);
+
+        let mut a = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(StopWordFilter::remove(vec!["The".to_string()]))
+            .build();
+        let mut token_stream = a.token_stream("the THE tHe fox");
+        assert_eq!(token_stream.next().unwrap().text, "fox");
+        assert!(token_stream.next().is_none());
+    }
+
+    #[test]
+    fn test_stop_word_from_reader() {
+        let stop_words_file = b"# English articles\nThe\n  a  \n\n#an\n";
+        let stop_word_filter = StopWordFilter::from_reader(&stop_words_file[..]).unwrap();
+        assert_eq!(stop_word_filter.words.len(), 2);
+        let mut a = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(stop_word_filter)
+            .build();
+        let mut tokens: Vec<Token> = vec![];
+        a.token_stream("the cat and a dog, an owl")
+            .process(&mut |token: &Token| tokens.push(token.clone()));
+        assert_eq!(tokens.len(), 5);
+        assert_token(&tokens[0], 1, "cat", 4, 7);
+        assert_token(&tokens[1], 2, "and", 8, 11);
+        assert_token(&tokens[2], 4, "dog", 14, 17);
+        assert_token(&tokens[3], 5, "an", 19, 21);
+        assert_token(&tokens[4], 6, "owl", 22, 25);
+    }
+
+    #[cfg(feature = "stopwords")]
+    #[test]
+    fn test_stop_word_language() {
+        let mut a = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(StopWordFilter::new(Language::English).unwrap())
+            .filter(LowerCaser)
+            .build();
+        let mut tokens: Vec<Token> = vec![];
+        a.token_stream("The quick fox is in the House")
+            .process(&mut |token: &Token| tokens.push(token.clone()));
+        assert_eq!(tokens.len(), 3);
+        assert_token(&tokens[0], 1, "quick", 4, 9);
+        assert_token(&tokens[1], 2, "fox", 10, 13);
+        assert_token(&tokens[2], 6, "house", 24, 29);
+    }
+
     fn token_stream_helper(text: &str) -> Vec<Token> {
         let stops = vec![
             "a".to_string(),
@@ -0,0 +1,315 @@
+use std::io::{self, BufRead};
+use std::sync::Arc;
+
+use rustc_hash::FxHashMap;
+
+use super::{Token, TokenFilter, TokenStream, Tokenizer};
+
+/// A [`TokenFilter`] which injects synonyms into the token stream.
+///
+/// The original token is always preserved. Its synonyms are emitted right after it,
+/// starting at the same position. A synonym made of several words, e.g. `new york` for
+/// `ny`, is emitted as several tokens occupying consecutive positions, so that phrase
+/// queries on the synonym still line up. The `position_length` of the original token is
+/// set to the number of positions spanned by its longest synonym.
+///
+/// Matching is done on the exact token text, so this filter should typically come
+/// after a [`LowerCaser`](super::LowerCaser), with lowercased synonyms.
+///
+/// # Example
+///
+/// ```rust
+/// use tantivy::tokenizer::*;
+///
+/// let mut tokenizer = TextAnalyzer::builder(SimpleTokenizer::default())
+///     .filter(LowerCaser)
+///     .filter(SynonymFilter::new([
+///         ("ny", vec!["new york"]),
+///         ("tv", vec!["television"]),
+///     ]))
+///     .build();
+///
+/// let mut stream = tokenizer.token_stream("NY tv");
+/// let token = stream.next().unwrap();
+/// assert_eq!((token.text.as_str(), token.position, token.position_length), ("ny", 0, 2));
+/// let token = stream.next().unwrap();
+/// assert_eq!((token.text.as_str(), token.position), ("new", 0));
+/// let token = stream.next().unwrap();
+/// assert_eq!((token.text.as_str(), token.position), ("york", 1));
+/// let token = stream.next().unwrap();
+/// assert_eq!((token.text.as_str(), token.position), ("tv", 1));
+/// let token = stream.next().unwrap();
+/// assert_eq!((token.text.as_str(), token.position), ("television", 1));
+/// assert!(stream.next().is_none());
+/// ```
+#[derive(Clone)]
+pub struct SynonymFilter {
+    synonyms: Arc<FxHashMap<String, Vec<Vec<String>>>>,
+}
+
+impl SynonymFilter {
+    /// Creates a `SynonymFilter` from a list of `(word, synonyms)` pairs.
+    ///
+    /// Each synonym is split on whitespaces into the words it is made of.
+    pub fn new<I, K, V, S>(synonyms: I) -> SynonymFilter
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut synonym_map: FxHashMap<String, Vec<Vec<String>>> = FxHashMap::default();
+        for (word, word_synonyms) in synonyms {
+            let entry = synonym_map.entry(word.into()).or_default();
+            for synonym in word_synonyms {
+                add_synonym(entry, synonym.as_ref());
+            }
+        }
+        synonym_map.retain(|_, word_synonyms| !word_synonyms.is_empty());
+        SynonymFilter {
+            synonyms: Arc::new(synonym_map),
+        }
+    }
+
+    /// Creates a `SynonymFilter` from a list of rules in the Solr synonyms format.
+    ///
+    /// Each line holds one rule, that is either
+    /// - an explicit mapping `i-pod, ipod => ipod, i pod`, where all of the words on the left hand
+    ///   side get the synonyms listed on the right hand side,
+    /// - a list of equivalent words `tv, television`, where each word gets all of the others as
+    ///   synonyms.
+    ///
+    /// Blank lines and lines starting with `#` are ignored.
+    ///
+    /// Only single words are matched in the token stream, so entries made of several words
+    /// are only used as synonyms, never as words to match.
+    pub fn from_solr_rules<R: BufRead>(reader: R) -> io::Result<SynonymFilter> {
+        let mut rules: Vec<(String, Vec<String>)> = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let sides: Vec<&str> = line.split("=>").collect();
+            match sides[..] {
+                [equivalent_words] => {
+                    let equivalent_words = split_solr_list(equivalent_words);
+                    for word in &equivalent_words {
+                        let synonyms = equivalent_words
+                            .iter()
+                            .filter(|synonym| *synonym != word)
+                            .cloned()
+                            .collect();
+                        rules.push((word.clone(), synonyms));
+                    }
+                }
+                [words, synonyms] => {
+                    let synonyms = split_solr_list(synonyms);
+                    for word in split_solr_list(words) {
+                        rules.push((word, synonyms.clone()));
+                    }
+                }
+                _ => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("Invalid synonym rule `{line}`: more than one `=>`."),
+                    ));
+                }
+            }
+        }
+        let single_word_rules = rules
+            .into_iter()
+            .filter(|(word, _)| !word.contains(char::is_whitespace));
+        Ok(SynonymFilter::new(single_word_rules))
+    }
+}
+
+fn split_solr_list(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn add_synonym(word_synonyms: &mut Vec<Vec<String>>, synonym: &str) {
+    let synonym_words: Vec<String> = synonym.split_whitespace().map(str::to_string).collect();
+    if !synonym_words.is_empty() && !word_synonyms.contains(&synonym_words) {
+        word_synonyms.push(synonym_words);
+    }
+}
+
+impl TokenFilter for SynonymFilter {
+    type Tokenizer<T: Tokenizer> = SynonymFilterWrapper<T>;
+
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> SynonymFilterWrapper<T> {
+        SynonymFilterWrapper {
+            synonyms: self.synonyms,
+            inner: tokenizer,
+            parts: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct SynonymFilterWrapper<T> {
+    synonyms: Arc<FxHashMap<String, Vec<Vec<String>>>>,
+    inner: T,
+    parts: Vec<Token>,
+}
+
+impl<T: Tokenizer> Tokenizer for SynonymFilterWrapper<T> {
+    type TokenStream<'a> = SynonymFilterStream<'a, T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        self.parts.clear();
+        SynonymFilterStream {
+            synonyms: &self.synonyms,
+            tail: self.inner.token_stream(text),
+            parts: &mut self.parts,
+        }
+    }
+}
+
+pub struct SynonymFilterStream<'a, T> {
+    synonyms: &'a FxHashMap<String, Vec<Vec<String>>>,
+    tail: T,
+    parts: &'a mut Vec<Token>,
+}
+
+impl<'a, T: TokenStream> SynonymFilterStream<'a, T> {
+    // Fills `self.parts` with the original token followed by its synonyms,
+    // if `self.tail.token()` has any.
+    fn inject_synonyms(&mut self) {
+        let token = self.tail.token();
+        let Some(synonyms) = self.synonyms.get(&token.text) else {
+            return;
+        };
+        // Fill `self.parts` in reverse order,
+        // so that `self.parts.pop()` yields
+        // the tokens in their emission order.
+        for synonym_words in synonyms.iter().rev() {
+            for (word_ord, word) in synonym_words.iter().enumerate().rev() {
+                self.parts.push(Token {
+                    position: token.position + word_ord,
+                    text: word.clone(),
+                    position_length: 1,
+                    ..*token
+                });
+            }
+        }
+        let position_length = synonyms
+            .iter()
+            .map(Vec::len)
+            .max()
+            .unwrap_or(1)
+            .max(token.position_length);
+        self.parts.push(Token {
+            text: token.text.clone(),
+            position_length,
+            ..*token
+        });
+    }
+}
+
+impl<'a, T: TokenStream> TokenStream for SynonymFilterStream<'a, T> {
+    fn advance(&mut self) -> bool {
+        self.parts.pop();
+
+        if !self.parts.is_empty() {
+            return true;
+        }
+
+        if !self.tail.advance() {
+            return false;
+        }
+
+        // Will yield either `self.parts.last()` or
+        // `self.tail.token()` if it has no synonyms.
+        self.inject_synonyms();
+        true
+    }
+
+    fn token(&self) -> &Token {
+        self.parts.last().unwrap_or_else(|| self.tail.token())
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.parts
+            .last_mut()
+            .unwrap_or_else(|| self.tail.token_mut())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::tests::assert_token;
+    use crate::tokenizer::{LowerCaser, SimpleTokenizer, TextAnalyzer};
+
+    fn token_stream_helper(filter: SynonymFilter, text: &str) -> Vec<Token> {
+        let mut a = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(LowerCaser)
+            .filter(filter)
+            .build();
+        let mut token_stream = a.token_stream(text);
+        let mut tokens: Vec<Token> = vec![];
+        let mut add_token = |token: &Token| {
+            tokens.push(token.clone());
+        };
+        token_stream.process(&mut add_token);
+        tokens
+    }
+
+    #[test]
+    fn test_synonym_filter_multi_word() {
+        let filter = SynonymFilter::new([("ny", vec!["new york", "big apple"])]);
+        let tokens = token_stream_helper(filter, "I love NY city");
+        assert_eq!(tokens.len(), 8);
+        assert_token(&tokens[0], 0, "i", 0, 1);
+        assert_token(&tokens[1], 1, "love", 2, 6);
+        assert_token(&tokens[2], 2, "ny", 7, 9);
+        assert_eq!(tokens[2].position_length, 2);
+        assert_token(&tokens[3], 2, "new", 7, 9);
+        assert_token(&tokens[4], 3, "york", 7, 9);
+        assert_token(&tokens[5], 2, "big", 7, 9);
+        assert_token(&tokens[6], 3, "apple", 7, 9);
+        assert_token(&tokens[7], 3, "city", 10, 14);
+        assert!(tokens[3..7].iter().all(|token| token.position_length == 1));
+    }
+
+    #[test]
+    fn test_synonym_filter_no_synonym() {
+        let filter = SynonymFilter::new([("tv", vec!["television"])]);
+        let tokens = token_stream_helper(filter, "watch tv");
+        assert_eq!(tokens.len(), 3);
+        assert_token(&tokens[0], 0, "watch", 0, 5);
+        assert_token(&tokens[1], 1, "tv", 6, 8);
+        assert_token(&tokens[2], 1, "television", 6, 8);
+        assert!(tokens.iter().all(|token| token.position_length == 1));
+
+        let filter = SynonymFilter::new([("tv", Vec::<String>::new())]);
+        let tokens = token_stream_helper(filter, "watch tv");
+        assert_eq!(tokens.len(), 2);
+    }
+
+    #[test]
+    fn test_synonym_filter_solr_rules() {
+        let rules = b"# some comment\n\ntv, television\nny, nyc => new york\n";
+        let filter = SynonymFilter::from_solr_rules(&rules[..]).unwrap();
+        let tokens = token_stream_helper(filter, "Television nyc");
+        assert_eq!(tokens.len(), 5);
+        assert_token(&tokens[0], 0, "television", 0, 10);
+        assert_token(&tokens[1], 0, "tv", 0, 10);
+        assert_token(&tokens[2], 1, "nyc", 11, 14);
+        assert_token(&tokens[3], 1, "new", 11, 14);
+        assert_token(&tokens[4], 2, "york", 11, 14);
+    }
+
+    #[test]
+    fn test_synonym_filter_invalid_solr_rules() {
+        let rules = b"a => b => c\n";
+        assert!(SynonymFilter::from_solr_rules(&rules[..]).is_err());
+    }
+}
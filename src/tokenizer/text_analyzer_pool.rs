@@ -0,0 +1,183 @@
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex};
+
+use super::TextAnalyzer;
+
+/// Pool of [`TextAnalyzer`], recycling analyzers instead of cloning a new one
+/// for each text.
+///
+/// Cloning a `TextAnalyzer` clones its whole chain of token filters, with their buffers.
+/// [`TextAnalyzerPool::get`] hands out an analyzer that goes back to the pool when the
+/// returned [`PooledAnalyzer`] is dropped, so that the next call reuses it.
+///
+/// The pool is cheap to clone, and all of its clones share the same analyzers.
+///
+/// # Example
+///
+/// ```rust
+/// use tantivy::tokenizer::*;
+///
+/// let pool = TextAnalyzerPool::new(TextAnalyzer::from(SimpleTokenizer::default()));
+/// for text in ["hello world", "happy tax payer"] {
+///     let mut analyzer = pool.get();
+///     let mut stream = analyzer.token_stream(text);
+///     while let Some(token) = stream.next() {
+///         println!("{}", token.text);
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct TextAnalyzerPool {
+    inner: Arc<InnerPool>,
+}
+
+struct InnerPool {
+    prototype: TextAnalyzer,
+    max_idle_analyzers: usize,
+    idle_analyzers: Mutex<Vec<TextAnalyzer>>,
+}
+
+// Default maximum number of analyzers kept in the pool.
+const DEFAULT_MAX_IDLE_ANALYZERS: usize = 64;
+
+impl TextAnalyzerPool {
+    /// Creates a pool handing out clones of `analyzer`.
+    pub fn new(analyzer: TextAnalyzer) -> TextAnalyzerPool {
+        TextAnalyzerPool::with_max_idle_analyzers(analyzer, DEFAULT_MAX_IDLE_ANALYZERS)
+    }
+
+    /// Creates a pool handing out clones of `analyzer`, and keeping at most
+    /// `max_idle_analyzers` of them when they are not in use.
+    ///
+    /// Analyzers given back to a full pool are dropped.
+    pub fn with_max_idle_analyzers(
+        analyzer: TextAnalyzer,
+        max_idle_analyzers: usize,
+    ) -> TextAnalyzerPool {
+        TextAnalyzerPool {
+            inner: Arc::new(InnerPool {
+                prototype: analyzer,
+                max_idle_analyzers,
+                idle_analyzers: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Returns an analyzer from the pool, or a new clone of the pool's analyzer
+    /// if all of them are in use.
+    pub fn get(&self) -> PooledAnalyzer {
+        let idle_analyzer = self
+            .inner
+            .idle_analyzers
+            .lock()
+            .expect("Acquiring the lock should never fail")
+            .pop();
+        let analyzer = idle_analyzer.unwrap_or_else(|| self.inner.prototype.clone());
+        PooledAnalyzer {
+            analyzer: Some(analyzer),
+            pool: self.inner.clone(),
+        }
+    }
+}
+
+/// A [`TextAnalyzer`] borrowed from a [`TextAnalyzerPool`].
+///
+/// It dereferences to the `TextAnalyzer`, and goes back to the pool when dropped.
+pub struct PooledAnalyzer {
+    // Only `None` while being dropped.
+    analyzer: Option<TextAnalyzer>,
+    pool: Arc<InnerPool>,
+}
+
+impl Deref for PooledAnalyzer {
+    type Target = TextAnalyzer;
+
+    fn deref(&self) -> &TextAnalyzer {
+        self.analyzer
+            .as_ref()
+            .expect("The analyzer is only taken on drop")
+    }
+}
+
+impl DerefMut for PooledAnalyzer {
+    fn deref_mut(&mut self) -> &mut TextAnalyzer {
+        self.analyzer
+            .as_mut()
+            .expect("The analyzer is only taken on drop")
+    }
+}
+
+impl Drop for PooledAnalyzer {
+    fn drop(&mut self) {
+        let Some(analyzer) = self.analyzer.take() else {
+            return;
+        };
+        // The analyzer is given back even if another thread panicked while holding the lock:
+        // the vector of idle analyzers is always in a consistent state.
+        let mut idle_analyzers = match self.pool.idle_analyzers.lock() {
+            Ok(idle_analyzers) => idle_analyzers,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if idle_analyzers.len() < self.pool.max_idle_analyzers {
+            idle_analyzers.push(analyzer);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TextAnalyzerPool;
+    use crate::tokenizer::{LowerCaser, SimpleTokenizer, TextAnalyzer};
+
+    fn num_idle_analyzers(pool: &TextAnalyzerPool) -> usize {
+        pool.inner.idle_analyzers.lock().unwrap().len()
+    }
+
+    #[test]
+    fn test_text_analyzer_pool_recycles_analyzers() {
+        let analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(LowerCaser)
+            .build();
+        let pool = TextAnalyzerPool::with_max_idle_analyzers(analyzer, 2);
+        assert_eq!(num_idle_analyzers(&pool), 0);
+        {
+            let mut analyzer = pool.get();
+            let texts: Vec<String> = analyzer
+                .analyze("Hello World")
+                .into_iter()
+                .map(|token| token.text)
+                .collect();
+            assert_eq!(texts, ["hello", "world"]);
+        }
+        assert_eq!(num_idle_analyzers(&pool), 1);
+        {
+            let _analyzer = pool.clone().get();
+            assert_eq!(num_idle_analyzers(&pool), 0);
+        }
+        assert_eq!(num_idle_analyzers(&pool), 1);
+
+        // The pool keeps at most 2 idle analyzers.
+        let analyzers: Vec<_> = (0..3).map(|_| pool.get()).collect();
+        drop(analyzers);
+        assert_eq!(num_idle_analyzers(&pool), 2);
+    }
+
+    #[test]
+    fn test_text_analyzer_pool_across_threads() {
+        let pool = TextAnalyzerPool::new(TextAnalyzer::from(SimpleTokenizer::default()));
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let pool = pool.clone();
+                std::thread::spawn(move || {
+                    (0..100)
+                        .map(|_| pool.get().analyze("a b c").len())
+                        .sum::<usize>()
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), 300);
+        }
+        assert!(num_idle_analyzers(&pool) <= 4);
+    }
+}
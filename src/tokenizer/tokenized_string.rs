@@ -95,6 +95,7 @@ impl TokenStream for PreTokenizedStream {
 mod tests {
 
     use super::*;
+    use crate::tokenizer::TokenType;
 
     #[test]
     fn test_tokenized_stream() {
@@ -107,6 +108,9 @@ mod tests {
                     position: 0,
                     text: String::from("A"),
                     position_length: 1,
+                    keyword: false,
+                    token_type: TokenType::default(),
+                    trailing_punctuation: false,
                 },
                 Token {
                     offset_from: 2,
@@ -114,6 +118,9 @@ mod tests {
                     position: 1,
                     text: String::from("a"),
                     position_length: 1,
+                    keyword: false,
+                    token_type: TokenType::default(),
+                    trailing_punctuation: false,
                 },
             ],
         };
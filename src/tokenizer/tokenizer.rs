@@ -1,6 +1,6 @@
 /// The tokenizer module contains all of the tools used to process
 /// text in `tantivy`.
-use tokenizer_api::{BoxTokenStream, TokenFilter, Tokenizer};
+use tokenizer_api::{BoxTokenStream, Token, TokenFilter, TokenStream, Tokenizer};
 
 use crate::tokenizer::empty_tokenizer::EmptyTokenizer;
 
@@ -68,6 +68,33 @@ impl TextAnalyzer {
     pub fn token_stream<'a>(&'a mut self, text: &'a str) -> BoxTokenStream<'a> {
         self.tokenizer.token_stream(text)
     }
+
+    /// Runs the whole pipeline on `text`, and returns a copy of all of the emitted tokens.
+    ///
+    /// This is mostly useful for tests and analysis tools. Indexing code should rather go
+    /// through [`TextAnalyzer::token_stream`], which does not allocate the tokens.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tantivy::tokenizer::*;
+    ///
+    /// let mut analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+    ///     .filter(LowerCaser)
+    ///     .build();
+    /// let texts: Vec<String> = analyzer
+    ///     .analyze("Hello, World!")
+    ///     .into_iter()
+    ///     .map(|token| token.text)
+    ///     .collect();
+    /// assert_eq!(texts, ["hello", "world"]);
+    /// ```
+    pub fn analyze(&mut self, text: &str) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        self.token_stream(text)
+            .process(&mut |token: &Token| tokens.push(token.clone()));
+        tokens
+    }
 }
 
 /// Builder helper for [`TextAnalyzer`]
@@ -137,6 +164,33 @@ mod tests {
         assert_eq!(stream.next().unwrap().text, "bullet");
     }
 
+    #[test]
+    fn test_text_analyzer_analyze() {
+        let mut analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(LowerCaser)
+            .build();
+        let tokens = analyzer.analyze("Hello, happy tax payer!");
+        assert_eq!(tokens.len(), 4);
+        assert_eq!(tokens[0].text, "hello");
+        assert_eq!((tokens[3].position, tokens[3].offset_from), (3, 17));
+        // The analyzer can be reused.
+        assert_eq!(analyzer.analyze("Tax").len(), 1);
+        assert!(analyzer.analyze("").is_empty());
+    }
+
+    #[test]
+    fn test_token_stream_cloned_tokens() {
+        let mut analyzer = TextAnalyzer::from(SimpleTokenizer::default());
+        let mut stream = analyzer.token_stream("first bullet point");
+        assert_eq!(stream.next().unwrap().text, "first");
+        let mut texts = Vec::new();
+        for token in stream.cloned_tokens() {
+            texts.push(token.text);
+        }
+        assert_eq!(texts, ["bullet", "point"]);
+        assert_eq!(stream.cloned_tokens().count(), 0);
+    }
+
     #[test]
     fn test_text_analyzer_with_filters_boxed() {
         // This test shows how one can build a TextAnalyzer dynamically, by stacking a list
@@ -0,0 +1,528 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::tokenizer::{
+    AlphaNumOnlyFilter, ArabicNormalizationFilter, AsciiFoldingFilter, CjkTokenizer,
+    EdgeNgramFilter, ElisionFilter, GreekNormalizationFilter, KeywordMarkerFilter, Language,
+    LengthFilter, LowerCaser, NgramTokenizer, PatternReplaceFilter, PhoneticAlgorithm,
+    PhoneticFilter, RawTokenizer, RegexTokenizer, RemoveLongFilter, RemoveShortFilter,
+    ShingleFilter, SimpleTokenizer, SplitCompoundWords, Stemmer, StopWordFilter, SynonymFilter,
+    TextAnalyzer, TextAnalyzerBuilder, WhitespaceTokenizer,
+};
+use crate::TantivyError;
+
+/// Serializable description of a [`TextAnalyzer`]: a tokenizer followed by a list of
+/// token filters.
+///
+/// It makes it possible to persist and reload a tokenizer pipeline, see
+/// [`TokenizerManager::from_configs`](super::TokenizerManager::from_configs).
+///
+/// # Example
+///
+/// ```rust
+/// use tantivy::tokenizer::*;
+///
+/// let config: TokenizerConfig = serde_json::from_str(
+///     r#"{
+///         "tokenizer": {"type": "simple"},
+///         "filters": [
+///             {"type": "remove_long", "limit": 40},
+///             {"type": "lower_caser"},
+///             {"type": "stemmer", "language": "English"}
+///         ]
+///     }"#,
+/// )
+/// .unwrap();
+/// let mut en_stem = config.build().unwrap();
+/// let mut stream = en_stem.token_stream("Happy");
+/// assert_eq!(stream.next().unwrap().text, "happi");
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TokenizerConfig {
+    /// The tokenizer splitting the text into tokens.
+    pub tokenizer: BaseTokenizerConfig,
+    /// The token filters, in the order they are applied.
+    #[serde(default)]
+    pub filters: Vec<TokenFilterConfig>,
+}
+
+impl TokenizerConfig {
+    /// Builds the `TextAnalyzer` described by this configuration.
+    ///
+    /// Returns an error if one of the parameters is invalid, e.g. a regex
+    /// that does not compile.
+    pub fn build(&self) -> crate::Result<TextAnalyzer> {
+        let mut builder = self.tokenizer.builder()?;
+        for filter in &self.filters {
+            builder = filter.append_to(builder)?;
+        }
+        Ok(builder.build())
+    }
+}
+
+/// Serializable description of a [`Tokenizer`](super::Tokenizer).
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BaseTokenizerConfig {
+    /// The [`SimpleTokenizer`].
+    Simple,
+    /// The [`WhitespaceTokenizer`].
+    Whitespace,
+    /// The [`RawTokenizer`].
+    Raw,
+    /// The [`CjkTokenizer`].
+    Cjk,
+    /// The [`NgramTokenizer`].
+    Ngram {
+        /// Minimum size of the n-grams.
+        min_gram: usize,
+        /// Maximum size of the n-grams.
+        max_gram: usize,
+        /// If true, only the n-grams starting at the beginning of the text are emitted.
+        #[serde(default)]
+        prefix_only: bool,
+        /// If true, the n-grams get increasing positions, instead of all being at position 0.
+        #[serde(default)]
+        increasing_positions: bool,
+    },
+    /// The [`RegexTokenizer`].
+    Regex {
+        /// The regex pattern.
+        pattern: String,
+        /// If true, the text is split on the matches of the regex,
+        /// instead of emitting the matches as tokens.
+        #[serde(default)]
+        split: bool,
+    },
+}
+
+impl BaseTokenizerConfig {
+    fn builder(&self) -> crate::Result<TextAnalyzerBuilder> {
+        let builder = match self {
+            BaseTokenizerConfig::Simple => {
+                TextAnalyzer::builder(SimpleTokenizer::default()).dynamic()
+            }
+            BaseTokenizerConfig::Whitespace => {
+                TextAnalyzer::builder(WhitespaceTokenizer::default()).dynamic()
+            }
+            BaseTokenizerConfig::Raw => TextAnalyzer::builder(RawTokenizer::default()).dynamic(),
+            BaseTokenizerConfig::Cjk => TextAnalyzer::builder(CjkTokenizer::default()).dynamic(),
+            BaseTokenizerConfig::Ngram {
+                min_gram,
+                max_gram,
+                prefix_only,
+                increasing_positions,
+            } => TextAnalyzer::builder(
+                NgramTokenizer::new(*min_gram, *max_gram, *prefix_only)?
+                    .set_increasing_positions(*increasing_positions),
+            )
+            .dynamic(),
+            BaseTokenizerConfig::Regex { pattern, split } => {
+                let tokenizer = if *split {
+                    RegexTokenizer::split(pattern)?
+                } else {
+                    RegexTokenizer::matches(pattern)?
+                };
+                TextAnalyzer::builder(tokenizer).dynamic()
+            }
+        };
+        Ok(builder)
+    }
+}
+
+/// Serializable description of a [`TokenFilter`](super::TokenFilter).
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TokenFilterConfig {
+    /// The [`RemoveLongFilter`].
+    RemoveLong {
+        /// Limit in bytes of the UTF-8 representation, or in chars if `count_chars` is set.
+        limit: usize,
+        /// If true, the limit is expressed in number of chars.
+        #[serde(default)]
+        count_chars: bool,
+    },
+    /// The [`RemoveShortFilter`].
+    RemoveShort {
+        /// Minimum number of characters.
+        limit: usize,
+    },
+    /// The [`LengthFilter`].
+    Length {
+        /// Minimum number of characters.
+        min: usize,
+        /// Maximum number of characters.
+        max: usize,
+    },
+    /// The [`LowerCaser`], following the rules of `language` if any.
+    LowerCaser {
+        /// Language whose lowercasing rules are used, e.g. Turkish.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        language: Option<Language>,
+    },
+    /// The [`AsciiFoldingFilter`].
+    AsciiFolding {
+        /// If true, the original token is emitted as well when it is not ASCII.
+        #[serde(default)]
+        preserve_original: bool,
+    },
+    /// The [`AlphaNumOnlyFilter`].
+    AlphaNumOnly,
+    /// The [`Stemmer`].
+    Stemmer {
+        /// Language of the stemmer.
+        language: Language,
+        /// Words left untouched.
+        #[serde(default)]
+        exclusions: Vec<String>,
+    },
+    /// The [`StopWordFilter`], removing either the given `words`, or the built-in stop words
+    /// of `language`.
+    StopWord {
+        /// Language whose built-in list of stop words is used.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        language: Option<Language>,
+        /// List of stop words.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        words: Vec<String>,
+    },
+    /// The [`ArabicNormalizationFilter`].
+    ArabicNormalization,
+    /// The [`GreekNormalizationFilter`].
+    GreekNormalization,
+    /// The [`ElisionFilter`].
+    Elision {
+        /// Elided articles to remove. Defaults to the French ones.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        articles: Option<Vec<String>>,
+    },
+    /// The [`EdgeNgramFilter`].
+    EdgeNgram {
+        /// Minimum size of the n-grams.
+        min_gram: usize,
+        /// Maximum size of the n-grams.
+        max_gram: usize,
+        /// If true, tokens shorter than `min_gram` are kept unchanged.
+        #[serde(default)]
+        keep_short_tokens: bool,
+    },
+    /// The [`PatternReplaceFilter`].
+    PatternReplace {
+        /// The regex pattern.
+        pattern: String,
+        /// The replacement of the matches.
+        replacement: String,
+        /// If false, only the first match is replaced.
+        #[serde(default)]
+        replace_all: bool,
+    },
+    /// The [`KeywordMarkerFilter`].
+    KeywordMarker {
+        /// Words protected from the following filters.
+        words: Vec<String>,
+    },
+    /// The [`SynonymFilter`].
+    Synonym {
+        /// Synonyms of each word.
+        synonyms: HashMap<String, Vec<String>>,
+    },
+    /// The [`PhoneticFilter`].
+    Phonetic {
+        /// The algorithm computing the phonetic codes.
+        algorithm: PhoneticAlgorithm,
+        /// If true, the original token is kept before its phonetic codes.
+        #[serde(default)]
+        inject: bool,
+    },
+    /// The [`SplitCompoundWords`] filter.
+    SplitCompoundWords {
+        /// The words the compound words are made of.
+        dictionary: Vec<String>,
+    },
+    /// The [`ShingleFilter`].
+    Shingle {
+        /// Minimum number of tokens of the shingles, at least 2.
+        min_shingle: usize,
+        /// Maximum number of tokens of the shingles.
+        max_shingle: usize,
+        /// Text joining the tokens of a shingle, a space by default.
+        #[serde(default = "default_token_separator")]
+        token_separator: String,
+        /// Text standing for a missing token, `_` by default.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        filler_token: Option<String>,
+        /// If true, the original tokens are emitted as well as the shingles.
+        #[serde(default)]
+        output_unigrams: bool,
+    },
+}
+
+fn default_token_separator() -> String {
+    " ".to_string()
+}
+
+impl TokenFilterConfig {
+    fn append_to(&self, builder: TextAnalyzerBuilder) -> crate::Result<TextAnalyzerBuilder> {
+        let builder = match self {
+            TokenFilterConfig::RemoveLong {
+                limit,
+                count_chars: false,
+            } => builder.filter_dynamic(RemoveLongFilter::limit(*limit)),
+            TokenFilterConfig::RemoveLong {
+                limit,
+                count_chars: true,
+            } => builder.filter_dynamic(RemoveLongFilter::limit_chars(*limit)),
+            TokenFilterConfig::RemoveShort { limit } => {
+                builder.filter_dynamic(RemoveShortFilter::limit(*limit))
+            }
+            TokenFilterConfig::Length { min, max } => {
+                builder.filter_dynamic(LengthFilter::new(*min, *max)?)
+            }
+            TokenFilterConfig::LowerCaser { language: None } => builder.filter_dynamic(LowerCaser),
+            TokenFilterConfig::LowerCaser {
+                language: Some(language),
+            } => builder.filter_dynamic(LowerCaser::with_language(*language)),
+            TokenFilterConfig::AsciiFolding { preserve_original } => {
+                builder.filter_dynamic(AsciiFoldingFilter::preserve_original(*preserve_original))
+            }
+            TokenFilterConfig::AlphaNumOnly => builder.filter_dynamic(AlphaNumOnlyFilter),
+            TokenFilterConfig::Stemmer {
+                language,
+                exclusions,
+            } => builder.filter_dynamic(Stemmer::with_exclusions(*language, exclusions)),
+            TokenFilterConfig::StopWord { language, words } => {
+                builder.filter_dynamic(stop_word_filter(*language, words)?)
+            }
+            TokenFilterConfig::ArabicNormalization => {
+                builder.filter_dynamic(ArabicNormalizationFilter)
+            }
+            TokenFilterConfig::GreekNormalization => {
+                builder.filter_dynamic(GreekNormalizationFilter)
+            }
+            TokenFilterConfig::Elision { articles: None } => {
+                builder.filter_dynamic(ElisionFilter::french())
+            }
+            TokenFilterConfig::Elision {
+                articles: Some(articles),
+            } => builder.filter_dynamic(ElisionFilter::from_articles(articles.iter().cloned())),
+            TokenFilterConfig::EdgeNgram {
+                min_gram,
+                max_gram,
+                keep_short_tokens,
+            } => builder.filter_dynamic(
+                EdgeNgramFilter::new(*min_gram, *max_gram)?
+                    .set_keep_short_tokens(*keep_short_tokens),
+            ),
+            TokenFilterConfig::PatternReplace {
+                pattern,
+                replacement,
+                replace_all,
+            } => builder.filter_dynamic(PatternReplaceFilter::new(
+                pattern,
+                replacement,
+                *replace_all,
+            )?),
+            TokenFilterConfig::KeywordMarker { words } => {
+                builder.filter_dynamic(KeywordMarkerFilter::protect(words))
+            }
+            TokenFilterConfig::Synonym { synonyms } => builder.filter_dynamic(SynonymFilter::new(
+                synonyms
+                    .iter()
+                    .map(|(word, word_synonyms)| (word.clone(), word_synonyms)),
+            )),
+            TokenFilterConfig::Phonetic { algorithm, inject } => {
+                builder.filter_dynamic(PhoneticFilter::new(*algorithm).set_inject(*inject))
+            }
+            TokenFilterConfig::SplitCompoundWords { dictionary } => {
+                builder.filter_dynamic(SplitCompoundWords::from_dictionary(dictionary)?)
+            }
+            TokenFilterConfig::Shingle {
+                min_shingle,
+                max_shingle,
+                token_separator,
+                filler_token,
+                output_unigrams,
+            } => {
+                let mut shingle_filter =
+                    ShingleFilter::new(*min_shingle, *max_shingle, token_separator)?
+                        .set_output_unigrams(*output_unigrams);
+                if let Some(filler_token) = filler_token {
+                    shingle_filter = shingle_filter.set_filler_token(filler_token);
+                }
+                builder.filter_dynamic(shingle_filter)
+            }
+        };
+        Ok(builder)
+    }
+}
+
+fn stop_word_filter(language: Option<Language>, words: &[String]) -> crate::Result<StopWordFilter> {
+    match language {
+        None => Ok(StopWordFilter::remove(words.iter().cloned())),
+        Some(_) if !words.is_empty() => Err(TantivyError::InvalidArgument(
+            "A stop word filter takes either a language or a list of words, not both".to_string(),
+        )),
+        #[cfg(feature = "stopwords")]
+        Some(language) => StopWordFilter::new(language).ok_or_else(|| {
+            TantivyError::InvalidArgument(format!("No stop words available for {language:?}"))
+        }),
+        #[cfg(not(feature = "stopwords"))]
+        Some(_) => Err(TantivyError::InvalidArgument(
+            "Built-in stop words require the `stopwords` feature".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{BaseTokenizerConfig, TokenFilterConfig, TokenizerConfig};
+    use crate::tokenizer::{Language, Token, TokenizerManager};
+
+    fn tokenize(tokenizer_manager: &TokenizerManager, name: &str, text: &str) -> Vec<String> {
+        let mut tokenizer = tokenizer_manager.get(name).unwrap();
+        let mut tokens: Vec<String> = vec![];
+        tokenizer
+            .token_stream(text)
+            .process(&mut |token: &Token| tokens.push(token.text.clone()));
+        tokens
+    }
+
+    #[test]
+    fn test_tokenizer_config_json_round_trip() {
+        let json = r#"{
+            "en_stem": {
+                "tokenizer": {"type": "simple"},
+                "filters": [
+                    {"type": "remove_long", "limit": 40},
+                    {"type": "lower_caser"},
+                    {"type": "stop_word", "words": ["the"]},
+                    {"type": "stemmer", "language": "English"}
+                ]
+            },
+            "ngram": {
+                "tokenizer": {"type": "ngram", "min_gram": 2, "max_gram": 3}
+            }
+        }"#;
+        let configs: HashMap<String, TokenizerConfig> = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            configs["en_stem"].filters[0],
+            TokenFilterConfig::RemoveLong {
+                limit: 40,
+                count_chars: false
+            }
+        );
+        assert_eq!(
+            configs["ngram"].tokenizer,
+            BaseTokenizerConfig::Ngram {
+                min_gram: 2,
+                max_gram: 3,
+                prefix_only: false,
+                increasing_positions: false
+            }
+        );
+        let tokenizer_manager = TokenizerManager::from_configs(configs.clone()).unwrap();
+        assert_eq!(
+            tokenize(&tokenizer_manager, "en_stem", "The happy TAX payers"),
+            ["happi", "tax", "payer"]
+        );
+        assert_eq!(
+            tokenize(&tokenizer_manager, "ngram", "abc"),
+            ["ab", "abc", "bc"]
+        );
+
+        let serialized = serde_json::to_string(&tokenizer_manager.to_configs()).unwrap();
+        let deserialized: HashMap<String, TokenizerConfig> =
+            serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, configs);
+    }
+
+    #[test]
+    fn test_tokenizer_config_phonetic_compound_shingle_round_trip() {
+        let json = r#"{
+            "phonetic": {
+                "tokenizer": {"type": "simple"},
+                "filters": [{"type": "phonetic", "algorithm": "soundex", "inject": true}]
+            },
+            "compound": {
+                "tokenizer": {"type": "simple"},
+                "filters": [
+                    {"type": "split_compound_words", "dictionary": ["dampf", "schiff", "fahrt"]}
+                ]
+            },
+            "shingle": {
+                "tokenizer": {"type": "simple"},
+                "filters": [{"type": "shingle", "min_shingle": 2, "max_shingle": 2}]
+            }
+        }"#;
+        let configs: HashMap<String, TokenizerConfig> = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            configs["shingle"].filters[0],
+            TokenFilterConfig::Shingle {
+                min_shingle: 2,
+                max_shingle: 2,
+                token_separator: " ".to_string(),
+                filler_token: None,
+                output_unigrams: false,
+            }
+        );
+        let tokenizer_manager = TokenizerManager::from_configs(configs.clone()).unwrap();
+        assert_eq!(
+            tokenize(&tokenizer_manager, "phonetic", "Robert"),
+            ["Robert", "R163"]
+        );
+        assert_eq!(
+            tokenize(&tokenizer_manager, "compound", "dampfschifffahrt"),
+            ["dampf", "schiff", "fahrt"]
+        );
+        assert_eq!(
+            tokenize(&tokenizer_manager, "shingle", "please divide this"),
+            ["please divide", "divide this"]
+        );
+
+        let serialized = serde_json::to_string(&tokenizer_manager.to_configs()).unwrap();
+        let deserialized: HashMap<String, TokenizerConfig> =
+            serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, configs);
+    }
+
+    #[test]
+    fn test_tokenizer_config_registered_with_code_are_not_exported() {
+        let tokenizer_manager = TokenizerManager::default();
+        assert!(tokenizer_manager.to_configs().is_empty());
+        let config = TokenizerConfig {
+            tokenizer: BaseTokenizerConfig::Whitespace,
+            filters: vec![TokenFilterConfig::LowerCaser {
+                language: Some(Language::Turkish),
+            }],
+        };
+        tokenizer_manager
+            .register_config("tr_lower", config.clone())
+            .unwrap();
+        assert_eq!(tokenize(&tokenizer_manager, "tr_lower", "IŞIK"), ["ışık"]);
+        assert_eq!(tokenizer_manager.to_configs()["tr_lower"], config);
+        tokenizer_manager.register("tr_lower", crate::tokenizer::RawTokenizer::default());
+        assert!(tokenizer_manager.to_configs().is_empty());
+    }
+
+    #[test]
+    fn test_tokenizer_config_invalid() {
+        let config = TokenizerConfig {
+            tokenizer: BaseTokenizerConfig::Regex {
+                pattern: r"\@(".to_string(),
+                split: false,
+            },
+            filters: Vec::new(),
+        };
+        assert!(config.build().is_err());
+        let config = TokenizerConfig {
+            tokenizer: BaseTokenizerConfig::Simple,
+            filters: vec![TokenFilterConfig::Length { min: 3, max: 2 }],
+        };
+        assert!(config.build().is_err());
+        let unknown_filter = r#"{"tokenizer": {"type": "simple"}, "filters": [{"type": "foo"}]}"#;
+        assert!(serde_json::from_str::<TokenizerConfig>(unknown_filter).is_err());
+    }
+}
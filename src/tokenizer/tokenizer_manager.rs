@@ -4,7 +4,9 @@ use std::sync::{Arc, RwLock};
 use crate::tokenizer::stemmer::Language;
 use crate::tokenizer::tokenizer::TextAnalyzer;
 use crate::tokenizer::{
-    LowerCaser, RawTokenizer, RemoveLongFilter, SimpleTokenizer, Stemmer, WhitespaceTokenizer,
+    ArabicNormalizationFilter, AsciiFoldingFilter, CjkTokenizer, ElisionFilter,
+    GreekNormalizationFilter, LowerCaser, PhoneticFilter, RawTokenizer, RemoveLongFilter,
+    SimpleTokenizer, Stemmer, TokenizerConfig, WhitespaceTokenizer,
 };
 
 /// The tokenizer manager serves as a store for
@@ -19,10 +21,33 @@ use crate::tokenizer::{
 ///  * `en_stem` : Like `default`, but also applies stemming on the
 ///  resulting tokens. Stemming can improve the recall of your
 ///  search engine.
+///  * `da_stem`, `fi_stem`, `fr_stem`, `no_stem`, `ru_stem`, `sv_stem` : Like `en_stem`,
+///  for Danish, Finnish, French, Norwegian, Russian and Swedish respectively.
+///  * `fr_stem_elision` : Like `fr_stem`, but keeps the apostrophes inside words, and removes
+///  the elided articles, e.g. `l'` in `l'avion`, see [`ElisionFilter`].
+///  * `tr_stem` : Like `en_stem`, for Turkish. Lowercasing follows the Turkish
+///  rules for the dotted and dotless i.
+///  * `ar_stem` : Like `en_stem`, for Arabic. Diacritics and letter variants are
+///  normalized beforehand, see [`ArabicNormalizationFilter`].
+///  * `el_stem` : Like `en_stem`, for Greek. Accents and final sigmas are
+///  normalized while lowercasing, see [`GreekNormalizationFilter`].
+///  * `cjk` : Emits bigrams for the runs of Chinese, Japanese and Korean characters,
+///  see [`CjkTokenizer`]. The rest of the text is handled like `default` does.
+///  * `name_phonetic` : Replaces the words by their Double Metaphone codes, so that names
+///  that sound alike, e.g. `Smith` and `Smyth`, match. It is meant for a field dedicated to
+///  the phonetic matching of names, next to the field holding the text itself, see
+///  [`PhoneticFilter`].
 /// * `whitespace` : Splits the text on whitespaces.
 #[derive(Clone)]
 pub struct TokenizerManager {
-    tokenizers: Arc<RwLock<HashMap<String, TextAnalyzer>>>,
+    tokenizers: Arc<RwLock<HashMap<String, RegisteredTokenizer>>>,
+}
+
+#[derive(Clone)]
+struct RegisteredTokenizer {
+    text_analyzer: TextAnalyzer,
+    // Only available for the tokenizers registered from their configuration.
+    config: Option<TokenizerConfig>,
 }
 
 impl TokenizerManager {
@@ -35,14 +60,70 @@ impl TokenizerManager {
 
     /// Registers a new tokenizer associated with a given name.
     pub fn register<T>(&self, tokenizer_name: &str, tokenizer: T)
-    where
-        TextAnalyzer: From<T>,
-    {
+    where TextAnalyzer: From<T> {
         let boxed_tokenizer: TextAnalyzer = TextAnalyzer::from(tokenizer);
+        self.insert(tokenizer_name, boxed_tokenizer, None);
+    }
+
+    /// Registers a new tokenizer built from its configuration, associated with a given name.
+    ///
+    /// Returns an error if the configuration is invalid.
+    pub fn register_config(
+        &self,
+        tokenizer_name: &str,
+        config: TokenizerConfig,
+    ) -> crate::Result<()> {
+        let text_analyzer = config.build()?;
+        self.insert(tokenizer_name, text_analyzer, Some(config));
+        Ok(())
+    }
+
+    fn insert(
+        &self,
+        tokenizer_name: &str,
+        text_analyzer: TextAnalyzer,
+        config: Option<TokenizerConfig>,
+    ) {
+        let registered_tokenizer = RegisteredTokenizer {
+            text_analyzer,
+            config,
+        };
         self.tokenizers
             .write()
             .expect("Acquiring the lock should never fail")
-            .insert(tokenizer_name.to_string(), boxed_tokenizer);
+            .insert(tokenizer_name.to_string(), registered_tokenizer);
+    }
+
+    /// Creates a tokenizer manager populated with the tokenizers described by `configs`,
+    /// associated with their name.
+    ///
+    /// Returns an error if one of the configurations is invalid.
+    pub fn from_configs(
+        configs: HashMap<String, TokenizerConfig>,
+    ) -> crate::Result<TokenizerManager> {
+        let manager = TokenizerManager::new();
+        for (tokenizer_name, config) in configs {
+            manager.register_config(&tokenizer_name, config)?;
+        }
+        Ok(manager)
+    }
+
+    /// Returns the configurations of the tokenizers, associated with their name.
+    ///
+    /// Only the tokenizers registered from a configuration, with
+    /// [`TokenizerManager::register_config`] or [`TokenizerManager::from_configs`],
+    /// are part of the result: the tokenizers registered with [`TokenizerManager::register`],
+    /// including the default ones, are opaque.
+    pub fn to_configs(&self) -> HashMap<String, TokenizerConfig> {
+        self.tokenizers
+            .read()
+            .expect("Acquiring the lock should never fail")
+            .iter()
+            .filter_map(|(tokenizer_name, registered_tokenizer)| {
+                let config = registered_tokenizer.config.clone()?;
+                Some((tokenizer_name.clone(), config))
+            })
+            .collect()
     }
 
     /// Accessing a tokenizer given its name.
@@ -51,7 +132,39 @@ impl TokenizerManager {
             .read()
             .expect("Acquiring the lock should never fail")
             .get(tokenizer_name)
+            .map(|registered_tokenizer| registered_tokenizer.text_analyzer.clone())
+    }
+
+    /// Returns the names of all of the registered tokenizers, sorted alphabetically.
+    pub fn list(&self) -> Vec<String> {
+        let mut tokenizer_names: Vec<String> = self
+            .tokenizers
+            .read()
+            .expect("Acquiring the lock should never fail")
+            .keys()
             .cloned()
+            .collect();
+        tokenizer_names.sort();
+        tokenizer_names
+    }
+
+    /// Removes the tokenizer associated with a given name, and returns it.
+    ///
+    /// Returns `None` if no tokenizer was registered with this name.
+    pub fn remove(&self, tokenizer_name: &str) -> Option<TextAnalyzer> {
+        self.tokenizers
+            .write()
+            .expect("Acquiring the lock should never fail")
+            .remove(tokenizer_name)
+            .map(|registered_tokenizer| registered_tokenizer.text_analyzer)
+    }
+
+    /// Returns true if a tokenizer is registered with the given name.
+    pub fn contains(&self, tokenizer_name: &str) -> bool {
+        self.tokenizers
+            .read()
+            .expect("Acquiring the lock should never fail")
+            .contains_key(tokenizer_name)
     }
 }
 
@@ -76,6 +189,39 @@ impl Default for TokenizerManager {
                 .filter(Stemmer::new(Language::English))
                 .build(),
         );
+        manager.register(
+            "ar_stem",
+            TextAnalyzer::builder(SimpleTokenizer::default())
+                .filter(RemoveLongFilter::limit(40))
+                .filter(LowerCaser)
+                .filter(ArabicNormalizationFilter)
+                .filter(Stemmer::new(Language::Arabic))
+                .build(),
+        );
+        manager.register(
+            "da_stem",
+            TextAnalyzer::builder(SimpleTokenizer::default())
+                .filter(RemoveLongFilter::limit(40))
+                .filter(LowerCaser)
+                .filter(Stemmer::new(Language::Danish))
+                .build(),
+        );
+        manager.register(
+            "el_stem",
+            TextAnalyzer::builder(SimpleTokenizer::default())
+                .filter(RemoveLongFilter::limit(40))
+                .filter(GreekNormalizationFilter)
+                .filter(Stemmer::new(Language::Greek))
+                .build(),
+        );
+        manager.register(
+            "fi_stem",
+            TextAnalyzer::builder(SimpleTokenizer::default())
+                .filter(RemoveLongFilter::limit(40))
+                .filter(LowerCaser)
+                .filter(Stemmer::new(Language::Finnish))
+                .build(),
+        );
         manager.register(
             "fr_stem",
             TextAnalyzer::builder(SimpleTokenizer::default())
@@ -84,6 +230,23 @@ impl Default for TokenizerManager {
                 .filter(Stemmer::new(Language::French))
                 .build(),
         );
+        manager.register(
+            "fr_stem_elision",
+            TextAnalyzer::builder(SimpleTokenizer::default().set_keep_apostrophes(true))
+                .filter(RemoveLongFilter::limit(40))
+                .filter(LowerCaser)
+                .filter(ElisionFilter::french())
+                .filter(Stemmer::new(Language::French))
+                .build(),
+        );
+        manager.register(
+            "no_stem",
+            TextAnalyzer::builder(SimpleTokenizer::default())
+                .filter(RemoveLongFilter::limit(40))
+                .filter(LowerCaser)
+                .filter(Stemmer::new(Language::Norwegian))
+                .build(),
+        );
         manager.register(
             "pl_stem",
             TextAnalyzer::builder(SimpleTokenizer::default())
@@ -99,6 +262,37 @@ impl Default for TokenizerManager {
                 .filter(Stemmer::new(Language::Russian))
                 .build(),
         );
+        manager.register(
+            "sv_stem",
+            TextAnalyzer::builder(SimpleTokenizer::default())
+                .filter(RemoveLongFilter::limit(40))
+                .filter(LowerCaser)
+                .filter(Stemmer::new(Language::Swedish))
+                .build(),
+        );
+        manager.register(
+            "tr_stem",
+            TextAnalyzer::builder(SimpleTokenizer::default())
+                .filter(RemoveLongFilter::limit(40))
+                .filter(LowerCaser::with_language(Language::Turkish))
+                .filter(Stemmer::new(Language::Turkish))
+                .build(),
+        );
+        manager.register(
+            "cjk",
+            TextAnalyzer::builder(CjkTokenizer::default())
+                .filter(RemoveLongFilter::limit(40))
+                .filter(LowerCaser)
+                .build(),
+        );
+        manager.register(
+            "name_phonetic",
+            TextAnalyzer::builder(SimpleTokenizer::default())
+                .filter(LowerCaser)
+                .filter(AsciiFoldingFilter)
+                .filter(PhoneticFilter::double_metaphone())
+                .build(),
+        );
         manager.register("whitespace", WhitespaceTokenizer::default());
         manager
     }
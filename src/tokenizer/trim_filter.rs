@@ -0,0 +1,126 @@
+//! # Example
+//! ```rust
+//! use tantivy::tokenizer::*;
+//!
+//! let mut tokenizer = TextAnalyzer::builder(RawTokenizer::default())
+//!   .filter(TrimFilter)
+//!   .build();
+//!
+//! let mut stream = tokenizer.token_stream("  New York ");
+//! let token = stream.next().unwrap();
+//! assert_eq!(token.text, "New York");
+//! assert_eq!((token.offset_from, token.offset_to), (2, 10));
+//! assert!(stream.next().is_none());
+//! ```
+use super::{Token, TokenFilter, TokenStream, Tokenizer};
+
+/// `TokenFilter` that removes the leading and trailing whitespaces of the tokens.
+///
+/// The offsets of the tokens are shrunk by the number of bytes removed, which assumes that
+/// the text of the token is a copy of the original text. Tokens made only of whitespaces
+/// are removed.
+///
+/// It is mostly useful after a [`RawTokenizer`](super::RawTokenizer), or after a
+/// [`RegexTokenizer`](super::RegexTokenizer) splitting the text on a delimiter.
+#[derive(Clone)]
+pub struct TrimFilter;
+
+impl TokenFilter for TrimFilter {
+    type Tokenizer<T: Tokenizer> = TrimFilterWrapper<T>;
+
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> TrimFilterWrapper<T> {
+        TrimFilterWrapper(tokenizer)
+    }
+}
+
+#[derive(Clone)]
+pub struct TrimFilterWrapper<T>(T);
+
+impl<T: Tokenizer> Tokenizer for TrimFilterWrapper<T> {
+    type TokenStream<'a> = TrimFilterStream<T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        TrimFilterStream {
+            tail: self.0.token_stream(text),
+        }
+    }
+}
+
+pub struct TrimFilterStream<T> {
+    tail: T,
+}
+
+// Trims `token` in place.
+fn trim_token(token: &mut Token) {
+    let trimmed_end_len = token.text.trim_end().len();
+    let num_trailing_bytes = token.text.len() - trimmed_end_len;
+    token.text.truncate(trimmed_end_len);
+    let num_leading_bytes = token.text.len() - token.text.trim_start().len();
+    token.text.drain(..num_leading_bytes);
+    token.offset_from = (token.offset_from + num_leading_bytes).min(token.offset_to);
+    token.offset_to = token
+        .offset_to
+        .saturating_sub(num_trailing_bytes)
+        .max(token.offset_from);
+}
+
+impl<T: TokenStream> TokenStream for TrimFilterStream<T> {
+    fn advance(&mut self) -> bool {
+        while self.tail.advance() {
+            let token = self.tail.token_mut();
+            trim_token(token);
+            if !token.text.is_empty() {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn token(&self) -> &Token {
+        self.tail.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.tail.token_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tokenizer::tests::assert_token;
+    use crate::tokenizer::{RawTokenizer, RegexTokenizer, TextAnalyzer, Token, TrimFilter};
+
+    fn token_stream_helper(mut analyzer: TextAnalyzer, text: &str) -> Vec<Token> {
+        let mut token_stream = analyzer.token_stream(text);
+        let mut tokens: Vec<Token> = vec![];
+        let mut add_token = |token: &Token| {
+            tokens.push(token.clone());
+        };
+        token_stream.process(&mut add_token);
+        tokens
+    }
+
+    #[test]
+    fn test_trim_filter_raw() {
+        let analyzer = TextAnalyzer::builder(RawTokenizer::default())
+            .filter(TrimFilter)
+            .build();
+        let tokens = token_stream_helper(analyzer.clone(), "\u{3000} hello world \t");
+        assert_eq!(tokens.len(), 1);
+        assert_token(&tokens[0], 0, "hello world", 4, 15);
+
+        assert!(token_stream_helper(analyzer, " \n ").is_empty());
+    }
+
+    #[test]
+    fn test_trim_filter_split_values() {
+        let analyzer = TextAnalyzer::builder(RegexTokenizer::split(",").unwrap())
+            .filter(TrimFilter)
+            .build();
+        let tokens = token_stream_helper(analyzer, " a , b,  ,c");
+        assert_eq!(tokens.len(), 3);
+        assert_token(&tokens[0], 0, "a", 1, 2);
+        assert_token(&tokens[1], 1, "b", 5, 6);
+        assert_token(&tokens[2], 3, "c", 10, 11);
+    }
+}
@@ -0,0 +1,153 @@
+//! # Example
+//! ```rust
+//! use tantivy::tokenizer::*;
+//!
+//! let mut tokenizer = TextAnalyzer::builder(SimpleTokenizer::default())
+//!   .filter(TypeFilter::keep([TokenType::Numeric]))
+//!   .build();
+//!
+//! let mut stream = tokenizer.token_stream("Order 66 shipped in 2024");
+//! assert_eq!(stream.next().unwrap().text, "66");
+//! assert_eq!(stream.next().unwrap().text, "2024");
+//! assert!(stream.next().is_none());
+//! ```
+use std::sync::Arc;
+
+use super::{Token, TokenFilter, TokenStream, TokenType, Tokenizer};
+
+/// `TokenFilter` that keeps or removes tokens depending on their [`TokenType`].
+///
+/// The type of the tokens is set by the tokenizer, e.g. the
+/// [`SimpleTokenizer`](super::SimpleTokenizer). Tokenizers that do not classify their
+/// tokens emit `Alphanumeric` tokens.
+#[derive(Clone)]
+pub struct TypeFilter {
+    token_types: Arc<[TokenType]>,
+    keep: bool,
+}
+
+impl TypeFilter {
+    /// Creates a `TypeFilter` keeping only the tokens of the given types.
+    pub fn keep<I: IntoIterator<Item = TokenType>>(token_types: I) -> TypeFilter {
+        TypeFilter {
+            token_types: token_types.into_iter().collect(),
+            keep: true,
+        }
+    }
+
+    /// Creates a `TypeFilter` removing the tokens of the given types.
+    pub fn remove<I: IntoIterator<Item = TokenType>>(token_types: I) -> TypeFilter {
+        TypeFilter {
+            token_types: token_types.into_iter().collect(),
+            keep: false,
+        }
+    }
+}
+
+impl TokenFilter for TypeFilter {
+    type Tokenizer<T: Tokenizer> = TypeFilterWrapper<T>;
+
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> TypeFilterWrapper<T> {
+        TypeFilterWrapper {
+            config: self,
+            inner: tokenizer,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct TypeFilterWrapper<T> {
+    config: TypeFilter,
+    inner: T,
+}
+
+impl<T: Tokenizer> Tokenizer for TypeFilterWrapper<T> {
+    type TokenStream<'a> = TypeFilterStream<'a, T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        TypeFilterStream {
+            config: &self.config,
+            tail: self.inner.token_stream(text),
+        }
+    }
+}
+
+pub struct TypeFilterStream<'a, T> {
+    config: &'a TypeFilter,
+    tail: T,
+}
+
+impl<'a, T> TypeFilterStream<'a, T> {
+    fn predicate(&self, token: &Token) -> bool {
+        self.config.token_types.contains(&token.token_type) == self.config.keep
+    }
+}
+
+impl<'a, T: TokenStream> TokenStream for TypeFilterStream<'a, T> {
+    fn advance(&mut self) -> bool {
+        while self.tail.advance() {
+            if self.predicate(self.tail.token()) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn token(&self) -> &Token {
+        self.tail.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.tail.token_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tokenizer::tests::assert_token;
+    use crate::tokenizer::{
+        RawTokenizer, SimpleTokenizer, TextAnalyzer, Token, TokenType, TypeFilter,
+    };
+
+    fn token_stream_helper(mut analyzer: TextAnalyzer, text: &str) -> Vec<Token> {
+        let mut token_stream = analyzer.token_stream(text);
+        let mut tokens: Vec<Token> = vec![];
+        let mut add_token = |token: &Token| {
+            tokens.push(token.clone());
+        };
+        token_stream.process(&mut add_token);
+        tokens
+    }
+
+    #[test]
+    fn test_type_filter_keep() {
+        let analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(TypeFilter::keep([TokenType::Numeric, TokenType::Cjk]))
+            .build();
+        let tokens = token_stream_helper(analyzer, "mp3 1906 東京 cat");
+        assert_eq!(tokens.len(), 2);
+        assert_token(&tokens[0], 1, "1906", 4, 8);
+        assert_token(&tokens[1], 2, "東京", 9, 15);
+    }
+
+    #[test]
+    fn test_type_filter_remove() {
+        let analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(TypeFilter::remove([TokenType::Numeric]))
+            .build();
+        let tokens = token_stream_helper(analyzer, "mp3 1906 cat");
+        assert_eq!(tokens.len(), 2);
+        assert_token(&tokens[0], 0, "mp3", 0, 3);
+        assert_token(&tokens[1], 2, "cat", 9, 12);
+    }
+
+    #[test]
+    fn test_type_filter_default_type() {
+        let analyzer = TextAnalyzer::builder(RawTokenizer::default())
+            .filter(TypeFilter::keep([TokenType::Alphanumeric]))
+            .build();
+        let tokens = token_stream_helper(analyzer, "1906");
+        assert_eq!(tokens.len(), 1);
+        assert_token(&tokens[0], 0, "1906", 0, 4);
+    }
+}
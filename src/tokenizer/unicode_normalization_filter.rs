@@ -0,0 +1,198 @@
+//! # Example
+//! ```rust
+//! use tantivy::tokenizer::*;
+//!
+//! let mut tokenizer = TextAnalyzer::builder(WhitespaceTokenizer::default())
+//!   .filter(UnicodeNormalizationFilter::new(NormalizationForm::Nfkc))
+//!   .filter(LowerCaser)
+//!   .build();
+//!
+//! let mut stream = tokenizer.token_stream("ﬁle ＡＢＣ");
+//! assert_eq!(stream.next().unwrap().text, "file");
+//! assert_eq!(stream.next().unwrap().text, "abc");
+//! assert!(stream.next().is_none());
+//! ```
+use std::mem;
+
+use unicode_normalization::{
+    is_nfc_quick, is_nfd_quick, is_nfkc_quick, is_nfkd_quick, IsNormalized, UnicodeNormalization,
+};
+
+use super::{Token, TokenFilter, TokenStream, Tokenizer};
+
+/// Unicode normalization form, as defined by
+/// [Unicode Standard Annex #15](https://unicode.org/reports/tr15/).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NormalizationForm {
+    /// Canonical decomposition, followed by canonical composition.
+    Nfc,
+    /// Canonical decomposition.
+    ///
+    /// e.g. `é` is decomposed into `e` followed by a combining acute accent.
+    Nfd,
+    /// Compatibility decomposition, followed by canonical composition.
+    ///
+    /// e.g. the ligature `ﬁ` is replaced by `fi`, and the fullwidth `Ａ` by `A`.
+    Nfkc,
+    /// Compatibility decomposition.
+    Nfkd,
+}
+
+impl NormalizationForm {
+    // Returns true if `text` is known to be normalized already.
+    fn is_normalized(self, text: &str) -> bool {
+        let is_normalized = match self {
+            NormalizationForm::Nfc => is_nfc_quick(text.chars()),
+            NormalizationForm::Nfd => is_nfd_quick(text.chars()),
+            NormalizationForm::Nfkc => is_nfkc_quick(text.chars()),
+            NormalizationForm::Nfkd => is_nfkd_quick(text.chars()),
+        };
+        is_normalized == IsNormalized::Yes
+    }
+
+    // Writes the normalized version of `text` into `output`.
+    fn normalize(self, text: &str, output: &mut String) {
+        output.clear();
+        match self {
+            NormalizationForm::Nfc => output.extend(text.nfc()),
+            NormalizationForm::Nfd => output.extend(text.nfd()),
+            NormalizationForm::Nfkc => output.extend(text.nfkc()),
+            NormalizationForm::Nfkd => output.extend(text.nfkd()),
+        }
+    }
+}
+
+/// `TokenFilter` that applies a Unicode normalization form to the text of the tokens.
+///
+/// The same text can be encoded by different sequences of code points, which would
+/// otherwise produce different terms. The compatibility forms `Nfkc` and `Nfkd` also fold
+/// the ligatures, the fullwidth and halfwidth forms, the superscripts, etc.
+/// to their plain equivalent.
+///
+/// The offsets of the tokens are not modified: they still point to the original text,
+/// even if the length of the normalized text differs.
+///
+/// It is typically placed early in the pipeline, e.g. before the [`LowerCaser`](super::LowerCaser),
+/// so that the following filters work on the normalized text.
+#[derive(Clone)]
+pub struct UnicodeNormalizationFilter {
+    form: NormalizationForm,
+}
+
+impl UnicodeNormalizationFilter {
+    /// Creates a `UnicodeNormalizationFilter` applying the given normalization form.
+    pub fn new(form: NormalizationForm) -> UnicodeNormalizationFilter {
+        UnicodeNormalizationFilter { form }
+    }
+}
+
+impl TokenFilter for UnicodeNormalizationFilter {
+    type Tokenizer<T: Tokenizer> = UnicodeNormalizationFilterWrapper<T>;
+
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> UnicodeNormalizationFilterWrapper<T> {
+        UnicodeNormalizationFilterWrapper {
+            form: self.form,
+            inner: tokenizer,
+            buffer: String::new(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct UnicodeNormalizationFilterWrapper<T> {
+    form: NormalizationForm,
+    inner: T,
+    buffer: String,
+}
+
+impl<T: Tokenizer> Tokenizer for UnicodeNormalizationFilterWrapper<T> {
+    type TokenStream<'a> = UnicodeNormalizationFilterStream<'a, T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        self.buffer.clear();
+        UnicodeNormalizationFilterStream {
+            form: self.form,
+            tail: self.inner.token_stream(text),
+            buffer: &mut self.buffer,
+        }
+    }
+}
+
+pub struct UnicodeNormalizationFilterStream<'a, T> {
+    form: NormalizationForm,
+    tail: T,
+    buffer: &'a mut String,
+}
+
+impl<'a, T: TokenStream> TokenStream for UnicodeNormalizationFilterStream<'a, T> {
+    fn advance(&mut self) -> bool {
+        if !self.tail.advance() {
+            return false;
+        }
+        // ASCII text is normalized in all forms.
+        let text = &self.tail.token().text;
+        if !text.is_ascii() && !self.form.is_normalized(text) {
+            self.form.normalize(text, self.buffer);
+            mem::swap(&mut self.tail.token_mut().text, self.buffer);
+        }
+        true
+    }
+
+    fn token(&self) -> &Token {
+        self.tail.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.tail.token_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tokenizer::tests::assert_token;
+    use crate::tokenizer::{
+        NormalizationForm, TextAnalyzer, Token, UnicodeNormalizationFilter, WhitespaceTokenizer,
+    };
+
+    fn token_stream_helper(text: &str, form: NormalizationForm) -> Vec<Token> {
+        let mut a = TextAnalyzer::builder(WhitespaceTokenizer::default())
+            .filter(UnicodeNormalizationFilter::new(form))
+            .build();
+        let mut token_stream = a.token_stream(text);
+        let mut tokens: Vec<Token> = vec![];
+        let mut add_token = |token: &Token| {
+            tokens.push(token.clone());
+        };
+        token_stream.process(&mut add_token);
+        tokens
+    }
+
+    #[test]
+    fn test_unicode_normalization_nfkc() {
+        let tokens = token_stream_helper("ﬁle １２３ Ａbc", NormalizationForm::Nfkc);
+        assert_eq!(tokens.len(), 3);
+        assert_token(&tokens[0], 0, "file", 0, 5);
+        assert_token(&tokens[1], 1, "123", 6, 15);
+        assert_token(&tokens[2], 2, "Abc", 16, 21);
+    }
+
+    #[test]
+    fn test_unicode_normalization_nfkd_ligature() {
+        let tokens = token_stream_helper("ﬁancé", NormalizationForm::Nfkd);
+        assert_eq!(tokens.len(), 1);
+        assert_token(&tokens[0], 0, "fiance\u{301}", 0, 8);
+    }
+
+    #[test]
+    fn test_unicode_normalization_canonical_forms() {
+        let tokens = token_stream_helper("cafe\u{301} ﬁ", NormalizationForm::Nfc);
+        assert_eq!(tokens.len(), 2);
+        assert_token(&tokens[0], 0, "café", 0, 6);
+        // Compatibility characters are not folded by the canonical forms.
+        assert_token(&tokens[1], 1, "ﬁ", 7, 10);
+
+        let tokens = token_stream_helper("café", NormalizationForm::Nfd);
+        assert_eq!(tokens.len(), 1);
+        assert_token(&tokens[0], 0, "cafe\u{301}", 0, 5);
+    }
+}
@@ -3,15 +3,36 @@ use std::str::CharIndices;
 use super::{Token, TokenStream, Tokenizer};
 
 /// Tokenize the text by splitting on whitespaces.
+///
+/// Punctuation is kept attached to the tokens, see
+/// [`set_mark_trailing_punctuation`](WhitespaceTokenizer::set_mark_trailing_punctuation)
+/// to flag the tokens ending with it.
 #[derive(Clone, Default)]
 pub struct WhitespaceTokenizer {
     token: Token,
+    mark_trailing_punctuation: bool,
+}
+
+impl WhitespaceTokenizer {
+    /// If `mark_trailing_punctuation` is true, the
+    /// [`trailing_punctuation`](Token::trailing_punctuation) flag is set on the tokens ending
+    /// with a character that is neither alphanumeric nor a whitespace, e.g. `hello,`.
+    ///
+    /// The punctuation is not stripped, and the offsets still cover the whole token.
+    pub fn set_mark_trailing_punctuation(
+        mut self,
+        mark_trailing_punctuation: bool,
+    ) -> WhitespaceTokenizer {
+        self.mark_trailing_punctuation = mark_trailing_punctuation;
+        self
+    }
 }
 
 pub struct WhitespaceTokenStream<'a> {
     text: &'a str,
     chars: CharIndices<'a>,
     token: &'a mut Token,
+    mark_trailing_punctuation: bool,
 }
 
 impl Tokenizer for WhitespaceTokenizer {
@@ -22,6 +43,7 @@ impl Tokenizer for WhitespaceTokenizer {
             text,
             chars: text.char_indices(),
             token: &mut self.token,
+            mark_trailing_punctuation: self.mark_trailing_punctuation,
         }
     }
 }
@@ -47,6 +69,15 @@ impl<'a> TokenStream for WhitespaceTokenStream<'a> {
                 self.token.offset_from = offset_from;
                 self.token.offset_to = offset_to;
                 self.token.text.push_str(&self.text[offset_from..offset_to]);
+                if self.mark_trailing_punctuation {
+                    self.token.trailing_punctuation = self
+                        .token
+                        .text
+                        .chars()
+                        .next_back()
+                        .map(|c| !c.is_alphanumeric())
+                        .unwrap_or(false);
+                }
                 return true;
             }
         }
@@ -77,6 +108,26 @@ mod tests {
         assert_token(&tokens[3], 3, "payer!", 17, 23);
     }
 
+    #[test]
+    fn test_whitespace_tokenizer_trailing_punctuation() {
+        let mut analyzer =
+            TextAnalyzer::from(WhitespaceTokenizer::default().set_mark_trailing_punctuation(true));
+        let tokens = analyzer.analyze("Hello, (happy) tax payer!");
+        assert_eq!(tokens.len(), 4);
+        assert_token(&tokens[0], 0, "Hello,", 0, 6);
+        assert_token(&tokens[1], 1, "(happy)", 7, 14);
+        assert_token(&tokens[3], 3, "payer!", 19, 25);
+        let trailing_punctuation: Vec<bool> = tokens
+            .iter()
+            .map(|token| token.trailing_punctuation)
+            .collect();
+        assert_eq!(trailing_punctuation, [true, true, false, true]);
+
+        // The flag is not set by default.
+        let tokens = token_stream_helper("Hello, world");
+        assert!(!tokens[0].trailing_punctuation);
+    }
+
     fn token_stream_helper(text: &str) -> Vec<Token> {
         let mut a = TextAnalyzer::from(WhitespaceTokenizer::default());
         let mut token_stream = a.token_stream(text);
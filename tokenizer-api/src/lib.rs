@@ -26,6 +26,36 @@ pub struct Token {
     pub text: String,
     /// Is the length expressed in term of number of original tokens.
     pub position_length: usize,
+    /// Keyword tokens are protected from being modified by some of the following
+    /// token filters, e.g. stemmers.
+    #[serde(skip)]
+    pub keyword: bool,
+    /// Kind of characters the token is made of, as classified by the tokenizer.
+    #[serde(skip)]
+    pub token_type: TokenType,
+    /// Set by some tokenizers, e.g. the whitespace tokenizer, if the text of the token
+    /// ends with punctuation.
+    #[serde(skip)]
+    pub trailing_punctuation: bool,
+}
+
+/// Kind of characters a [`Token`] is made of.
+///
+/// Tokenizers that do not classify their tokens leave the default, `Alphanumeric`.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Eq, PartialEq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenType {
+    /// Only alphabetic characters, e.g. `hello`.
+    Alphabetic,
+    /// Only numeric characters, e.g. `1906`.
+    Numeric,
+    /// A mix of alphabetic and numeric characters, e.g. `mp3`.
+    #[default]
+    Alphanumeric,
+    /// No alphanumeric characters, e.g. `&` or an emoji.
+    Symbol,
+    /// Chinese, Japanese or Korean characters.
+    Cjk,
 }
 
 impl Default for Token {
@@ -36,6 +66,9 @@ impl Default for Token {
             position: usize::MAX,
             text: String::new(),
             position_length: 1,
+            keyword: false,
+            token_type: TokenType::default(),
+            trailing_punctuation: false,
         }
     }
 }
@@ -48,6 +81,9 @@ impl Token {
         self.position = usize::MAX;
         self.text.clear();
         self.position_length = 1;
+        self.keyword = false;
+        self.token_type = TokenType::default();
+        self.trailing_punctuation = false;
     }
 }
 
@@ -146,6 +182,32 @@ pub trait TokenStream {
             sink(self.token());
         }
     }
+
+    /// Returns an iterator over clones of the remaining tokens of the `TokenStream`.
+    ///
+    /// There is no iterator yielding `&Token`: the stream reuses the same `Token` from one step
+    /// to the next, and `Iterator::next` cannot return a reference which is invalidated by the
+    /// following call. This iterator therefore yields a clone of each token, allocating its
+    /// text. To borrow the tokens instead, call [`TokenStream::next`] in a loop, i.e.
+    /// `while let Some(token) = token_stream.next() { ... }`.
+    fn cloned_tokens(&mut self) -> ClonedTokens<'_, Self>
+    where Self: Sized {
+        ClonedTokens { token_stream: self }
+    }
+}
+
+/// Iterator over clones of the tokens of a [`TokenStream`], see
+/// [`TokenStream::cloned_tokens`].
+pub struct ClonedTokens<'a, T> {
+    token_stream: &'a mut T,
+}
+
+impl<'a, T: TokenStream> Iterator for ClonedTokens<'a, T> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        self.token_stream.next().cloned()
+    }
 }
 
 /// Trait for the pluggable components of `Tokenizer`s.
@@ -169,6 +231,9 @@ mod test {
             offset_to: 3,
             text: "abc".to_string(),
             position_length: 1,
+            keyword: false,
+            token_type: TokenType::Alphabetic,
+            trailing_punctuation: false,
         };
         let t2 = t1.clone();
 